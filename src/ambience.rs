@@ -0,0 +1,533 @@
+//! Ambience zones.
+//!
+//! An [`AmbienceZone`] plays a looping sound while the [`AmbienceListener`]
+//! is inside its region. Near the edge of a zone the loop fades with the
+//! listener's depth into the zone, so overlapping zones blend smoothly as the
+//! listener walks from one into the other.
+//!
+//! A [`BlendedAmbience`] blends several loops by a parameter of the game
+//! instead, such as the time of day, altitude, or danger.
+
+use bevy::prelude::*;
+use std::time::Duration;
+
+use crate::traits::SfxCategory;
+
+/// Default distance over which a zone fades in from its edge.
+pub const DEFAULT_AMBIENCE_EDGE_FADE: f32 = 5.0;
+
+/// Default duration over which a zone's loop follows its target volume.
+pub const DEFAULT_AMBIENCE_FADE: Duration = Duration::from_millis(500);
+
+/// Marks the entity whose position selects the active ambience zones,
+/// usually the player or the camera.
+#[derive(Component, Reflect, Debug, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct AmbienceListener;
+
+/// Region covered by an [`AmbienceZone`], relative to the zone's transform.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub enum ZoneShape {
+    /// A sphere around the zone's translation.
+    Sphere {
+        /// Radius of the sphere.
+        radius: f32,
+    },
+    /// A box in the zone's local space.
+    Box {
+        /// Half of the box size along each local axis.
+        half_extents: Vec3,
+    },
+}
+
+impl ZoneShape {
+    /// Returns how far `point` lies inside the shape, measured to the nearest
+    /// edge. Points outside the shape return a negative depth.
+    #[must_use]
+    pub fn depth(&self, transform: &GlobalTransform, point: Vec3) -> f32 {
+        match *self {
+            Self::Sphere { radius } => radius - transform.translation().distance(point),
+            Self::Box { half_extents } => {
+                let local = transform.affine().inverse().transform_point3(point);
+                (half_extents - local.abs()).min_element()
+            }
+        }
+    }
+}
+
+/// Plays a looping ambience sound while the listener is inside a region.
+///
+/// The loop is spawned as a child entity with category `S` once the listener
+/// enters, and despawned after it has faded out.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{AmbienceListener, AmbienceZone};
+///
+/// commands.spawn((
+///     AmbienceZone::sphere(assets.load("ambience/forest.ogg"), GameSfx::Ambience, 30.0),
+///     Transform::from_xyz(0.0, 0.0, 50.0),
+/// ));
+/// commands.spawn((Player, AmbienceListener, Transform::default()));
+/// ```
+#[derive(Component, Debug, Clone)]
+#[require(Transform)]
+pub struct AmbienceZone<S: SfxCategory> {
+    /// The looping ambience sound.
+    pub handle: Handle<AudioSource>,
+    /// The sound effect category for volume control.
+    pub category: S,
+    /// Region covered by the zone.
+    pub shape: ZoneShape,
+    /// Linear volume at full depth.
+    pub volume: f32,
+    /// Depth at which the zone reaches full volume.
+    pub edge_fade: f32,
+    /// Time the loop takes to move between silence and full volume.
+    pub fade: Duration,
+    pub(crate) source: Option<Entity>,
+    pub(crate) gain: f32,
+}
+
+impl<S: SfxCategory> AmbienceZone<S> {
+    /// Creates a zone covering `shape`.
+    #[must_use]
+    pub fn new(handle: Handle<AudioSource>, category: S, shape: ZoneShape) -> Self {
+        Self {
+            handle,
+            category,
+            shape,
+            volume: 1.0,
+            edge_fade: DEFAULT_AMBIENCE_EDGE_FADE,
+            fade: DEFAULT_AMBIENCE_FADE,
+            source: None,
+            gain: 0.0,
+        }
+    }
+
+    /// Creates a spherical zone.
+    #[must_use]
+    pub fn sphere(handle: Handle<AudioSource>, category: S, radius: f32) -> Self {
+        Self::new(handle, category, ZoneShape::Sphere { radius })
+    }
+
+    /// Creates a box-shaped zone.
+    #[must_use]
+    pub fn cuboid(handle: Handle<AudioSource>, category: S, half_extents: Vec3) -> Self {
+        Self::new(handle, category, ZoneShape::Box { half_extents })
+    }
+
+    /// Sets the volume at full depth.
+    #[must_use]
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Sets the depth at which the zone reaches full volume.
+    #[must_use]
+    pub fn with_edge_fade(mut self, edge_fade: f32) -> Self {
+        self.edge_fade = edge_fade;
+        self
+    }
+
+    /// Sets the time the loop takes to move between silence and full volume.
+    #[must_use]
+    pub fn with_fade(mut self, fade: Duration) -> Self {
+        self.fade = fade;
+        self
+    }
+
+    /// Returns the zone's weight in `[0.0, 1.0]` for a listener at `point`.
+    #[must_use]
+    pub fn weight(&self, transform: &GlobalTransform, point: Vec3) -> f32 {
+        let depth = self.shape.depth(transform, point);
+        if depth <= 0.0 {
+            return 0.0;
+        }
+        if self.edge_fade <= 0.0 {
+            return 1.0;
+        }
+        (depth / self.edge_fade).min(1.0)
+    }
+
+    /// Returns the gain currently applied to the zone's loop.
+    #[must_use]
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Returns the entity playing the zone's loop.
+    #[must_use]
+    pub fn source(&self) -> Option<Entity> {
+        self.source
+    }
+
+    /// Moves the gain towards `target` over `delta`, limited by the fade time.
+    pub(crate) fn step_gain(&mut self, target: f32, delta: Duration) -> f32 {
+        self.gain = step_towards(self.gain, target, self.volume, self.fade, delta);
+        self.gain
+    }
+}
+
+/// Moves `gain` towards `target`, taking `fade` to move between silence and
+/// `volume`.
+pub(crate) fn step_towards(
+    gain: f32,
+    target: f32,
+    volume: f32,
+    fade: Duration,
+    delta: Duration,
+) -> f32 {
+    if fade.is_zero() {
+        return target;
+    }
+    let max_step = volume.max(f32::EPSILON) * delta.as_secs_f32() / fade.as_secs_f32();
+    if gain > target {
+        (gain - max_step).max(target)
+    } else {
+        (gain + max_step).min(target)
+    }
+}
+
+/// Marks the entity playing the loop of an [`AmbienceZone`].
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct AmbienceSource {
+    /// The zone the loop belongs to.
+    pub zone: Entity,
+}
+
+/// One loop of a [`BlendedAmbience`].
+#[derive(Debug, Clone)]
+pub struct AmbienceLayer {
+    /// The looping sound.
+    pub handle: Handle<AudioSource>,
+    /// Weight of the loop at parameter values, sorted by parameter.
+    ///
+    /// Weights are interpolated linearly between the keys and held past the
+    /// first and last key.
+    pub keys: Vec<(f32, f32)>,
+    pub(crate) source: Option<Entity>,
+    pub(crate) gain: f32,
+}
+
+impl AmbienceLayer {
+    /// Creates a layer weighted by `keys` of `(parameter, weight)`.
+    #[must_use]
+    pub fn new(handle: Handle<AudioSource>, keys: impl IntoIterator<Item = (f32, f32)>) -> Self {
+        let mut keys: Vec<_> = keys.into_iter().collect();
+        keys.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self {
+            handle,
+            keys,
+            source: None,
+            gain: 0.0,
+        }
+    }
+
+    /// Returns the weight of the loop at `parameter`.
+    #[must_use]
+    pub fn weight(&self, parameter: f32) -> f32 {
+        let (Some(first), Some(last)) = (self.keys.first(), self.keys.last()) else {
+            return 0.0;
+        };
+        if parameter <= first.0 {
+            return first.1;
+        }
+        if parameter >= last.0 {
+            return last.1;
+        }
+        self.keys
+            .windows(2)
+            .find(|pair| parameter <= pair[1].0)
+            .map_or(last.1, |pair| {
+                let (from, to) = (pair[0], pair[1]);
+                let span = to.0 - from.0;
+                if span <= 0.0 {
+                    to.1
+                } else {
+                    from.1 + (to.1 - from.1) * (parameter - from.0) / span
+                }
+            })
+    }
+
+    /// Returns the gain currently applied to the loop.
+    #[must_use]
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Returns the entity playing the loop.
+    #[must_use]
+    pub fn source(&self) -> Option<Entity> {
+        self.source
+    }
+}
+
+/// Blends looping ambience by a parameter of the game.
+///
+/// Each [`AmbienceLayer`] maps the parameter to a weight. Every frame the
+/// loops move towards their weight times `volume`, limited by the fade
+/// time, so changing the parameter crossfades the environment smoothly.
+/// Loops are spawned as children while their gain is above zero.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::BlendedAmbience;
+///
+/// commands.spawn(
+///     BlendedAmbience::new(GameSfx::Ambience)
+///         .with_layer(assets.load("ambience/birds.ogg"), [(5.0, 0.0), (8.0, 1.0), (18.0, 1.0), (21.0, 0.0)])
+///         .with_layer(assets.load("ambience/crickets.ogg"), [(5.0, 1.0), (8.0, 0.0), (18.0, 0.0), (21.0, 1.0)]),
+/// );
+///
+/// fn follow_clock(clock: Res<GameClock>, mut ambience: Query<&mut BlendedAmbience<GameSfx>>) {
+///     for mut ambience in &mut ambience {
+///         ambience.parameter = clock.hours();
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone)]
+pub struct BlendedAmbience<S: SfxCategory> {
+    /// The sound effect category for volume control.
+    pub category: S,
+    /// Value the layer weights are read at.
+    pub parameter: f32,
+    /// Linear volume of a layer at weight `1.0`.
+    pub volume: f32,
+    /// Time a loop takes to move between silence and full volume.
+    pub fade: Duration,
+    /// Divide the weights by their sum when it exceeds `1.0`, keeping the
+    /// overall level constant while layers overlap.
+    pub normalize: bool,
+    /// The blended loops.
+    pub layers: Vec<AmbienceLayer>,
+}
+
+impl<S: SfxCategory> BlendedAmbience<S> {
+    /// Creates a blend without layers, at parameter `0.0`.
+    #[must_use]
+    pub fn new(category: S) -> Self {
+        Self {
+            category,
+            parameter: 0.0,
+            volume: 1.0,
+            fade: DEFAULT_AMBIENCE_FADE,
+            normalize: false,
+            layers: Vec::new(),
+        }
+    }
+
+    /// Adds a loop weighted by `keys` of `(parameter, weight)`.
+    #[must_use]
+    pub fn with_layer(
+        mut self,
+        handle: Handle<AudioSource>,
+        keys: impl IntoIterator<Item = (f32, f32)>,
+    ) -> Self {
+        self.layers.push(AmbienceLayer::new(handle, keys));
+        self
+    }
+
+    /// Sets the starting parameter.
+    #[must_use]
+    pub fn with_parameter(mut self, parameter: f32) -> Self {
+        self.parameter = parameter;
+        self
+    }
+
+    /// Sets the volume of a layer at weight `1.0`.
+    #[must_use]
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Sets the time a loop takes to move between silence and full volume.
+    #[must_use]
+    pub fn with_fade(mut self, fade: Duration) -> Self {
+        self.fade = fade;
+        self
+    }
+
+    /// Keeps the overall level constant while layers overlap.
+    #[must_use]
+    pub fn normalized(mut self) -> Self {
+        self.normalize = true;
+        self
+    }
+
+    /// Returns the target gain of every layer at the current parameter.
+    #[must_use]
+    pub fn targets(&self) -> Vec<f32> {
+        let weights: Vec<f32> = self
+            .layers
+            .iter()
+            .map(|layer| layer.weight(self.parameter).max(0.0))
+            .collect();
+        let total: f32 = weights.iter().sum();
+        let scale = if self.normalize && total > 1.0 {
+            self.volume / total
+        } else {
+            self.volume
+        };
+        weights.into_iter().map(|weight| weight * scale).collect()
+    }
+
+    /// Moves the gain of a layer towards `target` over `delta`.
+    pub(crate) fn step_gain(&mut self, layer: usize, target: f32, delta: Duration) -> f32 {
+        let (volume, fade) = (self.volume, self.fade);
+        let layer = &mut self.layers[layer];
+        layer.gain = step_towards(layer.gain, target, volume, fade, delta);
+        layer.gain
+    }
+}
+
+/// Marks the entity playing a layer of a [`BlendedAmbience`].
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct BlendedAmbienceSource {
+    /// The blend the loop belongs to.
+    pub ambience: Entity,
+    /// Index of the layer in [`BlendedAmbience::layers`].
+    pub layer: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestPlugin, TestSfx};
+
+    use crate::TrackGain;
+
+    #[test]
+    fn sphere_weight_fades_from_edge() {
+        let zone =
+            AmbienceZone::sphere(Handle::default(), TestSfx::Ambience, 10.0).with_edge_fade(4.0);
+        let transform = GlobalTransform::IDENTITY;
+
+        assert!(zone.weight(&transform, Vec3::new(12.0, 0.0, 0.0)).abs() < f32::EPSILON);
+        assert!((zone.weight(&transform, Vec3::new(8.0, 0.0, 0.0)) - 0.5).abs() < 1e-5);
+        assert!((zone.weight(&transform, Vec3::ZERO) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn box_depth_uses_local_space() {
+        let shape = ZoneShape::Box {
+            half_extents: Vec3::new(2.0, 1.0, 2.0),
+        };
+        let transform = GlobalTransform::from_translation(Vec3::new(10.0, 0.0, 0.0));
+
+        assert!((shape.depth(&transform, Vec3::new(10.0, 0.0, 0.0)) - 1.0).abs() < 1e-5);
+        assert!(shape.depth(&transform, Vec3::ZERO) < 0.0);
+    }
+
+    #[test]
+    fn gain_follows_target_at_fade_rate() {
+        let mut zone = AmbienceZone::sphere(Handle::default(), TestSfx::Ambience, 10.0)
+            .with_fade(Duration::from_secs(1));
+
+        assert!((zone.step_gain(1.0, Duration::from_millis(250)) - 0.25).abs() < 1e-5);
+        assert!((zone.step_gain(0.0, Duration::from_millis(100)) - 0.15).abs() < 1e-5);
+    }
+
+    #[test]
+    fn layer_weights_interpolate_between_keys() {
+        let blend = BlendedAmbience::new(TestSfx::Ambience)
+            .with_layer(Handle::default(), [(8.0, 1.0), (6.0, 0.0)])
+            .with_layer(Handle::default(), [(6.0, 1.0), (8.0, 1.0)])
+            .with_parameter(7.0);
+
+        assert!((blend.layers[0].weight(0.0)).abs() < f32::EPSILON);
+        assert!((blend.layers[0].weight(20.0) - 1.0).abs() < f32::EPSILON);
+        assert_eq!(blend.targets(), vec![0.5, 1.0]);
+        let normalized = blend.normalized().with_volume(0.6);
+        let targets = normalized.targets();
+        assert!((targets[0] - 0.2).abs() < 1e-5);
+        assert!((targets[1] - 0.4).abs() < 1e-5);
+    }
+
+    #[test]
+    fn ambience_zone_spawns_loop_when_listener_enters() {
+        let mut app = test_app(TestPlugin::default());
+
+        let zone = app
+            .world_mut()
+            .spawn((
+                AmbienceZone::sphere(Handle::default(), TestSfx::UI, 10.0)
+                    .with_fade(Duration::ZERO),
+                GlobalTransform::IDENTITY,
+            ))
+            .id();
+        let listener = app
+            .world_mut()
+            .spawn((AmbienceListener, GlobalTransform::from_xyz(50.0, 0.0, 0.0)))
+            .id();
+        app.update();
+        let zone_state = app.world().get::<AmbienceZone<TestSfx>>(zone).unwrap();
+        assert!(zone_state.source().is_none());
+
+        *app.world_mut()
+            .get_mut::<GlobalTransform>(listener)
+            .unwrap() = GlobalTransform::IDENTITY;
+        app.update();
+        let zone_state = app.world().get::<AmbienceZone<TestSfx>>(zone).unwrap();
+        let source = zone_state.source().unwrap();
+        assert!((zone_state.gain() - 1.0).abs() < f32::EPSILON);
+        assert_eq!(
+            app.world().get::<AmbienceSource>(source).unwrap().zone,
+            zone
+        );
+    }
+
+    #[test]
+    fn blended_ambience_crossfades_layers_by_parameter() {
+        let mut app = test_app(TestPlugin::default());
+
+        let blend = app
+            .world_mut()
+            .spawn(
+                BlendedAmbience::new(TestSfx::UI)
+                    .with_layer(Handle::default(), [(6.0, 0.0), (8.0, 1.0)])
+                    .with_layer(Handle::default(), [(6.0, 1.0), (8.0, 0.0)])
+                    .with_parameter(6.0)
+                    .with_fade(Duration::ZERO),
+            )
+            .id();
+        app.update();
+        let state = app.world().get::<BlendedAmbience<TestSfx>>(blend).unwrap();
+        assert!(state.layers[0].source().is_none());
+        let night = state.layers[1].source().unwrap();
+        assert_eq!(
+            app.world()
+                .get::<BlendedAmbienceSource>(night)
+                .unwrap()
+                .layer,
+            1
+        );
+
+        app.world_mut()
+            .get_mut::<BlendedAmbience<TestSfx>>(blend)
+            .unwrap()
+            .parameter = 7.5;
+        app.update();
+        let state = app.world().get::<BlendedAmbience<TestSfx>>(blend).unwrap();
+        let day = state.layers[0].source().unwrap();
+        assert!((state.layers[0].gain() - 0.75).abs() < 1e-5);
+        assert!((app.world().get::<TrackGain>(day).unwrap().0 - 0.75).abs() < 1e-5);
+        assert!((state.layers[1].gain() - 0.25).abs() < 1e-5);
+
+        app.world_mut()
+            .get_mut::<BlendedAmbience<TestSfx>>(blend)
+            .unwrap()
+            .parameter = 9.0;
+        app.update();
+        app.update();
+        let state = app.world().get::<BlendedAmbience<TestSfx>>(blend).unwrap();
+        assert!(state.layers[1].source().is_none());
+        assert!(app.world().get_entity(night).is_err());
+    }
+}
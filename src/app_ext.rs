@@ -0,0 +1,456 @@
+//! App extension for registering audio category types.
+//!
+//! [`MsgAudioPlugin`](crate::MsgAudioPlugin) registers its own category types.
+//! DLC or sub-plugins can bring additional category types afterwards without
+//! touching the central enums.
+
+use bevy::prelude::*;
+
+use crate::backend::{has_spatial_sink, AudioBackend, BevyAudioBackend};
+use crate::delay::{self, DelayedMessages};
+use crate::environment::{self, EnvironmentProfiles};
+use crate::events::{
+    self, CrossfadeMusic, FadeOutAllMusic, FadeOutMusic, FadeOutSfx, MuteCategory,
+    PlayLayeredMusic, PlayLoopingSfx, PlayMusic, PlaySfx, PlaySfxBatch, PlayStinger, PlayVoiceLine,
+    PopMixerSnapshot, PushMixerSnapshot, SeekMusic, SetAudioVolume, SetLayerVolume, SoloCategory,
+    StopAllMusic, StopLoopingSfx, StopMusic, VoiceLineFinished,
+};
+use crate::focus::{self, AudioFocus};
+use crate::library::SoundLibrary;
+use crate::limiter::{self, MixLevel};
+use crate::memory::MusicMemory;
+use crate::mixer::{CategoryMixerState, CategorySpeed};
+use crate::muffle::{self, AudioMuffle};
+use crate::now_playing::{self, NowPlaying, NowPlayingChanged};
+use crate::pool;
+use crate::systems;
+use crate::traits::{AudioCategory, AudioConfigTrait, MusicCategory, SfxCategory, VoiceCategory};
+use crate::voice::DialogueQueue;
+use crate::MsgAudioSet;
+
+/// Plugin options applied to every registered sound effect category type.
+#[derive(Resource, Clone, Copy, Default)]
+pub(crate) struct SfxOptions {
+    pub time_scaled: bool,
+    pub pause_on_game_pause: bool,
+}
+
+/// Extension trait for registering additional audio category types.
+///
+/// Requires [`MsgAudioPlugin`](crate::MsgAudioPlugin) to be added first, which
+/// provides the category-independent systems and resources. Additional sound
+/// effect types follow the plugin's sound effect options.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::AddAudioCategory;
+///
+/// impl Plugin for DlcPlugin {
+///     fn build(&self, app: &mut App) {
+///         app.add_music_category::<DlcMusic>()
+///             .add_sfx_category::<DlcSfx>()
+///             .add_voice_category::<DlcVoice>();
+///     }
+/// }
+/// ```
+pub trait AddAudioCategory {
+    /// Registers the messages and systems for a music category type played
+    /// through [`BevyAudioBackend`].
+    fn add_music_category<M>(&mut self) -> &mut Self
+    where
+        M: MusicCategory,
+        M::Config: AudioConfigTrait,
+    {
+        self.add_music_category_with::<M, BevyAudioBackend>()
+    }
+
+    /// Registers the messages and systems for a sound effect category type
+    /// played through [`BevyAudioBackend`].
+    fn add_sfx_category<S>(&mut self) -> &mut Self
+    where
+        S: SfxCategory,
+        S::Config: AudioConfigTrait,
+    {
+        self.add_sfx_category_with::<S, BevyAudioBackend>()
+    }
+
+    /// Registers the dialogue queue, messages, and systems for a voice
+    /// category type played through [`BevyAudioBackend`].
+    fn add_voice_category<V>(&mut self) -> &mut Self
+    where
+        V: VoiceCategory,
+        V::Config: AudioConfigTrait,
+    {
+        self.add_voice_category_with::<V, BevyAudioBackend>()
+    }
+
+    /// Registers the messages and systems for a music category type played
+    /// through the backend `B`.
+    fn add_music_category_with<M, B>(&mut self) -> &mut Self
+    where
+        M: MusicCategory,
+        M::Config: AudioConfigTrait,
+        B: AudioBackend;
+
+    /// Registers the messages and systems for a sound effect category type
+    /// played through the backend `B`.
+    fn add_sfx_category_with<S, B>(&mut self) -> &mut Self
+    where
+        S: SfxCategory,
+        S::Config: AudioConfigTrait,
+        B: AudioBackend;
+
+    /// Registers the dialogue queue, messages, and systems for a voice
+    /// category type played through the backend `B`.
+    fn add_voice_category_with<V, B>(&mut self) -> &mut Self
+    where
+        V: VoiceCategory,
+        V::Config: AudioConfigTrait,
+        B: AudioBackend;
+}
+
+impl AddAudioCategory for App {
+    fn add_music_category_with<M, B>(&mut self) -> &mut Self
+    where
+        M: MusicCategory,
+        M::Config: AudioConfigTrait,
+        B: AudioBackend,
+    {
+        // Add messages (renamed from events in Bevy 0.17)
+        self.add_message::<PlayMusic<M>>();
+        self.add_message::<StopMusic<M>>();
+        self.add_message::<SeekMusic<M>>();
+        self.add_message::<PlayLayeredMusic<M>>();
+        self.add_message::<SetLayerVolume<M>>();
+        self.add_message::<StopAllMusic<M>>();
+        self.add_message::<FadeOutMusic<M>>();
+        self.add_message::<FadeOutAllMusic<M>>();
+        self.add_message::<CrossfadeMusic<M>>();
+        self.add_message::<PlayStinger<M>>();
+        register_delayed::<PlayMusic<M>>(self);
+        register_mixer::<M, B>(self);
+        self.init_resource::<MusicMemory<M>>();
+        self.init_resource::<NowPlaying<M>>();
+        self.add_message::<NowPlayingChanged<M>>();
+        self.add_observer(systems::remember_music_position::<M, B>);
+        if has_spatial_sink::<B>() {
+            self.add_observer(systems::remember_music_position::<M, B::Spatial>);
+            self.add_systems(
+                Update,
+                (
+                    (
+                        events::handle_seek_music_events::<M, B::Spatial>,
+                        events::handle_set_layer_volume_events::<M, B::Spatial>,
+                    )
+                        .in_set(MsgAudioSet::EventHandling),
+                    (
+                        systems::apply_volume_to_new_music::<M, M::Config, B::Spatial>,
+                        systems::update_music_volume::<M, M::Config, B::Spatial>.run_if(
+                            resource_changed::<M::Config>
+                                .or(resource_changed::<CategoryMixerState<M>>),
+                        ),
+                    )
+                        .chain()
+                        .in_set(MsgAudioSet::VolumeApplication),
+                ),
+            );
+        }
+
+        self.add_systems(
+            Update,
+            (
+                (
+                    events::handle_play_music_events::<M, B>,
+                    // Stops only reach music that was playing before this frame
+                    events::handle_stop_music_events::<M, B>
+                        .before(events::handle_play_music_events::<M, B>)
+                        .before(events::handle_play_layered_music_events::<M>),
+                    events::handle_seek_music_events::<M, B>,
+                    events::handle_play_layered_music_events::<M>,
+                    events::handle_set_layer_volume_events::<M, B>,
+                    events::handle_stop_all_music_events::<M, B>
+                        .before(events::handle_play_music_events::<M, B>)
+                        .before(events::handle_play_layered_music_events::<M>),
+                    events::handle_fade_out_music_events::<M, B>,
+                    events::handle_fade_out_all_music_events::<M, B>,
+                    events::handle_play_stinger_events::<M, B>,
+                    events::handle_crossfade_music_events::<M, B>
+                        .before(events::handle_play_music_events::<M, B>)
+                        .before(events::handle_fade_out_music_events::<M, B>),
+                )
+                    .in_set(MsgAudioSet::EventHandling),
+                systems::enforce_sfx_concurrency::<M, B, AudioSource>
+                    .in_set(MsgAudioSet::Concurrency)
+                    .before(systems::enforce_global_voice_limit::<B>),
+                (
+                    systems::apply_volume_to_new_music::<M, M::Config, B>,
+                    systems::update_music_volume::<M, M::Config, B>.run_if(
+                        resource_changed::<M::Config>.or(resource_changed::<CategoryMixerState<M>>),
+                    ),
+                )
+                    .chain()
+                    .in_set(MsgAudioSet::VolumeApplication),
+                (
+                    systems::duck_music_for_stingers::<M>,
+                    systems::duck_music_for_voice::<M>,
+                )
+                    .in_set(MsgAudioSet::Fades),
+                now_playing::update_now_playing::<M, B>.after(MsgAudioSet::Fades),
+            ),
+        )
+    }
+
+    fn add_sfx_category_with<S, B>(&mut self) -> &mut Self
+    where
+        S: SfxCategory,
+        S::Config: AudioConfigTrait,
+        B: AudioBackend,
+    {
+        let options = self
+            .world()
+            .get_resource::<SfxOptions>()
+            .copied()
+            .unwrap_or_default();
+
+        self.add_message::<PlaySfx<S>>();
+        self.add_message::<PlaySfxBatch<S>>();
+        self.add_message::<FadeOutSfx<S>>();
+        self.add_message::<PlayLoopingSfx<S>>();
+        self.add_message::<StopLoopingSfx<S>>();
+        register_delayed::<PlaySfx<S>>(self);
+        register_delayed::<PlayLoopingSfx<S>>(self);
+        register_mixer::<S, B>(self);
+        self.init_resource::<SoundLibrary<S>>();
+
+        self.add_systems(
+            Update,
+            (
+                (
+                    events::handle_play_sfx_events::<S, B>.after(pool::recycle_pooled_sfx),
+                    events::handle_play_sfx_batch_events::<S, B>.after(pool::recycle_pooled_sfx),
+                    events::handle_fade_out_sfx_events::<S, B>,
+                    events::handle_play_looping_sfx_events::<S, B>,
+                    events::handle_stop_looping_sfx_events::<S, B>,
+                )
+                    .in_set(MsgAudioSet::EventHandling),
+                systems::enforce_sfx_concurrency::<S, B, AudioSource>
+                    .in_set(MsgAudioSet::Concurrency)
+                    .before(systems::enforce_global_voice_limit::<B>),
+                (
+                    systems::update_ambience_zones::<S, S::Config, B>,
+                    systems::update_blended_ambience::<S, S::Config, B>,
+                )
+                    .in_set(MsgAudioSet::Fades),
+                (
+                    systems::apply_volume_to_new_sfx::<S, S::Config, B>,
+                    systems::route_sfx_to_effect_bus::<S>,
+                    systems::play_sfx_echoes::<S>,
+                    systems::update_sfx_volume::<S, S::Config, B>.run_if(
+                        resource_changed::<S::Config>.or(resource_changed::<CategoryMixerState<S>>),
+                    ),
+                )
+                    .chain()
+                    .in_set(MsgAudioSet::VolumeApplication),
+            ),
+        );
+        if has_spatial_sink::<B>() {
+            self.add_systems(
+                Update,
+                (
+                    systems::apply_volume_to_new_sfx::<S, S::Config, B::Spatial>
+                        .before(systems::route_sfx_to_effect_bus::<S>),
+                    systems::update_sfx_volume::<S, S::Config, B::Spatial>
+                        .after(systems::play_sfx_echoes::<S>)
+                        .run_if(
+                            resource_changed::<S::Config>
+                                .or(resource_changed::<CategoryMixerState<S>>),
+                        ),
+                )
+                    .in_set(MsgAudioSet::VolumeApplication),
+            );
+        }
+
+        if options.time_scaled {
+            self.add_systems(
+                Update,
+                systems::apply_time_scale_to_sfx::<S, B>
+                    .after(systems::apply_category_speed::<S, B>)
+                    .in_set(MsgAudioSet::VolumeApplication),
+            );
+            if has_spatial_sink::<B>() {
+                self.add_systems(
+                    Update,
+                    systems::apply_time_scale_to_sfx::<S, B::Spatial>
+                        .after(systems::apply_category_speed::<S, B::Spatial>)
+                        .in_set(MsgAudioSet::VolumeApplication),
+                );
+            }
+        }
+
+        if options.pause_on_game_pause {
+            self.add_systems(
+                Update,
+                systems::virtual_time_paused
+                    .pipe(systems::sync_sfx_game_pause::<S, B>)
+                    .in_set(MsgAudioSet::VolumeApplication),
+            );
+            if has_spatial_sink::<B>() {
+                self.add_systems(
+                    Update,
+                    systems::virtual_time_paused
+                        .pipe(systems::sync_sfx_game_pause::<S, B::Spatial>)
+                        .in_set(MsgAudioSet::VolumeApplication),
+                );
+            }
+        }
+
+        self
+    }
+
+    fn add_voice_category_with<V, B>(&mut self) -> &mut Self
+    where
+        V: VoiceCategory,
+        V::Config: AudioConfigTrait,
+        B: AudioBackend,
+    {
+        self.add_message::<PlayVoiceLine<V>>();
+        self.add_message::<VoiceLineFinished<V>>();
+        self.init_resource::<DialogueQueue<V>>();
+        register_mixer::<V, B>(self);
+        if has_spatial_sink::<B>() {
+            self.add_systems(
+                Update,
+                (
+                    systems::apply_volume_to_new_voice::<V, V::Config, B::Spatial>,
+                    systems::update_voice_volume::<V, V::Config, B::Spatial>.run_if(
+                        resource_changed::<V::Config>.or(resource_changed::<CategoryMixerState<V>>),
+                    ),
+                )
+                    .chain()
+                    .in_set(MsgAudioSet::VolumeApplication),
+            );
+        }
+
+        self.add_systems(
+            Update,
+            (
+                (
+                    events::handle_play_voice_line_events::<V>,
+                    systems::advance_dialogue_queue::<V, B>,
+                )
+                    .chain()
+                    .in_set(MsgAudioSet::EventHandling),
+                (
+                    systems::apply_volume_to_new_voice::<V, V::Config, B>,
+                    systems::update_voice_volume::<V, V::Config, B>.run_if(
+                        resource_changed::<V::Config>.or(resource_changed::<CategoryMixerState<V>>),
+                    ),
+                )
+                    .chain()
+                    .in_set(MsgAudioSet::VolumeApplication),
+            ),
+        )
+    }
+}
+
+/// Registers the queue and release system for delayed messages of type `E`.
+fn register_delayed<E: Message>(app: &mut App) {
+    app.init_resource::<DelayedMessages<E>>();
+    app.add_systems(
+        Update,
+        delay::release_delayed_messages::<E>.before(MsgAudioSet::EventHandling),
+    );
+}
+
+/// Registers the mixer state and messages for a category type.
+fn register_mixer<A, B>(app: &mut App)
+where
+    A: AudioCategory,
+    A::Config: AudioConfigTrait,
+    B: AudioBackend,
+{
+    app.init_resource::<CategoryMixerState<A>>();
+    app.init_resource::<CategorySpeed<A>>();
+    app.init_resource::<EnvironmentProfiles<A>>();
+    app.init_resource::<AudioFocus>();
+    app.add_message::<MuteCategory<A>>();
+    app.add_message::<SoloCategory<A>>();
+    app.add_message::<PushMixerSnapshot<A>>();
+    app.add_message::<PopMixerSnapshot<A>>();
+    app.add_message::<SetAudioVolume>();
+    if has_spatial_sink::<B>() {
+        app.add_systems(
+            Update,
+            (
+                events::handle_set_audio_volume_events::<A, B::Spatial>
+                    .in_set(MsgAudioSet::EventHandling),
+                (
+                    focus::sync_focus_loss::<A, B::Spatial>,
+                    systems::apply_category_speed::<A, B::Spatial>,
+                )
+                    .in_set(MsgAudioSet::VolumeApplication),
+            ),
+        );
+    }
+    app.add_systems(
+        Update,
+        (
+            (
+                events::handle_mute_category_events::<A>,
+                events::handle_solo_category_events::<A>,
+                events::handle_push_mixer_snapshot_events::<A>,
+                events::handle_pop_mixer_snapshot_events::<A>,
+                events::handle_set_audio_volume_events::<A, B>,
+            )
+                .in_set(MsgAudioSet::EventHandling),
+            systems::advance_mixer_snapshots::<A>.in_set(MsgAudioSet::Fades),
+            focus::sync_focus_loss::<A, B>.in_set(MsgAudioSet::VolumeApplication),
+            limiter::apply_mix_limiter::<A>
+                .run_if(resource_changed::<MixLevel>)
+                .in_set(MsgAudioSet::Fades)
+                .after(limiter::update_mix_limiter::<B>),
+            (
+                muffle::apply_audio_muffle::<A>.run_if(resource_changed::<AudioMuffle>),
+                environment::apply_environment_profile::<A>,
+            )
+                .in_set(MsgAudioSet::VolumeApplication)
+                .before(systems::apply_category_speed::<A, B>)
+                .before(systems::apply_category_speed::<A, B::Spatial>),
+            systems::apply_category_speed::<A, B>.in_set(MsgAudioSet::VolumeApplication),
+        ),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestConfig, TestPlugin};
+
+    #[derive(Component, Clone, Copy, Default, Debug, PartialEq, Eq, Reflect)]
+    #[reflect(Component)]
+    enum TestDlcMusic {
+        #[default]
+        Expansion,
+    }
+
+    impl AudioCategory for TestDlcMusic {
+        type Config = TestConfig;
+        fn volume_multiplier(&self, config: &Self::Config) -> f32 {
+            config.music
+        }
+    }
+
+    impl MusicCategory for TestDlcMusic {}
+
+    #[test]
+    fn add_music_category_after_plugin() {
+        let mut app = test_app(TestPlugin::default());
+        app.add_music_category::<TestDlcMusic>();
+        app.update();
+
+        assert!(app
+            .world()
+            .contains_resource::<Messages<PlayMusic<TestDlcMusic>>>());
+    }
+}
@@ -0,0 +1,281 @@
+//! Distance attenuation for spatial sounds.
+//!
+//! A [`SpatialSettings`] component picks how a sound gets quieter with its
+//! distance to the [`SpatialListener`]. The gain is recomputed every frame
+//! and applied as the attenuation stage of the volume pipeline. On spatial
+//! sinks it comes on top of the panning and distance falloff of `bevy_audio`;
+//! non-spatial sounds get distance falloff without panning.
+
+use bevy::prelude::*;
+
+use crate::backend::AudioBackend;
+use crate::components::{
+    AudioVolumeState, FadeIn, FadeOut, NonSpatial, RampingVolume, TrackGain, VolumeTween,
+};
+use crate::spatial_2d::Spatial2d;
+
+/// Default distance below which a sound plays at full volume.
+pub const DEFAULT_MIN_DISTANCE: f32 = 1.0;
+
+/// Default distance beyond which a sound stops getting quieter.
+pub const DEFAULT_MAX_DISTANCE: f32 = 100.0;
+
+/// Curve mapping distance to gain.
+///
+/// Distances are clamped to the min and max distance of the
+/// [`SpatialSettings`] first, with `d` the clamped distance below.
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Attenuation {
+    /// `1 - rolloff * (d - min) / (max - min)`, silent at the max distance
+    /// with a rolloff of `1.0`.
+    Linear,
+    /// `min / (min + rolloff * (d - min))`, the physically plausible default.
+    #[default]
+    InverseDistance,
+    /// `(d / min) ^ -rolloff`.
+    Exponential,
+}
+
+/// Selects the distance attenuation of a sound positioned by its transform.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{Attenuation, SpatialSettings};
+///
+/// commands.spawn((
+///     SfxBundle::new(assets.load("sfx/waterfall.ogg"), GameSfx::Ambience).spatial(),
+///     SpatialSettings::new(Attenuation::Linear).with_distance(5.0, 40.0),
+///     Transform::from_xyz(10.0, 0.0, 0.0),
+/// ));
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpatialSettings {
+    /// The attenuation curve.
+    pub model: Attenuation,
+    /// Distance below which the sound plays at full volume.
+    pub min_distance: f32,
+    /// Distance beyond which the sound stops getting quieter.
+    pub max_distance: f32,
+    /// How fast the gain falls off, `1.0` for the plain curve.
+    pub rolloff: f32,
+}
+
+impl Default for SpatialSettings {
+    fn default() -> Self {
+        Self::new(Attenuation::default())
+    }
+}
+
+impl SpatialSettings {
+    /// Creates settings with the given curve and the default distances.
+    #[must_use]
+    pub fn new(model: Attenuation) -> Self {
+        Self {
+            model,
+            min_distance: DEFAULT_MIN_DISTANCE,
+            max_distance: DEFAULT_MAX_DISTANCE,
+            rolloff: 1.0,
+        }
+    }
+
+    /// Sets the min and max distance.
+    #[must_use]
+    pub fn with_distance(mut self, min: f32, max: f32) -> Self {
+        self.min_distance = min;
+        self.max_distance = max;
+        self
+    }
+
+    /// Sets the rolloff factor.
+    #[must_use]
+    pub fn with_rolloff(mut self, rolloff: f32) -> Self {
+        self.rolloff = rolloff;
+        self
+    }
+
+    /// Returns the gain at `distance` from the listener, from `0.0` to `1.0`.
+    #[must_use]
+    pub fn gain(&self, distance: f32) -> f32 {
+        let min = self.min_distance.max(f32::EPSILON);
+        let max = self.max_distance.max(min);
+        let d = distance.clamp(min, max);
+        let gain = match self.model {
+            Attenuation::Linear if max > min => 1.0 - self.rolloff * (d - min) / (max - min),
+            Attenuation::Linear => 1.0,
+            Attenuation::InverseDistance => min / (min + self.rolloff * (d - min)),
+            Attenuation::Exponential => (d / min).powf(-self.rolloff),
+        };
+        gain.clamp(0.0, 1.0)
+    }
+}
+
+/// Applies the [`SpatialSettings`] gain of sounds for their current distance
+/// to the [`SpatialListener`], measured on the 2D plane for [`Spatial2d`]
+/// sounds.
+///
+/// Fade-ins and volume ramps in progress are retargeted to the new volume,
+/// and the end value of a [`VolumeTween`] is scaled by the change in gain.
+/// Sounds fading out keep fading from where they are.
+pub fn apply_spatial_attenuation<B: AudioBackend>(
+    listeners: Query<&GlobalTransform, With<SpatialListener>>,
+    mut sounds: Query<
+        (
+            &SpatialSettings,
+            &GlobalTransform,
+            &mut AudioVolumeState,
+            Option<&TrackGain>,
+            &mut B::Sink,
+            Has<Spatial2d>,
+            Option<&mut FadeIn>,
+            Option<&mut VolumeTween>,
+            Option<&mut RampingVolume>,
+            Has<FadeOut>,
+        ),
+        Without<NonSpatial>,
+    >,
+) {
+    let Some(listener) = listeners.iter().next().map(GlobalTransform::translation) else {
+        return;
+    };
+    for (settings, transform, mut state, gain, mut sink, planar, fade_in, tween, ramp, fading) in
+        &mut sounds
+    {
+        let position = transform.translation();
+        let distance = if planar {
+            position.truncate().distance(listener.truncate())
+        } else {
+            position.distance(listener)
+        };
+        let attenuation = settings.gain(distance);
+        if state.attenuation == attenuation {
+            continue;
+        }
+        let previous = std::mem::replace(&mut state.attenuation, attenuation);
+        let volume = state.volume(gain);
+        let tweening = tween.is_some();
+        if let Some(mut tween) = tween {
+            if previous > 0.0 {
+                tween.target *= attenuation / previous;
+            }
+        }
+        if let Some(mut ramp) = ramp {
+            ramp.tween.target = volume;
+        } else if let Some(mut fade_in) = fade_in {
+            fade_in.target_volume = volume;
+        } else if !(fading || tweening) {
+            B::set_volume(&mut sink, volume);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestConfig, TestPlugin, TestSfx};
+    use crate::{AudioRequest, SfxBundle, TestAudioPlugin};
+    use std::time::Duration;
+
+    #[test]
+    fn gain_follows_attenuation_model() {
+        let linear = SpatialSettings::new(Attenuation::Linear).with_distance(10.0, 20.0);
+        assert_eq!(linear.gain(5.0), 1.0);
+        assert!((linear.gain(15.0) - 0.5).abs() < f32::EPSILON);
+        assert_eq!(linear.gain(30.0), 0.0);
+
+        let inverse = SpatialSettings::new(Attenuation::InverseDistance).with_distance(2.0, 100.0);
+        assert!((inverse.gain(4.0) - 0.5).abs() < f32::EPSILON);
+        assert!((inverse.with_rolloff(2.0).gain(4.0) - 1.0 / 3.0).abs() < 0.0001);
+
+        let exponential = SpatialSettings::new(Attenuation::Exponential).with_rolloff(2.0);
+        assert!((exponential.gain(2.0) - 0.25).abs() < f32::EPSILON);
+        assert!((exponential.gain(1000.0) - 0.0001).abs() < 0.00001);
+    }
+
+    #[test]
+    fn spatial_settings_attenuate_with_distance() {
+        let mut app = test_app((
+            TestPlugin::default().with_volume_ramp(Duration::ZERO),
+            TestAudioPlugin,
+        ));
+        app.insert_resource(TestConfig::FULL);
+        app.world_mut()
+            .spawn((SpatialListener::default(), GlobalTransform::IDENTITY));
+        let at = |x: f32| GlobalTransform::from_translation(Vec3::new(x, 0.0, 0.0));
+        let sound = app
+            .world_mut()
+            .spawn((
+                SfxBundle::new(Handle::default(), TestSfx::UI)
+                    .with_playback(PlaybackSettings::LOOP),
+                SpatialSettings::new(Attenuation::Linear).with_distance(10.0, 30.0),
+                at(20.0),
+            ))
+            .id();
+        let volume = |app: &App| {
+            crate::systems::extract_linear_volume(
+                app.world().get::<AudioSink>(sound).unwrap().volume(),
+            )
+        };
+
+        app.update();
+        app.update();
+        assert!((volume(&app) - 0.5).abs() < 1e-5);
+
+        *app.world_mut().get_mut::<GlobalTransform>(sound).unwrap() = at(5.0);
+        app.update();
+        assert!((volume(&app) - 1.0).abs() < 1e-5);
+        let state = app.world().get::<AudioVolumeState>(sound).unwrap();
+        assert_eq!(state.attenuation, 1.0);
+    }
+
+    #[test]
+    fn spatial_attenuation_retargets_fades_and_ramps() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.insert_resource(TestConfig::FULL);
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            Duration::from_millis(10),
+        ));
+        app.world_mut()
+            .spawn((SpatialListener::default(), GlobalTransform::IDENTITY));
+        let at = |x: f32| GlobalTransform::from_translation(Vec3::new(x, 0.0, 0.0));
+        let spawn = |app: &mut App| {
+            app.world_mut()
+                .spawn((
+                    SfxBundle::new(Handle::default(), TestSfx::UI)
+                        .with_playback(PlaybackSettings::LOOP),
+                    SpatialSettings::new(Attenuation::Linear).with_distance(10.0, 30.0),
+                    at(20.0),
+                ))
+                .id()
+        };
+        let fading = spawn(&mut app);
+        app.world_mut()
+            .entity_mut(fading)
+            .insert(FadeIn::from_secs(10.0));
+        let ramping = spawn(&mut app);
+        app.update();
+        app.update();
+
+        *app.world_mut().get_mut::<GlobalTransform>(fading).unwrap() = at(5.0);
+        app.update();
+        let fade = app.world().get::<FadeIn>(fading).unwrap();
+        assert!((fade.target_volume - 1.0).abs() < 1e-5);
+
+        app.world_mut().resource_mut::<TestConfig>().sfx = 0.5;
+        app.update();
+        *app.world_mut().get_mut::<GlobalTransform>(ramping).unwrap() = at(5.0);
+        app.update();
+        let state = *app.world().get::<AudioVolumeState>(ramping).unwrap();
+        assert_eq!(state.attenuation, 1.0);
+        let ramp = app.world().get::<RampingVolume>(ramping).unwrap();
+        assert!((ramp.tween.target - state.volume(None)).abs() < 1e-5);
+        for _ in 0..10 {
+            app.update();
+        }
+        let volume = app.world().get::<AudioSink>(ramping).unwrap().volume();
+        assert!((crate::systems::extract_linear_volume(volume) - state.volume(None)).abs() < 1e-5);
+    }
+}
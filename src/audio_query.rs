@@ -0,0 +1,165 @@
+//! Read-only access to the playing audio.
+//!
+//! [`AudioQuery`] bundles the queries gameplay code needs to ask what is
+//! playing, so it does not depend on the components the crate uses
+//! internally.
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use std::time::Duration;
+
+use crate::backend::{either_sink, AnySink, AudioBackend, BevyAudioBackend};
+use crate::conditions::is_playing;
+use crate::traits::{MusicCategory, SfxCategory};
+
+/// System parameter answering questions about the music of type `M` and the
+/// sound effects of type `S`, played through the backend `B`.
+///
+/// Sounds count as playing from spawn until despawn, including while they
+/// load or fade out, unless their sink is paused.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::AudioQuery;
+///
+/// fn boss_intro(audio: AudioQuery<GameMusic, GameSfx>, roar: Res<RoarHandle>) {
+///     if !audio.is_music_playing(GameMusic::Boss) && audio.active_count(&roar.0) == 0 {
+///         // ...
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct AudioQuery<'w, 's, M, S, B = BevyAudioBackend>
+where
+    M: MusicCategory,
+    S: SfxCategory,
+    B: AudioBackend,
+{
+    music: Query<
+        'w,
+        's,
+        (
+            &'static M,
+            Option<&'static <B as AudioBackend>::Sink>,
+            Option<&'static <<B as AudioBackend>::Spatial as AudioBackend>::Sink>,
+        ),
+    >,
+    sfx: Query<
+        'w,
+        's,
+        (
+            &'static S,
+            Option<&'static <B as AudioBackend>::Sink>,
+            Option<&'static <<B as AudioBackend>::Spatial as AudioBackend>::Sink>,
+        ),
+    >,
+    players: Query<'w, 's, &'static AudioPlayer, Or<(With<M>, With<S>)>>,
+    sinks: Query<'w, 's, AnySink<B>, Or<(With<M>, With<S>)>>,
+}
+
+impl<M, S, B> AudioQuery<'_, '_, M, S, B>
+where
+    M: MusicCategory,
+    S: SfxCategory,
+    B: AudioBackend,
+{
+    /// Returns `true` if music of the category plays.
+    #[must_use]
+    pub fn is_music_playing(&self, category: M) -> bool {
+        self.music.iter().any(|(playing, sink, spatial_sink)| {
+            *playing == category && is_playing::<B>((sink, spatial_sink))
+        })
+    }
+
+    /// Returns `true` if a sound effect of the category plays.
+    #[must_use]
+    pub fn is_sfx_playing(&self, category: S) -> bool {
+        self.sfx.iter().any(|(playing, sink, spatial_sink)| {
+            *playing == category && is_playing::<B>((sink, spatial_sink))
+        })
+    }
+
+    /// Returns the number of music and sound effect entities playing the
+    /// source, paused ones included.
+    #[must_use]
+    pub fn active_count(&self, source: impl Into<AssetId<AudioSource>>) -> usize {
+        let id = source.into();
+        self.players
+            .iter()
+            .filter(|player| player.0.id() == id)
+            .count()
+    }
+
+    /// Returns the current linear volume of a sound's sink, or `None` if it
+    /// has not started playing.
+    #[must_use]
+    pub fn current_volume(&self, entity: Entity) -> Option<f32> {
+        let sink = self.sinks.get(entity).ok()?;
+        Some(either_sink::<B>(sink).volume())
+    }
+
+    /// Returns the playback position of a sound, or `None` if it has not
+    /// started playing.
+    #[must_use]
+    pub fn position(&self, entity: Entity) -> Option<Duration> {
+        let sink = self.sinks.get(entity).ok()?;
+        Some(either_sink::<B>(sink).position())
+    }
+
+    /// Returns `true` if a sound's sink is paused.
+    #[must_use]
+    pub fn is_paused(&self, entity: Entity) -> bool {
+        self.sinks
+            .get(entity)
+            .is_ok_and(|sink| either_sink::<B>(sink).is_paused())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin, TestSfx};
+    use crate::{PlayMusic, PlaySfx, TestAudioPlugin};
+
+    #[test]
+    fn audio_query_reports_playing_audio() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        let handle = Handle::<AudioSource>::default();
+        app.world_mut()
+            .write_message(PlayMusic::new(handle.clone(), TestMusic::Main));
+        for _ in 0..2 {
+            app.world_mut()
+                .write_message(PlaySfx::new(handle.clone(), TestSfx::UI));
+        }
+        app.update();
+        app.update();
+        let sfx = app
+            .world_mut()
+            .query_filtered::<Entity, With<TestSfx>>()
+            .iter(app.world())
+            .next()
+            .unwrap();
+
+        let (music_playing, count, volume, paused) = app
+            .world_mut()
+            .run_system_once(move |audio: AudioQuery<TestMusic, TestSfx>| {
+                (
+                    audio.is_music_playing(TestMusic::Main),
+                    audio.active_count(&Handle::<AudioSource>::default()),
+                    audio.current_volume(sfx),
+                    audio.is_paused(sfx),
+                )
+            })
+            .unwrap();
+        assert!(music_playing);
+        assert_eq!(count, 3);
+        let sink = app.world().get::<AudioSink>(sfx).unwrap();
+        assert_eq!(
+            volume,
+            Some(crate::systems::extract_linear_volume(sink.volume()))
+        );
+        assert!(!paused);
+    }
+}
@@ -0,0 +1,429 @@
+//! Audio backend abstraction.
+//!
+//! The volume, speed, pause, fade, and concurrency systems drive playing
+//! sounds through [`AudioBackend`] instead of `bevy_audio`'s [`AudioSink`]
+//! directly, and the message handlers start sounds with
+//! [`AudioBackend::play`], so they can run against any engine whose
+//! per-sound handle is a component. [`BevyAudioBackend`] is the
+//! implementation used by [`MsgAudioPlugin`](crate::MsgAudioPlugin).
+//!
+//! Systems acting on one sound at a time are generic over the backend and
+//! registered once per sink type. Systems weighing sounds against each other,
+//! such as concurrency limits, read the sink of every sound through
+//! [`AnySink`], which covers a backend and its [`AudioBackend::Spatial`]
+//! twin, and change it with [`with_sink_mut`].
+//!
+//! Plugins take the backend as a type parameter that defaults to
+//! [`BevyAudioBackend`]. With the `kira` feature, `KiraAudioBackend` plays
+//! the same sources through `bevy_kira_audio`, mirroring each kira
+//! `AudioInstance` in a component sink. A backend whose spatial twin shares
+//! its sink type gets one copy of each system, see [`has_spatial_sink`].
+
+use bevy::{
+    audio::{Decodable, Volume},
+    ecs::{component::Mutable, system::SystemParam},
+    prelude::*,
+};
+use std::{any::TypeId, time::Duration};
+
+use crate::systems::extract_linear_volume;
+
+/// Operations the crate needs from an audio engine.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{audio_systems, AudioBackend};
+///
+/// struct MyBackend;
+///
+/// impl AudioBackend for MyBackend {
+///     type Sink = MyVoiceHandle;
+///     // ...
+/// }
+///
+/// app.add_systems(Update, audio_systems::process_fade_outs::<MyBackend>);
+/// ```
+pub trait AudioBackend: Send + Sync + 'static {
+    /// Component controlling one playing sound.
+    type Sink: Component<Mutability = Mutable>;
+
+    /// Backend of the sounds this engine plays spatially, when they use a
+    /// different [`Sink`](Self::Sink). Engines with a single sink type use
+    /// `Self`.
+    type Spatial: AudioBackend;
+
+    /// Starts playing `handle` on the entity.
+    ///
+    /// Sources are any [`Decodable`] asset, such as an [`AudioSource`] or a
+    /// [`Tone`](crate::Tone), so the crate's messages don't depend on the
+    /// engine. Engines with their own asset types decode them on playback.
+    fn play<P: Asset + Decodable>(
+        entity: &mut EntityCommands,
+        handle: Handle<P>,
+        playback: PlaybackSettings,
+    );
+
+    /// Returns the linear volume of a sound.
+    fn volume(sink: &Self::Sink) -> f32;
+
+    /// Sets the linear volume of a sound.
+    fn set_volume(sink: &mut Self::Sink, volume: f32);
+
+    /// Returns the playback speed of a sound.
+    fn speed(sink: &Self::Sink) -> f32;
+
+    /// Sets the playback speed of a sound.
+    fn set_speed(sink: &mut Self::Sink, speed: f32);
+
+    /// Returns `true` if the sound is paused.
+    fn is_paused(sink: &Self::Sink) -> bool;
+
+    /// Pauses a sound.
+    fn pause(sink: &mut Self::Sink);
+
+    /// Resumes a paused sound.
+    fn resume(sink: &mut Self::Sink);
+
+    /// Stops a sound for good.
+    fn stop(sink: &mut Self::Sink);
+
+    /// Returns `true` once a sound has played to its end.
+    fn is_finished(sink: &Self::Sink) -> bool;
+
+    /// Returns how far a sound has played, counted from the start of its
+    /// source and growing past the source's length while it loops.
+    fn position(sink: &Self::Sink) -> Duration;
+
+    /// Moves a sound to `position`, logging a warning if it cannot seek.
+    fn seek(sink: &mut Self::Sink, position: Duration);
+}
+
+/// [`AudioBackend`] for `bevy_audio`, the default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BevyAudioBackend;
+
+impl AudioBackend for BevyAudioBackend {
+    type Sink = AudioSink;
+    type Spatial = BevySpatialAudioBackend;
+
+    fn play<P: Asset + Decodable>(
+        entity: &mut EntityCommands,
+        handle: Handle<P>,
+        playback: PlaybackSettings,
+    ) {
+        entity.insert((AudioPlayer(handle), playback));
+    }
+
+    fn volume(sink: &AudioSink) -> f32 {
+        extract_linear_volume(sink.volume())
+    }
+
+    fn set_volume(sink: &mut AudioSink, volume: f32) {
+        sink.set_volume(Volume::Linear(volume));
+    }
+
+    fn speed(sink: &AudioSink) -> f32 {
+        AudioSinkPlayback::speed(sink)
+    }
+
+    fn set_speed(sink: &mut AudioSink, speed: f32) {
+        AudioSinkPlayback::set_speed(sink, speed);
+    }
+
+    fn is_paused(sink: &AudioSink) -> bool {
+        AudioSinkPlayback::is_paused(sink)
+    }
+
+    fn pause(sink: &mut AudioSink) {
+        AudioSinkPlayback::pause(sink);
+    }
+
+    fn resume(sink: &mut AudioSink) {
+        sink.play();
+    }
+
+    fn stop(sink: &mut AudioSink) {
+        AudioSinkPlayback::stop(sink);
+    }
+
+    fn is_finished(sink: &AudioSink) -> bool {
+        sink.empty()
+    }
+
+    fn position(sink: &AudioSink) -> Duration {
+        AudioSinkPlayback::position(sink)
+    }
+
+    fn seek(sink: &mut AudioSink, position: Duration) {
+        if let Err(err) = sink.try_seek(position) {
+            warn!("Failed to seek sound to {position:?}: {err:?}");
+        }
+    }
+}
+
+/// [`AudioBackend`] for spatial `bevy_audio` sounds, which play through a
+/// [`SpatialAudioSink`] instead of an [`AudioSink`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BevySpatialAudioBackend;
+
+impl AudioBackend for BevySpatialAudioBackend {
+    type Sink = SpatialAudioSink;
+    type Spatial = Self;
+
+    fn play<P: Asset + Decodable>(
+        entity: &mut EntityCommands,
+        handle: Handle<P>,
+        playback: PlaybackSettings,
+    ) {
+        entity.insert((AudioPlayer(handle), playback.with_spatial(true)));
+    }
+
+    fn volume(sink: &SpatialAudioSink) -> f32 {
+        extract_linear_volume(sink.volume())
+    }
+
+    fn set_volume(sink: &mut SpatialAudioSink, volume: f32) {
+        sink.set_volume(Volume::Linear(volume));
+    }
+
+    fn speed(sink: &SpatialAudioSink) -> f32 {
+        AudioSinkPlayback::speed(sink)
+    }
+
+    fn set_speed(sink: &mut SpatialAudioSink, speed: f32) {
+        AudioSinkPlayback::set_speed(sink, speed);
+    }
+
+    fn is_paused(sink: &SpatialAudioSink) -> bool {
+        AudioSinkPlayback::is_paused(sink)
+    }
+
+    fn pause(sink: &mut SpatialAudioSink) {
+        AudioSinkPlayback::pause(sink);
+    }
+
+    fn resume(sink: &mut SpatialAudioSink) {
+        sink.play();
+    }
+
+    fn stop(sink: &mut SpatialAudioSink) {
+        AudioSinkPlayback::stop(sink);
+    }
+
+    fn is_finished(sink: &SpatialAudioSink) -> bool {
+        sink.empty()
+    }
+
+    fn position(sink: &SpatialAudioSink) -> Duration {
+        AudioSinkPlayback::position(sink)
+    }
+
+    fn seek(sink: &mut SpatialAudioSink, position: Duration) {
+        if let Err(err) = sink.try_seek(position) {
+            warn!("Failed to seek sound to {position:?}: {err:?}");
+        }
+    }
+}
+
+/// Returns `true` if the [`AudioBackend::Spatial`] twin of `B` plays through
+/// a sink type of its own.
+///
+/// Plugins only register the twin's copy of a system when this holds, so
+/// engines with a single sink type don't process each sound twice.
+pub fn has_spatial_sink<B: AudioBackend>() -> bool {
+    TypeId::of::<B::Sink>() != TypeId::of::<<B::Spatial as AudioBackend>::Sink>()
+}
+
+/// Query data matching the sink of a sound played by `B` or its
+/// [`AudioBackend::Spatial`] twin.
+///
+/// Read it with [`either_sink`].
+pub type AnySink<B> = AnyOf<(
+    &'static <B as AudioBackend>::Sink,
+    &'static <<B as AudioBackend>::Spatial as AudioBackend>::Sink,
+)>;
+
+/// Sink of a sound matched by [`AnySink`].
+pub enum EitherSink<'a, B: AudioBackend> {
+    /// The sound plays through a [`AudioBackend::Sink`] of `B`.
+    Plain(&'a B::Sink),
+    /// The sound plays through a sink of the spatial twin of `B`.
+    Spatial(&'a <B::Spatial as AudioBackend>::Sink),
+}
+
+impl<B: AudioBackend> EitherSink<'_, B> {
+    /// Returns the linear volume of the sound.
+    #[must_use]
+    pub fn volume(&self) -> f32 {
+        match self {
+            Self::Plain(sink) => B::volume(sink),
+            Self::Spatial(sink) => B::Spatial::volume(sink),
+        }
+    }
+
+    /// Returns the playback speed of the sound.
+    #[must_use]
+    pub fn speed(&self) -> f32 {
+        match self {
+            Self::Plain(sink) => B::speed(sink),
+            Self::Spatial(sink) => B::Spatial::speed(sink),
+        }
+    }
+
+    /// Returns `true` if the sound is paused.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        match self {
+            Self::Plain(sink) => B::is_paused(sink),
+            Self::Spatial(sink) => B::Spatial::is_paused(sink),
+        }
+    }
+
+    /// Returns `true` once the sound has played to its end.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        match self {
+            Self::Plain(sink) => B::is_finished(sink),
+            Self::Spatial(sink) => B::Spatial::is_finished(sink),
+        }
+    }
+
+    /// Returns how far the sound has played.
+    #[must_use]
+    pub fn position(&self) -> Duration {
+        match self {
+            Self::Plain(sink) => B::position(sink),
+            Self::Spatial(sink) => B::Spatial::position(sink),
+        }
+    }
+}
+
+/// Returns the sink a sound matched by [`AnySink`] plays through.
+pub fn either_sink<'a, B: AudioBackend>(
+    (sink, spatial_sink): (
+        Option<&'a B::Sink>,
+        Option<&'a <B::Spatial as AudioBackend>::Sink>,
+    ),
+) -> EitherSink<'a, B> {
+    match (sink, spatial_sink) {
+        (Some(sink), _) => EitherSink::Plain(sink),
+        (None, Some(sink)) => EitherSink::Spatial(sink),
+        (None, None) => unreachable!("AnyOf matches at least one sink"),
+    }
+}
+
+/// Runs `plain` or `spatial` on the sink `entity` plays through, returning
+/// `false` if it has neither.
+///
+/// The sinks come from the last two queries of a [`ParamSet`], which keeps
+/// them from conflicting with each other when the spatial twin of `B` is `B`
+/// itself, and with a first parameter reading the same sounds, typically
+/// through [`AnySink`].
+pub fn with_sink_mut<B: AudioBackend, P: SystemParam>(
+    sinks: &mut ParamSet<(
+        P,
+        Query<&mut B::Sink>,
+        Query<&mut <B::Spatial as AudioBackend>::Sink>,
+    )>,
+    entity: Entity,
+    plain: impl FnOnce(&mut B::Sink),
+    spatial: impl FnOnce(&mut <B::Spatial as AudioBackend>::Sink),
+) -> bool {
+    if let Ok(mut sink) = sinks.p1().get_mut(entity) {
+        plain(&mut sink);
+        return true;
+    }
+    if let Ok(mut sink) = sinks.p2().get_mut(entity) {
+        spatial(&mut sink);
+        return true;
+    }
+    false
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Backend whose sinks are plain components, so tests can move and
+    /// inspect playback positions.
+    #[derive(Default)]
+    pub(crate) struct MockBackend;
+
+    #[derive(Component, Debug, Default)]
+    pub(crate) struct MockSink {
+        pub volume: f32,
+        pub speed: f32,
+        pub paused: bool,
+        pub finished: bool,
+        pub position: Duration,
+        pub seeks: Vec<Duration>,
+    }
+
+    impl AudioBackend for MockBackend {
+        type Sink = MockSink;
+        type Spatial = Self;
+
+        fn play<P: Asset + Decodable>(
+            entity: &mut EntityCommands,
+            handle: Handle<P>,
+            playback: PlaybackSettings,
+        ) {
+            entity.insert((
+                AudioPlayer(handle),
+                MockSink {
+                    volume: extract_linear_volume(playback.volume),
+                    speed: playback.speed,
+                    paused: playback.paused,
+                    ..default()
+                },
+                playback,
+            ));
+        }
+
+        fn volume(sink: &MockSink) -> f32 {
+            sink.volume
+        }
+
+        fn set_volume(sink: &mut MockSink, volume: f32) {
+            sink.volume = volume;
+        }
+
+        fn speed(sink: &MockSink) -> f32 {
+            sink.speed
+        }
+
+        fn set_speed(sink: &mut MockSink, speed: f32) {
+            sink.speed = speed;
+        }
+
+        fn is_paused(sink: &MockSink) -> bool {
+            sink.paused
+        }
+
+        fn pause(sink: &mut MockSink) {
+            sink.paused = true;
+        }
+
+        fn resume(sink: &mut MockSink) {
+            sink.paused = false;
+        }
+
+        fn stop(sink: &mut MockSink) {
+            sink.finished = true;
+        }
+
+        fn is_finished(sink: &MockSink) -> bool {
+            sink.finished
+        }
+
+        fn position(sink: &MockSink) -> Duration {
+            sink.position
+        }
+
+        fn seek(sink: &mut MockSink, position: Duration) {
+            sink.position = position;
+            sink.seeks.push(position);
+        }
+    }
+}
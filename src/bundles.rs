@@ -1,20 +1,24 @@
 //! Audio bundles for spawning music and sound effects.
 
 use bevy::prelude::*;
+use std::time::Duration;
 
-use crate::components::{MaxConcurrent, PlaybackRandomizer};
+use crate::components::{
+    ConcurrencyPolicy, FadeIn, LoopingSfx, MaxConcurrent, PlayChance, PlaybackLabel,
+    PlaybackRandomizer, SpawnRequest, StereoPan, StereoPanRange,
+};
+use crate::request::{AudioRequest, ConcurrencyRequest, SfxRequest};
 use crate::traits::{MusicCategory, SfxCategory};
 
 /// Default maximum concurrent instances for sound effects.
 pub const DEFAULT_MAX_CONCURRENT: u32 = 5;
 
-/// Default timer interval for concurrency checking (in seconds).
-pub const DEFAULT_CONCURRENCY_INTERVAL: f32 = 0.5;
-
 /// Bundle for spawning music audio.
 ///
 /// Music uses looping playback by default. The category determines
-/// which volume setting applies from the audio configuration.
+/// which volume setting applies from the audio configuration. Concurrency is
+/// unlimited unless set with [`ConcurrencyRequest::with_max_concurrent`] or
+/// [`ConcurrencyRequest::unique`].
 ///
 /// # Example
 ///
@@ -34,17 +38,17 @@ pub struct MusicBundle<C: MusicCategory> {
     pub playback: PlaybackSettings,
     /// The music category for volume control.
     pub category: C,
+    /// Concurrency limiting component.
+    pub max_concurrent: MaxConcurrent,
+    /// Randomization, fade-in, and delay applied when the bundle is spawned.
+    pub request: SpawnRequest,
 }
 
 impl<C: MusicCategory> MusicBundle<C> {
     /// Creates a new music bundle with looping playback.
     #[must_use]
     pub fn new(handle: Handle<AudioSource>, category: C) -> Self {
-        Self {
-            audio_player: AudioPlayer(handle),
-            playback: PlaybackSettings::LOOP,
-            category,
-        }
+        Self::with_settings(handle, category, PlaybackSettings::LOOP)
     }
 
     /// Creates a new music bundle with custom playback settings.
@@ -55,13 +59,50 @@ impl<C: MusicCategory> MusicBundle<C> {
         playback: PlaybackSettings,
     ) -> Self {
         Self {
+            max_concurrent: MaxConcurrent::new(&handle, u32::MAX),
             audio_player: AudioPlayer(handle),
             playback,
             category,
+            request: SpawnRequest::default(),
         }
     }
 }
 
+impl<C: MusicCategory> AudioRequest for MusicBundle<C> {
+    fn playback_mut(&mut self) -> &mut PlaybackSettings {
+        &mut self.playback
+    }
+
+    fn randomizer_mut(&mut self) -> Option<&mut PlaybackRandomizer> {
+        self.request.randomizer.as_mut()
+    }
+
+    fn fade_in_mut(&mut self) -> &mut Option<FadeIn> {
+        &mut self.request.fade_in
+    }
+
+    fn delay_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.request.delay
+    }
+
+    fn with_randomizer(mut self, randomizer: PlaybackRandomizer) -> Self {
+        self.request.randomizer = Some(randomizer);
+        self
+    }
+}
+
+impl<C: MusicCategory> ConcurrencyRequest for MusicBundle<C> {
+    fn with_max_concurrent(mut self, max: u32) -> Self {
+        self.max_concurrent.max = max;
+        self
+    }
+
+    fn with_concurrency_policy(mut self, policy: ConcurrencyPolicy) -> Self {
+        self.max_concurrent.policy = policy;
+        self
+    }
+}
+
 /// Bundle for spawning sound effect audio.
 ///
 /// Sound effects use despawn-on-finish playback by default.
@@ -96,6 +137,14 @@ pub struct SfxBundle<C: SfxCategory> {
     pub category: C,
     /// Concurrency limiting component.
     pub max_concurrent: MaxConcurrent,
+    /// Probability that the sound plays.
+    pub chance: PlayChance,
+    /// Stereo position of the sound.
+    pub pan: StereoPan,
+    /// Range the stereo position is rolled from when the sound is spawned.
+    pub pan_range: StereoPanRange,
+    /// Randomization, fade-in, and delay applied when the bundle is spawned.
+    pub request: SpawnRequest,
 }
 
 impl<C: SfxCategory> SfxBundle<C> {
@@ -105,90 +154,148 @@ impl<C: SfxCategory> SfxBundle<C> {
     #[must_use]
     pub fn new(handle: Handle<AudioSource>, category: C) -> Self {
         Self {
-            audio_player: AudioPlayer(handle.clone()),
+            max_concurrent: MaxConcurrent::new(&handle, DEFAULT_MAX_CONCURRENT),
+            audio_player: AudioPlayer(handle),
             playback: PlaybackSettings::DESPAWN,
             category,
-            max_concurrent: MaxConcurrent::new(handle, DEFAULT_MAX_CONCURRENT),
+            chance: PlayChance::default(),
+            pan: StereoPan::default(),
+            pan_range: StereoPanRange::default(),
+            request: SpawnRequest::default(),
         }
     }
+}
 
-    /// Sets the volume randomization range.
-    ///
-    /// The actual volume will be randomly chosen between `min` and `max`.
-    #[must_use]
-    pub fn with_volume(mut self, min: f32, max: f32) -> Self {
-        PlaybackRandomizer::new()
-            .with_volume(min, max)
-            .apply(&mut self.playback);
-        self
+impl<C: SfxCategory> AudioRequest for SfxBundle<C> {
+    fn playback_mut(&mut self) -> &mut PlaybackSettings {
+        &mut self.playback
     }
 
-    /// Sets the speed (pitch) randomization range.
-    ///
-    /// The actual speed will be randomly chosen between `min` and `max`.
-    #[must_use]
-    pub fn with_speed(mut self, min: f32, max: f32) -> Self {
-        PlaybackRandomizer::new()
-            .with_speed(min, max)
-            .apply(&mut self.playback);
+    fn randomizer_mut(&mut self) -> Option<&mut PlaybackRandomizer> {
+        self.request.randomizer.as_mut()
+    }
+
+    fn fade_in_mut(&mut self) -> &mut Option<FadeIn> {
+        &mut self.request.fade_in
+    }
+
+    fn delay_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.request.delay
+    }
+
+    fn with_randomizer(mut self, randomizer: PlaybackRandomizer) -> Self {
+        self.request.randomizer = Some(randomizer);
         self
     }
+}
 
-    /// Sets the maximum number of concurrent instances of this sound.
-    #[must_use]
-    pub fn with_max_concurrent(mut self, max: u32) -> Self {
+impl<C: SfxCategory> ConcurrencyRequest for SfxBundle<C> {
+    fn with_max_concurrent(mut self, max: u32) -> Self {
         self.max_concurrent.max = max;
         self
     }
 
-    /// Applies standard randomization (speed 0.7-1.3, volume 0.6-1.0).
-    #[must_use]
-    pub fn randomized(mut self) -> Self {
-        PlaybackRandomizer::standard().apply(&mut self.playback);
+    fn with_concurrency_policy(mut self, policy: ConcurrencyPolicy) -> Self {
+        self.max_concurrent.policy = policy;
         self
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl<C: SfxCategory> SfxRequest for SfxBundle<C> {
+    fn with_chance(mut self, chance: f32) -> Self {
+        self.chance = PlayChance(chance);
+        self
+    }
 
-    #[derive(Component, Clone, Copy, Default, Debug, PartialEq, Eq, Reflect)]
-    #[reflect(Component)]
-    enum TestMusic {
-        #[default]
-        Main,
+    fn with_pan(mut self, pan: f32) -> Self {
+        self.pan = StereoPan(pan);
+        self.pan_range = StereoPanRange::default();
+        self
     }
 
-    #[derive(Resource, Clone, Default)]
-    struct TestConfig;
+    /// The pan is rolled from the [`AudioRng`](crate::AudioRng) when the
+    /// bundle is spawned.
+    fn with_pan_range(mut self, min: f32, max: f32) -> Self {
+        self.pan_range = StereoPanRange::new(min, max);
+        self
+    }
+}
 
-    impl crate::traits::AudioCategory for TestMusic {
-        type Config = TestConfig;
+/// Bundle for spawning a persistent looping sound effect, such as rain or an
+/// engine.
+///
+/// Unlike [`SfxBundle`], the sound loops until it is stopped and is not
+/// concurrency limited. The label identifies the loop for
+/// [`StopLoopingSfx`](crate::StopLoopingSfx).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{LoopingSfxBundle, StopLoopingSfx};
+///
+/// commands.spawn(LoopingSfxBundle::new(rain_handle, GameSfx::Ambience, "rain"));
+/// // Later
+/// stop.write(StopLoopingSfx::new(GameSfx::Ambience).with_label("rain"));
+/// ```
+#[derive(Bundle)]
+pub struct LoopingSfxBundle<C: SfxCategory> {
+    /// The audio player component.
+    pub audio_player: AudioPlayer,
+    /// Playback settings (defaults to looping).
+    pub playback: PlaybackSettings,
+    /// The sound effect category for volume control.
+    pub category: C,
+    /// Label identifying the loop.
+    pub label: PlaybackLabel,
+    /// Marker for looping sound effects.
+    pub looping: LoopingSfx,
+    /// Randomization, fade-in, and delay applied when the bundle is spawned.
+    pub request: SpawnRequest,
+}
 
-        fn volume_multiplier(&self, _config: &Self::Config) -> f32 {
-            1.0
+impl<C: SfxCategory> LoopingSfxBundle<C> {
+    /// Creates a new looping sound effect bundle.
+    #[must_use]
+    pub fn new(handle: Handle<AudioSource>, category: C, label: impl Into<PlaybackLabel>) -> Self {
+        Self {
+            audio_player: AudioPlayer(handle),
+            playback: PlaybackSettings::LOOP,
+            category,
+            label: label.into(),
+            looping: LoopingSfx,
+            request: SpawnRequest::default(),
         }
     }
+}
 
-    impl MusicCategory for TestMusic {}
+impl<C: SfxCategory> AudioRequest for LoopingSfxBundle<C> {
+    fn playback_mut(&mut self) -> &mut PlaybackSettings {
+        &mut self.playback
+    }
 
-    #[derive(Component, Clone, Copy, Default, Debug, PartialEq, Eq, Reflect)]
-    #[reflect(Component)]
-    enum TestSfx {
-        #[default]
-        UI,
+    fn randomizer_mut(&mut self) -> Option<&mut PlaybackRandomizer> {
+        self.request.randomizer.as_mut()
     }
 
-    impl crate::traits::AudioCategory for TestSfx {
-        type Config = TestConfig;
+    fn fade_in_mut(&mut self) -> &mut Option<FadeIn> {
+        &mut self.request.fade_in
+    }
 
-        fn volume_multiplier(&self, _config: &Self::Config) -> f32 {
-            1.0
-        }
+    fn delay_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.request.delay
     }
 
-    impl SfxCategory for TestSfx {}
+    fn with_randomizer(mut self, randomizer: PlaybackRandomizer) -> Self {
+        self.request.randomizer = Some(randomizer);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin, TestSfx};
+    use crate::{AudioRng, TestAudioPlugin};
 
     #[test]
     fn music_bundle_uses_loop_playback() {
@@ -219,6 +326,16 @@ mod tests {
         assert_eq!(bundle.max_concurrent.max, DEFAULT_MAX_CONCURRENT);
     }
 
+    #[test]
+    fn sfx_bundle_fixed_volume_and_speed() {
+        let bundle = SfxBundle::new(Handle::default(), TestSfx::UI)
+            .volume(0.3)
+            .speed(1.2);
+
+        assert_eq!(bundle.playback.volume, bevy::audio::Volume::Linear(0.3));
+        assert!((bundle.playback.speed - 1.2).abs() < f32::EPSILON);
+    }
+
     #[test]
     fn sfx_bundle_with_max_concurrent() {
         let handle = Handle::default();
@@ -226,4 +343,26 @@ mod tests {
 
         assert_eq!(bundle.max_concurrent.max, 3);
     }
+
+    #[test]
+    fn bundle_randomization_rolls_from_the_audio_rng() {
+        fn rolled_speeds(seed: u64) -> Vec<f32> {
+            let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+            app.insert_resource(AudioRng::seeded(seed));
+            (0..4)
+                .map(|_| {
+                    let entity = app
+                        .world_mut()
+                        .spawn(SfxBundle::new(Handle::default(), TestSfx::UI).with_speed(0.5, 2.0))
+                        .id();
+                    app.world().get::<PlaybackSettings>(entity).unwrap().speed
+                })
+                .collect()
+        }
+
+        let speeds = rolled_speeds(7);
+        assert_eq!(speeds, rolled_speeds(7));
+        assert!(speeds.iter().all(|speed| (0.5..=2.0).contains(speed)));
+        assert!(speeds.windows(2).any(|pair| pair[0] != pair[1]));
+    }
 }
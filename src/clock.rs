@@ -0,0 +1,387 @@
+//! Musical time for playing tracks.
+//!
+//! Tracks with a [`MusicTempo`] are followed by the [`MusicClock`], which
+//! emits [`Beat`](crate::Beat) and [`Bar`](crate::Bar) messages as their
+//! playback position crosses beat boundaries. Beats count from the start of
+//! the track again each time a looping track wraps around.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use std::time::Duration;
+
+use crate::easing::Easing;
+
+/// Tempo and meter of a music track.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{MusicTempo, PlayMusic};
+///
+/// messages.write(
+///     PlayMusic::new(handle, GameMusic::Combat).with_tempo(MusicTempo::new(128.0, 4)),
+/// );
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MusicTempo {
+    /// Beats per minute.
+    pub bpm: f32,
+    /// Beats per bar (the time signature numerator).
+    pub beats_per_bar: u32,
+    /// Position of the first downbeat, e.g. to skip a pickup or silence.
+    pub offset: Duration,
+}
+
+impl MusicTempo {
+    /// Creates a new tempo with the first downbeat at the start of the track.
+    #[must_use]
+    pub fn new(bpm: f32, beats_per_bar: u32) -> Self {
+        Self {
+            bpm,
+            beats_per_bar: beats_per_bar.max(1),
+            offset: Duration::ZERO,
+        }
+    }
+
+    /// Sets the position of the first downbeat.
+    #[must_use]
+    pub fn with_offset(mut self, offset: Duration) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Returns the duration of one beat.
+    #[must_use]
+    pub fn beat_duration(&self) -> Duration {
+        Duration::from_secs_f32(60.0 / self.bpm.max(f32::EPSILON))
+    }
+
+    /// Returns the duration of one bar.
+    #[must_use]
+    pub fn bar_duration(&self) -> Duration {
+        self.beat_duration() * self.beats_per_bar
+    }
+
+    /// Returns the index of the beat playing at `position`, or `None` before
+    /// the first downbeat.
+    #[must_use]
+    pub fn beat_at(&self, position: Duration) -> Option<u64> {
+        let elapsed = position.checked_sub(self.offset)?;
+        Some((elapsed.as_secs_f64() / self.beat_duration().as_secs_f64()) as u64)
+    }
+
+    /// Returns the time from `position` until the next beat starts.
+    #[must_use]
+    pub fn time_to_next_beat(&self, position: Duration) -> Duration {
+        self.time_to_next(position, self.beat_duration())
+    }
+
+    /// Returns the time from `position` until the next bar starts.
+    #[must_use]
+    pub fn time_to_next_bar(&self, position: Duration) -> Duration {
+        self.time_to_next(position, self.bar_duration())
+    }
+
+    fn time_to_next(&self, position: Duration, unit: Duration) -> Duration {
+        let Some(elapsed) = position.checked_sub(self.offset) else {
+            return self.offset - position;
+        };
+        let unit = unit.as_secs_f64();
+        let into = elapsed.as_secs_f64() % unit;
+        Duration::from_secs_f64(unit - into)
+    }
+}
+
+/// When a music transition takes effect.
+///
+/// Quantized transitions wait for the next beat or bar boundary of the track's
+/// [`MusicTempo`] so switches land in time with the music. Tracks without a
+/// tempo always transition immediately.
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Quantize {
+    /// Transition right away.
+    #[default]
+    Immediate,
+    /// Wait for the next beat.
+    NextBeat,
+    /// Wait for the next bar.
+    NextBar,
+}
+
+impl Quantize {
+    /// Returns how long to wait from `position` before transitioning.
+    #[must_use]
+    pub fn delay(&self, tempo: &MusicTempo, position: Duration) -> Duration {
+        match self {
+            Self::Immediate => Duration::ZERO,
+            Self::NextBeat => tempo.time_to_next_beat(position),
+            Self::NextBar => tempo.time_to_next_bar(position),
+        }
+    }
+}
+
+/// A music transition waiting for a beat or bar boundary.
+///
+/// Inserted by quantized [`StopMusic`](crate::StopMusic) and
+/// [`FadeOutMusic`](crate::FadeOutMusic) messages. Once playback reaches
+/// `at`, the track is stopped, or starts fading out if `fade` is set.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct QuantizedStop {
+    /// Playback position at which the transition happens.
+    pub at: Duration,
+    /// Playback position when the transition was scheduled.
+    pub from: Duration,
+    /// Fade-out duration, or `None` to stop immediately.
+    pub fade: Option<Duration>,
+    /// Curve applied to the fade-out.
+    #[reflect(ignore)]
+    pub easing: Easing,
+}
+
+impl QuantizedStop {
+    /// Schedules a transition at the boundary chosen by `quantize`.
+    #[must_use]
+    pub fn new(quantize: Quantize, tempo: &MusicTempo, position: Duration) -> Self {
+        Self {
+            at: position + quantize.delay(tempo, position),
+            from: position,
+            fade: None,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Fades out over `duration` instead of stopping.
+    #[must_use]
+    pub fn with_fade(mut self, duration: Duration, easing: Easing) -> Self {
+        self.fade = Some(duration);
+        self.easing = easing;
+        self
+    }
+
+    /// Returns `true` once playback has reached the boundary.
+    ///
+    /// A looping track that wraps around before the boundary also counts.
+    #[must_use]
+    pub fn is_due(&self, position: Duration) -> bool {
+        position >= self.at || position < self.from
+    }
+}
+
+/// Musical position of a clocked track.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClockState {
+    /// The track's tempo.
+    pub tempo: MusicTempo,
+    /// Position within the track when the clock was last updated.
+    pub position: Duration,
+    /// How many times the track has looped.
+    pub loops: u64,
+    /// Index of the current beat since the first downbeat.
+    pub beat: Option<u64>,
+}
+
+impl ClockState {
+    /// Returns the index of the current bar.
+    #[must_use]
+    pub fn bar(&self) -> Option<u64> {
+        self.beat
+            .map(|beat| beat / u64::from(self.tempo.beats_per_bar))
+    }
+
+    /// Returns the current beat within its bar, starting at `0`.
+    #[must_use]
+    pub fn beat_in_bar(&self) -> Option<u32> {
+        self.beat
+            .map(|beat| (beat % u64::from(self.tempo.beats_per_bar)) as u32)
+    }
+
+    /// Returns the time until the next beat starts.
+    #[must_use]
+    pub fn time_to_next_beat(&self) -> Duration {
+        self.tempo.time_to_next_beat(self.position)
+    }
+
+    /// Returns the time until the next bar starts.
+    #[must_use]
+    pub fn time_to_next_bar(&self) -> Duration {
+        self.tempo.time_to_next_bar(self.position)
+    }
+}
+
+/// Resource tracking the musical position of every track with a [`MusicTempo`].
+///
+/// Updated each frame from the sinks' playback positions. Read it to query
+/// the current beat, or listen for [`Beat`](crate::Beat) and
+/// [`Bar`](crate::Bar) messages.
+#[derive(Resource, Debug, Default)]
+pub struct MusicClock {
+    tracks: HashMap<Entity, ClockState>,
+}
+
+impl MusicClock {
+    /// Returns the clock state of a track.
+    #[must_use]
+    pub fn track(&self, entity: Entity) -> Option<&ClockState> {
+        self.tracks.get(&entity)
+    }
+
+    /// Returns all clocked tracks.
+    pub fn tracks(&self) -> impl Iterator<Item = (Entity, &ClockState)> {
+        self.tracks.iter().map(|(entity, state)| (*entity, state))
+    }
+
+    /// Updates a track's sink position and returns the index of every beat
+    /// crossed since the last update, in order.
+    ///
+    /// The sink position keeps growing while a track loops, so it is split
+    /// into passes of the track's `length` when that is known. A newly
+    /// followed track, or one that jumped backwards, only reports its
+    /// current beat.
+    pub(crate) fn advance(
+        &mut self,
+        entity: Entity,
+        tempo: MusicTempo,
+        position: Duration,
+        length: Option<Duration>,
+    ) -> Vec<u64> {
+        let length = length.filter(|length| !length.is_zero());
+        let (loops, position) = match length {
+            Some(length) => (
+                (position.as_nanos() / length.as_nanos()) as u64,
+                Duration::from_nanos((position.as_nanos() % length.as_nanos()) as u64),
+            ),
+            None => (0, position),
+        };
+        let beat = tempo.beat_at(position);
+        let state = ClockState {
+            tempo,
+            position,
+            loops,
+            beat,
+        };
+        let Some(previous) = self.tracks.insert(entity, state) else {
+            return beat.into_iter().collect();
+        };
+        if (loops, position) < (previous.loops, previous.position) {
+            return beat.into_iter().collect();
+        }
+
+        // Last beat starting before the end of the track
+        let last_beat =
+            length.and_then(|length| tempo.beat_at(length.checked_sub(Duration::from_nanos(1))?));
+        let mut crossed = Vec::new();
+        for pass in previous.loops..=loops {
+            let first = match previous.beat {
+                Some(beat) if pass == previous.loops => beat + 1,
+                _ => 0,
+            };
+            let last = if pass == loops { beat } else { last_beat };
+            if let Some(last) = last {
+                crossed.extend(first..=last);
+            }
+        }
+        crossed
+    }
+
+    /// Drops tracks that are no longer playing.
+    pub(crate) fn retain(&mut self, mut keep: impl FnMut(Entity) -> bool) {
+        self.tracks.retain(|entity, _| keep(*entity));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn beat_and_bar_durations() {
+        let tempo = MusicTempo::new(120.0, 4);
+        assert_eq!(tempo.beat_duration(), Duration::from_millis(500));
+        assert_eq!(tempo.bar_duration(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn beat_at_respects_offset() {
+        let tempo = MusicTempo::new(120.0, 4).with_offset(Duration::from_secs(1));
+        assert_eq!(tempo.beat_at(Duration::from_millis(500)), None);
+        assert_eq!(tempo.beat_at(Duration::from_millis(1000)), Some(0));
+        assert_eq!(tempo.beat_at(Duration::from_millis(2600)), Some(3));
+    }
+
+    #[test]
+    fn time_to_next_bar() {
+        let tempo = MusicTempo::new(120.0, 4);
+        let remaining = tempo.time_to_next_bar(Duration::from_millis(2500));
+        assert!((remaining.as_secs_f32() - 1.5).abs() < 0.001);
+
+        let tempo = tempo.with_offset(Duration::from_secs(1));
+        assert_eq!(
+            tempo.time_to_next_bar(Duration::from_millis(250)),
+            Duration::from_millis(750)
+        );
+    }
+
+    #[test]
+    fn clock_reports_every_crossed_beat() {
+        let mut clock = MusicClock::default();
+        let entity = Entity::from_raw_u32(1).unwrap();
+        let tempo = MusicTempo::new(120.0, 4);
+        let mut advance =
+            |millis| clock.advance(entity, tempo, Duration::from_millis(millis), None);
+
+        assert_eq!(advance(0), [0]);
+        assert_eq!(advance(200), Vec::<u64>::new());
+        // A long frame spans several beats
+        assert_eq!(advance(2100), [1, 2, 3, 4]);
+
+        let state = clock.track(entity).unwrap();
+        assert_eq!(state.bar(), Some(1));
+        assert_eq!(state.beat_in_bar(), Some(0));
+
+        // Seeking backwards only reports the beat it lands on
+        let mut advance =
+            |millis| clock.advance(entity, tempo, Duration::from_millis(millis), None);
+        assert_eq!(advance(10), [0]);
+    }
+
+    #[test]
+    fn clock_wraps_looping_tracks_by_their_length() {
+        let mut clock = MusicClock::default();
+        let entity = Entity::from_raw_u32(1).unwrap();
+        let tempo = MusicTempo::new(120.0, 4);
+        let length = Some(Duration::from_millis(1800));
+        let mut advance =
+            |millis| clock.advance(entity, tempo, Duration::from_millis(millis), length);
+
+        assert_eq!(advance(1200), [2]);
+        // Beat 3 of the first pass, then the start of the second one
+        assert_eq!(advance(2000), [3, 0]);
+        assert_eq!(advance(2400), [1]);
+
+        let state = clock.track(entity).unwrap();
+        assert_eq!(state.loops, 1);
+        assert_eq!(state.position, Duration::from_millis(600));
+    }
+
+    #[test]
+    fn quantize_delays_to_boundary() {
+        let tempo = MusicTempo::new(120.0, 4);
+        let position = Duration::from_millis(2100);
+        assert_eq!(Quantize::Immediate.delay(&tempo, position), Duration::ZERO);
+        assert!((Quantize::NextBeat.delay(&tempo, position).as_secs_f32() - 0.4).abs() < 0.001);
+        assert!((Quantize::NextBar.delay(&tempo, position).as_secs_f32() - 1.9).abs() < 0.001);
+    }
+
+    #[test]
+    fn quantized_stop_is_due_at_boundary_or_loop() {
+        let tempo = MusicTempo::new(120.0, 4);
+        let stop = QuantizedStop::new(Quantize::NextBar, &tempo, Duration::from_millis(2100));
+
+        assert!(!stop.is_due(Duration::from_millis(3000)));
+        assert!(stop.is_due(Duration::from_millis(4000)));
+        assert!(stop.is_due(Duration::from_millis(50)));
+    }
+}
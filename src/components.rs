@@ -1,124 +1,972 @@
 //! Audio components for tracking concurrency and playback settings.
 
-use bevy::{audio::Volume, platform::collections::HashMap, prelude::*};
+use bevy::{
+    asset::UntypedAssetId,
+    audio::Volume,
+    ecs::{lifecycle::HookContext, world::DeferredWorld},
+    platform::collections::HashMap,
+    prelude::*,
+};
 use rand::prelude::*;
+use std::borrow::Cow;
 use std::time::Duration;
 
+use crate::easing::Easing;
+use crate::rng::random_between;
+
 /// Component that limits the maximum concurrent instances of a sound.
 ///
-/// When more than `max` sounds with the same `handle` are playing,
-/// the excess sounds are despawned (keeping the first N spawned).
+/// When more than `max` sounds with the same `source` are playing, the
+/// [`ConcurrencyPolicy`] of the newest sound picks which ones are despawned.
+///
+/// Instances are counted in the [`SoundEffectCounter`] as the component is
+/// inserted and removed, so the limit is checked only for new sounds.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{ConcurrencyPolicy, MaxConcurrent};
+///
+/// // Limit to 3 concurrent footstep sounds
+/// commands.spawn((
+///     AudioPlayer(footstep_handle.clone()),
+///     MaxConcurrent::new(footstep_handle, 3).with_policy(ConcurrencyPolicy::StealOldest),
+/// ));
+/// ```
+#[derive(Component, Reflect, Debug, Clone)]
+#[component(on_insert = count_concurrent_sound, on_replace = uncount_concurrent_sound)]
+#[reflect(Component)]
+pub struct MaxConcurrent {
+    /// The audio source to track concurrency for, e.g. an [`AudioSource`] or
+    /// a [`Tone`](crate::Tone).
+    ///
+    /// Stored as an asset id so the limit does not keep the source loaded.
+    pub source: UntypedAssetId,
+    /// Maximum number of concurrent instances allowed.
+    pub max: u32,
+    /// Which sounds are despawned when the limit is exceeded.
+    pub policy: ConcurrencyPolicy,
+}
+
+impl MaxConcurrent {
+    /// Creates a new `MaxConcurrent` component for a source, given as a
+    /// [`Handle`] or [`AssetId`].
+    #[must_use]
+    pub fn new(source: impl Into<UntypedAssetId>, max: u32) -> Self {
+        Self {
+            source: source.into(),
+            max,
+            policy: ConcurrencyPolicy::default(),
+        }
+    }
+
+    /// Sets which sounds are despawned when the limit is exceeded.
+    #[must_use]
+    pub fn with_policy(mut self, policy: ConcurrencyPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+/// Chooses which instances of a sound are despawned when a new one exceeds
+/// its [`MaxConcurrent`] limit.
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConcurrencyPolicy {
+    /// Refuse to start the new sound, leaving playing ones untouched.
+    #[default]
+    RejectNew,
+    /// Stop the instance that started first to make room for the new one.
+    StealOldest,
+    /// Stop the instance currently playing at the lowest volume. Sounds that
+    /// have not started yet are only stopped when no playing one is left.
+    StealQuietest,
+}
+
+fn count_concurrent_sound(mut world: DeferredWorld, context: HookContext) {
+    let Some(source) = world
+        .get::<MaxConcurrent>(context.entity)
+        .map(|max| max.source)
+    else {
+        return;
+    };
+    if let Some(mut counter) = world.get_resource_mut::<SoundEffectCounter>() {
+        *counter.counts.entry(source).or_insert(0) += 1;
+    }
+}
+
+fn uncount_concurrent_sound(mut world: DeferredWorld, context: HookContext) {
+    let Some(source) = world
+        .get::<MaxConcurrent>(context.entity)
+        .map(|max| max.source)
+    else {
+        return;
+    };
+    if let Some(mut counter) = world.get_resource_mut::<SoundEffectCounter>() {
+        counter.release(source);
+    }
+}
+
+/// Resource that tracks the count of active sound effects per source.
+///
+/// Maintained by the [`MaxConcurrent`] component hooks and read by the
+/// concurrency limiting system.
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+pub struct SoundEffectCounter {
+    /// Map of audio source to current count of playing instances.
+    pub counts: HashMap<UntypedAssetId, u32>,
+}
+
+impl SoundEffectCounter {
+    /// Returns how many instances of a sound are currently alive.
+    #[must_use]
+    pub fn count(&self, source: impl Into<UntypedAssetId>) -> u32 {
+        self.counts.get(&source.into()).copied().unwrap_or(0)
+    }
+
+    fn release(&mut self, source: UntypedAssetId) {
+        if let Some(count) = self.counts.get_mut(&source) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                self.counts.remove(&source);
+            }
+        }
+    }
+}
+
+/// Default duration over which config volume changes are ramped.
+pub const DEFAULT_VOLUME_RAMP: Duration = Duration::from_millis(100);
+
+/// Resource controlling how config volume changes reach playing audio.
+///
+/// When the audio config changes, playing sinks move to their new volume
+/// over this duration instead of snapping, which avoids audible clicks while
+/// dragging volume sliders. A zero duration applies changes instantly.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+pub struct VolumeRamp {
+    /// Duration of the ramp.
+    pub duration: Duration,
+}
+
+impl VolumeRamp {
+    /// Applies config volume changes instantly.
+    pub const INSTANT: Self = Self {
+        duration: Duration::ZERO,
+    };
+
+    /// Creates a new ramp with the given duration.
+    #[must_use]
+    pub fn new(duration: Duration) -> Self {
+        Self { duration }
+    }
+}
+
+impl Default for VolumeRamp {
+    fn default() -> Self {
+        Self::new(DEFAULT_VOLUME_RAMP)
+    }
+}
+
+/// Default duration of the crossfade between music stopped and started in
+/// the same frame, short enough to only hide the gap between the tracks.
+pub const DEFAULT_MUSIC_CROSSFADE: Duration = Duration::from_millis(500);
+
+/// Resource controlling the automatic crossfade between music tracks.
+///
+/// When [`StopMusic`](crate::StopMusic) and [`PlayMusic`](crate::PlayMusic)
+/// messages are sent in the same frame, the stopped music fades out and the
+/// new music fades in over this duration instead of cutting over with a gap.
+/// Messages with their own fade-in or quantization keep it. Defaults to
+/// [`DEFAULT_MUSIC_CROSSFADE`]; a zero duration disables the crossfade.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+pub struct MusicCrossfade {
+    /// Duration of the crossfade.
+    pub duration: Duration,
+}
+
+impl MusicCrossfade {
+    /// Stops and starts music without a crossfade.
+    pub const DISABLED: Self = Self {
+        duration: Duration::ZERO,
+    };
+
+    /// Creates a new crossfade with the given duration.
+    #[must_use]
+    pub fn new(duration: Duration) -> Self {
+        Self { duration }
+    }
+}
+
+impl Default for MusicCrossfade {
+    fn default() -> Self {
+        Self::new(DEFAULT_MUSIC_CROSSFADE)
+    }
+}
+
+/// Resource choosing what happens when [`PlayMusic`](crate::PlayMusic)
+/// requests a track that is already playing in the same category.
+///
+/// Defaults to [`DuplicateMusic::Stack`], which plays a second copy.
+/// Individual messages can override it with
+/// [`PlayMusic::on_duplicate`](crate::PlayMusic::on_duplicate).
+#[derive(Resource, Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[reflect(Resource)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DuplicateMusic {
+    /// Play another copy of the track alongside the playing one.
+    #[default]
+    Stack,
+    /// Ignore the request and keep the playing track.
+    Ignore,
+    /// Stop the playing track and start it again from the beginning.
+    Restart,
+    /// Fade the playing track out while the new one fades in, over the
+    /// [`MusicCrossfade`] duration. Restarts the track while the crossfade
+    /// is disabled.
+    Crossfade,
+}
+
+/// Ramps a sink to a new config volume.
+///
+/// Inserted by the volume update systems when the config changes and
+/// removed once the ramp completes. A further config change restarts the
+/// ramp from the current volume.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct RampingVolume {
+    /// The underlying volume tween.
+    pub tween: VolumeTween,
+}
+
+/// Action taken when a [`VolumeTween`] completes.
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TweenCompletion {
+    /// Keep playing at the target volume.
+    #[default]
+    Keep,
+    /// Despawn the audio entity.
+    Despawn,
+    /// Pause the audio sink.
+    Pause,
+}
+
+/// Component that animates the volume of an audio entity.
+///
+/// The volume moves from `start` to `target` over the timer's duration,
+/// shaped by the easing curve. When the tween completes the component is
+/// removed and the [`TweenCompletion`] action is applied.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{Easing, VolumeTween};
+///
+/// // Duck music to 30% while a dialog box is open
+/// commands
+///     .entity(music_entity)
+///     .insert(VolumeTween::from_secs(1.0, 0.3, 0.5).with_easing(Easing::SCurve));
+/// ```
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct VolumeTween {
+    /// Timer tracking the tween progress.
+    pub timer: Timer,
+    /// Volume when the tween started.
+    pub start: f32,
+    /// Volume reached when the tween completes.
+    pub target: f32,
+    /// Curve applied to the tween progress (defaults to linear).
+    #[reflect(ignore)]
+    pub easing: Easing,
+    /// Action taken when the tween completes.
+    pub on_complete: TweenCompletion,
+}
+
+impl VolumeTween {
+    /// Creates a new volume tween with the specified duration.
+    #[must_use]
+    pub fn new(start: f32, target: f32, duration: Duration) -> Self {
+        Self {
+            timer: Timer::new(duration, TimerMode::Once),
+            start,
+            target,
+            easing: Easing::Linear,
+            on_complete: TweenCompletion::Keep,
+        }
+    }
+
+    /// Creates a volume tween with a duration in seconds.
+    #[must_use]
+    pub fn from_secs(start: f32, target: f32, seconds: f32) -> Self {
+        Self::new(start, target, Duration::from_secs_f32(seconds))
+    }
+
+    /// Sets the easing curve for the tween.
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Sets the action taken when the tween completes.
+    #[must_use]
+    pub fn with_on_complete(mut self, on_complete: TweenCompletion) -> Self {
+        self.on_complete = on_complete;
+        self
+    }
+
+    /// Returns the current volume based on tween progress.
+    #[must_use]
+    pub fn current_volume(&self) -> f32 {
+        let progress = self.easing.apply(self.timer.fraction());
+        self.start + (self.target - self.start) * progress
+    }
+
+    /// Returns true if the tween has completed.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.timer.is_finished()
+    }
+}
+
+/// Component that animates the playback speed (pitch) of an audio entity.
+///
+/// The speed moves from `start` to `target` over the timer's duration,
+/// shaped by the easing curve. When the tween completes the component is
+/// removed and the [`TweenCompletion`] action is applied.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{SpeedTween, TweenCompletion};
+///
+/// // Slow music down to a stop on the death screen
+/// commands.entity(music_entity).insert(
+///     SpeedTween::from_secs(1.0, 0.1, 2.0).with_on_complete(TweenCompletion::Pause),
+/// );
+/// ```
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct SpeedTween {
+    /// Timer tracking the tween progress.
+    pub timer: Timer,
+    /// Speed when the tween started.
+    pub start: f32,
+    /// Speed reached when the tween completes.
+    pub target: f32,
+    /// Curve applied to the tween progress (defaults to linear).
+    #[reflect(ignore)]
+    pub easing: Easing,
+    /// Action taken when the tween completes.
+    pub on_complete: TweenCompletion,
+}
+
+impl SpeedTween {
+    /// Creates a new speed tween with the specified duration.
+    #[must_use]
+    pub fn new(start: f32, target: f32, duration: Duration) -> Self {
+        Self {
+            timer: Timer::new(duration, TimerMode::Once),
+            start,
+            target,
+            easing: Easing::Linear,
+            on_complete: TweenCompletion::Keep,
+        }
+    }
+
+    /// Creates a speed tween with a duration in seconds.
+    #[must_use]
+    pub fn from_secs(start: f32, target: f32, seconds: f32) -> Self {
+        Self::new(start, target, Duration::from_secs_f32(seconds))
+    }
+
+    /// Sets the easing curve for the tween.
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Sets the action taken when the tween completes.
+    #[must_use]
+    pub fn with_on_complete(mut self, on_complete: TweenCompletion) -> Self {
+        self.on_complete = on_complete;
+        self
+    }
+
+    /// Returns the current speed based on tween progress.
+    #[must_use]
+    pub fn current_speed(&self) -> f32 {
+        let progress = self.easing.apply(self.timer.fraction());
+        self.start + (self.target - self.start) * progress
+    }
+
+    /// Returns true if the tween has completed.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.timer.is_finished()
+    }
+}
+
+/// Component for audio that is fading out.
+///
+/// When attached to an audio entity, the volume will be gradually reduced
+/// over the specified duration, then the entity will be despawned.
+/// This is a [`VolumeTween`] towards silence that despawns on completion.
+///
+/// The tween's volumes are fractions of the sound's full volume, as kept in
+/// its [`AudioVolumeState`], so master and category volume changes made
+/// during the fade are still heard. Sounds without a category fade from an
+/// absolute volume instead.
+///
+/// [`with_on_complete`](Self::with_on_complete) keeps the entity instead:
+/// [`TweenCompletion::Pause`] pauses it, and [`TweenCompletion::Keep`] only
+/// removes the fade, leaving the sound playing silently so it can be faded
+/// back in with a [`VolumeTween`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{FadeOut, TweenCompletion};
+/// use std::time::Duration;
+///
+/// // Manually add fade-out to an existing audio entity
+/// commands.entity(music_entity).insert(FadeOut::new(Duration::from_secs(2)));
+///
+/// // Fade the radio down while the menu is open, without losing its position
+/// commands.entity(radio).insert(
+///     FadeOut::new(Duration::from_millis(500)).with_on_complete(TweenCompletion::Pause),
+/// );
+/// ```
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct FadeOut {
+    /// The underlying tween towards silence.
+    pub tween: VolumeTween,
+    /// Whether the fade starts from the sink's volume when it begins, instead
+    /// of the tween's start volume.
+    pub from_current: bool,
+    /// Whether the fade is timed with real time instead of virtual time, so
+    /// it completes while `Time<Virtual>` is paused.
+    pub real_time: bool,
+}
+
+impl FadeOut {
+    /// Creates a new fade-out component with the specified duration.
+    #[must_use]
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            tween: VolumeTween::new(1.0, 0.0, duration).with_on_complete(TweenCompletion::Despawn),
+            from_current: false,
+            real_time: false,
+        }
+    }
+
+    /// Creates a fade-out from seconds.
+    #[must_use]
+    pub fn from_secs(seconds: f32) -> Self {
+        Self::new(Duration::from_secs_f32(seconds))
+    }
+
+    /// Sets the initial volume for the fade, as a fraction of the sound's
+    /// full volume.
+    #[must_use]
+    pub fn with_initial_volume(mut self, volume: f32) -> Self {
+        self.tween.start = volume;
+        self.from_current = false;
+        self
+    }
+
+    /// Starts the fade from the volume the sink plays at when the fade
+    /// begins, e.g. partway through a fade-in or a duck.
+    #[must_use]
+    pub fn from_current_volume(mut self) -> Self {
+        self.from_current = true;
+        self
+    }
+
+    /// Sets the easing curve for the fade.
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.tween.easing = easing;
+        self
+    }
+
+    /// Sets the action taken when the fade completes, despawning by default.
+    #[must_use]
+    pub fn with_on_complete(mut self, on_complete: TweenCompletion) -> Self {
+        self.tween.on_complete = on_complete;
+        self
+    }
+
+    /// Times the fade with real time, so it completes while the game is
+    /// paused.
+    #[must_use]
+    pub fn with_real_time(mut self) -> Self {
+        self.real_time = true;
+        self
+    }
+
+    /// Returns the initial volume when the fade started.
+    #[must_use]
+    pub fn initial_volume(&self) -> f32 {
+        self.tween.start
+    }
+
+    /// Returns the current volume based on fade progress.
+    ///
+    /// Returns a value from the initial volume down to 0.0 as the timer progresses,
+    /// shaped by the easing curve.
+    #[must_use]
+    pub fn current_volume(&self) -> f32 {
+        self.tween.current_volume()
+    }
+
+    /// Returns true if the fade has completed.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.tween.is_finished()
+    }
+}
+
+/// Component for audio that is fading in.
+///
+/// When attached to an audio entity, the volume will be gradually raised
+/// from silence to `target_volume` over the specified duration, then the
+/// component is removed. The volume systems keep `target_volume` in sync
+/// with the master and category volume.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{Easing, FadeIn, MusicBundle};
+///
+/// commands.spawn((
+///     MusicBundle::new(music_handle, MyMusicCategory::Exploration),
+///     FadeIn::from_secs(3.0).with_easing(Easing::SCurve),
+/// ));
+/// ```
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct FadeIn {
+    /// Timer tracking the fade progress.
+    pub timer: Timer,
+    /// Volume reached when the fade completes.
+    pub target_volume: f32,
+    /// Curve applied to the fade progress (defaults to linear).
+    #[reflect(ignore)]
+    pub easing: Easing,
+}
+
+impl FadeIn {
+    /// Creates a new fade-in component with the specified duration.
+    #[must_use]
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            timer: Timer::new(duration, TimerMode::Once),
+            target_volume: 1.0,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Creates a fade-in from seconds.
+    #[must_use]
+    pub fn from_secs(seconds: f32) -> Self {
+        Self::new(Duration::from_secs_f32(seconds))
+    }
+
+    /// Sets the easing curve for the fade.
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Returns the current volume based on fade progress.
+    ///
+    /// Returns a value from 0.0 up to `target_volume` as the timer progresses,
+    /// shaped by the easing curve.
+    #[must_use]
+    pub fn current_volume(&self) -> f32 {
+        self.target_volume * self.easing.apply(self.timer.fraction())
+    }
+
+    /// Returns true if the fade has completed.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.timer.is_finished()
+    }
+}
+
+/// Default volume multiplier applied to music while a stinger plays.
+pub const DEFAULT_STINGER_DUCK_VOLUME: f32 = 0.3;
+
+/// Default duration of the duck and restore ramps around a stinger.
+pub const DEFAULT_STINGER_FADE: Duration = Duration::from_millis(250);
+
+/// Marks a music entity as a stinger.
+///
+/// While any stinger of a music category type is alive, the other music
+/// entities of that type are ducked to `duck_volume` of their volume, and
+/// restored once every stinger has finished.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct Stinger {
+    /// Volume multiplier applied to the ducked music.
+    pub duck_volume: f32,
+    /// Duration of the duck and restore ramps.
+    pub fade: Duration,
+}
+
+impl Default for Stinger {
+    fn default() -> Self {
+        Self {
+            duck_volume: DEFAULT_STINGER_DUCK_VOLUME,
+            fade: DEFAULT_STINGER_FADE,
+        }
+    }
+}
+
+/// Marks a sound effect that was paused because the game was paused.
+///
+/// Only sinks carrying this marker are resumed when the game unpauses, so
+/// sounds paused deliberately by other systems stay paused.
+#[derive(Component, Reflect, Debug, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct PausedByGame;
+
+/// Defers playback of an audio entity until its source has loaded.
+///
+/// The entity carries everything except the [`AudioPlayer`]. Once the audio
+/// source is available, the player is inserted and this component removed.
+/// If loading fails, the entity is despawned and a warning logged.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::WaitForLoad;
+///
+/// commands.spawn((
+///     WaitForLoad::new(asset_server.load("music/boss.ogg")),
+///     PlaybackSettings::LOOP,
+///     MyMusicCategory::Combat,
+/// ));
+/// ```
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct WaitForLoad {
+    /// The audio source to wait for.
+    pub handle: Handle<AudioSource>,
+}
+
+impl WaitForLoad {
+    /// Creates a new `WaitForLoad` component.
+    #[must_use]
+    pub fn new(handle: Handle<AudioSource>) -> Self {
+        Self { handle }
+    }
+}
+
+/// Per-track gain applied on top of the category volume.
+///
+/// Use this to level individual assets that were mastered too loud or too
+/// quiet. The final sink volume is
+/// `effective master * category * playback volume * gain`, so the gain is
+/// preserved whenever the volume systems reapply the configuration.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::TrackGain;
+///
+/// commands.spawn((
+///     MusicBundle::new(asset_server.load("music/too_loud.ogg"), MyMusicCategory::Menu),
+///     TrackGain::new(0.6),
+/// ));
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct TrackGain(pub f32);
+
+impl TrackGain {
+    /// Creates a new linear track gain, clamped to be non-negative.
+    #[must_use]
+    pub fn new(gain: f32) -> Self {
+        Self(gain.max(0.0))
+    }
+}
+
+impl Default for TrackGain {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// Volume state of a playing sound, kept by the volume systems.
+///
+/// Inserted when the sink appears, capturing the playback volume the sound
+/// started with, after any randomization. Config and mixer changes recompute
+/// the sink volume from this state and the live [`TrackGain`] instead of
+/// re-reading [`PlaybackSettings`], so rolled and runtime volumes survive
+/// them.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+pub struct AudioVolumeState {
+    /// Linear playback volume of the sound, before its [`TrackGain`].
+    pub base: f32,
+    /// Loudness normalization gain of the audio source.
+    pub loudness: f32,
+    /// Distance attenuation last applied by
+    /// [`SpatialSettings`](crate::SpatialSettings).
+    pub attenuation: f32,
+    /// Category gain last applied: master, category, duck, and snapshot
+    /// stages combined.
+    pub category: f32,
+}
+
+impl AudioVolumeState {
+    /// Returns the volume the sink should play at with the given gain.
+    #[must_use]
+    pub fn volume(&self, gain: Option<&TrackGain>) -> f32 {
+        self.category
+            * self.base
+            * self.loudness
+            * self.attenuation
+            * gain.map_or(1.0, |gain| gain.0)
+    }
+}
+
+/// Probability that a sound plays at all, from `0.0` to `1.0`.
+///
+/// Sounds losing the roll are despawned before they start, which suits
+/// occasional flavor sounds such as creaks or chirps. The roll draws from the
+/// [`AudioRng`](crate::AudioRng) resource.
 ///
 /// # Example
 ///
 /// ```rust,ignore
-/// use dmg_audio::MaxConcurrent;
+/// use msg_audio::SfxBundle;
 ///
-/// // Limit to 3 concurrent footstep sounds
-/// commands.spawn((
-///     AudioPlayer(footstep_handle.clone()),
-///     MaxConcurrent { handle: footstep_handle, max: 3 },
-/// ));
+/// // Creaks on a third of the steps
+/// commands.spawn(SfxBundle::new(creak_handle, GameSfx::Gameplay).with_chance(0.33));
 /// ```
-#[derive(Component, Reflect, Debug, Clone)]
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
 #[reflect(Component)]
-pub struct MaxConcurrent {
-    /// The audio source handle to track concurrency for.
-    pub handle: Handle<AudioSource>,
-    /// Maximum number of concurrent instances allowed.
-    pub max: u32,
+pub struct PlayChance(pub f32);
+
+impl Default for PlayChance {
+    fn default() -> Self {
+        Self(1.0)
+    }
 }
 
-impl MaxConcurrent {
-    /// Creates a new `MaxConcurrent` component.
-    #[must_use]
-    pub fn new(handle: Handle<AudioSource>, max: u32) -> Self {
-        Self { handle, max }
+impl PlayChance {
+    /// Rolls whether the sound plays.
+    pub fn roll<R: Rng + ?Sized>(self, rng: &mut R) -> bool {
+        self.0 >= 1.0 || rng.random_bool(f64::from(self.0.max(0.0)))
     }
 }
 
-/// Resource that tracks the count of active sound effects per handle.
+/// Places a sound left or right in the stereo field, from `-1.0` (left) to
+/// `1.0` (right).
 ///
-/// This is used internally by the concurrency limiting system.
-#[derive(Resource, Reflect, Debug, Default)]
-#[reflect(Resource)]
-pub struct SoundEffectCounter {
-    /// Map of audio handle to current count of playing instances.
-    pub counts: HashMap<Handle<AudioSource>, u32>,
-    /// Timer for periodic count resets to prevent stale data.
-    pub timer: Timer,
-}
+/// `bevy_audio` has no pan control on its sinks, so panned sounds play
+/// through a spatial sink whose emitter is kept on the ear axis of the
+/// [`SpatialListener`], or of a default listener at the origin when there is
+/// none. This works for UI and 2D games without any other spatial setup. A
+/// pan of `0.0` leaves the sound non-spatial.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::SfxBundle;
+///
+/// commands.spawn(SfxBundle::new(click_handle, GameSfx::UI).with_pan(-0.5));
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, PartialEq)]
+#[reflect(Component)]
+pub struct StereoPan(pub f32);
 
-impl SoundEffectCounter {
-    /// Creates a new counter with the specified reset interval.
+/// Range the [`StereoPan`] of a sound is rolled from when it is spawned.
+///
+/// Set by [`SfxRequest::with_pan_range`](crate::SfxRequest::with_pan_range)
+/// on [`SfxBundle`](crate::SfxBundle). The pan is drawn from the
+/// [`AudioRng`](crate::AudioRng) resource as soon as the component is added,
+/// and the bounds may be given in either order.
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, PartialEq)]
+#[reflect(Component)]
+pub struct StereoPanRange(pub Option<(f32, f32)>);
+
+impl StereoPanRange {
+    /// Creates a range between two pans, from `-1.0` (left) to `1.0` (right).
     #[must_use]
-    pub fn with_interval(seconds: f32) -> Self {
-        Self {
-            counts: HashMap::default(),
-            timer: Timer::from_seconds(seconds, TimerMode::Repeating),
-        }
+    pub fn new(min: f32, max: f32) -> Self {
+        Self(Some((min, max)))
+    }
+
+    /// Rolls a pan from the range, or returns `None` if there is no range.
+    pub fn roll<R: Rng + ?Sized>(self, rng: &mut R) -> Option<f32> {
+        self.0.map(|(min, max)| random_between(rng, min, max))
     }
 }
 
-/// Component for audio that is fading out.
+/// Keeps a sound non-spatial and centered, even when it is attached to a
+/// world entity in a spatial-audio game.
 ///
-/// When attached to an audio entity, the volume will be gradually reduced
-/// over the specified duration, then the entity will be despawned.
+/// Suits confirmation sounds such as pickups that should not be panned or
+/// attenuated by where they happened. The sound's [`PlaybackSettings`] are
+/// switched back to non-spatial playback before it starts, and any
+/// [`StereoPan`], [`Spatial2d`](crate::Spatial2d), and
+/// [`SpatialSettings`](crate::SpatialSettings) on it are ignored.
 ///
 /// # Example
 ///
 /// ```rust,ignore
-/// use dmg_audio::FadeOut;
-/// use std::time::Duration;
+/// use msg_audio::PlaySfx;
 ///
-/// // Manually add fade-out to an existing audio entity
-/// commands.entity(music_entity).insert(FadeOut::new(Duration::from_secs(2)));
+/// sfx.write(PlaySfx::new(pickup, GameSfx::Gameplay).attached_to(coin).non_spatial());
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct NonSpatial;
+
+/// Stops a sound once it has played for a maximum duration.
+///
+/// Guards the sound effect path against assets that are accidentally long or
+/// looping. Time only counts while the sound's sink exists and is not paused,
+/// so loading and pauses do not eat into the duration.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::PlaySfx;
+///
+/// sfx.write(PlaySfx::new(zap_handle, GameSfx::Gameplay).with_max_duration(Duration::from_secs(2)));
 /// ```
 #[derive(Component, Reflect, Debug, Clone)]
 #[reflect(Component)]
-pub struct FadeOut {
-    /// Timer tracking the fade progress.
+pub struct MaxDuration {
+    /// Time left until the sound is stopped.
     pub timer: Timer,
-    /// Initial volume when fade started.
-    pub initial_volume: f32,
 }
 
-impl FadeOut {
-    /// Creates a new fade-out component with the specified duration.
+impl MaxDuration {
+    /// Creates a limit stopping the sound after `duration`.
     #[must_use]
     pub fn new(duration: Duration) -> Self {
         Self {
             timer: Timer::new(duration, TimerMode::Once),
-            initial_volume: 1.0,
         }
     }
+}
 
-    /// Creates a fade-out from seconds.
+/// Marks a persistent looping sound effect.
+///
+/// Spawned by [`LoopingSfxBundle`](crate::LoopingSfxBundle) and
+/// [`PlayLoopingSfx`](crate::PlayLoopingSfx). A loop is identified by its
+/// category and [`PlaybackLabel`], which [`StopLoopingSfx`](crate::StopLoopingSfx)
+/// targets.
+#[derive(Component, Reflect, Debug, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct LoopingSfx;
+
+/// Identifies a specific playing instance.
+///
+/// Attach a label when playing a sound to stop, pause, or modify that exact
+/// instance later instead of a whole category. Labels do not need to be
+/// unique; messages targeting a label affect every entity carrying it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{PlaybackLabel, PlaySfx, StopLabeled};
+///
+/// const ENGINE: PlaybackLabel = PlaybackLabel::from_static("player_engine");
+///
+/// sfx.write(PlaySfx::new(engine_loop, GameSfx::Player).with_label(ENGINE));
+/// // Later
+/// stop.write(StopLabeled::new(ENGINE));
+/// ```
+#[derive(Component, Reflect, Debug, Clone, PartialEq, Eq, Hash)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct PlaybackLabel(pub Cow<'static, str>);
+
+impl PlaybackLabel {
+    /// Creates a new label.
     #[must_use]
-    pub fn from_secs(seconds: f32) -> Self {
-        Self::new(Duration::from_secs_f32(seconds))
+    pub fn new(label: impl Into<Cow<'static, str>>) -> Self {
+        Self(label.into())
     }
 
-    /// Sets the initial volume for the fade.
+    /// Creates a label from a static string, usable in constants.
     #[must_use]
-    pub fn with_initial_volume(mut self, volume: f32) -> Self {
-        self.initial_volume = volume;
-        self
+    pub const fn from_static(label: &'static str) -> Self {
+        Self(Cow::Borrowed(label))
     }
 
-    /// Returns the current volume based on fade progress.
-    ///
-    /// Returns a value from `initial_volume` down to 0.0 as the timer progresses.
+    /// Returns the label as a string slice.
     #[must_use]
-    pub fn current_volume(&self) -> f32 {
-        let progress = self.timer.fraction();
-        self.initial_volume * (1.0 - progress)
+    pub fn as_str(&self) -> &str {
+        &self.0
     }
+}
 
-    /// Returns true if the fade has completed.
+impl From<&'static str> for PlaybackLabel {
+    fn from(label: &'static str) -> Self {
+        Self::from_static(label)
+    }
+}
+
+impl From<String> for PlaybackLabel {
+    fn from(label: String) -> Self {
+        Self(Cow::Owned(label))
+    }
+}
+
+/// Attaches a sound to a gameplay entity.
+///
+/// The sound follows the entity's position, so spatial sounds such as an
+/// engine loop or a torch crackle move with their source, and it is despawned
+/// together with the entity. Use [`PlaySfx::attached_to`](crate::PlaySfx::attached_to)
+/// to attach sounds spawned by the crate.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::PlaySfx;
+///
+/// sfx.write(
+///     PlaySfx::new(assets.load("sfx/engine.ogg"), GameSfx::Vehicles)
+///         .with_playback(PlaybackSettings::LOOP.with_spatial(true))
+///         .attached_to(car),
+/// );
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+#[relationship(relationship_target = AttachedSounds)]
+#[require(Transform)]
+pub struct AttachedAudio(#[entities] pub Entity);
+
+/// The sounds attached to an entity through [`AttachedAudio`].
+///
+/// Despawning the entity despawns its attached sounds.
+#[derive(Component, Reflect, Debug, Default, PartialEq, Eq)]
+#[reflect(Component)]
+#[relationship_target(relationship = AttachedAudio, linked_spawn)]
+pub struct AttachedSounds(Vec<Entity>);
+
+impl AttachedSounds {
+    /// Returns the attached sound entities.
     #[must_use]
-    pub fn is_finished(&self) -> bool {
-        self.timer.is_finished()
+    pub fn entities(&self) -> &[Entity] {
+        &self.0
     }
 }
 
@@ -126,21 +974,34 @@ impl FadeOut {
 ///
 /// Provides a fluent API for configuring volume and speed randomization
 /// on sound effects to add variety.
-#[derive(Clone, Debug)]
+///
+/// Applied through builders such as
+/// [`AudioRequest::randomized`](crate::AudioRequest::randomized), the values
+/// are rolled once from the [`AudioRng`](crate::AudioRng). Inserted as a component, it rolls again each
+/// time the sound starts: when its sink is created, including pooled and
+/// retriggered sounds, and whenever a looping sound wraps around.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::PlaybackRandomizer;
+///
+/// // A looping engine hum that varies its pitch every cycle
+/// commands.spawn((
+///     MusicBundle::new(hum_handle, GameMusic::Ambience),
+///     PlaybackRandomizer::new().with_speed(0.95, 1.05),
+/// ));
+/// ```
+#[derive(Component, Reflect, Clone, Debug, Default, PartialEq)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PlaybackRandomizer {
     /// Minimum and maximum volume range.
     pub volume_range: Option<(f32, f32)>,
     /// Minimum and maximum speed range.
     pub speed_range: Option<(f32, f32)>,
-}
-
-impl Default for PlaybackRandomizer {
-    fn default() -> Self {
-        Self {
-            volume_range: None,
-            speed_range: None,
-        }
-    }
+    /// Minimum and maximum position playback starts at.
+    pub start_offset_range: Option<(Duration, Duration)>,
 }
 
 impl PlaybackRandomizer {
@@ -168,6 +1029,18 @@ impl PlaybackRandomizer {
         self
     }
 
+    /// Sets the start offset randomization range.
+    ///
+    /// Playback starts at a position randomly chosen between `min` and `max`
+    /// (inclusive), so several instances of the same loop, such as torches or
+    /// machinery, do not play in lockstep. The offset is only rolled when the
+    /// sound first starts, not each time a loop wraps around.
+    #[must_use]
+    pub fn with_start_offset(mut self, min: Duration, max: Duration) -> Self {
+        self.start_offset_range = Some((min, max));
+        self
+    }
+
     /// Creates a randomizer with standard variation.
     ///
     /// Uses speed range [0.7, 1.3] and volume range [0.6, 1.0].
@@ -176,42 +1049,166 @@ impl PlaybackRandomizer {
         Self {
             volume_range: Some((0.6, 1.0)),
             speed_range: Some((0.7, 1.3)),
+            start_offset_range: None,
         }
     }
 
-    /// Applies randomization to the given playback settings.
-    pub fn apply(&self, settings: &mut PlaybackSettings) {
-        let mut rng = rand::rng();
-
+    /// Applies randomization to the given playback settings, drawing from
+    /// `rng`, usually the [`AudioRng`](crate::AudioRng).
+    pub fn apply_with<R: Rng + ?Sized>(&self, settings: &mut PlaybackSettings, rng: &mut R) {
         if let Some((min, max)) = self.volume_range {
-            settings.volume = Volume::Linear(rng.random_range(min..=max));
+            settings.volume = Volume::Linear(random_between(rng, min, max));
         }
 
         if let Some((min, max)) = self.speed_range {
-            settings.speed = rng.random_range(min..=max);
+            settings.speed = random_between(rng, min, max);
+        }
+
+        if let Some((min, max)) = self.start_offset_range {
+            settings.start_position = Some(random_between(rng, min, max));
+        }
+    }
+}
+
+/// Position a sound starts playing from, seeked to once its sink exists.
+///
+/// `bevy_audio` skips [`PlaybackSettings::start_position`] again each time a
+/// looping sound wraps around, and leaves it out of the sink's position. The
+/// crate therefore moves the start positions of the sounds it spawns into this
+/// component, which is removed after the seek, so loops play their beginning
+/// from the second pass on and the sink's position counts from the start of
+/// the source.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct StartPosition(pub Duration);
+
+impl StartPosition {
+    /// Takes the start position out of `playback`, if it has one.
+    pub fn take_from(playback: &mut PlaybackSettings) -> Option<Self> {
+        playback.start_position.take().map(Self)
+    }
+}
+
+/// Randomization, fade-in, and delay a bundle applies when it is spawned.
+///
+/// Set by the [`AudioRequest`](crate::AudioRequest) builders of
+/// [`MusicBundle`](crate::MusicBundle), [`SfxBundle`](crate::SfxBundle), and
+/// [`LoopingSfxBundle`](crate::LoopingSfxBundle). As soon as the component is
+/// added, the randomization is rolled from the [`AudioRng`](crate::AudioRng),
+/// the fade-in is inserted, a delayed sound is held back with a
+/// [`StartDelay`], and the component is removed.
+#[derive(Component, Reflect, Debug, Clone, Default)]
+#[reflect(Component)]
+pub struct SpawnRequest {
+    /// Randomization rolled once when the sound is spawned.
+    pub randomizer: Option<PlaybackRandomizer>,
+    /// Fade from silence when the sound starts.
+    pub fade_in: Option<FadeIn>,
+    /// Time the sound waits before it starts.
+    pub delay: Option<Duration>,
+}
+
+/// Holds a sound back until its delay has elapsed.
+///
+/// Inserted in place of the [`AudioPlayer`] of bundles spawned with
+/// [`AudioRequest::after`](crate::AudioRequest::after), which is inserted
+/// again once the timer finishes.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct StartDelay {
+    /// Timer tracking the delay.
+    pub timer: Timer,
+    /// The audio source started once the delay has elapsed.
+    pub handle: Handle<AudioSource>,
+}
+
+impl StartDelay {
+    /// Creates a delay starting `handle` after `delay`.
+    #[must_use]
+    pub fn new(handle: Handle<AudioSource>, delay: Duration) -> Self {
+        Self {
+            timer: Timer::new(delay, TimerMode::Once),
+            handle,
+        }
+    }
+}
+
+/// A set of interchangeable variations of a sound.
+///
+/// Playing a random variation each time keeps repeated sounds such as
+/// footsteps and impacts from sounding mechanical.
+#[derive(Clone, Debug, Default)]
+pub struct SoundPool {
+    /// The variations to choose from.
+    pub handles: Vec<Handle<AudioSource>>,
+}
+
+impl SoundPool {
+    /// Creates a pool from its variations.
+    #[must_use]
+    pub fn new(handles: impl IntoIterator<Item = Handle<AudioSource>>) -> Self {
+        Self {
+            handles: handles.into_iter().collect(),
         }
     }
+
+    /// Adds a variation.
+    #[must_use]
+    pub fn with(mut self, handle: Handle<AudioSource>) -> Self {
+        self.handles.push(handle);
+        self
+    }
+
+    /// Returns a variation chosen with `rng`, usually the
+    /// [`AudioRng`](crate::AudioRng), or `None` if the pool is empty.
+    #[must_use]
+    pub fn pick_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<&Handle<AudioSource>> {
+        self.handles.choose(rng)
+    }
+
+    /// Returns `true` if the pool has no variations.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tests::{test_app, TestPlugin, TestSfx};
+    use crate::{AudioRequest, PlaySfx};
+
+    #[test]
+    fn sound_pool_picks_from_variations() {
+        let mut rng = crate::AudioRng::seeded(7);
+        assert!(SoundPool::default().pick_with(&mut rng).is_none());
+
+        let pool = SoundPool::new([Handle::default()]).with(Handle::default());
+        assert_eq!(pool.handles.len(), 2);
+        assert!(pool.pick_with(&mut rng).is_some());
+    }
 
     #[test]
     fn max_concurrent_new() {
-        let handle = Handle::default();
-        let mc = MaxConcurrent::new(handle.clone(), 5);
+        let handle = Handle::<AudioSource>::default();
+        let mc = MaxConcurrent::new(&handle, 5);
 
         assert_eq!(mc.max, 5);
     }
 
     #[test]
-    fn sound_effect_counter_with_interval() {
-        let counter = SoundEffectCounter::with_interval(0.5);
+    fn sound_effect_counter_follows_max_concurrent_hooks() {
+        let mut world = World::new();
+        world.init_resource::<SoundEffectCounter>();
+        let handle = Handle::<AudioSource>::default();
 
-        assert!(counter.counts.is_empty());
-        assert_eq!(counter.timer.duration().as_secs_f32(), 0.5);
-        assert_eq!(counter.timer.mode(), TimerMode::Repeating);
+        let first = world.spawn(MaxConcurrent::new(&handle, 2)).id();
+        world.spawn(MaxConcurrent::new(&handle, 2));
+        assert_eq!(world.resource::<SoundEffectCounter>().count(&handle), 2);
+
+        world.despawn(first);
+        assert_eq!(world.resource::<SoundEffectCounter>().count(&handle), 1);
     }
 
     #[test]
@@ -237,7 +1234,7 @@ mod tests {
         let randomizer = PlaybackRandomizer::new().with_volume(0.5, 0.5); // Fixed value for testing
 
         let mut settings = PlaybackSettings::default();
-        randomizer.apply(&mut settings);
+        randomizer.apply_with(&mut settings, &mut crate::AudioRng::seeded(7));
 
         match settings.volume {
             Volume::Linear(v) => assert!((v - 0.5).abs() < f32::EPSILON),
@@ -245,28 +1242,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn playback_randomizer_rolls_start_offset() {
+        let (min, max) = (Duration::from_secs(2), Duration::from_secs(5));
+        let randomizer = PlaybackRandomizer::new().with_start_offset(min, max);
+
+        let mut settings = PlaybackSettings::LOOP;
+        randomizer.apply_with(&mut settings, &mut crate::AudioRng::seeded(7));
+
+        let offset = settings.start_position.unwrap();
+        assert!((min..=max).contains(&offset));
+        assert_eq!(settings.speed, 1.0);
+    }
+
     #[test]
     fn fade_out_new() {
         let fade = FadeOut::new(Duration::from_secs(2));
 
-        assert_eq!(fade.timer.duration(), Duration::from_secs(2));
-        assert_eq!(fade.timer.mode(), TimerMode::Once);
-        assert!((fade.initial_volume - 1.0).abs() < f32::EPSILON);
+        assert_eq!(fade.tween.timer.duration(), Duration::from_secs(2));
+        assert_eq!(fade.tween.timer.mode(), TimerMode::Once);
+        assert_eq!(fade.tween.on_complete, TweenCompletion::Despawn);
+        assert!((fade.initial_volume() - 1.0).abs() < f32::EPSILON);
         assert!(!fade.is_finished());
     }
 
+    #[test]
+    fn fade_out_with_on_complete() {
+        let fade = FadeOut::new(Duration::from_secs(1)).with_on_complete(TweenCompletion::Pause);
+
+        assert_eq!(fade.tween.on_complete, TweenCompletion::Pause);
+    }
+
     #[test]
     fn fade_out_from_secs() {
         let fade = FadeOut::from_secs(1.5);
 
-        assert!((fade.timer.duration().as_secs_f32() - 1.5).abs() < 0.001);
+        assert!((fade.tween.timer.duration().as_secs_f32() - 1.5).abs() < 0.001);
     }
 
     #[test]
     fn fade_out_with_initial_volume() {
         let fade = FadeOut::new(Duration::from_secs(1)).with_initial_volume(0.8);
 
-        assert!((fade.initial_volume - 0.8).abs() < f32::EPSILON);
+        assert!((fade.initial_volume() - 0.8).abs() < f32::EPSILON);
     }
 
     #[test]
@@ -282,7 +1300,7 @@ mod tests {
         let mut fade = FadeOut::new(Duration::from_millis(100)).with_initial_volume(1.0);
 
         // Tick the timer to completion
-        fade.timer.tick(Duration::from_millis(100));
+        fade.tween.timer.tick(Duration::from_millis(100));
 
         // At end (timer progress = 1), volume should be 0
         assert!(fade.is_finished());
@@ -294,10 +1312,168 @@ mod tests {
         let mut fade = FadeOut::new(Duration::from_millis(100)).with_initial_volume(1.0);
 
         // Tick to 50%
-        fade.timer.tick(Duration::from_millis(50));
+        fade.tween.timer.tick(Duration::from_millis(50));
 
         // At midway, volume should be ~0.5
         let vol = fade.current_volume();
         assert!(vol > 0.4 && vol < 0.6, "Expected ~0.5, got {}", vol);
     }
+
+    #[test]
+    fn fade_out_with_easing() {
+        let mut fade = FadeOut::new(Duration::from_millis(100)).with_easing(Easing::Exponential);
+
+        fade.tween.timer.tick(Duration::from_millis(50));
+
+        // Exponential removes more volume than linear by the midpoint
+        assert!(fade.current_volume() < 0.5);
+    }
+
+    #[test]
+    fn fade_in_new() {
+        let fade = FadeIn::new(Duration::from_secs(2));
+
+        assert_eq!(fade.timer.duration(), Duration::from_secs(2));
+        assert!((fade.target_volume - 1.0).abs() < f32::EPSILON);
+        assert!(fade.current_volume().abs() < f32::EPSILON);
+        assert!(!fade.is_finished());
+    }
+
+    #[test]
+    fn fade_in_current_volume_at_end() {
+        let mut fade = FadeIn::new(Duration::from_millis(100));
+        fade.target_volume = 0.6;
+
+        fade.timer.tick(Duration::from_millis(100));
+
+        assert!(fade.is_finished());
+        assert!((fade.current_volume() - 0.6).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn fade_in_with_custom_easing() {
+        let mut fade =
+            FadeIn::new(Duration::from_millis(100)).with_easing(Easing::custom(|t| t * t));
+
+        fade.timer.tick(Duration::from_millis(50));
+
+        assert!((fade.current_volume() - 0.25).abs() < 0.001);
+    }
+
+    #[test]
+    fn volume_tween_new() {
+        let tween = VolumeTween::new(1.0, 0.3, Duration::from_secs(1));
+
+        assert!((tween.start - 1.0).abs() < f32::EPSILON);
+        assert!((tween.target - 0.3).abs() < f32::EPSILON);
+        assert_eq!(tween.on_complete, TweenCompletion::Keep);
+        assert!((tween.current_volume() - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn volume_tween_reaches_partial_target() {
+        let mut tween = VolumeTween::new(1.0, 0.3, Duration::from_millis(100));
+
+        tween.timer.tick(Duration::from_millis(50));
+        assert!((tween.current_volume() - 0.65).abs() < 0.001);
+
+        tween.timer.tick(Duration::from_millis(50));
+        assert!(tween.is_finished());
+        assert!((tween.current_volume() - 0.3).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn volume_tween_can_rise() {
+        let mut tween = VolumeTween::new(0.2, 0.8, Duration::from_millis(100));
+
+        tween.timer.tick(Duration::from_millis(100));
+
+        assert!((tween.current_volume() - 0.8).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn volume_tween_with_on_complete() {
+        let tween = VolumeTween::from_secs(1.0, 0.0, 1.0).with_on_complete(TweenCompletion::Pause);
+
+        assert_eq!(tween.on_complete, TweenCompletion::Pause);
+    }
+
+    #[test]
+    fn speed_tween_interpolates() {
+        let mut tween = SpeedTween::new(1.0, 0.5, Duration::from_millis(100));
+
+        assert!((tween.current_speed() - 1.0).abs() < f32::EPSILON);
+
+        tween.timer.tick(Duration::from_millis(50));
+        assert!((tween.current_speed() - 0.75).abs() < 0.001);
+
+        tween.timer.tick(Duration::from_millis(50));
+        assert!(tween.is_finished());
+        assert!((tween.current_speed() - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn speed_tween_defaults_to_keep() {
+        let tween = SpeedTween::from_secs(1.0, 2.0, 1.0);
+
+        assert_eq!(tween.on_complete, TweenCompletion::Keep);
+    }
+
+    #[test]
+    fn stinger_default() {
+        let stinger = Stinger::default();
+
+        assert!((stinger.duck_volume - DEFAULT_STINGER_DUCK_VOLUME).abs() < f32::EPSILON);
+        assert_eq!(stinger.fade, DEFAULT_STINGER_FADE);
+    }
+
+    #[test]
+    fn track_gain_defaults_to_unity() {
+        assert_eq!(TrackGain::default(), TrackGain(1.0));
+    }
+
+    #[test]
+    fn track_gain_clamps_negative() {
+        assert_eq!(TrackGain::new(-1.0), TrackGain(0.0));
+    }
+
+    #[test]
+    fn volume_ramp_defaults() {
+        assert_eq!(VolumeRamp::default().duration, DEFAULT_VOLUME_RAMP);
+        assert_eq!(VolumeRamp::INSTANT.duration, Duration::ZERO);
+    }
+
+    #[test]
+    fn playback_label_conversions() {
+        const LABEL: PlaybackLabel = PlaybackLabel::from_static("engine");
+
+        assert_eq!(PlaybackLabel::from("engine"), LABEL);
+        assert_eq!(PlaybackLabel::from(String::from("engine")), LABEL);
+        assert_eq!(LABEL.as_str(), "engine");
+    }
+
+    #[test]
+    fn attached_sfx_follows_and_despawns_with_entity() {
+        let mut app = test_app(TestPlugin::default());
+
+        let car = app
+            .world_mut()
+            .spawn(GlobalTransform::from_translation(Vec3::new(3.0, 0.0, 1.0)))
+            .id();
+        app.world_mut().write_message(
+            PlaySfx::new(Handle::default(), TestSfx::UI)
+                .with_playback(PlaybackSettings::LOOP)
+                .attached_to(car),
+        );
+        app.update();
+
+        let mut sounds = app.world_mut().query::<(&AttachedAudio, &Transform)>();
+        let (attached, transform) = sounds.single(app.world()).unwrap();
+        assert_eq!(attached.0, car);
+        assert_eq!(transform.translation, Vec3::new(3.0, 0.0, 1.0));
+
+        app.world_mut().despawn(car);
+        let mut sfx = app.world_mut().query::<&TestSfx>();
+        assert_eq!(sfx.iter(app.world()).count(), 0);
+    }
 }
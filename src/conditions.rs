@@ -0,0 +1,176 @@
+//! Run conditions on the playing audio.
+//!
+//! Gate systems on whether music or sound effects play without writing sink
+//! queries, e.g. to roll the credits once the ending theme is over.
+//!
+//! A sound counts as playing from the moment it is spawned until it is
+//! despawned, including while it loads or fades out, unless its sink is
+//! paused.
+
+use bevy::prelude::*;
+
+use crate::backend::{AudioBackend, BevyAudioBackend};
+use crate::traits::{AudioCategory, MusicCategory, SfxCategory};
+
+/// Returns `true` if the entity's sound has not been paused, whether it plays
+/// through a sink of `B` or of its spatial twin.
+pub(crate) fn is_playing<B: AudioBackend>(
+    (sink, spatial_sink): (
+        Option<&B::Sink>,
+        Option<&<B::Spatial as AudioBackend>::Sink>,
+    ),
+) -> bool {
+    sink.is_none_or(|sink| !B::is_paused(sink))
+        && spatial_sink.is_none_or(|sink| !B::Spatial::is_paused(sink))
+}
+
+/// Run condition that is `true` while any music of type `M` plays.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::any_music_playing;
+///
+/// app.add_systems(Update, roll_credits.run_if(not(any_music_playing::<GameMusic>())));
+/// ```
+pub fn any_music_playing<M: MusicCategory>(
+) -> impl FnMut(Query<(Option<&AudioSink>, Option<&SpatialAudioSink>), With<M>>) -> bool + Clone {
+    any_music_playing_with::<M, BevyAudioBackend>()
+}
+
+/// [`any_music_playing`] for music played through the backend `B`.
+pub fn any_music_playing_with<M: MusicCategory, B: AudioBackend>() -> impl FnMut(
+    Query<
+        (
+            Option<&B::Sink>,
+            Option<&<B::Spatial as AudioBackend>::Sink>,
+        ),
+        With<M>,
+    >,
+) -> bool
+       + Clone {
+    |query: Query<
+        (
+            Option<&B::Sink>,
+            Option<&<B::Spatial as AudioBackend>::Sink>,
+        ),
+        With<M>,
+    >| query.iter().any(is_playing::<B>)
+}
+
+/// Run condition that is `true` while a sound of the given category plays.
+///
+/// Works for music, sound effect, and voice categories alike.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::category_playing;
+///
+/// app.add_systems(Update, show_boss_bar.run_if(category_playing(GameMusic::Boss)));
+/// ```
+pub fn category_playing<A: AudioCategory>(
+    category: A,
+) -> impl FnMut(Query<(&A, Option<&AudioSink>, Option<&SpatialAudioSink>)>) -> bool + Clone {
+    category_playing_with::<A, BevyAudioBackend>(category)
+}
+
+/// [`category_playing`] for sounds played through the backend `B`.
+pub fn category_playing_with<A: AudioCategory, B: AudioBackend>(
+    category: A,
+) -> impl FnMut(
+    Query<(
+        &A,
+        Option<&B::Sink>,
+        Option<&<B::Spatial as AudioBackend>::Sink>,
+    )>,
+) -> bool
+       + Clone {
+    move |query: Query<(
+        &A,
+        Option<&B::Sink>,
+        Option<&<B::Spatial as AudioBackend>::Sink>,
+    )>| {
+        query.iter().any(|(playing, sink, spatial_sink)| {
+            *playing == category && is_playing::<B>((sink, spatial_sink))
+        })
+    }
+}
+
+/// Run condition that is `true` while no sound effect of type `S` plays.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::no_sfx_active;
+///
+/// app.add_systems(Update, advance_cutscene.run_if(no_sfx_active::<GameSfx>()));
+/// ```
+pub fn no_sfx_active<S: SfxCategory>(
+) -> impl FnMut(Query<(Option<&AudioSink>, Option<&SpatialAudioSink>), With<S>>) -> bool + Clone {
+    no_sfx_active_with::<S, BevyAudioBackend>()
+}
+
+/// [`no_sfx_active`] for sound effects played through the backend `B`.
+pub fn no_sfx_active_with<S: SfxCategory, B: AudioBackend>() -> impl FnMut(
+    Query<
+        (
+            Option<&B::Sink>,
+            Option<&<B::Spatial as AudioBackend>::Sink>,
+        ),
+        With<S>,
+    >,
+) -> bool
+       + Clone {
+    |query: Query<
+        (
+            Option<&B::Sink>,
+            Option<&<B::Spatial as AudioBackend>::Sink>,
+        ),
+        With<S>,
+    >| !query.iter().any(is_playing::<B>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin, TestSfx};
+    use crate::{PauseAudio, PlayMusic, PlaySfx, TestAudioPlugin};
+
+    #[test]
+    fn run_conditions_follow_playing_audio() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        let check = |app: &mut App| {
+            let world = app.world_mut();
+            (
+                world
+                    .run_system_once(any_music_playing::<TestMusic>())
+                    .unwrap(),
+                world
+                    .run_system_once(category_playing(TestMusic::Main))
+                    .unwrap(),
+                world.run_system_once(no_sfx_active::<TestSfx>()).unwrap(),
+            )
+        };
+        assert_eq!(check(&mut app), (false, false, true));
+
+        app.world_mut()
+            .write_message(PlayMusic::new(Handle::default(), TestMusic::Main));
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI));
+        app.update();
+        app.update();
+        assert_eq!(check(&mut app), (true, true, false));
+
+        let music = app
+            .world_mut()
+            .query_filtered::<Entity, With<TestMusic>>()
+            .single(app.world())
+            .unwrap();
+        app.world_mut().write_message(PauseAudio::new(music));
+        app.update();
+        assert!(!check(&mut app).0);
+    }
+}
@@ -0,0 +1,391 @@
+//! Console commands for live audio debugging.
+//!
+//! [`AudioConsolePlugin`] reads text commands from [`AudioConsoleInput`]
+//! messages and answers with [`AudioConsoleOutput`] lines, so it can be wired
+//! to any in-game console or command registry. Enable the `console` feature
+//! to use it.
+//!
+//! | Command | Effect |
+//! |---------|--------|
+//! | `audio volume <channel>` | Prints a channel volume |
+//! | `audio volume <channel> <value>` | Sets a channel volume |
+//! | `audio mute <channel>` | Mutes a channel |
+//! | `audio unmute <channel>` | Unmutes a channel |
+//! | `audio list` | Prints every playing sound |
+
+use bevy::prelude::*;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use crate::backend::{either_sink, AnySink, AudioBackend, BevyAudioBackend};
+use crate::traits::{AudioConfigTrait, MusicCategory, SfxCategory};
+
+/// Audio config whose volumes can be changed by name from the console.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::ConsoleAudioConfig;
+///
+/// impl ConsoleAudioConfig for GameAudioConfig {
+///     fn channels(&self) -> &[&'static str] {
+///         &["master", "music", "sfx"]
+///     }
+///
+///     fn channel_volume(&self, channel: &str) -> Option<f32> {
+///         match channel {
+///             "master" => Some(self.master),
+///             "music" => Some(self.music),
+///             "sfx" => Some(self.sfx),
+///             _ => None,
+///         }
+///     }
+///
+///     fn set_channel_volume(&mut self, channel: &str, volume: f32) -> bool {
+///         match channel {
+///             "master" => self.master = volume,
+///             "music" => self.music = volume,
+///             "sfx" => self.sfx = volume,
+///             _ => return false,
+///         }
+///         true
+///     }
+/// }
+/// ```
+pub trait ConsoleAudioConfig: AudioConfigTrait {
+    /// Returns the names of the volume channels, e.g. `"master"` or `"sfx"`.
+    fn channels(&self) -> &[&'static str];
+
+    /// Returns the volume of a channel, or `None` if there is no such channel.
+    fn channel_volume(&self, channel: &str) -> Option<f32>;
+
+    /// Sets the volume of a channel, returning `false` if there is no such
+    /// channel.
+    fn set_channel_volume(&mut self, channel: &str, volume: f32) -> bool;
+
+    /// Mutes or unmutes a channel, returning `false` if the channel cannot
+    /// be muted.
+    ///
+    /// Default implementation returns `false`.
+    fn set_channel_muted(&mut self, channel: &str, muted: bool) -> bool {
+        let _ = (channel, muted);
+        false
+    }
+}
+
+/// A parsed `audio` console command.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AudioConsoleCommand {
+    /// Prints a channel volume, or sets it when a value is given.
+    Volume {
+        /// The channel name.
+        channel: String,
+        /// The new volume.
+        value: Option<f32>,
+    },
+    /// Mutes or unmutes a channel.
+    Mute {
+        /// The channel name.
+        channel: String,
+        /// Whether the channel should be muted.
+        muted: bool,
+    },
+    /// Prints every playing sound.
+    List,
+}
+
+/// Error returned when a line is not a valid `audio` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseAudioCommandError(String);
+
+impl fmt::Display for ParseAudioCommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ParseAudioCommandError {}
+
+const USAGE: &str = "usage: audio volume <channel> [value] | audio mute <channel> | audio unmute <channel> | audio list";
+
+impl FromStr for AudioConsoleCommand {
+    type Err = ParseAudioCommandError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let usage = || ParseAudioCommandError(USAGE.to_string());
+        let mut words = line.split_whitespace();
+        if words.next() != Some("audio") {
+            return Err(usage());
+        }
+        let command = match (words.next(), words.next(), words.next()) {
+            (Some("volume"), Some(channel), value) => Self::Volume {
+                channel: channel.to_string(),
+                value: value
+                    .map(|value| {
+                        value
+                            .parse::<f32>()
+                            .ok()
+                            .filter(|volume| volume.is_finite() && *volume >= 0.0)
+                            .ok_or_else(|| {
+                                ParseAudioCommandError(format!("invalid volume: {value}"))
+                            })
+                    })
+                    .transpose()?,
+            },
+            (Some(action @ ("mute" | "unmute")), Some(channel), None) => Self::Mute {
+                channel: channel.to_string(),
+                muted: action == "mute",
+            },
+            (Some("list"), None, None) => Self::List,
+            _ => return Err(usage()),
+        };
+        if words.next().is_some() {
+            return Err(usage());
+        }
+        Ok(command)
+    }
+}
+
+/// Message carrying a console line for the [`AudioConsolePlugin`].
+///
+/// Lines that do not start with `audio` are ignored, so every console line
+/// can be forwarded.
+#[derive(Message, Clone, Debug)]
+pub struct AudioConsoleInput(pub String);
+
+/// Message carrying a line printed in answer to an [`AudioConsoleInput`].
+#[derive(Message, Clone, Debug, PartialEq, Eq)]
+pub struct AudioConsoleOutput(pub String);
+
+/// Plugin running `audio` console commands against config `C`, with music
+/// type `M` and sound effect type `S` played through the backend `B`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{AudioConsoleInput, AudioConsoleOutput, AudioConsolePlugin};
+///
+/// app.add_plugins(AudioConsolePlugin::<GameMusic, GameSfx, GameAudioConfig>::default());
+///
+/// fn forward(mut lines: MessageReader<MyConsoleLine>, mut input: MessageWriter<AudioConsoleInput>) {
+///     for line in lines.read() {
+///         input.write(AudioConsoleInput(line.text.clone()));
+///     }
+/// }
+///
+/// fn print(mut output: MessageReader<AudioConsoleOutput>, mut console: ResMut<MyConsole>) {
+///     for line in output.read() {
+///         console.print(&line.0);
+///     }
+/// }
+/// ```
+pub struct AudioConsolePlugin<M, S, C, B = BevyAudioBackend> {
+    _phantom: PhantomData<(M, S, C, B)>,
+}
+
+impl<M, S, C, B> Default for AudioConsolePlugin<M, S, C, B> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M, S, C, B> Plugin for AudioConsolePlugin<M, S, C, B>
+where
+    M: MusicCategory + Debug,
+    S: SfxCategory + Debug,
+    C: ConsoleAudioConfig,
+    B: AudioBackend,
+{
+    fn build(&self, app: &mut App) {
+        app.add_message::<AudioConsoleInput>()
+            .add_message::<AudioConsoleOutput>()
+            .add_systems(Update, run_audio_console_commands::<M, S, C, B>);
+    }
+}
+
+fn run_audio_console_commands<M, S, C, B>(
+    mut input: MessageReader<AudioConsoleInput>,
+    mut output: MessageWriter<AudioConsoleOutput>,
+    mut config: ResMut<C>,
+    asset_server: Option<Res<AssetServer>>,
+    sounds: Query<(Entity, &AudioPlayer, AnySink<B>, Option<&M>, Option<&S>)>,
+) where
+    M: MusicCategory + Debug,
+    S: SfxCategory + Debug,
+    C: ConsoleAudioConfig,
+    B: AudioBackend,
+{
+    for AudioConsoleInput(line) in input.read() {
+        if line.split_whitespace().next() != Some("audio") {
+            continue;
+        }
+        let command = match line.parse::<AudioConsoleCommand>() {
+            Ok(command) => command,
+            Err(err) => {
+                output.write(AudioConsoleOutput(err.to_string()));
+                continue;
+            }
+        };
+        let unknown = |channel: &str, config: &C| {
+            format!(
+                "unknown channel {channel}, expected one of: {}",
+                config.channels().join(", ")
+            )
+        };
+        let reply = match command {
+            AudioConsoleCommand::Volume {
+                channel,
+                value: None,
+            } => config.channel_volume(&channel).map_or_else(
+                || unknown(&channel, &config),
+                |volume| format!("{channel} volume: {volume}"),
+            ),
+            AudioConsoleCommand::Volume {
+                channel,
+                value: Some(volume),
+            } => {
+                if config.set_channel_volume(&channel, volume) {
+                    format!("{channel} volume set to {volume}")
+                } else {
+                    unknown(&channel, &config)
+                }
+            }
+            AudioConsoleCommand::Mute { channel, muted } => {
+                if config.set_channel_muted(&channel, muted) {
+                    format!("{channel} {}", if muted { "muted" } else { "unmuted" })
+                } else {
+                    format!("{channel} cannot be muted")
+                }
+            }
+            AudioConsoleCommand::List => {
+                let mut lines = vec![format!("{} sounds playing", sounds.iter().len())];
+                for (entity, player, sink, music, sfx) in &sounds {
+                    let sink = either_sink::<B>(sink);
+                    let category = match (music, sfx) {
+                        (Some(music), _) => format!("{music:?}"),
+                        (_, Some(sfx)) => format!("{sfx:?}"),
+                        _ => "-".to_string(),
+                    };
+                    let path = asset_server
+                        .as_ref()
+                        .and_then(|server| server.get_path(&player.0))
+                        .map_or_else(|| format!("{:?}", player.0.id()), |path| path.to_string());
+                    lines.push(format!(
+                        "{entity} [{category}] {path} vol {:.2}{}",
+                        sink.volume(),
+                        if sink.is_paused() { " (paused)" } else { "" },
+                    ));
+                }
+                lines.join("\n")
+            }
+        };
+        output.write(AudioConsoleOutput(reply));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(
+            "audio volume master 0.5".parse(),
+            Ok(AudioConsoleCommand::Volume {
+                channel: "master".to_string(),
+                value: Some(0.5),
+            })
+        );
+        assert_eq!(
+            "audio mute sfx".parse(),
+            Ok(AudioConsoleCommand::Mute {
+                channel: "sfx".to_string(),
+                muted: true,
+            })
+        );
+        assert_eq!("audio list".parse(), Ok(AudioConsoleCommand::List));
+        for value in ["loud", "nan", "inf", "-0.5"] {
+            assert!(format!("audio volume master {value}")
+                .parse::<AudioConsoleCommand>()
+                .is_err());
+        }
+        assert!("audio list all".parse::<AudioConsoleCommand>().is_err());
+    }
+
+    #[derive(Component, Clone, Copy, Default, Debug, PartialEq)]
+    struct TestMusic;
+
+    impl crate::AudioCategory for TestMusic {
+        type Config = TestConfig;
+
+        fn volume_multiplier(&self, _config: &Self::Config) -> f32 {
+            1.0
+        }
+    }
+
+    impl MusicCategory for TestMusic {}
+
+    #[derive(Component, Clone, Copy, Default, Debug, PartialEq)]
+    struct TestSfx;
+
+    impl crate::AudioCategory for TestSfx {
+        type Config = TestConfig;
+
+        fn volume_multiplier(&self, config: &Self::Config) -> f32 {
+            config.sfx
+        }
+    }
+
+    impl SfxCategory for TestSfx {}
+
+    #[derive(Resource, Clone, Default)]
+    struct TestConfig {
+        sfx: f32,
+    }
+
+    impl AudioConfigTrait for TestConfig {
+        fn master_volume(&self) -> f32 {
+            1.0
+        }
+    }
+
+    impl ConsoleAudioConfig for TestConfig {
+        fn channels(&self) -> &[&'static str] {
+            &["sfx"]
+        }
+
+        fn channel_volume(&self, channel: &str) -> Option<f32> {
+            (channel == "sfx").then_some(self.sfx)
+        }
+
+        fn set_channel_volume(&mut self, channel: &str, volume: f32) -> bool {
+            if channel == "sfx" {
+                self.sfx = volume;
+            }
+            channel == "sfx"
+        }
+    }
+
+    #[test]
+    fn commands_change_config_and_reply() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.init_resource::<TestConfig>();
+        app.add_plugins(AudioConsolePlugin::<TestMusic, TestSfx, TestConfig>::default());
+
+        for line in ["audio volume sfx 0.3", "audio mute sfx", "say hello"] {
+            app.world_mut()
+                .write_message(AudioConsoleInput(line.to_string()));
+        }
+        app.update();
+
+        assert_eq!(app.world().resource::<TestConfig>().sfx, 0.3);
+        let messages = app.world().resource::<Messages<AudioConsoleOutput>>();
+        let mut cursor = messages.get_cursor();
+        let replies: Vec<_> = cursor.read(messages).map(|line| line.0.as_str()).collect();
+        assert_eq!(replies, ["sfx volume set to 0.3", "sfx cannot be muted"]);
+    }
+}
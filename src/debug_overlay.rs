@@ -0,0 +1,289 @@
+//! Debug overlay listing playing sounds.
+//!
+//! [`AudioDebugOverlayPlugin`] draws a `bevy_ui` panel with one row per audio
+//! entity: its category, source path, volume, speed, and remaining time, with
+//! buttons to stop the sound or solo it. Enable the `debug_overlay` feature to
+//! use it.
+
+use bevy::{audio::PlaybackMode, prelude::*};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use crate::backend::{either_sink, with_sink_mut, AnySink, AudioBackend, BevyAudioBackend};
+use crate::duration::SourceDurations;
+use crate::traits::{MusicCategory, SfxCategory};
+
+/// Default time between two refreshes of the overlay.
+pub const DEFAULT_DEBUG_OVERLAY_REFRESH: Duration = Duration::from_millis(250);
+
+/// Plugin drawing the audio debug overlay for music type `M` and sound effect
+/// type `S` played through the backend `B`.
+///
+/// The overlay starts visible and toggles with [`AudioDebugOverlay::toggle_key`]
+/// (F9 by default) when keyboard input is available.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::AudioDebugOverlayPlugin;
+///
+/// #[cfg(debug_assertions)]
+/// app.add_plugins(AudioDebugOverlayPlugin::<GameMusic, GameSfx>::default());
+/// ```
+pub struct AudioDebugOverlayPlugin<M, S, B = BevyAudioBackend> {
+    /// Time between two refreshes of the overlay.
+    pub refresh: Duration,
+    _phantom: PhantomData<(M, S, B)>,
+}
+
+impl<M, S, B> Default for AudioDebugOverlayPlugin<M, S, B> {
+    fn default() -> Self {
+        Self {
+            refresh: DEFAULT_DEBUG_OVERLAY_REFRESH,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M, S, B> Plugin for AudioDebugOverlayPlugin<M, S, B>
+where
+    M: MusicCategory + Debug,
+    S: SfxCategory + Debug,
+    B: AudioBackend,
+{
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AudioDebugOverlay {
+            refresh: Timer::new(self.refresh, TimerMode::Repeating),
+            ..default()
+        });
+        app.add_systems(
+            Update,
+            (
+                toggle_audio_debug_overlay.run_if(resource_exists::<ButtonInput<KeyCode>>),
+                handle_audio_debug_buttons::<B>,
+                refresh_audio_debug_overlay::<M, S, B>,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Resource controlling the audio debug overlay.
+#[derive(Resource, Debug, Clone)]
+pub struct AudioDebugOverlay {
+    /// Whether the overlay is shown.
+    pub visible: bool,
+    /// Key toggling the overlay, or `None` to only toggle through `visible`.
+    pub toggle_key: Option<KeyCode>,
+    /// The sound currently soloed from the overlay.
+    pub soloed: Option<Entity>,
+    refresh: Timer,
+}
+
+impl Default for AudioDebugOverlay {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            toggle_key: Some(KeyCode::F9),
+            soloed: None,
+            refresh: Timer::new(DEFAULT_DEBUG_OVERLAY_REFRESH, TimerMode::Repeating),
+        }
+    }
+}
+
+/// Marks the root node of the overlay.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct AudioDebugOverlayRoot;
+
+/// Marks sounds paused while another sound is soloed from the overlay.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct PausedByDebugSolo;
+
+/// Action of an overlay button.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum AudioDebugButton {
+    Stop(Entity),
+    Solo(Entity),
+}
+
+fn toggle_audio_debug_overlay(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<AudioDebugOverlay>,
+) {
+    if overlay.toggle_key.is_some_and(|key| keys.just_pressed(key)) {
+        overlay.visible = !overlay.visible;
+    }
+}
+
+fn handle_audio_debug_buttons<B: AudioBackend>(
+    mut commands: Commands,
+    mut overlay: ResMut<AudioDebugOverlay>,
+    buttons: Query<(&Interaction, &AudioDebugButton), Changed<Interaction>>,
+    mut sinks: ParamSet<(
+        Query<(Entity, AnySink<B>, Has<PausedByDebugSolo>)>,
+        Query<&mut B::Sink>,
+        Query<&mut <B::Spatial as AudioBackend>::Sink>,
+    )>,
+) {
+    for (interaction, button) in &buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *button {
+            AudioDebugButton::Stop(entity) => {
+                commands.entity(entity).try_despawn();
+            }
+            AudioDebugButton::Solo(entity) => {
+                let solo = overlay.soloed != Some(entity);
+                overlay.soloed = solo.then_some(entity);
+                let sounds: Vec<(Entity, bool, bool)> = sinks
+                    .p0()
+                    .iter()
+                    .map(|(other, sink, paused_by_solo)| {
+                        (other, either_sink::<B>(sink).is_paused(), paused_by_solo)
+                    })
+                    .collect();
+                for (other, paused, paused_by_solo) in sounds {
+                    if paused_by_solo {
+                        with_sink_mut::<B, _>(&mut sinks, other, B::resume, B::Spatial::resume);
+                        commands.entity(other).remove::<PausedByDebugSolo>();
+                    }
+                    if solo && other != entity && !paused_by_solo && !paused {
+                        with_sink_mut::<B, _>(&mut sinks, other, B::pause, B::Spatial::pause);
+                        commands.entity(other).insert(PausedByDebugSolo);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn refresh_audio_debug_overlay<M, S, B>(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut overlay: ResMut<AudioDebugOverlay>,
+    asset_server: Option<Res<AssetServer>>,
+    durations: Option<Res<SourceDurations>>,
+    roots: Query<Entity, With<AudioDebugOverlayRoot>>,
+    sounds: Query<(
+        Entity,
+        &AudioPlayer,
+        AnySink<B>,
+        Option<&PlaybackSettings>,
+        Option<&M>,
+        Option<&S>,
+    )>,
+) where
+    M: MusicCategory + Debug,
+    S: SfxCategory + Debug,
+    B: AudioBackend,
+{
+    let due = overlay.refresh.tick(time.delta()).just_finished();
+    let shown = !roots.is_empty();
+    if shown == overlay.visible && !(shown && due) {
+        return;
+    }
+    for root in &roots {
+        commands.entity(root).despawn();
+    }
+    if !overlay.visible {
+        return;
+    }
+
+    let mut rows: Vec<_> = sounds.iter().collect();
+    rows.sort_by_key(|(entity, ..)| *entity);
+    let soloed = overlay.soloed;
+
+    commands
+        .spawn((
+            AudioDebugOverlayRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(8.0),
+                left: Val::Px(8.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(2.0),
+                padding: UiRect::all(Val::Px(6.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+            GlobalZIndex(i32::MAX),
+        ))
+        .with_children(|panel| {
+            panel.spawn(label(format!("Audio: {} playing", rows.len())));
+            for (entity, player, sink, playback, music, sfx) in rows {
+                let sink = either_sink::<B>(sink);
+                let category = match (music, sfx) {
+                    (Some(music), _) => format!("{music:?}"),
+                    (_, Some(sfx)) => format!("{sfx:?}"),
+                    _ => "-".to_string(),
+                };
+                let path = asset_server
+                    .as_ref()
+                    .and_then(|server| server.get_path(&player.0))
+                    .map_or_else(|| format!("{:?}", player.0.id()), |path| path.to_string());
+                let looping =
+                    playback.is_some_and(|playback| matches!(playback.mode, PlaybackMode::Loop));
+                let total = durations
+                    .as_ref()
+                    .and_then(|durations| durations.get(&player.0));
+                let remaining = match total {
+                    _ if looping => "loop".to_string(),
+                    Some(total) => {
+                        format!(
+                            "{:.1}s left",
+                            total.saturating_sub(sink.position()).as_secs_f32()
+                        )
+                    }
+                    None => "?".to_string(),
+                };
+                let paused = if sink.is_paused() { " (paused)" } else { "" };
+
+                panel
+                    .spawn(Node {
+                        column_gap: Val::Px(6.0),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.spawn(label(format!(
+                            "{entity} [{category}] {path} vol {:.2} x{:.2} {remaining}{paused}",
+                            sink.volume(),
+                            sink.speed(),
+                        )));
+                        row.spawn(button("Stop", AudioDebugButton::Stop(entity)));
+                        let solo = if soloed == Some(entity) {
+                            "Unsolo"
+                        } else {
+                            "Solo"
+                        };
+                        row.spawn(button(solo, AudioDebugButton::Solo(entity)));
+                    });
+            }
+        });
+}
+
+fn label(text: String) -> impl Bundle {
+    (
+        Text::new(text),
+        TextFont {
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+    )
+}
+
+fn button(text: &str, action: AudioDebugButton) -> impl Bundle {
+    (
+        Button,
+        action,
+        Node {
+            padding: UiRect::axes(Val::Px(4.0), Val::Px(1.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.25, 0.25, 0.3)),
+        children![label(text.to_string())],
+    )
+}
@@ -0,0 +1,107 @@
+//! Delayed playback of audio messages.
+//!
+//! [`PlaySfx::after`](crate::PlaySfx::after) and
+//! [`PlayMusic::after`](crate::PlayMusic::after) hold a request back for a
+//! while, so sounds can be sequenced, e.g. thunder after lightning, without
+//! timers in game code. Handlers park delayed requests in a
+//! [`DelayedMessages`] resource, which sends them again once they are due.
+
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Resource holding messages of type `E` waiting to be sent.
+///
+/// Registered for the [`PlaySfx`](crate::PlaySfx) and
+/// [`PlayMusic`](crate::PlayMusic) messages of every category type. Delays
+/// count in [`Time<Virtual>`], so they pause with the game.
+#[derive(Resource)]
+pub struct DelayedMessages<E: Message> {
+    pending: Vec<(Timer, E)>,
+}
+
+impl<E: Message> Default for DelayedMessages<E> {
+    fn default() -> Self {
+        Self {
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl<E: Message> DelayedMessages<E> {
+    /// Sends `message` once `delay` has passed.
+    pub fn push(&mut self, delay: Duration, message: E) {
+        self.pending
+            .push((Timer::new(delay, TimerMode::Once), message));
+    }
+
+    /// Returns the number of messages waiting to be sent.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns `true` if no messages are waiting.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Drops every waiting message.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+}
+
+/// Sends delayed messages of type `E` whose delay has passed.
+///
+/// Runs before [`MsgAudioSet::EventHandling`](crate::MsgAudioSet::EventHandling),
+/// so released requests are handled in the same frame.
+pub fn release_delayed_messages<E: Message>(
+    time: Res<Time>,
+    mut delayed: ResMut<DelayedMessages<E>>,
+    mut messages: MessageWriter<E>,
+) {
+    if delayed.is_empty() {
+        return;
+    }
+    let mut index = 0;
+    while index < delayed.pending.len() {
+        if delayed.pending[index].0.tick(time.delta()).is_finished() {
+            let (_, message) = delayed.pending.remove(index);
+            messages.write(message);
+        } else {
+            index += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin, TestSfx};
+    use crate::{AudioLog, AudioRequest, PlayMusic, PlaySfx, TestAudioPlugin};
+
+    #[test]
+    fn delayed_requests_play_once_due() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        let delay = Duration::from_millis(100);
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI).after(delay));
+        app.world_mut()
+            .write_message(PlayMusic::new(Handle::default(), TestMusic::Main).after(delay));
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<AudioLog>().plays().count(), 0);
+        assert_eq!(
+            app.world()
+                .resource::<DelayedMessages<PlaySfx<TestSfx>>>()
+                .len(),
+            1
+        );
+
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(delay));
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<AudioLog>().plays().count(), 2);
+    }
+}
@@ -0,0 +1,168 @@
+//! Audio diagnostics.
+//!
+//! [`AudioDiagnosticsPlugin`] registers Bevy [`Diagnostic`]s for the number
+//! of playing sounds and dropped requests, so `LogDiagnosticsPlugin` and
+//! diagnostics overlays show audio health next to frame times.
+
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+};
+use std::marker::PhantomData;
+
+use crate::backend::{either_sink, AnySink, AudioBackend, BevyAudioBackend};
+use crate::events::AudioDropped;
+use crate::pool::SfxPool;
+use crate::tone::Tone;
+use crate::traits::{MusicCategory, SfxCategory};
+
+/// Number of music tracks currently playing.
+pub const AUDIO_ACTIVE_MUSIC: DiagnosticPath = DiagnosticPath::const_new("audio/active_music");
+
+/// Number of sound effects currently playing.
+pub const AUDIO_ACTIVE_SFX: DiagnosticPath = DiagnosticPath::const_new("audio/active_sfx");
+
+/// Sounds dropped per second, see [`AudioDropped`].
+pub const AUDIO_DROPPED_PER_SECOND: DiagnosticPath =
+    DiagnosticPath::const_new("audio/dropped_per_second");
+
+/// Share of pooled sound effects that reused an idle entity, see [`SfxPool`].
+pub const AUDIO_POOL_HIT_RATE: DiagnosticPath = DiagnosticPath::const_new("audio/pool_hit_rate");
+
+/// Highest number of sounds playing at once since startup.
+pub const AUDIO_PEAK_VOICES: DiagnosticPath = DiagnosticPath::const_new("audio/peak_voices");
+
+/// Optional plugin adding audio [`Diagnostic`]s for music type `M` and sound
+/// effect type `S`, played through the backend `B`.
+///
+/// Counts only include playing sounds, spatial or not; paused sinks are left
+/// out. Peak voices count every playing sink, whatever its category. The pool hit rate is
+/// only recorded while an [`SfxPool`] is enabled.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use bevy::diagnostic::LogDiagnosticsPlugin;
+/// use msg_audio::AudioDiagnosticsPlugin;
+///
+/// app.add_plugins((
+///     AudioDiagnosticsPlugin::<GameMusic, GameSfx>::default(),
+///     LogDiagnosticsPlugin::default(),
+/// ));
+/// ```
+pub struct AudioDiagnosticsPlugin<M, S, B = BevyAudioBackend>
+where
+    M: MusicCategory,
+    S: SfxCategory,
+    B: AudioBackend,
+{
+    _phantom: PhantomData<(M, S, B)>,
+}
+
+impl<M, S, B> Default for AudioDiagnosticsPlugin<M, S, B>
+where
+    M: MusicCategory,
+    S: SfxCategory,
+    B: AudioBackend,
+{
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M, S, B> Plugin for AudioDiagnosticsPlugin<M, S, B>
+where
+    M: MusicCategory,
+    S: SfxCategory,
+    B: AudioBackend,
+{
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(AUDIO_ACTIVE_MUSIC).with_smoothing_factor(0.0))
+            .register_diagnostic(Diagnostic::new(AUDIO_ACTIVE_SFX).with_smoothing_factor(0.0))
+            .register_diagnostic(Diagnostic::new(AUDIO_DROPPED_PER_SECOND).with_suffix("/s"))
+            .register_diagnostic(
+                Diagnostic::new(AUDIO_PEAK_VOICES)
+                    .with_smoothing_factor(0.0)
+                    .with_max_history_length(0),
+            )
+            .register_diagnostic(
+                Diagnostic::new(AUDIO_POOL_HIT_RATE)
+                    .with_smoothing_factor(0.0)
+                    .with_max_history_length(0),
+            )
+            .add_message::<AudioDropped>()
+            .add_message::<AudioDropped<Tone>>()
+            .add_systems(Last, audio_diagnostics_system::<M, S, B>);
+    }
+}
+
+/// Records the audio diagnostics for music type `M` and sound effect type `S`
+/// played through the backend `B`.
+pub fn audio_diagnostics_system<M: MusicCategory, S: SfxCategory, B: AudioBackend>(
+    mut diagnostics: Diagnostics,
+    time: Res<Time<Real>>,
+    mut dropped: MessageReader<AudioDropped>,
+    mut dropped_tones: MessageReader<AudioDropped<Tone>>,
+    sinks: Query<(AnySink<B>, Has<M>, Has<S>)>,
+    pool: Option<Res<SfxPool>>,
+    mut peak: Local<usize>,
+) {
+    let (mut music, mut sfx, mut voices) = (0, 0, 0);
+    for (sink, is_music, is_sfx) in &sinks {
+        if either_sink::<B>(sink).is_paused() {
+            continue;
+        }
+        voices += 1;
+        music += usize::from(is_music);
+        sfx += usize::from(is_sfx);
+    }
+    *peak = (*peak).max(voices);
+
+    diagnostics.add_measurement(&AUDIO_ACTIVE_MUSIC, || music as f64);
+    diagnostics.add_measurement(&AUDIO_ACTIVE_SFX, || sfx as f64);
+    diagnostics.add_measurement(&AUDIO_PEAK_VOICES, || *peak as f64);
+    if let Some(pool) = pool {
+        diagnostics.add_measurement(&AUDIO_POOL_HIT_RATE, || f64::from(pool.hit_rate()));
+    }
+
+    let dropped = dropped.read().count() + dropped_tones.read().count();
+    let delta = time.delta_secs_f64();
+    if delta > 0.0 {
+        diagnostics.add_measurement(&AUDIO_DROPPED_PER_SECOND, || dropped as f64 / delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin, TestSfx};
+    use crate::{PlayMusic, PlaySfx, TestAudioPlugin};
+
+    #[test]
+    fn diagnostics_count_playing_sounds() {
+        use bevy::diagnostic::{Diagnostic, DiagnosticPath, DiagnosticsStore};
+
+        let mut app = test_app((
+            TestPlugin::default(),
+            AudioDiagnosticsPlugin::<TestMusic, TestSfx>::default(),
+            TestAudioPlugin,
+        ));
+
+        app.world_mut()
+            .write_message(PlayMusic::new(Handle::default(), TestMusic::Main));
+        for _ in 0..2 {
+            app.world_mut()
+                .write_message(PlaySfx::new(Handle::default(), TestSfx::UI));
+        }
+        app.update();
+        app.update();
+
+        let store = app.world().resource::<DiagnosticsStore>();
+        let value = |path: &DiagnosticPath| store.get(path).and_then(Diagnostic::value).unwrap();
+        assert_eq!(value(&AUDIO_ACTIVE_MUSIC), 1.0);
+        assert_eq!(value(&AUDIO_ACTIVE_SFX), 2.0);
+        assert_eq!(value(&AUDIO_PEAK_VOICES), 3.0);
+    }
+}
@@ -0,0 +1,155 @@
+//! Lengths of audio sources.
+//!
+//! A sink's position keeps growing while its sound loops, so systems that
+//! care where in the track playback is, such as [`MusicMemory`] and the
+//! [`MusicClock`], wrap it by the length of the source stored in
+//! [`SourceDurations`].
+//!
+//! [`MusicMemory`]: crate::MusicMemory
+//! [`MusicClock`]: crate::MusicClock
+
+use bevy::{
+    audio::Source,
+    platform::collections::HashMap,
+    prelude::*,
+    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
+};
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Lengths of loaded audio sources.
+///
+/// Sources are measured in the background as they finish loading. Formats
+/// that do not report their length are decoded once to count their samples,
+/// so long tracks can take a moment to show up. Lengths can also be inserted
+/// by hand, for example from a track manifest.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::SourceDurations;
+///
+/// fn track_progress(durations: Res<SourceDurations>, music: Query<(&AudioPlayer, &AudioSink)>) {
+///     for (player, sink) in &music {
+///         let position = durations.wrap(&player.0, sink.position());
+///         info!("At {position:?} of {:?}", durations.get(&player.0));
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SourceDurations {
+    durations: HashMap<AssetId<AudioSource>, Duration>,
+}
+
+impl SourceDurations {
+    /// Returns the length of a source, if it is known.
+    #[must_use]
+    pub fn get(&self, source: impl Into<AssetId<AudioSource>>) -> Option<Duration> {
+        self.durations.get(&source.into()).copied()
+    }
+
+    /// Stores the length of a source, replacing any measured one.
+    pub fn insert(&mut self, source: impl Into<AssetId<AudioSource>>, duration: Duration) {
+        self.durations.insert(source.into(), duration);
+    }
+
+    /// Forgets the length of a source.
+    pub fn remove(&mut self, source: impl Into<AssetId<AudioSource>>) {
+        self.durations.remove(&source.into());
+    }
+
+    /// Returns where in its source a sink position is, wrapping positions
+    /// that have looped past the end.
+    ///
+    /// Positions of sources with an unknown or zero length are returned
+    /// unchanged.
+    #[must_use]
+    pub fn wrap(&self, source: impl Into<AssetId<AudioSource>>, position: Duration) -> Duration {
+        match self.get(source) {
+            Some(duration) if !duration.is_zero() => {
+                Duration::from_nanos((position.as_nanos() % duration.as_nanos()) as u64)
+            }
+            _ => position,
+        }
+    }
+
+    /// Returns how many times a sound has wrapped around at a sink position.
+    ///
+    /// Returns `0` for sources with an unknown or zero length.
+    #[must_use]
+    pub fn loops(&self, source: impl Into<AssetId<AudioSource>>, position: Duration) -> u128 {
+        match self.get(source) {
+            Some(duration) if !duration.is_zero() => position.as_nanos() / duration.as_nanos(),
+            _ => 0,
+        }
+    }
+}
+
+/// Measures audio sources in the background as they load and stores their
+/// lengths in [`SourceDurations`].
+pub fn measure_source_durations(
+    mut durations: ResMut<SourceDurations>,
+    mut events: MessageReader<AssetEvent<AudioSource>>,
+    sources: Res<Assets<AudioSource>>,
+    mut pending: Local<Vec<(AssetId<AudioSource>, Task<Option<Duration>>)>>,
+) {
+    for event in events.read() {
+        match *event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => {
+                let Some(source) = sources.get(id) else {
+                    continue;
+                };
+                let bytes = source.bytes.clone();
+                let task = AsyncComputeTaskPool::get().spawn(async move { measure(bytes) });
+                pending.retain(|(pending, _)| *pending != id);
+                pending.push((id, task));
+            }
+            AssetEvent::Removed { id } => {
+                durations.remove(id);
+                pending.retain(|(pending, _)| *pending != id);
+            }
+            AssetEvent::Added { .. } | AssetEvent::Unused { .. } => {}
+        }
+    }
+    pending.retain_mut(|(id, task)| match block_on(future::poll_once(task)) {
+        Some(measured) => {
+            if let Some(duration) = measured {
+                durations.insert(*id, duration);
+            }
+            false
+        }
+        None => true,
+    });
+}
+
+/// Returns the length of encoded audio, counting its samples if the format
+/// does not report it.
+fn measure(bytes: Arc<[u8]>) -> Option<Duration> {
+    let decoder = rodio::Decoder::new(Cursor::new(bytes)).ok()?;
+    if let Some(duration) = decoder.total_duration() {
+        return Some(duration);
+    }
+    let samples_per_second = f64::from(decoder.sample_rate()) * f64::from(decoder.channels());
+    let samples = decoder.count() as f64;
+    Some(Duration::from_secs_f64(samples / samples_per_second))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_wrap_by_source_length() {
+        let handle = Handle::<AudioSource>::default();
+        let mut durations = SourceDurations::default();
+        let position = Duration::from_secs(130);
+
+        assert_eq!(durations.wrap(&handle, position), position);
+        assert_eq!(durations.loops(&handle, position), 0);
+
+        durations.insert(&handle, Duration::from_secs(60));
+        assert_eq!(durations.wrap(&handle, position), Duration::from_secs(10));
+        assert_eq!(durations.loops(&handle, position), 2);
+    }
+}
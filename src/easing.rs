@@ -0,0 +1,161 @@
+//! Easing curves for volume fades.
+//!
+//! An [`Easing`] maps linear fade progress in `[0.0, 1.0]` to the fraction of
+//! the volume change that has been applied. Linear fades tend to sound abrupt
+//! at the tail, so music usually benefits from [`Easing::Exponential`] or
+//! [`Easing::SCurve`].
+
+use std::fmt;
+use std::sync::Arc;
+
+/// Curve applied to fade progress.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{Easing, FadeOut};
+///
+/// let fade = FadeOut::from_secs(2.0).with_easing(Easing::Exponential);
+/// let custom = FadeOut::from_secs(2.0).with_easing(Easing::custom(|t| t * t));
+/// ```
+#[derive(Clone, Default)]
+pub enum Easing {
+    /// Constant rate of change.
+    #[default]
+    Linear,
+    /// Fast change at the start that slows towards the end.
+    ///
+    /// Fading out with this curve removes most of the loudness early and
+    /// leaves a long quiet tail, which sounds closer to a linear fade in decibels.
+    Exponential,
+    /// Slow start and end with a faster change in the middle (smoothstep).
+    SCurve,
+    /// User-provided curve.
+    ///
+    /// The function receives progress in `[0.0, 1.0]` and should return
+    /// `0.0` at the start and `1.0` at the end.
+    Custom(Arc<dyn Fn(f32) -> f32 + Send + Sync>),
+}
+
+impl Easing {
+    /// Creates a custom easing curve from a function or closure.
+    #[must_use]
+    pub fn custom(f: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(f))
+    }
+
+    /// Applies the curve to the given progress.
+    ///
+    /// Progress is clamped to `[0.0, 1.0]` before the curve is evaluated.
+    #[must_use]
+    pub fn apply(&self, progress: f32) -> f32 {
+        let t = progress.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::Exponential => {
+                if t >= 1.0 {
+                    1.0
+                } else {
+                    1.0 - 2_f32.powf(-10.0 * t)
+                }
+            }
+            Self::SCurve => t * t * (3.0 - 2.0 * t),
+            Self::Custom(f) => f(t),
+        }
+    }
+}
+
+impl fmt::Debug for Easing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Linear => f.write_str("Linear"),
+            Self::Exponential => f.write_str("Exponential"),
+            Self::SCurve => f.write_str("SCurve"),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// Named curves, the serialized form of [`Easing`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Easing")]
+enum EasingName {
+    Linear,
+    Exponential,
+    SCurve,
+}
+
+/// Custom curves cannot be serialized and fail with an error.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Easing {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let name = match self {
+            Self::Linear => EasingName::Linear,
+            Self::Exponential => EasingName::Exponential,
+            Self::SCurve => EasingName::SCurve,
+            Self::Custom(_) => {
+                return Err(serde::ser::Error::custom(
+                    "custom easing curves cannot be serialized",
+                ))
+            }
+        };
+        serde::Serialize::serialize(&name, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Easing {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(
+            match <EasingName as serde::Deserialize>::deserialize(deserializer)? {
+                EasingName::Linear => Self::Linear,
+                EasingName::Exponential => Self::Exponential,
+                EasingName::SCurve => Self::SCurve,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_is_identity() {
+        assert!((Easing::Linear.apply(0.25) - 0.25).abs() < f32::EPSILON);
+        assert!((Easing::Linear.apply(0.75) - 0.75).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn curves_start_at_zero_and_end_at_one() {
+        for easing in [Easing::Linear, Easing::Exponential, Easing::SCurve] {
+            assert!(easing.apply(0.0).abs() < f32::EPSILON, "{easing:?}");
+            assert!((easing.apply(1.0) - 1.0).abs() < f32::EPSILON, "{easing:?}");
+        }
+    }
+
+    #[test]
+    fn exponential_changes_faster_than_linear_early() {
+        assert!(Easing::Exponential.apply(0.2) > Easing::Linear.apply(0.2));
+    }
+
+    #[test]
+    fn s_curve_is_symmetric_around_midpoint() {
+        assert!((Easing::SCurve.apply(0.5) - 0.5).abs() < f32::EPSILON);
+        assert!(Easing::SCurve.apply(0.1) < 0.1);
+        assert!(Easing::SCurve.apply(0.9) > 0.9);
+    }
+
+    #[test]
+    fn progress_is_clamped() {
+        assert!(Easing::Linear.apply(-1.0).abs() < f32::EPSILON);
+        assert!((Easing::Linear.apply(2.0) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn custom_curve_is_used() {
+        let easing = Easing::custom(|t| t * t);
+        assert!((easing.apply(0.5) - 0.25).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,314 @@
+//! Effect zones and buses.
+//!
+//! An [`AudioEffectZone`] assigns an [`EffectBus`] to a region, e.g. a reverb
+//! preset for a cave. While the [`AmbienceListener`](crate::AmbienceListener)
+//! is inside a zone, its bus becomes the [`ActiveEffectBus`], and sound
+//! effects spawned in that time carry an [`EffectSend`] describing how much of
+//! their signal goes through the bus (wet) and how much bypasses it (dry).
+//!
+//! `bevy_audio` has no effect processing, so the send is a routing model for
+//! audio backends and DSP integrations to honor. The one exception is the
+//! [`Echo`], which the crate approximates itself by playing delayed, quieter
+//! copies of one-shot sounds.
+
+use bevy::prelude::*;
+use std::borrow::Cow;
+use std::time::Duration;
+
+use crate::ambience::ZoneShape;
+
+/// Processing preset that sounds can be routed through.
+///
+/// The name identifies the preset for the backend (e.g. `"cave_reverb"`),
+/// while `wet` and `dry` are linear gains of the processed and unprocessed
+/// signal.
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub struct EffectBus {
+    /// Name of the processing preset.
+    pub name: Cow<'static, str>,
+    /// Linear gain of the processed signal.
+    pub wet: f32,
+    /// Linear gain of the unprocessed signal.
+    pub dry: f32,
+    /// Echo played for one-shot sounds routed through the bus.
+    pub echo: Option<Echo>,
+}
+
+impl EffectBus {
+    /// The bus used outside of any zone: no processing, full dry signal.
+    pub const DRY: Self = Self {
+        name: Cow::Borrowed("dry"),
+        wet: 0.0,
+        dry: 1.0,
+        echo: None,
+    };
+
+    /// Creates a bus that sends half of the signal through the named preset.
+    #[must_use]
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            wet: 0.5,
+            dry: 1.0,
+            echo: None,
+        }
+    }
+
+    /// Sets the linear gain of the processed signal.
+    #[must_use]
+    pub fn with_wet(mut self, wet: f32) -> Self {
+        self.wet = wet;
+        self
+    }
+
+    /// Sets the linear gain of the unprocessed signal.
+    #[must_use]
+    pub fn with_dry(mut self, dry: f32) -> Self {
+        self.dry = dry;
+        self
+    }
+
+    /// Echoes one-shot sounds routed through the bus.
+    #[must_use]
+    pub fn with_echo(mut self, echo: Echo) -> Self {
+        self.echo = Some(echo);
+        self
+    }
+
+    /// Returns `true` if the bus leaves the signal unprocessed.
+    #[must_use]
+    pub fn is_dry(&self) -> bool {
+        self.wet <= 0.0 && self.echo.is_none()
+    }
+}
+
+impl Default for EffectBus {
+    fn default() -> Self {
+        Self::DRY
+    }
+}
+
+/// Echo approximated by replaying a one-shot sound as delayed, quieter taps.
+///
+/// Works without a DSP-capable backend, at the cost of one extra sound per
+/// tap. Tag a sound with it to echo it directly, or add it to an
+/// [`EffectBus`] to echo every one-shot played in the bus's zones. The tag
+/// wins over the bus.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{Echo, PlaySfx};
+///
+/// messages.write(
+///     PlaySfx::new(assets.load("sfx/shout.ogg"), GameSfx::Gameplay)
+///         .with_components(Echo::new(Duration::from_millis(350)).with_taps(3).with_decay(0.4)),
+/// );
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+pub struct Echo {
+    /// Number of delayed copies.
+    pub taps: u32,
+    /// Time between the sound and its first tap, and between taps.
+    pub delay: Duration,
+    /// Linear gain of each tap relative to the previous one.
+    pub decay: f32,
+}
+
+impl Echo {
+    /// Creates an echo with two taps, each at half the volume of the last.
+    #[must_use]
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            taps: 2,
+            delay,
+            decay: 0.5,
+        }
+    }
+
+    /// Sets the number of delayed copies.
+    #[must_use]
+    pub fn with_taps(mut self, taps: u32) -> Self {
+        self.taps = taps;
+        self
+    }
+
+    /// Sets the gain of each tap relative to the previous one.
+    #[must_use]
+    pub fn with_decay(mut self, decay: f32) -> Self {
+        self.decay = decay.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Returns the delay of the tap, counting from `1`.
+    #[must_use]
+    pub fn tap_delay(&self, tap: u32) -> Duration {
+        self.delay * tap
+    }
+
+    /// Returns the linear gain of the tap, counting from `1`.
+    #[must_use]
+    pub fn tap_gain(&self, tap: u32) -> f32 {
+        self.decay.powi(tap.min(i32::MAX as u32) as i32)
+    }
+}
+
+/// Marks a delayed copy played by an [`Echo`], which is not echoed again.
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct EchoTap;
+
+/// Routes sounds through an [`EffectBus`] while the listener is inside a region.
+///
+/// When zones overlap, the zone with the highest `priority` wins, so a small
+/// room inside a large cave can use its own bus.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{AudioEffectZone, EffectBus};
+///
+/// commands.spawn((
+///     AudioEffectZone::sphere(EffectBus::new("cave_reverb").with_wet(0.7), 40.0),
+///     Transform::from_xyz(0.0, -20.0, 0.0),
+/// ));
+/// ```
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+#[require(Transform)]
+pub struct AudioEffectZone {
+    /// Bus used for sounds played inside the zone.
+    pub bus: EffectBus,
+    /// Region covered by the zone.
+    pub shape: ZoneShape,
+    /// Zones with a higher priority win where zones overlap.
+    pub priority: i32,
+}
+
+impl AudioEffectZone {
+    /// Creates a zone covering `shape`.
+    #[must_use]
+    pub fn new(bus: EffectBus, shape: ZoneShape) -> Self {
+        Self {
+            bus,
+            shape,
+            priority: 0,
+        }
+    }
+
+    /// Creates a spherical zone.
+    #[must_use]
+    pub fn sphere(bus: EffectBus, radius: f32) -> Self {
+        Self::new(bus, ZoneShape::Sphere { radius })
+    }
+
+    /// Creates a box-shaped zone.
+    #[must_use]
+    pub fn cuboid(bus: EffectBus, half_extents: Vec3) -> Self {
+        Self::new(bus, ZoneShape::Box { half_extents })
+    }
+
+    /// Sets the priority used where zones overlap.
+    #[must_use]
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+/// Resource holding the bus of the effect zone the listener is in.
+///
+/// [`EffectBus::DRY`] while the listener is outside of every zone.
+#[derive(Resource, Reflect, Debug, Clone, Default, PartialEq)]
+#[reflect(Resource)]
+pub struct ActiveEffectBus(pub EffectBus);
+
+/// The effect routing of a sound, taken from the [`ActiveEffectBus`] when
+/// the sound was spawned.
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+pub struct EffectSend(pub EffectBus);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestPlugin, TestSfx};
+    use crate::{AmbienceListener, AudioRequest, PlaySfx};
+
+    #[test]
+    fn dry_bus_is_default() {
+        assert!(EffectBus::default().is_dry());
+        assert!(!EffectBus::new("hall").is_dry());
+        assert!(EffectBus::new("hall").with_wet(0.0).is_dry());
+        assert!(!EffectBus::new("canyon")
+            .with_wet(0.0)
+            .with_echo(Echo::new(Duration::from_millis(300)))
+            .is_dry());
+    }
+
+    #[test]
+    fn echo_taps_decay() {
+        let echo = Echo::new(Duration::from_millis(200))
+            .with_taps(3)
+            .with_decay(0.5);
+        assert_eq!(echo.tap_delay(2), Duration::from_millis(400));
+        assert_eq!(echo.tap_gain(1), 0.5);
+        assert_eq!(echo.tap_gain(3), 0.125);
+    }
+
+    #[test]
+    fn sfx_spawned_inside_effect_zone_get_effect_send() {
+        let mut app = test_app(TestPlugin::default());
+
+        app.world_mut().spawn((
+            AudioEffectZone::sphere(EffectBus::new("cave"), 10.0),
+            GlobalTransform::IDENTITY,
+        ));
+        app.world_mut().spawn((
+            AudioEffectZone::sphere(EffectBus::new("room"), 2.0).with_priority(1),
+            GlobalTransform::from_translation(Vec3::new(20.0, 0.0, 0.0)),
+        ));
+        app.world_mut()
+            .spawn((AmbienceListener, GlobalTransform::IDENTITY));
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI));
+        app.update();
+
+        assert_eq!(app.world().resource::<ActiveEffectBus>().0.name, "cave");
+        let mut sends = app.world_mut().query::<&EffectSend>();
+        assert_eq!(sends.single(app.world()).unwrap().0.name, "cave");
+    }
+
+    #[test]
+    fn echo_bus_replays_one_shots_as_decaying_taps() {
+        let mut app = test_app(TestPlugin::default());
+
+        let echo = Echo::new(Duration::ZERO).with_taps(2).with_decay(0.5);
+        app.world_mut().spawn((
+            AudioEffectZone::sphere(EffectBus::new("canyon").with_echo(echo), 10.0),
+            GlobalTransform::IDENTITY,
+        ));
+        app.world_mut()
+            .spawn((AmbienceListener, GlobalTransform::IDENTITY));
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI).volume(0.8));
+        for _ in 0..4 {
+            app.update();
+        }
+
+        let mut sounds = app
+            .world_mut()
+            .query_filtered::<(&PlaybackSettings, Has<EchoTap>), With<TestSfx>>();
+        let mut taps: Vec<f32> = sounds
+            .iter(app.world())
+            .filter(|(_, tap)| *tap)
+            .map(|(playback, _)| playback.volume.to_linear())
+            .collect();
+        taps.sort_by(f32::total_cmp);
+        assert_eq!(sounds.iter(app.world()).count(), 3);
+        assert_eq!(taps.len(), 2);
+        assert!((taps[0] - 0.2).abs() < 0.0001);
+        assert!((taps[1] - 0.4).abs() < 0.0001);
+    }
+}
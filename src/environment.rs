@@ -0,0 +1,432 @@
+//! Environment audio profiles.
+//!
+//! An [`EnvironmentProfile`] changes the gain and playback speed of
+//! categories to make the whole mix sound like a place, e.g. muted and
+//! slowed down while underwater. Profiles are registered per category type in
+//! [`EnvironmentProfiles`] and selected by name through the
+//! [`ActiveEnvironment`] resource, either globally or by the
+//! [`EnvironmentZone`] the [`AmbienceListener`] is in. Switching profiles
+//! ramps between them.
+
+use bevy::prelude::*;
+use std::borrow::Cow;
+use std::time::Duration;
+
+use crate::ambience::{AmbienceListener, ZoneShape};
+use crate::mixer::{CategoryMixerState, CategorySpeed};
+use crate::traits::AudioCategory;
+
+/// Default time the mix takes to ramp from one profile to another.
+pub const DEFAULT_ENVIRONMENT_TRANSITION: Duration = Duration::from_millis(250);
+
+/// Gain and speed modifiers of the categories of type `A` in an environment.
+///
+/// Categories without an explicit entry use the default gain and speed.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::EnvironmentProfile;
+///
+/// let underwater = EnvironmentProfile::new("underwater")
+///     .with_default(0.4, 0.85)
+///     .with_category(GameSfx::UI, 1.0, 1.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvironmentProfile<A: AudioCategory> {
+    /// Name used to select the profile.
+    pub name: Cow<'static, str>,
+    /// Linear gain for categories without an explicit entry.
+    pub default_gain: f32,
+    /// Speed multiplier for categories without an explicit entry.
+    pub default_speed: f32,
+    /// Linear gain and speed multiplier per category.
+    pub categories: Vec<(A, f32, f32)>,
+}
+
+impl<A: AudioCategory> Default for EnvironmentProfile<A> {
+    fn default() -> Self {
+        Self::new("default")
+    }
+}
+
+impl<A: AudioCategory> EnvironmentProfile<A> {
+    /// Creates a profile that leaves every category unchanged.
+    #[must_use]
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            default_gain: 1.0,
+            default_speed: 1.0,
+            categories: Vec::new(),
+        }
+    }
+
+    /// Sets the gain and speed for categories without an explicit entry.
+    #[must_use]
+    pub fn with_default(mut self, gain: f32, speed: f32) -> Self {
+        self.default_gain = gain.max(0.0);
+        self.default_speed = speed;
+        self
+    }
+
+    /// Sets the gain and speed of a category.
+    #[must_use]
+    pub fn with_category(mut self, category: A, gain: f32, speed: f32) -> Self {
+        self.categories.retain(|(c, ..)| *c != category);
+        self.categories.push((category, gain.max(0.0), speed));
+        self
+    }
+
+    /// Returns the gain this profile applies to the category.
+    #[must_use]
+    pub fn gain(&self, category: A) -> f32 {
+        self.categories
+            .iter()
+            .find(|(c, ..)| *c == category)
+            .map_or(self.default_gain, |(_, gain, _)| *gain)
+    }
+
+    /// Returns the speed multiplier this profile applies to the category.
+    #[must_use]
+    pub fn speed(&self, category: A) -> f32 {
+        self.categories
+            .iter()
+            .find(|(c, ..)| *c == category)
+            .map_or(self.default_speed, |(.., speed)| *speed)
+    }
+
+    /// Returns the profile `t` of the way from `self` to `to`.
+    #[must_use]
+    pub fn lerp(&self, to: &Self, t: f32) -> Self {
+        let mix = |from: f32, to: f32| from + (to - from) * t;
+        let mut blended = Self {
+            name: to.name.clone(),
+            default_gain: mix(self.default_gain, to.default_gain),
+            default_speed: mix(self.default_speed, to.default_speed),
+            categories: Vec::new(),
+        };
+        for (category, ..) in self.categories.iter().chain(&to.categories) {
+            if !blended.categories.iter().any(|(c, ..)| c == category) {
+                blended.categories.push((
+                    *category,
+                    mix(self.gain(*category), to.gain(*category)),
+                    mix(self.speed(*category), to.speed(*category)),
+                ));
+            }
+        }
+        blended
+    }
+}
+
+/// Resource registering the [`EnvironmentProfile`]s of category type `A`.
+///
+/// Registered for every music, sound effect, and voice category type. An
+/// active environment without a profile for the type leaves it unchanged.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{EnvironmentProfile, EnvironmentProfiles};
+///
+/// app.insert_resource(
+///     EnvironmentProfiles::new()
+///         .with_profile(EnvironmentProfile::new("underwater").with_default(0.4, 0.85)),
+/// );
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct EnvironmentProfiles<A: AudioCategory> {
+    /// The registered profiles.
+    pub profiles: Vec<EnvironmentProfile<A>>,
+    /// Time the mix takes to ramp from one profile to another.
+    pub transition: Duration,
+    target: Option<Cow<'static, str>>,
+    from: EnvironmentProfile<A>,
+    blend: f32,
+}
+
+impl<A: AudioCategory> Default for EnvironmentProfiles<A> {
+    fn default() -> Self {
+        Self {
+            profiles: Vec::new(),
+            transition: DEFAULT_ENVIRONMENT_TRANSITION,
+            target: None,
+            from: EnvironmentProfile::default(),
+            blend: 1.0,
+        }
+    }
+}
+
+impl<A: AudioCategory> EnvironmentProfiles<A> {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces a profile.
+    #[must_use]
+    pub fn with_profile(mut self, profile: EnvironmentProfile<A>) -> Self {
+        self.profiles
+            .retain(|existing| existing.name != profile.name);
+        self.profiles.push(profile);
+        self
+    }
+
+    /// Sets the time the mix takes to ramp from one profile to another.
+    #[must_use]
+    pub fn with_transition(mut self, transition: Duration) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    /// Returns the profile with the given name.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&EnvironmentProfile<A>> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// Returns the modifiers currently applied, part way through a ramp.
+    #[must_use]
+    pub fn current(&self) -> EnvironmentProfile<A> {
+        let target = self
+            .target
+            .as_deref()
+            .and_then(|name| self.get(name))
+            .cloned()
+            .unwrap_or_default();
+        self.from.lerp(&target, self.blend)
+    }
+
+    /// Starts ramping towards the named profile, or back to no profile.
+    fn retarget(&mut self, target: Option<&str>) {
+        self.from = self.current();
+        self.target = target.map(|name| Cow::Owned(name.to_owned()));
+        self.blend = if self.transition.is_zero() { 1.0 } else { 0.0 };
+    }
+}
+
+/// Resource selecting the active environment by profile name.
+///
+/// A global environment set by game code, e.g. while the camera is below the
+/// water surface, wins over the one of the [`EnvironmentZone`] the listener
+/// is in.
+#[derive(Resource, Reflect, Debug, Clone, Default, PartialEq)]
+#[reflect(Resource)]
+pub struct ActiveEnvironment {
+    /// Environment set by game code.
+    pub global: Option<Cow<'static, str>>,
+    /// Environment of the zone the listener is in.
+    pub zone: Option<Cow<'static, str>>,
+}
+
+impl ActiveEnvironment {
+    /// Sets the global environment.
+    pub fn set(&mut self, name: impl Into<Cow<'static, str>>) {
+        self.global = Some(name.into());
+    }
+
+    /// Clears the global environment, falling back to the zone's.
+    pub fn clear(&mut self) {
+        self.global = None;
+    }
+
+    /// Returns the name of the active environment.
+    #[must_use]
+    pub fn active(&self) -> Option<&str> {
+        self.global.as_deref().or(self.zone.as_deref())
+    }
+}
+
+/// Selects an environment while the [`AmbienceListener`] is inside a region.
+///
+/// When zones overlap, the zone with the highest `priority` wins.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::EnvironmentZone;
+///
+/// commands.spawn((
+///     EnvironmentZone::cuboid("underwater", Vec3::new(50.0, 10.0, 50.0)),
+///     Transform::from_xyz(0.0, -10.0, 0.0),
+/// ));
+/// ```
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+#[require(Transform)]
+pub struct EnvironmentZone {
+    /// Name of the profile used inside the zone.
+    pub profile: Cow<'static, str>,
+    /// Region covered by the zone.
+    pub shape: ZoneShape,
+    /// Zones with a higher priority win where zones overlap.
+    pub priority: i32,
+}
+
+impl EnvironmentZone {
+    /// Creates a zone covering `shape`.
+    #[must_use]
+    pub fn new(profile: impl Into<Cow<'static, str>>, shape: ZoneShape) -> Self {
+        Self {
+            profile: profile.into(),
+            shape,
+            priority: 0,
+        }
+    }
+
+    /// Creates a spherical zone.
+    #[must_use]
+    pub fn sphere(profile: impl Into<Cow<'static, str>>, radius: f32) -> Self {
+        Self::new(profile, ZoneShape::Sphere { radius })
+    }
+
+    /// Creates a box-shaped zone.
+    #[must_use]
+    pub fn cuboid(profile: impl Into<Cow<'static, str>>, half_extents: Vec3) -> Self {
+        Self::new(profile, ZoneShape::Box { half_extents })
+    }
+
+    /// Sets the priority used where zones overlap.
+    #[must_use]
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+/// Sets the zone environment of [`ActiveEnvironment`] from the environment
+/// zones containing the listener.
+pub fn update_environment_zone(
+    mut active: ResMut<ActiveEnvironment>,
+    listeners: Query<&GlobalTransform, With<AmbienceListener>>,
+    zones: Query<(&EnvironmentZone, &GlobalTransform)>,
+) {
+    let zone = listeners.iter().next().and_then(|listener| {
+        let point = listener.translation();
+        zones
+            .iter()
+            .map(|(zone, transform)| (zone, zone.shape.depth(transform, point)))
+            .filter(|(_, depth)| *depth > 0.0)
+            .max_by(|(a, a_depth), (b, b_depth)| {
+                a.priority.cmp(&b.priority).then(a_depth.total_cmp(b_depth))
+            })
+            .map(|(zone, _)| zone.profile.clone())
+    });
+    if active.zone != zone {
+        active.zone = zone;
+    }
+}
+
+/// Ramps the mixer and speeds of category type `A` towards the profile of
+/// the [`ActiveEnvironment`].
+pub fn apply_environment_profile<A: AudioCategory>(
+    time: Res<Time<Real>>,
+    active: Res<ActiveEnvironment>,
+    mut profiles: ResMut<EnvironmentProfiles<A>>,
+    mut mixer: ResMut<CategoryMixerState<A>>,
+    mut speeds: ResMut<CategorySpeed<A>>,
+) {
+    let target = active.active();
+    if profiles.target.as_deref() != target {
+        profiles.retarget(target);
+    } else if profiles.blend >= 1.0 && !profiles.is_changed() {
+        return;
+    }
+    if profiles.blend < 1.0 {
+        profiles.blend =
+            (profiles.blend + time.delta_secs() / profiles.transition.as_secs_f32()).min(1.0);
+    }
+    let current = profiles.current();
+    if *mixer.environment() != current {
+        mixer.set_environment(current.clone());
+    }
+    if *speeds.environment() != current {
+        speeds.set_environment(current);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestConfig, TestMusic, TestPlugin, TestSfx};
+    use crate::{PlayMusic, TestAudioPlugin, VolumeRamp};
+
+    #[test]
+    fn profiles_blend_per_category() {
+        let underwater = EnvironmentProfile::new("underwater")
+            .with_default(0.4, 0.8)
+            .with_category(TestSfx::UI, 1.0, 1.0);
+        assert!((underwater.gain(TestSfx::World) - 0.4).abs() < f32::EPSILON);
+        assert!((underwater.speed(TestSfx::UI) - 1.0).abs() < f32::EPSILON);
+
+        let halfway = EnvironmentProfile::default().lerp(&underwater, 0.5);
+        assert!((halfway.gain(TestSfx::World) - 0.7).abs() < 0.0001);
+        assert!((halfway.speed(TestSfx::World) - 0.9).abs() < 0.0001);
+        assert!((halfway.gain(TestSfx::UI) - 1.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn global_environment_wins_over_zone() {
+        let mut active = ActiveEnvironment {
+            zone: Some("cave".into()),
+            ..default()
+        };
+        assert_eq!(active.active(), Some("cave"));
+        active.set("underwater");
+        assert_eq!(active.active(), Some("underwater"));
+        active.clear();
+        assert_eq!(active.active(), Some("cave"));
+    }
+
+    #[test]
+    fn environment_zone_applies_profile() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.insert_resource(TestConfig::FULL);
+        app.insert_resource(VolumeRamp::INSTANT);
+        app.insert_resource(
+            EnvironmentProfiles::<TestMusic>::new()
+                .with_profile(EnvironmentProfile::new("underwater").with_default(0.5, 0.8))
+                .with_transition(Duration::ZERO),
+        );
+        app.world_mut().spawn((
+            EnvironmentZone::sphere("underwater", 10.0),
+            GlobalTransform::IDENTITY,
+        ));
+        let listener = app
+            .world_mut()
+            .spawn((AmbienceListener, GlobalTransform::IDENTITY))
+            .id();
+        app.world_mut()
+            .write_message(PlayMusic::new(Handle::default(), TestMusic::Main));
+        app.update();
+        app.update();
+        app.update();
+
+        let music = |app: &mut App| {
+            let world = app.world_mut();
+            let mut sinks = world.query_filtered::<&AudioSink, With<TestMusic>>();
+            let sink = sinks.single(world).unwrap();
+            (
+                crate::systems::extract_linear_volume(sink.volume()),
+                sink.speed(),
+            )
+        };
+        assert_eq!(
+            app.world().resource::<ActiveEnvironment>().active(),
+            Some("underwater")
+        );
+        let (volume, speed) = music(&mut app);
+        assert!((volume - 0.5).abs() < 0.0001);
+        assert!((speed - 0.8).abs() < 0.0001);
+
+        *app.world_mut()
+            .get_mut::<GlobalTransform>(listener)
+            .unwrap() = GlobalTransform::from_xyz(50.0, 0.0, 0.0);
+        app.update();
+        app.update();
+        let (volume, speed) = music(&mut app);
+        assert!((volume - 1.0).abs() < 0.0001);
+        assert!((speed - 1.0).abs() < 0.0001);
+    }
+}
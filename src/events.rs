@@ -6,19 +6,77 @@
 //! ## Music Messages
 //!
 //! - [`PlayMusic`] - Start playing a music track
+//! - [`SeekMusic`] - Seek a specific music category to a position
 //! - [`StopMusic`] - Stop a specific music category
 //! - [`StopAllMusic`] - Stop all currently playing music
 //! - [`FadeOutMusic`] - Gradually fade out music over time
+//! - [`FadeOutAllMusic`] - Gradually fade out all music regardless of category
+//! - [`CrossfadeMusic`] - Fade a category over to a new track, optionally on the beat
+//! - [`PlayStinger`] - Play a short phrase over the music, ducking it meanwhile
+//! - [`PlayLayeredMusic`] - Start a track made of several synchronized stems
+//! - [`SetLayerVolume`] - Fade one layer of layered music in or out
 //!
 //! ## Sound Effect Messages
 //!
 //! - [`PlaySfx`] - Play a sound effect
+//! - [`FadeOutSfx`] - Gradually fade out sound effects of a specific category
+//!
+//! ## Voice Messages
+//!
+//! - [`PlayVoiceLine`] - Queue a voice line, or interrupt a lower-priority one
+//! - [`VoiceLineFinished`] - Emitted when a voice line ends or is interrupted
+//!
+//! ## Clock Messages
+//!
+//! - [`Beat`] - Emitted when a track with a [`MusicTempo`] reaches a new beat
+//! - [`Bar`] - Emitted when a track with a [`MusicTempo`] reaches a new bar
+//!
+//! ## Instance Messages
+//!
+//! - [`StopLabeled`] - Stop the instances carrying a [`PlaybackLabel`]
+//! - [`PauseLabeled`] - Pause the instances carrying a [`PlaybackLabel`]
+//! - [`ResumeLabeled`] - Resume the instances carrying a [`PlaybackLabel`]
+//! - [`PauseAudio`] - Pause one entity or labeled instances
+//! - [`ResumeAudio`] - Resume one entity or labeled instances
+//! - [`SetAudioVolume`] - Change the volume of one entity or labeled instances
+//!
+//! ## Mixer Messages
+//!
+//! - [`MuteCategory`] - Mute or unmute a music or sound effect category
+//! - [`SoloCategory`] - Solo a category, silencing the others of its type
+//! - [`PushMixerSnapshot`] - Blend in a named set of per-category gains
+//! - [`PopMixerSnapshot`] - Blend out a previously pushed snapshot
 
-use bevy::prelude::*;
+use bevy::{asset::AssetPath, audio::PlaybackMode, ecs::entity::Entities, prelude::*};
+use std::borrow::Cow;
 use std::time::Duration;
 
-use crate::components::PlaybackRandomizer;
-use crate::traits::{MusicCategory, SfxCategory};
+use crate::backend::{either_sink, AnySink, AudioBackend};
+use crate::clock::{MusicTempo, Quantize, QuantizedStop};
+use crate::components::{
+    AttachedAudio, AudioVolumeState, ConcurrencyPolicy, DuplicateMusic, FadeIn, FadeOut,
+    LoopingSfx, MaxDuration, MusicCrossfade, NonSpatial, PausedByGame, PlayChance, PlaybackLabel,
+    PlaybackRandomizer, StartPosition, StereoPan, StereoPanRange, Stinger, TrackGain, VolumeTween,
+    WaitForLoad,
+};
+use crate::delay::DelayedMessages;
+use crate::easing::Easing;
+use crate::effects::EchoTap;
+use crate::extra::ExtraComponents;
+use crate::focus::PausedByFocus;
+use crate::layers::{LayeredMusic, MusicLayer};
+use crate::library::{SoundId, SoundLibrary};
+use crate::localization::{CurrentAudioLanguage, LocalizedAudio};
+use crate::memory::MusicMemory;
+use crate::mixer::{CategoryMixerState, MixerSnapshot};
+use crate::pool::{PoolGeneration, Pooled, SfxPool};
+use crate::request::{AudioRequest, ConcurrencyRequest, SfxRequest};
+use crate::rng::AudioRng;
+use crate::systems::target_volume;
+use crate::track_info::TrackRegistry;
+use crate::traits::{AudioCategory, AudioConfigTrait, MusicCategory, SfxCategory, VoiceCategory};
+use crate::virtualization::{AudioPriority, Virtualized};
+use crate::voice::DialogueQueue;
 
 /// Message to request playing a music track.
 ///
@@ -41,6 +99,33 @@ pub struct PlayMusic<M: MusicCategory> {
     pub category: M,
     /// Custom playback settings (defaults to LOOP).
     pub playback: PlaybackSettings,
+    /// Optional fade-in applied when the track starts.
+    pub fade_in: Option<FadeIn>,
+    /// Defer playback until the audio source has finished loading.
+    pub wait_for_load: bool,
+    /// Optional per-track gain applied on top of the category volume.
+    pub gain: Option<TrackGain>,
+    /// Optional label identifying the spawned instance.
+    pub label: Option<PlaybackLabel>,
+    /// Continue from the position stored in [`MusicMemory`].
+    pub resume: bool,
+    /// Optional tempo that makes the track drive the [`MusicClock`](crate::MusicClock).
+    pub tempo: Option<MusicTempo>,
+    /// Time to wait before the track starts.
+    pub delay: Option<Duration>,
+    /// Randomization rolled from the [`AudioRng`] when the track is spawned.
+    pub randomizer: Option<PlaybackRandomizer>,
+    /// Maximum concurrent instances of this track, `u32::MAX` for no limit.
+    pub max_concurrent: u32,
+    /// Which instances are stopped when `max_concurrent` is exceeded.
+    pub concurrency_policy: ConcurrencyPolicy,
+    /// What happens if the track already plays in the category, overriding
+    /// the [`DuplicateMusic`] resource.
+    pub on_duplicate: Option<DuplicateMusic>,
+    /// Asset path loaded through the [`AssetServer`] in place of `handle`.
+    pub path: Option<AssetPath<'static>>,
+    /// User components inserted into the spawned entity.
+    pub components: Vec<ExtraComponents>,
 }
 
 impl<M: MusicCategory> PlayMusic<M> {
@@ -51,21 +136,261 @@ impl<M: MusicCategory> PlayMusic<M> {
             handle,
             category,
             playback: PlaybackSettings::LOOP,
+            fade_in: None,
+            wait_for_load: false,
+            gain: None,
+            label: None,
+            resume: false,
+            tempo: None,
+            delay: None,
+            randomizer: None,
+            max_concurrent: u32::MAX,
+            concurrency_policy: ConcurrencyPolicy::default(),
+            on_duplicate: None,
+            path: None,
+            components: Vec::new(),
+        }
+    }
+
+    /// Creates a play music event that loads its source from an asset path.
+    ///
+    /// The [`AssetServer`] loads the path when the message is handled,
+    /// reusing the handle if the source is already loaded. Useful for
+    /// scripts, consoles, and data-driven triggers that have no handles.
+    #[must_use]
+    pub fn path(path: impl Into<AssetPath<'static>>, category: M) -> Self {
+        let mut event = Self::new(Handle::default(), category);
+        event.path = Some(path.into());
+        event
+    }
+
+    /// Sets what happens if the track already plays in the category,
+    /// overriding the [`DuplicateMusic`] resource.
+    #[must_use]
+    pub fn on_duplicate(mut self, policy: DuplicateMusic) -> Self {
+        self.on_duplicate = Some(policy);
+        self
+    }
+
+    /// Sets the track's tempo so it emits [`Beat`] and [`Bar`] messages.
+    #[must_use]
+    pub fn with_tempo(mut self, tempo: MusicTempo) -> Self {
+        self.tempo = Some(tempo);
+        self
+    }
+
+    /// Starts the track at the given position, e.g. to skip an intro.
+    ///
+    /// The track seeks there once its sink exists, so a looping track still
+    /// plays its intro from the second pass on. See [`StartPosition`].
+    #[must_use]
+    pub fn starting_at(mut self, position: Duration) -> Self {
+        self.playback.start_position = Some(position);
+        self
+    }
+
+    /// Continues the track from where it last stopped, if remembered.
+    ///
+    /// See [`MusicMemory`] for how positions are recorded.
+    #[must_use]
+    pub fn resume_from_memory(mut self) -> Self {
+        self.resume = true;
+        self
+    }
+
+    /// Labels the spawned instance so it can be targeted later.
+    ///
+    /// See [`PlaybackLabel`] for details.
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<PlaybackLabel>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Inserts a clone of `bundle` into the spawned entity, e.g. a marker
+    /// component.
+    ///
+    /// Can be called repeatedly to add several bundles.
+    #[must_use]
+    pub fn with_components(mut self, bundle: impl Bundle + Clone) -> Self {
+        self.components.push(ExtraComponents::new(bundle));
+        self
+    }
+
+    /// Sets a linear gain for this track, independent of the category volume.
+    ///
+    /// See [`TrackGain`] for details.
+    #[must_use]
+    pub fn with_base_gain(mut self, gain: f32) -> Self {
+        self.gain = Some(TrackGain::new(gain));
+        self
+    }
+
+    /// Defers playback until the audio source has finished loading.
+    ///
+    /// See [`WaitForLoad`] for details.
+    #[must_use]
+    pub fn wait_for_load(mut self) -> Self {
+        self.wait_for_load = true;
+        self
+    }
+}
+
+impl<M: MusicCategory> AudioRequest for PlayMusic<M> {
+    fn playback_mut(&mut self) -> &mut PlaybackSettings {
+        &mut self.playback
+    }
+
+    fn randomizer_mut(&mut self) -> Option<&mut PlaybackRandomizer> {
+        self.randomizer.as_mut()
+    }
+
+    fn fade_in_mut(&mut self) -> &mut Option<FadeIn> {
+        &mut self.fade_in
+    }
+
+    fn delay_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.delay
+    }
+
+    fn with_randomizer(mut self, randomizer: PlaybackRandomizer) -> Self {
+        self.randomizer = Some(randomizer);
+        self
+    }
+}
+
+impl<M: MusicCategory> ConcurrencyRequest for PlayMusic<M> {
+    fn with_max_concurrent(mut self, max: u32) -> Self {
+        self.max_concurrent = max;
+        self
+    }
+
+    fn with_concurrency_policy(mut self, policy: ConcurrencyPolicy) -> Self {
+        self.concurrency_policy = policy;
+        self
+    }
+}
+
+/// Message to play a [`LayeredMusic`] track.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{LayeredMusic, PlayLayeredMusic};
+///
+/// fn start_forest(mut messages: MessageWriter<PlayLayeredMusic<MyMusicCategory>>) {
+///     let track = LayeredMusic::new(MyMusicCategory::Exploration)
+///         .with_layer("pads", pads_handle, 1.0)
+///         .with_layer("drums", drums_handle, 0.0);
+///     messages.write(PlayLayeredMusic::new(track));
+/// }
+/// ```
+#[derive(Message, Clone)]
+pub struct PlayLayeredMusic<M: MusicCategory> {
+    /// The track to play.
+    pub track: LayeredMusic<M>,
+}
+
+impl<M: MusicCategory> PlayLayeredMusic<M> {
+    /// Creates a new play layered music event.
+    #[must_use]
+    pub fn new(track: LayeredMusic<M>) -> Self {
+        Self { track }
+    }
+}
+
+/// Message to change the volume of one layer of layered music.
+///
+/// Affects every stem of the category with a matching [`MusicLayer`] name.
+/// The new volume is kept as the stem's [`TrackGain`], so it survives
+/// config volume changes.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::SetLayerVolume;
+///
+/// fn enemies_spotted(mut messages: MessageWriter<SetLayerVolume<MyMusicCategory>>) {
+///     messages.write(
+///         SetLayerVolume::new(MyMusicCategory::Exploration, "drums", 1.0)
+///             .with_duration(Duration::from_secs(2)),
+///     );
+/// }
+/// ```
+#[derive(Message, Clone)]
+pub struct SetLayerVolume<M: MusicCategory> {
+    /// The music category of the layered track.
+    pub category: M,
+    /// The layer to change.
+    pub layer: MusicLayer,
+    /// New linear volume of the layer.
+    pub volume: f32,
+    /// Duration of the volume change (instant by default).
+    pub duration: Duration,
+    /// Curve applied to the change.
+    pub easing: Easing,
+}
+
+impl<M: MusicCategory> SetLayerVolume<M> {
+    /// Creates a message that changes the layer volume instantly.
+    #[must_use]
+    pub fn new(category: M, layer: impl Into<Cow<'static, str>>, volume: f32) -> Self {
+        Self {
+            category,
+            layer: MusicLayer::new(layer),
+            volume,
+            duration: Duration::ZERO,
+            easing: Easing::Linear,
         }
     }
 
-    /// Sets custom playback settings.
+    /// Fades to the new volume over the given duration.
+    #[must_use]
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Sets the easing curve for the fade.
     #[must_use]
-    pub fn with_playback(mut self, playback: PlaybackSettings) -> Self {
-        self.playback = playback;
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
         self
     }
 }
 
+/// Message to seek music of a specific category to a position.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::SeekMusic;
+///
+/// fn reach_checkpoint(mut messages: MessageWriter<SeekMusic<MyMusicCategory>>) {
+///     messages.write(SeekMusic::new(MyMusicCategory::Boss, Duration::from_secs(64)));
+/// }
+/// ```
+#[derive(Message, Clone)]
+pub struct SeekMusic<M: MusicCategory> {
+    /// The music category to seek.
+    pub category: M,
+    /// Position to seek to.
+    pub position: Duration,
+}
+
+impl<M: MusicCategory> SeekMusic<M> {
+    /// Creates a new seek music event.
+    #[must_use]
+    pub fn new(category: M, position: Duration) -> Self {
+        Self { category, position }
+    }
+}
+
 /// Message to stop music of a specific category.
 ///
 /// When triggered, immediately stops and despawns all music entities
-/// matching the specified category.
+/// matching the specified category, or fades them out first if a fade is
+/// set with [`with_fade`](Self::with_fade).
 ///
 /// # Example
 ///
@@ -73,27 +398,66 @@ impl<M: MusicCategory> PlayMusic<M> {
 /// use msg_audio::StopMusic;
 ///
 /// fn stop_combat_music(mut messages: MessageWriter<StopMusic<MyMusicCategory>>) {
-///     messages.write(StopMusic::new(MyMusicCategory::Combat));
+///     messages.write(StopMusic::new(MyMusicCategory::Combat).with_fade(Duration::from_secs(2)));
 /// }
 /// ```
 #[derive(Message, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StopMusic<M: MusicCategory> {
     /// The music category to stop.
     pub category: M,
+    /// Beat or bar boundary to wait for (tracks with a [`MusicTempo`] only).
+    pub quantize: Quantize,
+    /// Duration of the fade-out before the music stops, or `None` to stop
+    /// right away.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fade: Option<Duration>,
+    /// Curve applied to the fade-out (defaults to linear).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub easing: Easing,
 }
 
 impl<M: MusicCategory> StopMusic<M> {
     /// Creates a new stop music event.
     #[must_use]
     pub fn new(category: M) -> Self {
-        Self { category }
+        Self {
+            category,
+            quantize: Quantize::Immediate,
+            fade: None,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Fades the music out over `duration` before stopping it.
+    ///
+    /// Same as sending a [`FadeOutMusic`] message.
+    #[must_use]
+    pub fn with_fade(mut self, duration: Duration) -> Self {
+        self.fade = Some(duration);
+        self
+    }
+
+    /// Sets the easing curve for the fade.
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Waits for the next beat or bar of the track before stopping.
+    #[must_use]
+    pub fn quantized(mut self, quantize: Quantize) -> Self {
+        self.quantize = quantize;
+        self
     }
 }
 
 /// Message to stop all currently playing music.
 ///
 /// When triggered, immediately stops and despawns all music entities
-/// regardless of category.
+/// regardless of category, or fades them out first if a fade is set with
+/// [`with_fade`](Self::with_fade).
 ///
 /// # Example
 ///
@@ -105,10 +469,37 @@ impl<M: MusicCategory> StopMusic<M> {
 /// }
 /// ```
 #[derive(Message, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StopAllMusic<M: MusicCategory> {
+    /// Duration of the fade-out before the music stops, or `None` to stop
+    /// right away.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fade: Option<Duration>,
+    /// Curve applied to the fade-out (defaults to linear).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub easing: Easing,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _phantom: std::marker::PhantomData<M>,
 }
 
+impl<M: MusicCategory> StopAllMusic<M> {
+    /// Fades all music out over `duration` before stopping it.
+    ///
+    /// Same as sending a [`FadeOutAllMusic`] message.
+    #[must_use]
+    pub fn with_fade(mut self, duration: Duration) -> Self {
+        self.fade = Some(duration);
+        self
+    }
+
+    /// Sets the easing curve for the fade.
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
 /// Message to fade out music of a specific category.
 ///
 /// Gradually reduces the volume of matching music entities over the
@@ -128,28 +519,227 @@ pub struct StopAllMusic<M: MusicCategory> {
 /// }
 /// ```
 #[derive(Message, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FadeOutMusic<M: MusicCategory> {
     /// The music category to fade out.
     pub category: M,
     /// Duration of the fade-out effect.
     pub duration: Duration,
+    /// Curve applied to the fade progress (defaults to linear).
+    pub easing: Easing,
+    /// Beat or bar boundary to wait for (tracks with a [`MusicTempo`] only).
+    pub quantize: Quantize,
 }
 
 impl<M: MusicCategory> FadeOutMusic<M> {
     /// Creates a new fade-out music event.
     #[must_use]
     pub fn new(category: M, duration: Duration) -> Self {
-        Self { category, duration }
+        Self {
+            category,
+            duration,
+            easing: Easing::Linear,
+            quantize: Quantize::Immediate,
+        }
+    }
+
+    /// Waits for the next beat or bar of the track before fading.
+    #[must_use]
+    pub fn quantized(mut self, quantize: Quantize) -> Self {
+        self.quantize = quantize;
+        self
     }
 
     /// Creates a fade-out event with a duration in seconds.
     #[must_use]
     pub fn from_secs(category: M, seconds: f32) -> Self {
+        Self::new(category, Duration::from_secs_f32(seconds))
+    }
+
+    /// Sets the easing curve for the fade.
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// Message to fade out all currently playing music.
+///
+/// Gradually reduces the volume of every music entity over the specified
+/// duration, regardless of category, then despawns them.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::FadeOutAllMusic;
+/// use std::time::Duration;
+///
+/// fn fade_out_on_game_over(mut messages: MessageWriter<FadeOutAllMusic<MyMusicCategory>>) {
+///     messages.write(FadeOutAllMusic::new(Duration::from_secs(3)));
+/// }
+/// ```
+#[derive(Message, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FadeOutAllMusic<M: MusicCategory> {
+    /// Duration of the fade-out effect.
+    pub duration: Duration,
+    /// Curve applied to the fade progress (defaults to linear).
+    pub easing: Easing,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: std::marker::PhantomData<M>,
+}
+
+impl<M: MusicCategory> FadeOutAllMusic<M> {
+    /// Creates a new fade-out all music event.
+    #[must_use]
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            easing: Easing::Linear,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Creates a fade-out all music event with a duration in seconds.
+    #[must_use]
+    pub fn from_secs(seconds: f32) -> Self {
+        Self::new(Duration::from_secs_f32(seconds))
+    }
+
+    /// Sets the easing curve for the fade.
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// Message to crossfade the music of a category into a new track.
+///
+/// The tracks playing in the category of `play` fade out while the new track
+/// fades in over `duration`. Quantized crossfades wait for the next beat or
+/// bar of the playing track's [`MusicTempo`], so the switch lands in time
+/// with the music. Without a tempo they start right away.
+///
+/// Sent on as a [`FadeOutMusic`] and a delayed [`PlayMusic`], so recordings
+/// and other readers of those messages see the transition.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{CrossfadeMusic, PlayMusic};
+///
+/// fn enter_combat(mut messages: MessageWriter<CrossfadeMusic<GameMusic>>) {
+///     messages.write(
+///         CrossfadeMusic::new(
+///             PlayMusic::new(combat_theme, GameMusic::Gameplay),
+///             Duration::from_secs(2),
+///         )
+///         .on_next_bar(),
+///     );
+/// }
+/// ```
+#[derive(Message, Clone)]
+pub struct CrossfadeMusic<M: MusicCategory> {
+    /// The track to fade in.
+    pub play: PlayMusic<M>,
+    /// Duration of the fade-out of the old and the fade-in of the new track.
+    pub duration: Duration,
+    /// Curve applied to both fades (defaults to linear).
+    pub easing: Easing,
+    /// Beat or bar boundary to wait for (tracks with a [`MusicTempo`] only).
+    pub quantize: Quantize,
+}
+
+impl<M: MusicCategory> CrossfadeMusic<M> {
+    /// Creates a crossfade into `play` over `duration`.
+    #[must_use]
+    pub fn new(play: PlayMusic<M>, duration: Duration) -> Self {
+        Self {
+            play,
+            duration,
+            easing: Easing::Linear,
+            quantize: Quantize::Immediate,
+        }
+    }
+
+    /// Waits for the next beat or bar of the playing track before fading.
+    #[must_use]
+    pub fn quantized(mut self, quantize: Quantize) -> Self {
+        self.quantize = quantize;
+        self
+    }
+
+    /// Waits for the next beat of the playing track before fading.
+    #[must_use]
+    pub fn on_next_beat(self) -> Self {
+        self.quantized(Quantize::NextBeat)
+    }
+
+    /// Waits for the next bar of the playing track before fading.
+    #[must_use]
+    pub fn on_next_bar(self) -> Self {
+        self.quantized(Quantize::NextBar)
+    }
+
+    /// Sets the easing curve for both fades.
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// Message to play a stinger over the current music.
+///
+/// A stinger is a short musical phrase (victory fanfare, discovery jingle)
+/// played on top of the music. Other music of the same category type is
+/// ducked while the stinger plays and restored when it finishes.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::PlayStinger;
+///
+/// fn on_secret_found(mut messages: MessageWriter<PlayStinger<MyMusicCategory>>) {
+///     messages.write(PlayStinger::new(jingle_handle, MyMusicCategory::Exploration).with_duck_volume(0.2));
+/// }
+/// ```
+#[derive(Message, Clone)]
+pub struct PlayStinger<M: MusicCategory> {
+    /// Handle to the audio source.
+    pub handle: Handle<AudioSource>,
+    /// The music category for volume control.
+    pub category: M,
+    /// Ducking applied to the other music while the stinger plays.
+    pub stinger: Stinger,
+}
+
+impl<M: MusicCategory> PlayStinger<M> {
+    /// Creates a new play stinger event with default ducking.
+    #[must_use]
+    pub fn new(handle: Handle<AudioSource>, category: M) -> Self {
         Self {
+            handle,
             category,
-            duration: Duration::from_secs_f32(seconds),
+            stinger: Stinger::default(),
         }
     }
+
+    /// Sets the volume multiplier applied to the ducked music.
+    #[must_use]
+    pub fn with_duck_volume(mut self, duck_volume: f32) -> Self {
+        self.stinger.duck_volume = duck_volume;
+        self
+    }
+
+    /// Sets the duration of the duck and restore ramps.
+    #[must_use]
+    pub fn with_fade(mut self, fade: Duration) -> Self {
+        self.stinger.fade = fade;
+        self
+    }
 }
 
 /// Message to request playing a sound effect.
@@ -179,6 +769,42 @@ pub struct PlaySfx<S: SfxCategory> {
     pub playback: PlaybackSettings,
     /// Maximum concurrent instances of this sound.
     pub max_concurrent: u32,
+    /// Which instances are stopped when `max_concurrent` is exceeded.
+    pub concurrency_policy: ConcurrencyPolicy,
+    /// Defer playback until the audio source has finished loading.
+    pub wait_for_load: bool,
+    /// Optional label identifying the spawned instance.
+    pub label: Option<PlaybackLabel>,
+    /// Per-language variants that replace `handle` when set.
+    pub localized: Option<LocalizedAudio>,
+    /// Entity the sound follows and is despawned with.
+    pub attached_to: Option<Entity>,
+    /// Priority when the [`VoiceBudget`](crate::VoiceBudget) is exceeded.
+    pub priority: i32,
+    /// Randomization rolled from the [`AudioRng`] when the sound is spawned.
+    pub randomizer: Option<PlaybackRandomizer>,
+    /// Probability that the sound plays, from `0.0` to `1.0`.
+    pub chance: f32,
+    /// Range the [`StereoPan`] is rolled from when the sound is spawned.
+    pub pan: Option<(f32, f32)>,
+    /// Playing time after which the sound is force-stopped.
+    pub max_duration: Option<Duration>,
+    /// Time to wait before the sound starts.
+    pub delay: Option<Duration>,
+    /// Optional fade-in applied when the sound starts.
+    pub fade_in: Option<FadeIn>,
+    /// Sound of the [`SoundLibrary`] that replaces `handle` and `category`.
+    pub sound: Option<SoundId>,
+    /// Asset path loaded through the [`AssetServer`] in place of `handle`.
+    pub path: Option<AssetPath<'static>>,
+    /// User components inserted into the spawned entity.
+    pub components: Vec<ExtraComponents>,
+    /// Play centered and without spatial attenuation, see [`NonSpatial`].
+    pub non_spatial: bool,
+    /// Delayed copy played by an [`Echo`](crate::Echo), see [`EchoTap`].
+    /// Taps are not echoed again or recorded, as the replayed original
+    /// echoes again.
+    pub echo_tap: bool,
 }
 
 impl<S: SfxCategory> PlaySfx<S> {
@@ -190,172 +816,2131 @@ impl<S: SfxCategory> PlaySfx<S> {
             category,
             playback: PlaybackSettings::DESPAWN,
             max_concurrent: crate::bundles::DEFAULT_MAX_CONCURRENT,
+            concurrency_policy: ConcurrencyPolicy::default(),
+            wait_for_load: false,
+            label: None,
+            localized: None,
+            attached_to: None,
+            priority: 0,
+            randomizer: None,
+            chance: 1.0,
+            pan: None,
+            max_duration: None,
+            delay: None,
+            fade_in: None,
+            sound: None,
+            path: None,
+            components: Vec::new(),
+            non_spatial: false,
+            echo_tap: false,
         }
     }
 
-    /// Sets custom playback settings.
+    /// Creates a sound effect that loads its source from an asset path.
+    ///
+    /// The [`AssetServer`] loads the path when the message is handled,
+    /// reusing the handle if the source is already loaded.
     #[must_use]
-    pub fn with_playback(mut self, playback: PlaybackSettings) -> Self {
-        self.playback = playback;
-        self
+    pub fn path(path: impl Into<AssetPath<'static>>, category: S) -> Self {
+        let mut event = Self::new(Handle::default(), category);
+        event.path = Some(path.into());
+        event
     }
 
-    /// Sets the maximum concurrent instances.
+    /// Creates a sound effect that plays a sound of the [`SoundLibrary`].
+    ///
+    /// The handle, category, and default settings are looked up when the
+    /// message is handled. Unknown ids are skipped with a warning.
     #[must_use]
-    pub fn with_max_concurrent(mut self, max: u32) -> Self {
-        self.max_concurrent = max;
-        self
+    pub fn id(id: impl Into<SoundId>) -> Self {
+        let mut event = Self::new(Handle::default(), S::default());
+        event.sound = Some(id.into());
+        event
+    }
+
+    /// Creates a sound effect that plays the variant for the [`CurrentAudioLanguage`].
+    #[must_use]
+    pub fn localized(audio: LocalizedAudio, category: S) -> Self {
+        let mut event = Self::new(audio.default.clone(), category);
+        event.localized = Some(audio);
+        event
     }
 
-    /// Sets volume randomization range.
+    /// Returns the handle to play for a language.
     #[must_use]
-    pub fn with_volume(mut self, min: f32, max: f32) -> Self {
-        PlaybackRandomizer::new()
-            .with_volume(min, max)
-            .apply(&mut self.playback);
+    pub fn resolve_handle(&self, language: &CurrentAudioLanguage) -> &Handle<AudioSource> {
+        self.localized
+            .as_ref()
+            .map_or(&self.handle, |audio| audio.resolve(language))
+    }
+
+    /// Labels the spawned instance so it can be targeted later.
+    ///
+    /// See [`PlaybackLabel`] for details.
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<PlaybackLabel>) -> Self {
+        self.label = Some(label.into());
         self
     }
 
-    /// Sets speed randomization range.
+    /// Inserts a clone of `bundle` into the spawned entity, e.g. a marker
+    /// component.
+    ///
+    /// Can be called repeatedly to add several bundles.
     #[must_use]
-    pub fn with_speed(mut self, min: f32, max: f32) -> Self {
-        PlaybackRandomizer::new()
-            .with_speed(min, max)
-            .apply(&mut self.playback);
+    pub fn with_components(mut self, bundle: impl Bundle + Clone) -> Self {
+        self.components.push(ExtraComponents::new(bundle));
         self
     }
 
-    /// Applies standard randomization (speed 0.7-1.3, volume 0.6-1.0).
+    /// Defers playback until the audio source has finished loading.
+    ///
+    /// See [`WaitForLoad`] for details.
     #[must_use]
-    pub fn randomized(mut self) -> Self {
-        PlaybackRandomizer::standard().apply(&mut self.playback);
+    pub fn wait_for_load(mut self) -> Self {
+        self.wait_for_load = true;
         self
     }
-}
+
+    /// Attaches the sound to an entity it follows and is despawned with.
+    ///
+    /// See [`AttachedAudio`] for details.
+    #[must_use]
+    pub fn attached_to(mut self, entity: Entity) -> Self {
+        self.attached_to = Some(entity);
+        self
+    }
+
+    /// Plays the sound centered and without spatial attenuation, even when
+    /// it is attached to an entity.
+    ///
+    /// See [`NonSpatial`] for details.
+    #[must_use]
+    pub fn non_spatial(mut self) -> Self {
+        self.non_spatial = true;
+        self
+    }
+
+    /// Sets the priority used when the [`VoiceBudget`](crate::VoiceBudget) is exceeded.
+    #[must_use]
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Force-stops the sound once it has played for `duration`.
+    ///
+    /// See [`MaxDuration`] for details.
+    #[must_use]
+    pub fn with_max_duration(mut self, duration: Duration) -> Self {
+        self.max_duration = Some(duration);
+        self
+    }
+}
+
+impl<S: SfxCategory> AudioRequest for PlaySfx<S> {
+    fn playback_mut(&mut self) -> &mut PlaybackSettings {
+        &mut self.playback
+    }
+
+    fn randomizer_mut(&mut self) -> Option<&mut PlaybackRandomizer> {
+        self.randomizer.as_mut()
+    }
+
+    fn fade_in_mut(&mut self) -> &mut Option<FadeIn> {
+        &mut self.fade_in
+    }
+
+    fn delay_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.delay
+    }
+
+    fn with_randomizer(mut self, randomizer: PlaybackRandomizer) -> Self {
+        self.randomizer = Some(randomizer);
+        self
+    }
+}
+
+impl<S: SfxCategory> ConcurrencyRequest for PlaySfx<S> {
+    fn with_max_concurrent(mut self, max: u32) -> Self {
+        self.max_concurrent = max;
+        self
+    }
+
+    fn with_concurrency_policy(mut self, policy: ConcurrencyPolicy) -> Self {
+        self.concurrency_policy = policy;
+        self
+    }
+}
+
+impl<S: SfxCategory> SfxRequest for PlaySfx<S> {
+    /// The roll draws from the [`AudioRng`] resource when the message is
+    /// handled.
+    fn with_chance(mut self, chance: f32) -> Self {
+        self.chance = chance;
+        self
+    }
+
+    fn with_pan(mut self, pan: f32) -> Self {
+        self.pan = Some((pan, pan));
+        self
+    }
+
+    /// The pan is rolled from the [`AudioRng`] when the message is handled.
+    fn with_pan_range(mut self, min: f32, max: f32) -> Self {
+        self.pan = Some((min, max));
+        self
+    }
+}
+
+/// Message to request playing many sound effects at once.
+///
+/// Useful when one event triggers dozens of sounds, such as debris from an
+/// explosion. The whole batch is spawned in a single pass, and concurrency
+/// limits are then evaluated once for all of it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{PlaySfx, PlaySfxBatch};
+///
+/// messages.write(
+///     debris
+///         .iter()
+///         .map(|handle| PlaySfx::new(handle.clone(), GameSfx::Gameplay).randomized())
+///         .collect::<PlaySfxBatch<_>>(),
+/// );
+/// ```
+#[derive(Message, Clone)]
+pub struct PlaySfxBatch<S: SfxCategory> {
+    /// The sound effects to play.
+    pub requests: Vec<PlaySfx<S>>,
+}
+
+impl<S: SfxCategory> Default for PlaySfxBatch<S> {
+    fn default() -> Self {
+        Self {
+            requests: Vec::new(),
+        }
+    }
+}
+
+impl<S: SfxCategory> PlaySfxBatch<S> {
+    /// Creates an empty batch.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a sound effect to the batch.
+    #[must_use]
+    pub fn with(mut self, request: PlaySfx<S>) -> Self {
+        self.requests.push(request);
+        self
+    }
+
+    /// Returns the number of sound effects in the batch.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Returns `true` if the batch holds no sound effects.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+}
+
+impl<S: SfxCategory> FromIterator<PlaySfx<S>> for PlaySfxBatch<S> {
+    fn from_iter<I: IntoIterator<Item = PlaySfx<S>>>(iter: I) -> Self {
+        Self {
+            requests: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Message to fade out sound effects of a specific category.
+///
+/// Gradually reduces the volume of matching sound effect entities over the
+/// specified duration, then despawns them. Mostly useful for long-running
+/// looping sounds such as ambience or engine loops.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::FadeOutSfx;
+/// use std::time::Duration;
+///
+/// fn leave_forest(mut messages: MessageWriter<FadeOutSfx<MySfxCategory>>) {
+///     messages.write(FadeOutSfx::new(MySfxCategory::Ambience, Duration::from_secs(2)));
+/// }
+/// ```
+#[derive(Message, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FadeOutSfx<S: SfxCategory> {
+    /// The sound effect category to fade out.
+    pub category: S,
+    /// Duration of the fade-out effect.
+    pub duration: Duration,
+    /// Curve applied to the fade progress (defaults to linear).
+    pub easing: Easing,
+}
+
+impl<S: SfxCategory> FadeOutSfx<S> {
+    /// Creates a new fade-out sound effect event.
+    #[must_use]
+    pub fn new(category: S, duration: Duration) -> Self {
+        Self {
+            category,
+            duration,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Creates a fade-out sound effect event with a duration in seconds.
+    #[must_use]
+    pub fn from_secs(category: S, seconds: f32) -> Self {
+        Self::new(category, Duration::from_secs_f32(seconds))
+    }
+
+    /// Sets the easing curve for the fade.
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// Message to start a persistent looping sound effect.
+///
+/// The loop keeps playing until it is stopped with [`StopLoopingSfx`].
+/// Starting a loop whose category and label are already playing does
+/// nothing, so the request can be sent every time, e.g. on entering a rainy
+/// area.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{AudioRequest, PlayLoopingSfx};
+///
+/// messages.write(
+///     PlayLoopingSfx::new(rain_handle, GameSfx::Ambience, "rain")
+///         .with_volume(0.6, 0.8)
+///         .with_fade_in(FadeIn::from_secs(2.0)),
+/// );
+/// ```
+#[derive(Message, Clone)]
+pub struct PlayLoopingSfx<S: SfxCategory> {
+    /// Handle to the audio source.
+    pub handle: Handle<AudioSource>,
+    /// The sound effect category for volume control.
+    pub category: S,
+    /// Label identifying the loop.
+    pub label: PlaybackLabel,
+    /// Custom playback settings (defaults to LOOP).
+    pub playback: PlaybackSettings,
+    /// Randomization rolled from the [`AudioRng`] when the loop is spawned.
+    pub randomizer: Option<PlaybackRandomizer>,
+    /// Optional fade-in applied when the loop starts.
+    pub fade_in: Option<FadeIn>,
+    /// Time to wait before the loop starts.
+    pub delay: Option<Duration>,
+    /// Defer playback until the audio source has finished loading.
+    pub wait_for_load: bool,
+    /// User components inserted into the spawned entity.
+    pub components: Vec<ExtraComponents>,
+}
+
+impl<S: SfxCategory> PlayLoopingSfx<S> {
+    /// Creates a new play looping sound effect event.
+    #[must_use]
+    pub fn new(handle: Handle<AudioSource>, category: S, label: impl Into<PlaybackLabel>) -> Self {
+        Self {
+            handle,
+            category,
+            label: label.into(),
+            playback: PlaybackSettings::LOOP,
+            randomizer: None,
+            fade_in: None,
+            delay: None,
+            wait_for_load: false,
+            components: Vec::new(),
+        }
+    }
+
+    /// Inserts a clone of `bundle` into the spawned entity, e.g. a marker
+    /// component.
+    ///
+    /// Can be called repeatedly to add several bundles.
+    #[must_use]
+    pub fn with_components(mut self, bundle: impl Bundle + Clone) -> Self {
+        self.components.push(ExtraComponents::new(bundle));
+        self
+    }
+
+    /// Defers playback until the audio source has finished loading.
+    ///
+    /// See [`WaitForLoad`] for details.
+    #[must_use]
+    pub fn wait_for_load(mut self) -> Self {
+        self.wait_for_load = true;
+        self
+    }
+}
+
+impl<S: SfxCategory> AudioRequest for PlayLoopingSfx<S> {
+    fn playback_mut(&mut self) -> &mut PlaybackSettings {
+        &mut self.playback
+    }
+
+    fn randomizer_mut(&mut self) -> Option<&mut PlaybackRandomizer> {
+        self.randomizer.as_mut()
+    }
+
+    fn fade_in_mut(&mut self) -> &mut Option<FadeIn> {
+        &mut self.fade_in
+    }
+
+    fn delay_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.delay
+    }
+
+    fn with_randomizer(mut self, randomizer: PlaybackRandomizer) -> Self {
+        self.randomizer = Some(randomizer);
+        self
+    }
+}
+
+/// Message to stop looping sound effects of a category.
+///
+/// Stops every loop of the category, or only the one with the given label.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::StopLoopingSfx;
+///
+/// messages.write(
+///     StopLoopingSfx::new(GameSfx::Ambience)
+///         .with_label("rain")
+///         .with_fade(Duration::from_secs(2)),
+/// );
+/// ```
+#[derive(Message, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StopLoopingSfx<S: SfxCategory> {
+    /// The sound effect category to stop.
+    pub category: S,
+    /// Only stop the loop with this label.
+    pub label: Option<PlaybackLabel>,
+    /// Fade-out duration, or `None` to stop immediately.
+    pub fade: Option<Duration>,
+    /// Curve applied to the fade progress (defaults to linear).
+    pub easing: Easing,
+}
+
+impl<S: SfxCategory> StopLoopingSfx<S> {
+    /// Creates a message stopping every loop of the category.
+    #[must_use]
+    pub fn new(category: S) -> Self {
+        Self {
+            category,
+            label: None,
+            fade: None,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Only stops the loop with this label.
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<PlaybackLabel>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Fades the loops out over `duration` instead of stopping them at once.
+    #[must_use]
+    pub fn with_fade(mut self, duration: Duration) -> Self {
+        self.fade = Some(duration);
+        self
+    }
+
+    /// Sets the easing curve for the fade.
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// Message to play a voice line through the [`DialogueQueue`].
+///
+/// Only one line of a voice category type plays at a time. A line with a
+/// higher priority than the current one interrupts it; otherwise the line is
+/// queued behind pending lines of equal or higher priority.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::PlayVoiceLine;
+///
+/// fn greet(mut messages: MessageWriter<PlayVoiceLine<GameVoice>>) {
+///     messages.write(PlayVoiceLine::new(greeting_handle, GameVoice::Dialogue).with_label("greeting"));
+/// }
+/// ```
+#[derive(Message, Clone, Debug)]
+pub struct PlayVoiceLine<V: VoiceCategory> {
+    /// Handle to the audio source.
+    pub handle: Handle<AudioSource>,
+    /// The voice category for volume control.
+    pub category: V,
+    /// Lines with a higher priority interrupt the current line.
+    pub priority: i32,
+    /// Optional label identifying the line, e.g. for subtitles.
+    pub label: Option<PlaybackLabel>,
+    /// Per-language variants that replace `handle` when set.
+    pub localized: Option<LocalizedAudio>,
+}
+
+impl<V: VoiceCategory> PlayVoiceLine<V> {
+    /// Creates a new voice line with priority `0`.
+    #[must_use]
+    pub fn new(handle: Handle<AudioSource>, category: V) -> Self {
+        Self {
+            handle,
+            category,
+            priority: 0,
+            label: None,
+            localized: None,
+        }
+    }
+
+    /// Creates a voice line that plays the variant for the [`CurrentAudioLanguage`].
+    ///
+    /// The language is resolved when the line starts, not when it is queued.
+    #[must_use]
+    pub fn localized(audio: LocalizedAudio, category: V) -> Self {
+        let mut event = Self::new(audio.default.clone(), category);
+        event.localized = Some(audio);
+        event
+    }
+
+    /// Returns the handle to play for a language.
+    #[must_use]
+    pub fn resolve_handle(&self, language: &CurrentAudioLanguage) -> &Handle<AudioSource> {
+        self.localized
+            .as_ref()
+            .map_or(&self.handle, |audio| audio.resolve(language))
+    }
+
+    /// Sets the priority of the line.
+    #[must_use]
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Labels the line so it can be identified when it starts and finishes.
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<PlaybackLabel>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+/// Message emitted when a voice line stops playing.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::VoiceLineFinished;
+///
+/// fn hide_subtitles(mut finished: MessageReader<VoiceLineFinished<GameVoice>>, mut subtitles: ResMut<Subtitles>) {
+///     for line in finished.read() {
+///         subtitles.hide(line.label.as_ref());
+///     }
+/// }
+/// ```
+#[derive(Message, Clone, Debug)]
+pub struct VoiceLineFinished<V: VoiceCategory> {
+    /// The voice line entity.
+    pub entity: Entity,
+    /// The voice category of the line.
+    pub category: V,
+    /// Label the line was requested with.
+    pub label: Option<PlaybackLabel>,
+    /// `true` if a higher-priority line cut this one off.
+    pub interrupted: bool,
+}
+
+/// Message emitted when a track with a [`MusicTempo`] reaches a new beat.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::Beat;
+///
+/// fn pulse_lights(mut beats: MessageReader<Beat>, mut lights: Query<&mut PointLight>) {
+///     for beat in beats.read() {
+///         let intensity = if beat.beat_in_bar == 0 { 2000.0 } else { 1000.0 };
+///         for mut light in &mut lights {
+///             light.intensity = intensity;
+///         }
+///     }
+/// }
+/// ```
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Beat {
+    /// The music entity.
+    pub entity: Entity,
+    /// Index of the beat since the first downbeat, counting again from the
+    /// start each time the track loops.
+    pub beat: u64,
+    /// Index of the bar containing the beat.
+    pub bar: u64,
+    /// Position of the beat within its bar, starting at `0`.
+    pub beat_in_bar: u32,
+}
+
+/// Message emitted when a track with a [`MusicTempo`] reaches a new bar.
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bar {
+    /// The music entity.
+    pub entity: Entity,
+    /// Index of the bar since the first downbeat.
+    pub bar: u64,
+}
+
+/// Message emitted when a sound starts playing, once its sink exists.
+///
+/// Sounds of other source types, like [`Tone`](crate::Tone)s, are reported
+/// as `AudioStarted<Tone>`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::AudioStarted;
+///
+/// fn log_playback(mut started: MessageReader<AudioStarted>, assets: Res<AssetServer>) {
+///     for event in started.read() {
+///         info!("Playing {:?}", assets.get_path(&event.handle));
+///     }
+/// }
+/// ```
+#[derive(Message, Debug)]
+pub struct AudioStarted<P: Asset = AudioSource> {
+    /// The audio entity.
+    pub entity: Entity,
+    /// The [`PoolGeneration`] of the entity when the sound started.
+    pub generation: PoolGeneration,
+    /// The audio source being played.
+    pub handle: Handle<P>,
+}
+
+impl<P: Asset> AudioStarted<P> {
+    /// Returns a target for this sound only, which stops matching once a
+    /// pooled entity is reused for another sound.
+    #[must_use]
+    pub fn target(&self) -> AudioTarget {
+        AudioTarget::Instance(self.entity, self.generation)
+    }
+}
+
+impl<P: Asset> Clone for AudioStarted<P> {
+    fn clone(&self) -> Self {
+        Self {
+            entity: self.entity,
+            generation: self.generation,
+            handle: self.handle.clone(),
+        }
+    }
+}
+
+impl<P: Asset> PartialEq for AudioStarted<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entity == other.entity
+            && self.generation == other.generation
+            && self.handle == other.handle
+    }
+}
+
+impl<P: Asset> Eq for AudioStarted<P> {}
+
+/// Why a sound stopped playing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StopReason {
+    /// The sound reached the end of its source.
+    Finished,
+    /// The sound was stopped or despawned before reaching its end.
+    Stopped,
+}
+
+/// Message emitted when a playing sound loses its sink, because it finished
+/// or was stopped.
+#[derive(Message, Debug)]
+pub struct AudioStopped<P: Asset = AudioSource> {
+    /// The audio entity.
+    pub entity: Entity,
+    /// The [`PoolGeneration`] of the entity when the sound stopped.
+    pub generation: PoolGeneration,
+    /// The audio source that was playing.
+    pub handle: Handle<P>,
+    /// Why the sound stopped.
+    pub reason: StopReason,
+}
+
+impl<P: Asset> Clone for AudioStopped<P> {
+    fn clone(&self) -> Self {
+        Self {
+            entity: self.entity,
+            generation: self.generation,
+            handle: self.handle.clone(),
+            reason: self.reason,
+        }
+    }
+}
+
+impl<P: Asset> PartialEq for AudioStopped<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entity == other.entity
+            && self.generation == other.generation
+            && self.handle == other.handle
+            && self.reason == other.reason
+    }
+}
+
+impl<P: Asset> Eq for AudioStopped<P> {}
+
+/// Why a sound was dropped before it started playing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DropReason {
+    /// More instances of the sound were playing than its
+    /// [`MaxConcurrent`](crate::MaxConcurrent) limit allows.
+    Concurrency,
+    /// More sounds existed than the [`GlobalVoiceLimit`](crate::GlobalVoiceLimit)
+    /// allows.
+    VoiceLimit,
+    /// The audio source failed to load while playback waited for it.
+    LoadFailed,
+}
+
+/// Message emitted when a requested sound is despawned without ever playing.
+#[derive(Message, Debug)]
+pub struct AudioDropped<P: Asset = AudioSource> {
+    /// The audio entity.
+    pub entity: Entity,
+    /// The audio source that was requested.
+    pub handle: Handle<P>,
+    /// Why the sound was dropped.
+    pub reason: DropReason,
+}
+
+impl<P: Asset> Clone for AudioDropped<P> {
+    fn clone(&self) -> Self {
+        Self {
+            entity: self.entity,
+            handle: self.handle.clone(),
+            reason: self.reason,
+        }
+    }
+}
+
+impl<P: Asset> PartialEq for AudioDropped<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entity == other.entity && self.handle == other.handle && self.reason == other.reason
+    }
+}
+
+impl<P: Asset> Eq for AudioDropped<P> {}
+
+/// Message to stop every audio entity carrying a [`PlaybackLabel`].
+///
+/// Stops immediately by default, or fades out with [`with_fade`](Self::with_fade).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::StopLabeled;
+///
+/// fn stop_engine(mut messages: MessageWriter<StopLabeled>) {
+///     messages.write(StopLabeled::new("player_engine").with_fade(Duration::from_millis(300)));
+/// }
+/// ```
+#[derive(Message, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StopLabeled {
+    /// The label to stop.
+    pub label: PlaybackLabel,
+    /// Optional fade-out duration before despawning.
+    pub fade: Option<Duration>,
+}
+
+impl StopLabeled {
+    /// Creates a message that stops the labeled instances immediately.
+    #[must_use]
+    pub fn new(label: impl Into<PlaybackLabel>) -> Self {
+        Self {
+            label: label.into(),
+            fade: None,
+        }
+    }
+
+    /// Fades the labeled instances out before despawning them.
+    #[must_use]
+    pub fn with_fade(mut self, duration: Duration) -> Self {
+        self.fade = Some(duration);
+        self
+    }
+}
+
+/// Message to pause every audio entity carrying a [`PlaybackLabel`].
+#[derive(Message, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PauseLabeled {
+    /// The label to pause.
+    pub label: PlaybackLabel,
+}
+
+impl PauseLabeled {
+    /// Creates a new pause message.
+    #[must_use]
+    pub fn new(label: impl Into<PlaybackLabel>) -> Self {
+        Self {
+            label: label.into(),
+        }
+    }
+}
+
+/// Message to resume every paused audio entity carrying a [`PlaybackLabel`].
+#[derive(Message, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResumeLabeled {
+    /// The label to resume.
+    pub label: PlaybackLabel,
+}
+
+impl ResumeLabeled {
+    /// Creates a new resume message.
+    #[must_use]
+    pub fn new(label: impl Into<PlaybackLabel>) -> Self {
+        Self {
+            label: label.into(),
+        }
+    }
+}
+
+/// The playing sound(s) a [`SetAudioVolume`], [`PauseAudio`], or
+/// [`ResumeAudio`] message applies to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AudioTarget {
+    /// A single audio entity, whatever sound it plays.
+    Entity(Entity),
+    /// A single audio entity while it is on the given [`PoolGeneration`],
+    /// see [`AudioStarted::target`].
+    Instance(Entity, PoolGeneration),
+    /// Every audio entity carrying the [`PlaybackLabel`].
+    Label(PlaybackLabel),
+}
+
+impl AudioTarget {
+    /// Returns `true` if the target covers the entity with the given label
+    /// and pool generation.
+    #[must_use]
+    pub fn matches(
+        &self,
+        entity: Entity,
+        label: Option<&PlaybackLabel>,
+        generation: Option<&PoolGeneration>,
+    ) -> bool {
+        match self {
+            Self::Entity(target) => *target == entity,
+            Self::Instance(target, expected) => {
+                *target == entity && PoolGeneration::of(generation) == *expected
+            }
+            Self::Label(target) => label == Some(target),
+        }
+    }
+}
+
+impl From<Entity> for AudioTarget {
+    fn from(entity: Entity) -> Self {
+        Self::Entity(entity)
+    }
+}
+
+impl From<PlaybackLabel> for AudioTarget {
+    fn from(label: PlaybackLabel) -> Self {
+        Self::Label(label)
+    }
+}
+
+impl From<&'static str> for AudioTarget {
+    fn from(label: &'static str) -> Self {
+        Self::Label(PlaybackLabel::new(label))
+    }
+}
+
+/// Message to pause a specific playing sound without despawning it.
+///
+/// The sound stays paused until a [`ResumeAudio`] message targets it; game
+/// pause, focus loss, and voice virtualization do not resume it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{PauseAudio, ResumeAudio};
+///
+/// fn toggle_radio(radio: Single<(Entity, &Radio)>, mut pause: MessageWriter<PauseAudio>, mut resume: MessageWriter<ResumeAudio>) {
+///     let (entity, radio) = *radio;
+///     if radio.on {
+///         resume.write(ResumeAudio::new(entity));
+///     } else {
+///         pause.write(PauseAudio::new(entity));
+///     }
+/// }
+/// ```
+#[derive(Message, Clone, Debug)]
+pub struct PauseAudio {
+    /// The sound(s) to pause.
+    pub target: AudioTarget,
+}
+
+impl PauseAudio {
+    /// Creates a new pause message.
+    #[must_use]
+    pub fn new(target: impl Into<AudioTarget>) -> Self {
+        Self {
+            target: target.into(),
+        }
+    }
+}
+
+/// Message to resume a specific paused sound.
+#[derive(Message, Clone, Debug)]
+pub struct ResumeAudio {
+    /// The sound(s) to resume.
+    pub target: AudioTarget,
+}
+
+impl ResumeAudio {
+    /// Creates a new resume message.
+    #[must_use]
+    pub fn new(target: impl Into<AudioTarget>) -> Self {
+        Self {
+            target: target.into(),
+        }
+    }
+}
+
+/// Message to change the volume of a specific playing sound at runtime.
+///
+/// The volume is kept as the sound's [`TrackGain`], so the master and
+/// category volumes and the mixer still apply on top of it and later config
+/// changes keep it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::SetAudioVolume;
+///
+/// fn engine_throttle(throttle: Res<Throttle>, mut messages: MessageWriter<SetAudioVolume>) {
+///     messages.write(SetAudioVolume::new("player_engine", 0.3 + throttle.0 * 0.7));
+/// }
+/// ```
+#[derive(Message, Clone, Debug)]
+pub struct SetAudioVolume {
+    /// The sound(s) to change.
+    pub target: AudioTarget,
+    /// New linear volume, before the master and category volumes.
+    ///
+    /// Negative volumes are treated as silence, and messages with a
+    /// non-finite volume are ignored.
+    pub volume: f32,
+    /// Duration of the volume change (instant by default).
+    pub duration: Duration,
+    /// Curve applied to the change.
+    pub easing: Easing,
+}
+
+impl SetAudioVolume {
+    /// Creates a message that changes the volume instantly.
+    #[must_use]
+    pub fn new(target: impl Into<AudioTarget>, volume: f32) -> Self {
+        Self {
+            target: target.into(),
+            volume,
+            duration: Duration::ZERO,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Fades to the new volume over the given duration.
+    #[must_use]
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Sets the easing curve for the fade.
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// Message to mute or unmute a category at runtime.
+///
+/// Works for both music and sound effect category types and is applied on
+/// top of the audio config via [`CategoryMixerState`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::MuteCategory;
+///
+/// fn toggle_ambience(mut messages: MessageWriter<MuteCategory<MySfxCategory>>) {
+///     messages.write(MuteCategory::mute(MySfxCategory::Ambience));
+/// }
+/// ```
+#[derive(Message, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MuteCategory<A: AudioCategory> {
+    /// The category to change.
+    pub category: A,
+    /// Whether the category should be muted.
+    pub muted: bool,
+}
+
+impl<A: AudioCategory> MuteCategory<A> {
+    /// Creates a message that mutes the category.
+    #[must_use]
+    pub fn mute(category: A) -> Self {
+        Self {
+            category,
+            muted: true,
+        }
+    }
+
+    /// Creates a message that unmutes the category.
+    #[must_use]
+    pub fn unmute(category: A) -> Self {
+        Self {
+            category,
+            muted: false,
+        }
+    }
+}
+
+/// Message to solo or unsolo a category at runtime.
+///
+/// While any category of a type is soloed, the other categories of that
+/// type are silenced. See [`CategoryMixerState`] for details.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::SoloCategory;
+///
+/// fn debug_dialogue(mut messages: MessageWriter<SoloCategory<MySfxCategory>>) {
+///     messages.write(SoloCategory::solo(MySfxCategory::Dialogue));
+/// }
+/// ```
+#[derive(Message, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoloCategory<A: AudioCategory> {
+    /// The category to change.
+    pub category: A,
+    /// Whether the category should be soloed.
+    pub soloed: bool,
+}
+
+impl<A: AudioCategory> SoloCategory<A> {
+    /// Creates a message that solos the category.
+    #[must_use]
+    pub fn solo(category: A) -> Self {
+        Self {
+            category,
+            soloed: true,
+        }
+    }
+
+    /// Creates a message that removes the category from the solo set.
+    #[must_use]
+    pub fn unsolo(category: A) -> Self {
+        Self {
+            category,
+            soloed: false,
+        }
+    }
+}
+
+/// Message to push a [`MixerSnapshot`] onto the mixer of category type `A`.
+///
+/// The snapshot blends in over the transition and stays applied on top of
+/// the audio config until it is popped.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{MixerSnapshot, PushMixerSnapshot};
+///
+/// fn open_pause_menu(mut messages: MessageWriter<PushMixerSnapshot<MySfxCategory>>) {
+///     let snapshot = MixerSnapshot::new("PauseMenu")
+///         .with_default_gain(0.3)
+///         .with_gain(MySfxCategory::UI, 1.0);
+///     messages.write(PushMixerSnapshot::new(snapshot, Duration::from_millis(300)));
+/// }
+/// ```
+#[derive(Message, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PushMixerSnapshot<A: AudioCategory> {
+    /// The snapshot to push.
+    pub snapshot: MixerSnapshot<A>,
+    /// Duration of the blend-in.
+    pub transition: Duration,
+}
+
+impl<A: AudioCategory> PushMixerSnapshot<A> {
+    /// Creates a new push snapshot message.
+    #[must_use]
+    pub fn new(snapshot: MixerSnapshot<A>, transition: Duration) -> Self {
+        Self {
+            snapshot,
+            transition,
+        }
+    }
+}
+
+/// Message to pop a [`MixerSnapshot`] from the mixer of category type `A`.
+///
+/// Without a name the most recently pushed snapshot is popped.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::PopMixerSnapshot;
+///
+/// fn close_pause_menu(mut messages: MessageWriter<PopMixerSnapshot<MySfxCategory>>) {
+///     messages.write(PopMixerSnapshot::named("PauseMenu", Duration::from_millis(300)));
+/// }
+/// ```
+#[derive(Message, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PopMixerSnapshot<A: AudioCategory> {
+    /// Name of the snapshot to pop, or `None` for the most recent one.
+    pub name: Option<Cow<'static, str>>,
+    /// Duration of the blend-out.
+    pub transition: Duration,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _phantom: std::marker::PhantomData<A>,
+}
+
+impl<A: AudioCategory> PopMixerSnapshot<A> {
+    /// Creates a message that pops the most recently pushed snapshot.
+    #[must_use]
+    pub fn new(transition: Duration) -> Self {
+        Self {
+            name: None,
+            transition,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Creates a message that pops the snapshot with the given name.
+    #[must_use]
+    pub fn named(name: impl Into<Cow<'static, str>>, transition: Duration) -> Self {
+        Self {
+            name: Some(name.into()),
+            transition,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
 
 /// System that handles `PlayMusic` messages by spawning music entities.
-pub fn handle_play_music_events<M: MusicCategory>(
+///
+/// Music started in the same frame as a [`StopMusic`] fades in over the
+/// [`MusicCrossfade`] duration unless it has its own fade-in.
+///
+/// Tracks already playing in the same category, or requested earlier in the
+/// same frame, are handled according to [`DuplicateMusic`].
+pub fn handle_play_music_events<M: MusicCategory, B: AudioBackend>(
     mut commands: Commands,
     mut messages: MessageReader<PlayMusic<M>>,
+    mut stops: MessageReader<StopMusic<M>>,
+    mut delayed: ResMut<DelayedMessages<PlayMusic<M>>>,
+    memory: Res<MusicMemory<M>>,
+    crossfade: Res<MusicCrossfade>,
+    duplicates: Res<DuplicateMusic>,
+    registry: Res<TrackRegistry>,
+    asset_server: Option<Res<AssetServer>>,
+    mut rng: ResMut<AudioRng>,
+    playing: Query<
+        (
+            Entity,
+            &M,
+            Option<&AudioPlayer>,
+            Option<&WaitForLoad>,
+            Has<B::Sink>,
+            Has<<B::Spatial as AudioBackend>::Sink>,
+        ),
+        (Without<FadeOut>, Without<Stinger>),
+    >,
+) {
+    let events: Vec<&PlayMusic<M>> = messages.read().collect();
+    let stops: Vec<&StopMusic<M>> = stops.read().collect();
+    let crossfading = crossfades(&crossfade, &stops, &events);
+    let mut started: Vec<(M, AssetId<AudioSource>)> = Vec::new();
+    for event in events {
+        if let Some(delay) = event.delay {
+            delayed.push(
+                delay,
+                PlayMusic {
+                    delay: None,
+                    ..event.clone()
+                },
+            );
+            continue;
+        }
+        let event = match &event.path {
+            Some(path) => match load_path(path, asset_server.as_deref()) {
+                Some(handle) => Cow::Owned(PlayMusic {
+                    handle,
+                    path: None,
+                    ..event.clone()
+                }),
+                None => continue,
+            },
+            None => Cow::Borrowed(event),
+        };
+        let policy = event.on_duplicate.unwrap_or(*duplicates);
+        let id = event.handle.id();
+        let mut crossfading = crossfading;
+        if policy != DuplicateMusic::Stack {
+            if started.contains(&(event.category, id)) {
+                continue;
+            }
+            let existing = playing.iter().filter(|(_, category, player, pending, ..)| {
+                **category == event.category
+                    && (player.is_some_and(|player| player.0.id() == id)
+                        || pending.is_some_and(|pending| pending.handle.id() == id))
+            });
+            let mut ignored = false;
+            for (entity, .., has_sink, has_spatial_sink) in existing {
+                match policy {
+                    DuplicateMusic::Ignore => {
+                        ignored = true;
+                        break;
+                    }
+                    DuplicateMusic::Crossfade
+                        if (has_sink || has_spatial_sink) && !crossfade.duration.is_zero() =>
+                    {
+                        start_fade_out(&mut commands, entity, crossfade.duration, &Easing::Linear);
+                        crossfading = true;
+                    }
+                    _ => commands.entity(entity).despawn(),
+                }
+            }
+            if ignored {
+                continue;
+            }
+            started.push((event.category, id));
+        }
+        let mut playback = event.playback;
+        if let Some(randomizer) = &event.randomizer {
+            randomizer.apply_with(&mut playback, &mut *rng);
+        }
+        if event.resume {
+            if let Some(position) = memory.position(event.category, &event.handle) {
+                playback.start_position = Some(position);
+            }
+        }
+        let start = StartPosition::take_from(&mut playback);
+        let mut entity = commands.spawn((playback, event.category));
+        if let Some(start) = start {
+            entity.insert(start);
+        }
+        if event.max_concurrent != u32::MAX {
+            entity.insert(
+                crate::components::MaxConcurrent::new(&event.handle, event.max_concurrent)
+                    .with_policy(event.concurrency_policy),
+            );
+        }
+        insert_player::<B>(&mut entity, &event.handle, playback, event.wait_for_load);
+        match &event.fade_in {
+            Some(fade_in) => {
+                entity.insert(fade_in.clone());
+            }
+            None if crossfading => {
+                entity.insert(FadeIn::new(crossfade.duration));
+            }
+            None => {}
+        }
+        if let Some(gain) = event.gain {
+            entity.insert(gain);
+        }
+        if let Some(label) = &event.label {
+            entity.insert(label.clone());
+        }
+        let info = registry.get(&event.handle);
+        if let Some(tempo) = event.tempo.or_else(|| info.and_then(|info| info.tempo)) {
+            entity.insert(tempo);
+        }
+        for components in &event.components {
+            components.insert_into(&mut entity);
+        }
+    }
+}
+
+/// System that handles `PlayStinger` messages by spawning stinger entities.
+pub fn handle_play_stinger_events<M: MusicCategory, B: AudioBackend>(
+    mut commands: Commands,
+    mut messages: MessageReader<PlayStinger<M>>,
+) {
+    for event in messages.read() {
+        let mut entity = commands.spawn((event.category, event.stinger.clone()));
+        B::play(&mut entity, event.handle.clone(), PlaybackSettings::DESPAWN);
+    }
+}
+
+/// System that handles `PlaySfx` messages by spawning sound effect entities.
+///
+/// One-shots reuse idle entities from the [`SfxPool`] when it is enabled.
+pub fn handle_play_sfx_events<S: SfxCategory, B: AudioBackend>(
+    mut commands: Commands,
+    mut messages: MessageReader<PlaySfx<S>>,
+    mut delayed: ResMut<DelayedMessages<PlaySfx<S>>>,
+    language: Res<CurrentAudioLanguage>,
+    library: Res<SoundLibrary<S>>,
+    asset_server: Option<Res<AssetServer>>,
+    mut pool: Option<ResMut<SfxPool>>,
+    mut rng: ResMut<AudioRng>,
+    entities: &Entities,
+) {
+    for event in messages.read() {
+        if let Some(delay) = event.delay {
+            delayed.push(
+                delay,
+                PlaySfx {
+                    delay: None,
+                    ..event.clone()
+                },
+            );
+            continue;
+        }
+        spawn_sfx::<S, B>(
+            &mut commands,
+            event,
+            &language,
+            &library,
+            asset_server.as_deref(),
+            pool.as_deref_mut(),
+            entities,
+            &mut rng,
+        );
+    }
+}
+
+/// System that handles `PlaySfxBatch` messages by spawning every request of
+/// a batch in one pass.
+pub fn handle_play_sfx_batch_events<S: SfxCategory, B: AudioBackend>(
+    mut commands: Commands,
+    mut messages: MessageReader<PlaySfxBatch<S>>,
+    mut delayed: ResMut<DelayedMessages<PlaySfx<S>>>,
+    language: Res<CurrentAudioLanguage>,
+    library: Res<SoundLibrary<S>>,
+    asset_server: Option<Res<AssetServer>>,
+    mut pool: Option<ResMut<SfxPool>>,
+    mut rng: ResMut<AudioRng>,
+    entities: &Entities,
+) {
+    for batch in messages.read() {
+        for event in &batch.requests {
+            if let Some(delay) = event.delay {
+                delayed.push(
+                    delay,
+                    PlaySfx {
+                        delay: None,
+                        ..event.clone()
+                    },
+                );
+                continue;
+            }
+            spawn_sfx::<S, B>(
+                &mut commands,
+                event,
+                &language,
+                &library,
+                asset_server.as_deref(),
+                pool.as_deref_mut(),
+                entities,
+                &mut rng,
+            );
+        }
+    }
+}
+
+/// Spawns the entity of a sound effect request.
+fn spawn_sfx<S: SfxCategory, B: AudioBackend>(
+    commands: &mut Commands,
+    event: &PlaySfx<S>,
+    language: &CurrentAudioLanguage,
+    library: &SoundLibrary<S>,
+    asset_server: Option<&AssetServer>,
+    pool: Option<&mut SfxPool>,
+    entities: &Entities,
+    rng: &mut AudioRng,
+) {
+    use crate::components::MaxConcurrent;
+
+    let Some(event) = library.resolve(event) else {
+        warn!(
+            "Sound {:?} is not in the sound library",
+            event.sound.as_ref().map(SoundId::as_str)
+        );
+        return;
+    };
+    if !PlayChance(event.chance).roll(rng) {
+        return;
+    }
+    let loaded;
+    let handle = match &event.path {
+        Some(path) => {
+            let Some(handle) = load_path(path, asset_server) else {
+                return;
+            };
+            loaded = handle;
+            &loaded
+        }
+        None => event.resolve_handle(language),
+    };
+    let mut playback = event.playback;
+    if let Some(randomizer) = &event.randomizer {
+        randomizer.apply_with(&mut playback, rng);
+    }
+    if event.non_spatial {
+        playback.spatial = false;
+    }
+    let start = StartPosition::take_from(&mut playback);
+    let pool =
+        pool.filter(|pool| pool.capacity > 0 && matches!(playback.mode, PlaybackMode::Despawn));
+    if pool.is_some() {
+        playback.mode = PlaybackMode::Remove;
+    }
+    let bundle = (
+        playback,
+        event.category,
+        MaxConcurrent::new(handle, event.max_concurrent).with_policy(event.concurrency_policy),
+    );
+    let mut entity = match pool {
+        Some(pool) => {
+            let mut entity = match pool.take(entities) {
+                Some(idle) => commands.entity(idle),
+                None => commands.spawn_empty(),
+            };
+            entity.insert((bundle, Pooled));
+            entity
+        }
+        None => commands.spawn(bundle),
+    };
+    insert_player::<B>(&mut entity, handle, playback, event.wait_for_load);
+    if let Some(start) = start {
+        entity.insert(start);
+    }
+    if let Some(label) = &event.label {
+        entity.insert(label.clone());
+    }
+    if let Some(target) = event.attached_to {
+        entity.insert(AttachedAudio(target));
+    }
+    if event.priority != 0 {
+        entity.insert(AudioPriority(event.priority));
+    }
+    if event.non_spatial {
+        entity.insert(NonSpatial);
+    } else if let Some(pan) = event
+        .pan
+        .and_then(|(min, max)| StereoPanRange::new(min, max).roll(&mut *rng))
+    {
+        entity.insert(StereoPan(pan));
+    }
+    if let Some(duration) = event.max_duration {
+        entity.insert(MaxDuration::new(duration));
+    }
+    if let Some(fade_in) = &event.fade_in {
+        entity.insert(fade_in.clone());
+    }
+    if event.echo_tap {
+        entity.insert(EchoTap);
+    }
+    for components in &event.components {
+        components.insert_into(&mut entity);
+    }
+}
+
+/// Loads the source of a request made from an asset path.
+///
+/// Returns `None` with a warning when there is no [`AssetServer`].
+fn load_path(
+    path: &AssetPath<'static>,
+    asset_server: Option<&AssetServer>,
+) -> Option<Handle<AudioSource>> {
+    let Some(asset_server) = asset_server else {
+        warn!("Cannot load {path} without an AssetServer");
+        return None;
+    };
+    Some(asset_server.load(path.clone()))
+}
+
+/// Starts playing `handle` through `B`, or inserts a [`WaitForLoad`] marker
+/// when playback is deferred.
+fn insert_player<B: AudioBackend>(
+    entity: &mut EntityCommands,
+    handle: &Handle<AudioSource>,
+    playback: PlaybackSettings,
+    wait_for_load: bool,
+) {
+    if wait_for_load {
+        entity.insert(WaitForLoad::new(handle.clone()));
+    } else {
+        B::play(entity, handle.clone(), playback);
+    }
+}
+
+/// Returns `true` if the music stopped and started in one frame crossfades.
+///
+/// Both the play and the stop handler decide with this, so the old track
+/// only fades out when the new one fades in. Each handler collects the other's
+/// messages in full first; a reader left half-read would see the rest again
+/// next frame and decide differently from its counterpart.
+fn crossfades<M: MusicCategory>(
+    crossfade: &MusicCrossfade,
+    stops: &[&StopMusic<M>],
+    plays: &[&PlayMusic<M>],
+) -> bool {
+    !crossfade.duration.is_zero()
+        && stops
+            .iter()
+            .any(|stop| stop.quantize == Quantize::Immediate)
+        && plays.iter().any(|play| play.delay.is_none())
+}
+
+/// System that handles `StopMusic` messages by despawning or fading out
+/// matching music entities.
+///
+/// Music stopped without a fade in the same frame as a [`PlayMusic`] fades
+/// out over the [`MusicCrossfade`] duration instead.
+pub fn handle_stop_music_events<M: MusicCategory, B: AudioBackend>(
+    mut commands: Commands,
+    mut messages: MessageReader<StopMusic<M>>,
+    mut plays: MessageReader<PlayMusic<M>>,
+    crossfade: Res<MusicCrossfade>,
+    query: Query<(Entity, &M, Option<&MusicTempo>, Option<AnySink<B>>)>,
+) {
+    let events: Vec<&StopMusic<M>> = messages.read().collect();
+    let plays: Vec<&PlayMusic<M>> = plays.read().collect();
+    let crossfading = crossfades(&crossfade, &events, &plays);
+    for event in events {
+        for (entity, category, tempo, sink) in &query {
+            if *category != event.category {
+                continue;
+            }
+            let position = sink.map(|sink| either_sink::<B>(sink).position());
+            let quantized = quantized_stop(event.quantize, tempo, position);
+            match (quantized, event.fade) {
+                (Some(stop), Some(fade)) => {
+                    commands
+                        .entity(entity)
+                        .insert(stop.with_fade(fade, event.easing.clone()));
+                }
+                (Some(stop), None) => {
+                    commands.entity(entity).insert(stop);
+                }
+                (None, Some(fade)) if sink.is_some() => {
+                    start_fade_out(&mut commands, entity, fade, &event.easing);
+                }
+                (None, None) if crossfading && sink.is_some() => {
+                    start_fade_out(&mut commands, entity, crossfade.duration, &Easing::Linear);
+                }
+                (None, _) => commands.entity(entity).despawn(),
+            }
+        }
+    }
+}
+
+/// System that handles `PlayVoiceLine` messages by queueing them, interrupting
+/// the current line when the new one has a higher priority.
+pub fn handle_play_voice_line_events<V: VoiceCategory>(
+    mut commands: Commands,
+    mut messages: MessageReader<PlayVoiceLine<V>>,
+    mut queue: ResMut<DialogueQueue<V>>,
+    mut finished: MessageWriter<VoiceLineFinished<V>>,
+) {
+    for event in messages.read() {
+        let interrupts = queue
+            .current()
+            .is_some_and(|current| event.priority > current.priority);
+        if interrupts {
+            if let Some(current) = queue.take_current() {
+                commands.entity(current.entity).try_despawn();
+                finished.write(VoiceLineFinished {
+                    entity: current.entity,
+                    category: current.category,
+                    label: current.label,
+                    interrupted: true,
+                });
+            }
+        }
+        queue.enqueue(event.clone());
+    }
+}
+
+/// System that handles `PlayLayeredMusic` messages by spawning the stems.
+pub fn handle_play_layered_music_events<M: MusicCategory>(
+    mut commands: Commands,
+    mut messages: MessageReader<PlayLayeredMusic<M>>,
+) {
+    for event in messages.read() {
+        event.track.spawn(&mut commands);
+    }
+}
+
+/// System that handles `SetLayerVolume` messages by updating the layer gain
+/// and tweening the stem sinks to their new volume.
+pub fn handle_set_layer_volume_events<M, B>(
+    mut commands: Commands,
+    mut messages: MessageReader<SetLayerVolume<M>>,
+    config: Res<M::Config>,
+    mixer: Res<CategoryMixerState<M>>,
+    mut query: Query<(
+        Entity,
+        &M,
+        &MusicLayer,
+        &PlaybackSettings,
+        &mut TrackGain,
+        Option<&AudioVolumeState>,
+        Option<&mut B::Sink>,
+    )>,
+) where
+    M: MusicCategory,
+    B: AudioBackend,
+    M::Config: AudioConfigTrait,
+{
+    for event in messages.read() {
+        for (entity, category, layer, playback, mut gain, state, sink) in &mut query {
+            if *category != event.category || *layer != event.layer {
+                continue;
+            }
+            *gain = TrackGain::new(event.volume);
+
+            let Some(mut sink) = sink else {
+                continue;
+            };
+            let target = match state {
+                Some(state) => state.volume(Some(&gain)),
+                None => target_volume(&*config, &mixer, category, playback, Some(&gain)),
+            };
+            if event.duration.is_zero() {
+                B::set_volume(&mut sink, target);
+            } else {
+                let current = B::volume(&sink);
+                commands.entity(entity).insert(
+                    VolumeTween::new(current, target, event.duration)
+                        .with_easing(event.easing.clone()),
+                );
+            }
+        }
+    }
+}
+
+/// System that handles `SeekMusic` messages by seeking matching sinks.
+pub fn handle_seek_music_events<M: MusicCategory, B: AudioBackend>(
+    mut messages: MessageReader<SeekMusic<M>>,
+    mut query: Query<(&M, &mut B::Sink)>,
+) {
+    for event in messages.read() {
+        for (category, mut sink) in &mut query {
+            if *category == event.category {
+                B::seek(&mut sink, event.position);
+            }
+        }
+    }
+}
+
+/// System that handles `StopAllMusic` messages by despawning or fading out
+/// all music entities.
+pub fn handle_stop_all_music_events<M: MusicCategory, B: AudioBackend>(
+    mut commands: Commands,
+    mut messages: MessageReader<StopAllMusic<M>>,
+    query: Query<
+        (
+            Entity,
+            Has<B::Sink>,
+            Has<<B::Spatial as AudioBackend>::Sink>,
+        ),
+        With<M>,
+    >,
+) {
+    for event in messages.read() {
+        for (entity, has_sink, has_spatial_sink) in &query {
+            match event.fade {
+                Some(fade) if has_sink || has_spatial_sink => {
+                    start_fade_out(&mut commands, entity, fade, &event.easing);
+                }
+                _ => commands.entity(entity).despawn(),
+            }
+        }
+    }
+}
+
+/// System that handles `FadeOutMusic` messages by adding fade-out components.
+pub fn handle_fade_out_music_events<M: MusicCategory, B: AudioBackend>(
+    mut commands: Commands,
+    mut messages: MessageReader<FadeOutMusic<M>>,
+    query: Query<(Entity, &M, AnySink<B>, Option<&MusicTempo>)>,
+) {
+    for event in messages.read() {
+        for (entity, category, sink, tempo) in &query {
+            if *category != event.category {
+                continue;
+            }
+            let position = either_sink::<B>(sink).position();
+            match quantized_stop(event.quantize, tempo, Some(position)) {
+                Some(stop) => {
+                    commands
+                        .entity(entity)
+                        .insert(stop.with_fade(event.duration, event.easing.clone()));
+                }
+                None => start_fade_out(&mut commands, entity, event.duration, &event.easing),
+            }
+        }
+    }
+}
+
+/// System that handles `CrossfadeMusic` messages by fading the category out
+/// and writing the new track, delayed to the same beat or bar boundary.
+pub fn handle_crossfade_music_events<M: MusicCategory, B: AudioBackend>(
+    mut messages: MessageReader<CrossfadeMusic<M>>,
+    mut fade_outs: MessageWriter<FadeOutMusic<M>>,
+    mut plays: MessageWriter<PlayMusic<M>>,
+    query: Query<(&M, AnySink<B>, Option<&MusicTempo>), (Without<FadeOut>, Without<Stinger>)>,
+) {
+    for event in messages.read() {
+        let category = event.play.category;
+        let wait = query
+            .iter()
+            .filter(|(playing, ..)| **playing == category)
+            .filter_map(|(_, sink, tempo)| {
+                let position = either_sink::<B>(sink).position();
+                quantized_stop(event.quantize, tempo, Some(position))
+            })
+            .map(|stop| stop.at - stop.from)
+            .max()
+            .unwrap_or_default();
+        fade_outs.write(
+            FadeOutMusic::new(category, event.duration)
+                .with_easing(event.easing.clone())
+                .quantized(event.quantize),
+        );
+        let mut play = event.play.clone();
+        if play.fade_in.is_none() {
+            play.fade_in = Some(FadeIn::new(event.duration).with_easing(event.easing.clone()));
+        }
+        if !wait.is_zero() {
+            play.delay = Some(play.delay.unwrap_or_default() + wait);
+        }
+        plays.write(play);
+    }
+}
+
+/// System that handles `FadeOutAllMusic` messages by adding fade-out components
+/// to every music entity.
+pub fn handle_fade_out_all_music_events<M: MusicCategory, B: AudioBackend>(
+    mut commands: Commands,
+    mut messages: MessageReader<FadeOutAllMusic<M>>,
+    query: Query<
+        Entity,
+        (
+            With<M>,
+            Or<(With<B::Sink>, With<<B::Spatial as AudioBackend>::Sink>)>,
+        ),
+    >,
 ) {
     for event in messages.read() {
-        commands.spawn((
-            AudioPlayer(event.handle.clone()),
-            event.playback,
-            event.category,
-        ));
+        for entity in &query {
+            start_fade_out(&mut commands, entity, event.duration, &event.easing);
+        }
     }
 }
 
-/// System that handles `PlaySfx` messages by spawning sound effect entities.
-pub fn handle_play_sfx_events<S: SfxCategory>(
+/// System that handles `FadeOutSfx` messages by adding fade-out components.
+pub fn handle_fade_out_sfx_events<S: SfxCategory, B: AudioBackend>(
     mut commands: Commands,
-    mut messages: MessageReader<PlaySfx<S>>,
+    mut messages: MessageReader<FadeOutSfx<S>>,
+    query: Query<(Entity, &S), Or<(With<B::Sink>, With<<B::Spatial as AudioBackend>::Sink>)>>,
 ) {
-    use crate::components::MaxConcurrent;
+    for event in messages.read() {
+        for (entity, category) in &query {
+            if *category == event.category {
+                start_fade_out(&mut commands, entity, event.duration, &event.easing);
+            }
+        }
+    }
+}
 
+/// System that handles `PlayLoopingSfx` messages by spawning loops that are
+/// not already playing.
+///
+/// Loops that are fading out do not count as playing, so a loop can be
+/// restarted while its previous instance fades away.
+pub fn handle_play_looping_sfx_events<S: SfxCategory, B: AudioBackend>(
+    mut commands: Commands,
+    mut messages: MessageReader<PlayLoopingSfx<S>>,
+    mut delayed: ResMut<DelayedMessages<PlayLoopingSfx<S>>>,
+    mut rng: ResMut<AudioRng>,
+    playing: Query<(&S, &PlaybackLabel), (With<LoopingSfx>, Without<FadeOut>)>,
+) {
+    let mut started: Vec<(S, &PlaybackLabel)> = Vec::new();
     for event in messages.read() {
-        commands.spawn((
-            AudioPlayer(event.handle.clone()),
-            event.playback,
-            event.category,
-            MaxConcurrent::new(event.handle.clone(), event.max_concurrent),
-        ));
+        if let Some(delay) = event.delay {
+            delayed.push(
+                delay,
+                PlayLoopingSfx {
+                    delay: None,
+                    ..event.clone()
+                },
+            );
+            continue;
+        }
+        let key = (event.category, &event.label);
+        if started.contains(&key)
+            || playing
+                .iter()
+                .any(|(category, label)| (*category, label) == key)
+        {
+            continue;
+        }
+        started.push(key);
+        let mut playback = event.playback;
+        if let Some(randomizer) = &event.randomizer {
+            randomizer.apply_with(&mut playback, &mut *rng);
+        }
+        let start = StartPosition::take_from(&mut playback);
+        let mut entity =
+            commands.spawn((playback, event.category, event.label.clone(), LoopingSfx));
+        insert_player::<B>(&mut entity, &event.handle, playback, event.wait_for_load);
+        if let Some(start) = start {
+            entity.insert(start);
+        }
+        if let Some(fade_in) = &event.fade_in {
+            entity.insert(fade_in.clone());
+        }
+        for components in &event.components {
+            components.insert_into(&mut entity);
+        }
     }
 }
 
-/// System that handles `StopMusic` messages by despawning matching music entities.
-pub fn handle_stop_music_events<M: MusicCategory>(
+/// System that handles `StopLoopingSfx` messages by despawning or fading out
+/// the matching loops.
+pub fn handle_stop_looping_sfx_events<S: SfxCategory, B: AudioBackend>(
     mut commands: Commands,
-    mut messages: MessageReader<StopMusic<M>>,
-    query: Query<(Entity, &M)>,
+    mut messages: MessageReader<StopLoopingSfx<S>>,
+    query: Query<
+        (
+            Entity,
+            &S,
+            &PlaybackLabel,
+            Has<B::Sink>,
+            Has<<B::Spatial as AudioBackend>::Sink>,
+        ),
+        With<LoopingSfx>,
+    >,
 ) {
     for event in messages.read() {
-        for (entity, category) in &query {
-            if *category == event.category {
-                commands.entity(entity).despawn();
+        for (entity, category, label, has_sink, has_spatial_sink) in &query {
+            if *category != event.category || event.label.as_ref().is_some_and(|l| l != label) {
+                continue;
+            }
+            match event.fade {
+                Some(duration) if has_sink || has_spatial_sink => {
+                    start_fade_out(&mut commands, entity, duration, &event.easing);
+                }
+                _ => commands.entity(entity).despawn(),
             }
         }
     }
 }
 
-/// System that handles `StopAllMusic` messages by despawning all music entities.
-pub fn handle_stop_all_music_events<M: MusicCategory>(
+/// System that handles `StopLabeled` messages by despawning or fading out
+/// the labeled entities.
+pub fn handle_stop_labeled_events<B: AudioBackend>(
     mut commands: Commands,
-    mut messages: MessageReader<StopAllMusic<M>>,
-    query: Query<Entity, With<M>>,
+    mut messages: MessageReader<StopLabeled>,
+    query: Query<(
+        Entity,
+        &PlaybackLabel,
+        Has<B::Sink>,
+        Has<<B::Spatial as AudioBackend>::Sink>,
+    )>,
 ) {
-    for _ in messages.read() {
-        for entity in &query {
-            commands.entity(entity).despawn();
+    for event in messages.read() {
+        for (entity, label, has_sink, has_spatial_sink) in &query {
+            if *label != event.label {
+                continue;
+            }
+            match event.fade {
+                Some(duration) if has_sink || has_spatial_sink => {
+                    start_fade_out(&mut commands, entity, duration, &Easing::Linear);
+                }
+                _ => commands.entity(entity).despawn(),
+            }
         }
     }
 }
 
-/// System that handles `FadeOutMusic` messages by adding fade-out components.
-pub fn handle_fade_out_music_events<M: MusicCategory>(
+/// System that handles `PauseLabeled` messages by pausing the labeled sinks.
+pub fn handle_pause_labeled_events<B: AudioBackend>(
     mut commands: Commands,
-    mut messages: MessageReader<FadeOutMusic<M>>,
-    query: Query<(Entity, &M, &AudioSink)>,
+    mut messages: MessageReader<PauseLabeled>,
+    mut query: Query<(Entity, &PlaybackLabel, &mut B::Sink)>,
 ) {
-    use crate::components::FadeOut;
-    use bevy::audio::Volume;
-
     for event in messages.read() {
-        for (entity, category, sink) in &query {
-            if *category == event.category {
-                // Get current volume to use as initial fade volume
-                let initial_volume = match sink.volume() {
-                    Volume::Linear(v) => v,
-                    Volume::Decibels(db) => 10_f32.powf(db / 20.0),
-                };
-                commands
-                    .entity(entity)
-                    .insert(FadeOut::new(event.duration).with_initial_volume(initial_volume));
+        for (entity, label, mut sink) in &mut query {
+            if *label == event.label {
+                pause_sink::<B>(&mut commands, entity, &mut sink);
             }
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// System that handles `ResumeLabeled` messages by resuming the labeled sinks.
+///
+/// Like [`handle_resume_audio_events`], sinks paused by another system are
+/// left alone.
+pub fn handle_resume_labeled_events<B: AudioBackend>(
+    mut messages: MessageReader<ResumeLabeled>,
+    mut query: Query<
+        (&PlaybackLabel, &mut B::Sink),
+        (
+            Without<PausedByGame>,
+            Without<PausedByFocus>,
+            Without<Virtualized>,
+        ),
+    >,
+) {
+    for event in messages.read() {
+        for (label, mut sink) in &mut query {
+            if *label == event.label {
+                B::resume(&mut sink);
+            }
+        }
+    }
+}
 
-    #[derive(Component, Clone, Copy, Default, Debug, PartialEq, Eq, Reflect)]
-    #[reflect(Component)]
-    enum TestSfx {
-        #[default]
-        UI,
+/// System that handles `PauseAudio` messages by pausing the targeted sinks.
+pub fn handle_pause_audio_events<B: AudioBackend>(
+    mut commands: Commands,
+    mut messages: MessageReader<PauseAudio>,
+    mut query: Query<(
+        Entity,
+        Option<&PlaybackLabel>,
+        Option<&PoolGeneration>,
+        &mut B::Sink,
+    )>,
+) {
+    for event in messages.read() {
+        for (entity, label, generation, mut sink) in &mut query {
+            if event.target.matches(entity, label, generation) {
+                pause_sink::<B>(&mut commands, entity, &mut sink);
+            }
+        }
     }
+}
 
-    #[derive(Component, Clone, Copy, Default, Debug, PartialEq, Eq, Reflect)]
-    #[reflect(Component)]
-    enum TestMusic {
-        #[default]
-        MainMenu,
-        Gameplay,
+/// System that handles `ResumeAudio` messages by resuming the targeted sinks.
+///
+/// Sinks paused by the game pause, the window focus, or voice virtualization
+/// are left to the system that paused them.
+pub fn handle_resume_audio_events<B: AudioBackend>(
+    mut messages: MessageReader<ResumeAudio>,
+    mut query: Query<
+        (
+            Entity,
+            Option<&PlaybackLabel>,
+            Option<&PoolGeneration>,
+            &mut B::Sink,
+        ),
+        (
+            Without<PausedByGame>,
+            Without<PausedByFocus>,
+            Without<Virtualized>,
+        ),
+    >,
+) {
+    for event in messages.read() {
+        for (entity, label, generation, mut sink) in &mut query {
+            if event.target.matches(entity, label, generation) {
+                B::resume(&mut sink);
+            }
+        }
     }
+}
+
+/// Pauses a sink on request, taking it over from the systems that pause and
+/// resume sinks on their own so they do not resume it.
+fn pause_sink<B: AudioBackend>(commands: &mut Commands, entity: Entity, sink: &mut B::Sink) {
+    B::pause(sink);
+    commands
+        .entity(entity)
+        .remove::<(PausedByGame, PausedByFocus, Virtualized)>();
+}
 
-    #[derive(Resource, Clone, Default)]
-    struct TestConfig;
+/// System that handles `SetAudioVolume` messages by updating the gain of the
+/// targeted entities of category type `A` and moving their sinks of backend
+/// `B` to the new volume.
+pub fn handle_set_audio_volume_events<A, B>(
+    mut commands: Commands,
+    mut messages: MessageReader<SetAudioVolume>,
+    config: Res<A::Config>,
+    mixer: Res<CategoryMixerState<A>>,
+    mut query: Query<(
+        Entity,
+        &A,
+        &PlaybackSettings,
+        Option<&PlaybackLabel>,
+        Option<&PoolGeneration>,
+        Option<&mut TrackGain>,
+        Option<&AudioVolumeState>,
+        Option<&mut B::Sink>,
+    )>,
+) where
+    A: AudioCategory,
+    B: AudioBackend,
+    A::Config: AudioConfigTrait,
+{
+    for event in messages.read() {
+        if !event.volume.is_finite() {
+            warn!("Ignoring SetAudioVolume with volume {}", event.volume);
+            continue;
+        }
+        let volume = event.volume.max(0.0);
+        for (entity, category, playback, label, generation, gain, state, sink) in &mut query {
+            if !event.target.matches(entity, label, generation) {
+                continue;
+            }
+            let new_gain = TrackGain::new(volume);
+            match gain {
+                Some(mut gain) => *gain = new_gain,
+                None => {
+                    commands.entity(entity).insert(new_gain);
+                }
+            }
 
-    impl crate::traits::AudioCategory for TestSfx {
-        type Config = TestConfig;
-        fn volume_multiplier(&self, _: &Self::Config) -> f32 {
-            1.0
+            let Some(mut sink) = sink else {
+                continue;
+            };
+            let target = match state {
+                Some(state) => state.volume(Some(&new_gain)),
+                None => target_volume(&*config, &mixer, category, playback, Some(&new_gain)),
+            };
+            if event.duration.is_zero() {
+                B::set_volume(&mut sink, target);
+            } else {
+                let current = B::volume(&sink);
+                commands.entity(entity).insert(
+                    VolumeTween::new(current, target, event.duration)
+                        .with_easing(event.easing.clone()),
+                );
+            }
         }
     }
+}
+
+/// System that handles `MuteCategory` messages by updating the mixer state.
+pub fn handle_mute_category_events<A: AudioCategory>(
+    mut messages: MessageReader<MuteCategory<A>>,
+    mut mixer: ResMut<CategoryMixerState<A>>,
+) {
+    for event in messages.read() {
+        mixer.set_muted(event.category, event.muted);
+    }
+}
+
+/// System that handles `SoloCategory` messages by updating the mixer state.
+pub fn handle_solo_category_events<A: AudioCategory>(
+    mut messages: MessageReader<SoloCategory<A>>,
+    mut mixer: ResMut<CategoryMixerState<A>>,
+) {
+    for event in messages.read() {
+        mixer.set_soloed(event.category, event.soloed);
+    }
+}
 
-    impl SfxCategory for TestSfx {}
+/// System that handles `PushMixerSnapshot` messages by updating the mixer state.
+pub fn handle_push_mixer_snapshot_events<A: AudioCategory>(
+    mut messages: MessageReader<PushMixerSnapshot<A>>,
+    mut mixer: ResMut<CategoryMixerState<A>>,
+) {
+    for event in messages.read() {
+        mixer.push_snapshot(event.snapshot.clone(), event.transition);
+    }
+}
 
-    impl crate::traits::AudioCategory for TestMusic {
-        type Config = TestConfig;
-        fn volume_multiplier(&self, _: &Self::Config) -> f32 {
-            1.0
+/// System that handles `PopMixerSnapshot` messages by updating the mixer state.
+pub fn handle_pop_mixer_snapshot_events<A: AudioCategory>(
+    mut messages: MessageReader<PopMixerSnapshot<A>>,
+    mut mixer: ResMut<CategoryMixerState<A>>,
+) {
+    for event in messages.read() {
+        let popped = match &event.name {
+            Some(name) => mixer.remove_snapshot(name, event.transition),
+            None => mixer.pop_snapshot(event.transition),
+        };
+        if !popped {
+            warn!("No active mixer snapshot to pop");
         }
     }
+}
+
+/// Schedules a [`QuantizedStop`] for tracks with a tempo and a sink at
+/// `position`.
+///
+/// Returns `None` when the transition should happen immediately.
+fn quantized_stop(
+    quantize: Quantize,
+    tempo: Option<&MusicTempo>,
+    position: Option<Duration>,
+) -> Option<QuantizedStop> {
+    if quantize == Quantize::Immediate {
+        return None;
+    }
+    let (tempo, position) = (tempo?, position?);
+    Some(QuantizedStop::new(quantize, tempo, position))
+}
+
+/// Inserts a [`FadeOut`] starting from the sink's current volume.
+pub(crate) fn start_fade_out(
+    commands: &mut Commands,
+    entity: Entity,
+    duration: Duration,
+    easing: &Easing,
+) {
+    commands.entity(entity).insert(
+        FadeOut::new(duration)
+            .from_current_volume()
+            .with_easing(easing.clone()),
+    );
+}
 
-    impl MusicCategory for TestMusic {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin, TestSfx};
+    use crate::{AudioLog, MusicBundle, SfxBundle, TestAudioPlugin, DEFAULT_MUSIC_CROSSFADE};
+    use bevy::audio::Volume;
 
     #[test]
     fn play_sfx_default_max_concurrent() {
@@ -363,6 +2948,19 @@ mod tests {
         assert_eq!(event.max_concurrent, crate::bundles::DEFAULT_MAX_CONCURRENT);
     }
 
+    #[test]
+    fn play_sfx_fixed_volume_replaces_randomization() {
+        let event = PlaySfx::new(Handle::default(), TestSfx::UI)
+            .randomized()
+            .volume(0.25)
+            .speed(0.8);
+        assert_eq!(event.playback.volume, Volume::Linear(0.25));
+        assert!((event.playback.speed - 0.8).abs() < f32::EPSILON);
+        let randomizer = event.randomizer.unwrap();
+        assert_eq!(randomizer.volume_range, None);
+        assert_eq!(randomizer.speed_range, None);
+    }
+
     #[test]
     fn play_sfx_with_max_concurrent() {
         let event = PlaySfx::new(Handle::default(), TestSfx::UI).with_max_concurrent(3);
@@ -373,13 +2971,13 @@ mod tests {
     fn play_music_defaults_to_loop() {
         use bevy::audio::PlaybackMode;
 
-        let event = PlayMusic::new(Handle::default(), TestMusic::MainMenu);
+        let event = PlayMusic::new(Handle::default(), TestMusic::Combat);
         assert!(matches!(event.playback.mode, PlaybackMode::Loop));
     }
 
     #[test]
     fn play_music_with_playback_settings() {
-        let event = PlayMusic::new(Handle::default(), TestMusic::Gameplay)
+        let event = PlayMusic::new(Handle::default(), TestMusic::Main)
             .with_playback(PlaybackSettings::ONCE);
 
         assert!(matches!(
@@ -390,8 +2988,8 @@ mod tests {
 
     #[test]
     fn stop_music_new() {
-        let event = StopMusic::new(TestMusic::Gameplay);
-        assert_eq!(event.category, TestMusic::Gameplay);
+        let event = StopMusic::new(TestMusic::Main);
+        assert_eq!(event.category, TestMusic::Main);
     }
 
     #[test]
@@ -402,17 +3000,625 @@ mod tests {
 
     #[test]
     fn fade_out_music_new() {
-        let event = FadeOutMusic::new(TestMusic::MainMenu, Duration::from_secs(2));
+        let event = FadeOutMusic::new(TestMusic::Combat, Duration::from_secs(2));
 
-        assert_eq!(event.category, TestMusic::MainMenu);
+        assert_eq!(event.category, TestMusic::Combat);
         assert_eq!(event.duration, Duration::from_secs(2));
     }
 
     #[test]
     fn fade_out_music_from_secs() {
-        let event = FadeOutMusic::from_secs(TestMusic::Gameplay, 1.5);
+        let event = FadeOutMusic::from_secs(TestMusic::Main, 1.5);
 
-        assert_eq!(event.category, TestMusic::Gameplay);
+        assert_eq!(event.category, TestMusic::Main);
         assert!((event.duration.as_secs_f32() - 1.5).abs() < 0.001);
     }
+
+    #[test]
+    fn fade_out_all_music_new() {
+        let event: FadeOutAllMusic<TestMusic> = FadeOutAllMusic::new(Duration::from_secs(3));
+
+        assert_eq!(event.duration, Duration::from_secs(3));
+    }
+
+    #[test]
+    fn fade_out_all_music_from_secs() {
+        let event: FadeOutAllMusic<TestMusic> = FadeOutAllMusic::from_secs(0.5);
+
+        assert!((event.duration.as_secs_f32() - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn fade_out_sfx_new() {
+        let event = FadeOutSfx::new(TestSfx::UI, Duration::from_secs(1));
+
+        assert_eq!(event.category, TestSfx::UI);
+        assert_eq!(event.duration, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn fade_out_sfx_from_secs() {
+        let event = FadeOutSfx::from_secs(TestSfx::UI, 2.5);
+
+        assert_eq!(event.category, TestSfx::UI);
+        assert!((event.duration.as_secs_f32() - 2.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn play_music_with_fade_in() {
+        let event = PlayMusic::new(Handle::default(), TestMusic::Combat)
+            .with_fade_in(FadeIn::from_secs(2.0));
+
+        let fade_in = event.fade_in.expect("fade-in should be set");
+        assert!((fade_in.timer.duration().as_secs_f32() - 2.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn fade_out_music_with_easing() {
+        let event = FadeOutMusic::from_secs(TestMusic::Combat, 1.0).with_easing(Easing::SCurve);
+
+        assert!(matches!(event.easing, Easing::SCurve));
+    }
+
+    #[test]
+    fn play_stinger_builder() {
+        let event = PlayStinger::new(Handle::default(), TestMusic::Main)
+            .with_duck_volume(0.1)
+            .with_fade(Duration::from_millis(500));
+
+        assert_eq!(event.category, TestMusic::Main);
+        assert!((event.stinger.duck_volume - 0.1).abs() < f32::EPSILON);
+        assert_eq!(event.stinger.fade, Duration::from_millis(500));
+    }
+
+    #[test]
+    fn play_music_with_base_gain() {
+        let event = PlayMusic::new(Handle::default(), TestMusic::Main);
+        assert!(event.gain.is_none());
+
+        let event = event.with_base_gain(0.5);
+        assert_eq!(event.gain, Some(TrackGain(0.5)));
+    }
+
+    #[test]
+    fn mute_and_solo_constructors() {
+        assert!(MuteCategory::mute(TestSfx::UI).muted);
+        assert!(!MuteCategory::unmute(TestSfx::UI).muted);
+        assert!(SoloCategory::solo(TestMusic::Main).soloed);
+        assert!(!SoloCategory::unsolo(TestMusic::Main).soloed);
+    }
+
+    #[test]
+    fn pop_mixer_snapshot_constructors() {
+        let pop = PopMixerSnapshot::<TestSfx>::new(Duration::ZERO);
+        assert!(pop.name.is_none());
+
+        let pop = PopMixerSnapshot::<TestSfx>::named("PauseMenu", Duration::from_secs(1));
+        assert_eq!(pop.name.as_deref(), Some("PauseMenu"));
+        assert_eq!(pop.transition, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn play_with_label() {
+        let event = PlaySfx::new(Handle::default(), TestSfx::UI).with_label("engine");
+        assert_eq!(event.label, Some(PlaybackLabel::from("engine")));
+
+        let event = PlayMusic::new(Handle::default(), TestMusic::Main).with_label("boss");
+        assert_eq!(event.label, Some(PlaybackLabel::from("boss")));
+    }
+
+    #[test]
+    fn stop_labeled_with_fade() {
+        let event = StopLabeled::new("engine");
+        assert!(event.fade.is_none());
+
+        let event = event.with_fade(Duration::from_secs(1));
+        assert_eq!(event.fade, Some(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn set_layer_volume_builder() {
+        let event = SetLayerVolume::new(TestMusic::Main, "drums", 0.8)
+            .with_duration(Duration::from_secs(2))
+            .with_easing(Easing::SCurve);
+
+        assert_eq!(event.layer, MusicLayer::new("drums"));
+        assert!((event.volume - 0.8).abs() < f32::EPSILON);
+        assert_eq!(event.duration, Duration::from_secs(2));
+        assert!(matches!(event.easing, Easing::SCurve));
+    }
+
+    #[test]
+    fn play_sfx_resolves_localized_handle() {
+        let mut assets = Assets::<AudioSource>::default();
+        let german = assets.add(AudioSource {
+            bytes: Vec::new().into(),
+        });
+        let audio = LocalizedAudio::new(Handle::default()).with_language("de", german.clone());
+        let event = PlaySfx::localized(audio, TestSfx::default());
+
+        assert_eq!(
+            event.resolve_handle(&CurrentAudioLanguage::new("de")),
+            &german
+        );
+        assert_eq!(
+            event.resolve_handle(&CurrentAudioLanguage::default()),
+            &event.handle
+        );
+    }
+
+    #[test]
+    fn stop_music_quantized() {
+        let event = StopMusic::new(TestMusic::Main);
+        assert_eq!(event.quantize, Quantize::Immediate);
+        let event = event.quantized(Quantize::NextBar);
+        assert_eq!(event.quantize, Quantize::NextBar);
+
+        let fade = FadeOutMusic::from_secs(TestMusic::Main, 1.0).quantized(Quantize::NextBeat);
+        assert_eq!(fade.quantize, Quantize::NextBeat);
+    }
+
+    #[test]
+    fn quantized_stop_without_tempo_is_immediate() {
+        assert!(quantized_stop(Quantize::NextBar, None, None).is_none());
+        let tempo = MusicTempo::new(120.0, 4);
+        assert!(quantized_stop(Quantize::Immediate, Some(&tempo), None).is_none());
+    }
+
+    #[test]
+    fn play_music_with_tempo() {
+        let tempo = MusicTempo::new(90.0, 3);
+        let event = PlayMusic::new(Handle::default(), TestMusic::Main).with_tempo(tempo);
+        assert_eq!(event.tempo, Some(tempo));
+    }
+
+    #[test]
+    fn play_music_starting_at() {
+        let event =
+            PlayMusic::new(Handle::default(), TestMusic::Main).starting_at(Duration::from_secs(12));
+        assert_eq!(event.playback.start_position, Some(Duration::from_secs(12)));
+        assert!(matches!(
+            event.playback.mode,
+            bevy::audio::PlaybackMode::Loop
+        ));
+    }
+
+    #[test]
+    fn play_music_resume_from_memory() {
+        let event = PlayMusic::new(Handle::default(), TestMusic::Main);
+        assert!(!event.resume);
+        assert!(event.resume_from_memory().resume);
+    }
+
+    #[test]
+    fn play_music_wait_for_load() {
+        let event = PlayMusic::new(Handle::default(), TestMusic::Combat);
+        assert!(!event.wait_for_load);
+
+        let event = event.wait_for_load();
+        assert!(event.wait_for_load);
+    }
+
+    #[test]
+    fn play_sfx_wait_for_load() {
+        let event = PlaySfx::new(Handle::default(), TestSfx::UI).wait_for_load();
+        assert!(event.wait_for_load);
+    }
+
+    #[test]
+    fn stop_labeled_despawns_only_matching_entities() {
+        let mut app = test_app(TestPlugin::default());
+
+        let engine = app.world_mut().spawn(PlaybackLabel::from("engine")).id();
+        let other = app.world_mut().spawn(PlaybackLabel::from("radio")).id();
+
+        app.world_mut().write_message(StopLabeled::new("engine"));
+        app.update();
+
+        assert!(app.world().get_entity(engine).is_err());
+        assert!(app.world().get_entity(other).is_ok());
+    }
+
+    #[test]
+    fn sfx_batch_applies_concurrency_once() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.world_mut().write_message(
+            (0..3)
+                .map(|_| PlaySfx::new(Handle::default(), TestSfx::UI).with_max_concurrent(2))
+                .collect::<PlaySfxBatch<_>>(),
+        );
+        app.update();
+        app.update();
+
+        assert_eq!(app.world().resource::<AudioLog>().plays().count(), 2);
+        let dropped = app.world().resource::<Messages<AudioDropped>>();
+        assert_eq!(dropped.get_cursor().read(dropped).count(), 1);
+    }
+
+    #[test]
+    fn pause_and_resume_audio_by_entity_or_label() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.world_mut()
+            .write_message(PlayMusic::new(Handle::default(), TestMusic::Main).with_label("radio"));
+        app.update();
+        app.update();
+        let radio = app
+            .world_mut()
+            .query_filtered::<Entity, With<TestMusic>>()
+            .single(app.world())
+            .unwrap();
+        let paused = |app: &App| app.world().get::<AudioSink>(radio).unwrap().is_paused();
+
+        app.world_mut().entity_mut(radio).insert(Virtualized);
+        app.world_mut().write_message(PauseAudio::new(radio));
+        app.update();
+        assert!(paused(&app));
+        assert!(app.world().get::<Virtualized>(radio).is_none());
+
+        app.world_mut().write_message(ResumeAudio::new("radio"));
+        app.update();
+        assert!(!paused(&app));
+
+        // Sinks paused by another owner stay paused
+        app.world_mut().entity_mut(radio).insert(PausedByFocus);
+        app.world().get::<AudioSink>(radio).unwrap().pause();
+        app.world_mut().write_message(ResumeAudio::new(radio));
+        app.update();
+        assert!(paused(&app));
+    }
+
+    #[test]
+    fn path_messages_load_through_asset_server() {
+        let mut app = test_app((
+            AssetPlugin::default(),
+            TestPlugin::default(),
+            TestAudioPlugin,
+        ));
+        app.init_asset::<AudioSource>();
+        app.world_mut()
+            .write_message(PlayMusic::path("music/theme.ogg", TestMusic::Main));
+        app.world_mut()
+            .write_message(PlaySfx::path("sfx/hit.ogg", TestSfx::UI));
+        app.world_mut()
+            .write_message(PlaySfx::path("sfx/hit.ogg", TestSfx::UI));
+        app.update();
+
+        let world = app.world_mut();
+        let music: Vec<_> = world
+            .query_filtered::<&AudioPlayer, With<TestMusic>>()
+            .iter(world)
+            .map(|player| player.0.path().map(ToString::to_string))
+            .collect();
+        assert_eq!(music, vec![Some("music/theme.ogg".to_string())]);
+        let sfx: Vec<_> = world
+            .query_filtered::<&AudioPlayer, With<TestSfx>>()
+            .iter(world)
+            .map(|player| player.0.clone())
+            .collect();
+        assert_eq!(sfx.len(), 2);
+        assert_eq!(sfx[0], sfx[1]);
+        assert_eq!(
+            sfx[0].path().map(ToString::to_string),
+            Some("sfx/hit.ogg".to_string())
+        );
+    }
+
+    #[test]
+    fn stop_music_with_fade_fades_out() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        let fade = Duration::from_secs(1);
+        let play = |app: &mut App| {
+            app.world_mut()
+                .write_message(PlayMusic::new(Handle::default(), TestMusic::Main));
+            app.update();
+            app.update();
+        };
+        play(&mut app);
+        app.world_mut()
+            .write_message(StopMusic::new(TestMusic::Main).with_fade(fade));
+        app.update();
+        let mut fading = app
+            .world_mut()
+            .query_filtered::<(), (With<TestMusic>, With<FadeOut>)>();
+        assert_eq!(fading.iter(app.world()).count(), 1);
+
+        play(&mut app);
+        app.world_mut()
+            .write_message(StopAllMusic::<TestMusic>::default().with_fade(fade));
+        app.update();
+        assert_eq!(fading.iter(app.world()).count(), 2);
+    }
+
+    #[test]
+    fn crossfade_music_waits_for_the_next_bar() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        let fade = Duration::from_secs(1);
+        app.world_mut().write_message(
+            PlayMusic::new(Handle::default(), TestMusic::Main)
+                .with_tempo(MusicTempo::new(120.0, 4)),
+        );
+        app.update();
+        app.update();
+        let old = app
+            .world_mut()
+            .query_filtered::<Entity, With<TestMusic>>()
+            .single(app.world())
+            .unwrap();
+
+        app.world_mut().write_message(
+            CrossfadeMusic::new(PlayMusic::new(Handle::default(), TestMusic::Main), fade)
+                .on_next_bar(),
+        );
+        app.update();
+        let stop = app.world().get::<QuantizedStop>(old).unwrap();
+        assert_eq!(stop.fade, Some(fade));
+        let delayed = app
+            .world()
+            .resource::<DelayedMessages<PlayMusic<TestMusic>>>();
+        assert_eq!(delayed.len(), 1);
+        let mut music = app.world_mut().query_filtered::<Entity, With<TestMusic>>();
+        assert_eq!(music.iter(app.world()).count(), 1);
+
+        app.world_mut().write_message(CrossfadeMusic::new(
+            PlayMusic::new(Handle::default(), TestMusic::Main),
+            fade,
+        ));
+        app.update();
+        let mut fading_in = app.world_mut().query_filtered::<&FadeIn, With<TestMusic>>();
+        let fade_in = fading_in.single(app.world()).unwrap();
+        assert_eq!(fade_in.timer.duration(), fade);
+    }
+
+    #[test]
+    fn stop_and_play_in_one_frame_crossfades_music() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        let switch = |app: &mut App| {
+            app.world_mut()
+                .write_message(StopMusic::new(TestMusic::Main));
+            app.world_mut()
+                .write_message(PlayMusic::new(Handle::default(), TestMusic::Main));
+            app.update();
+        };
+        app.world_mut()
+            .write_message(PlayMusic::new(Handle::default(), TestMusic::Main));
+        app.update();
+        app.update();
+        let old = app
+            .world_mut()
+            .query_filtered::<Entity, With<TestMusic>>()
+            .single(app.world())
+            .unwrap();
+
+        switch(&mut app);
+        assert!(app.world().get::<FadeOut>(old).is_some());
+        let mut fading_in = app
+            .world_mut()
+            .query_filtered::<Entity, (With<TestMusic>, With<FadeIn>)>();
+        assert_eq!(fading_in.iter(app.world()).count(), 1);
+
+        app.insert_resource(MusicCrossfade::DISABLED);
+        switch(&mut app);
+        assert!(app.world().get_entity(old).is_err());
+        let mut music = app.world_mut().query::<(&TestMusic, Has<FadeIn>)>();
+        let music: Vec<_> = music.iter(app.world()).collect();
+        assert_eq!(music.len(), 1);
+        assert!(!music[0].1);
+    }
+
+    #[test]
+    fn stop_music_cuts_over_with_a_zero_crossfade() {
+        let mut app = test_app((
+            TestPlugin::default().with_music_crossfade(Duration::ZERO),
+            TestAudioPlugin,
+        ));
+        app.world_mut()
+            .write_message(PlayMusic::new(Handle::default(), TestMusic::Main));
+        app.update();
+        app.update();
+        let old = app
+            .world_mut()
+            .query_filtered::<Entity, With<TestMusic>>()
+            .single(app.world())
+            .unwrap();
+
+        app.world_mut()
+            .write_message(StopMusic::new(TestMusic::Main));
+        app.world_mut()
+            .write_message(PlayMusic::new(Handle::default(), TestMusic::Main));
+        app.update();
+        assert!(app.world().get_entity(old).is_err());
+        let mut fading_in = app
+            .world_mut()
+            .query_filtered::<Entity, (With<TestMusic>, With<FadeIn>)>();
+        assert_eq!(fading_in.iter(app.world()).count(), 0);
+    }
+
+    #[test]
+    fn stop_music_a_frame_after_play_does_not_crossfade() {
+        let mut app = test_app((
+            TestPlugin::default().with_music_crossfade(DEFAULT_MUSIC_CROSSFADE),
+            TestAudioPlugin,
+        ));
+        app.world_mut()
+            .write_message(PlayMusic::new(Handle::default(), TestMusic::Main));
+        app.update();
+        app.update();
+        let old = app
+            .world_mut()
+            .query_filtered::<Entity, With<TestMusic>>()
+            .single(app.world())
+            .unwrap();
+
+        app.world_mut()
+            .write_message(PlayMusic::new(Handle::default(), TestMusic::Combat));
+        app.update();
+        app.world_mut()
+            .write_message(StopMusic::new(TestMusic::Main));
+        app.update();
+        assert!(app.world().get_entity(old).is_err());
+        let mut fading_in = app
+            .world_mut()
+            .query_filtered::<Entity, (With<TestMusic>, With<FadeIn>)>();
+        assert_eq!(fading_in.iter(app.world()).count(), 0);
+    }
+
+    #[test]
+    fn unique_music_is_not_started_twice() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        let handle = Handle::<AudioSource>::default();
+        for _ in 0..2 {
+            app.world_mut()
+                .write_message(PlayMusic::new(handle.clone(), TestMusic::Main).unique());
+        }
+        app.update();
+        app.world_mut()
+            .spawn(MusicBundle::new(handle.clone(), TestMusic::Main).unique());
+        app.update();
+
+        let world = app.world_mut();
+        assert_eq!(
+            world
+                .query_filtered::<(), With<TestMusic>>()
+                .iter(world)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn duplicate_music_follows_policy() {
+        let mut app = test_app((
+            TestPlugin::default()
+                .with_duplicate_music(DuplicateMusic::Ignore)
+                .with_music_crossfade(DEFAULT_MUSIC_CROSSFADE),
+            TestAudioPlugin,
+        ));
+        let play = |app: &mut App, message: PlayMusic<TestMusic>| {
+            app.world_mut().write_message(message);
+            app.update();
+            app.update();
+        };
+        let handle = Handle::<AudioSource>::default();
+        play(&mut app, PlayMusic::new(handle.clone(), TestMusic::Main));
+        let first = app
+            .world_mut()
+            .query_filtered::<Entity, With<TestMusic>>()
+            .single(app.world())
+            .unwrap();
+
+        play(&mut app, PlayMusic::new(handle.clone(), TestMusic::Main));
+        let mut music = app.world_mut().query_filtered::<Entity, With<TestMusic>>();
+        assert_eq!(music.iter(app.world()).collect::<Vec<_>>(), vec![first]);
+
+        play(
+            &mut app,
+            PlayMusic::new(handle.clone(), TestMusic::Main).on_duplicate(DuplicateMusic::Restart),
+        );
+        assert!(app.world().get_entity(first).is_err());
+        let second = music.single(app.world()).unwrap();
+
+        play(
+            &mut app,
+            PlayMusic::new(handle.clone(), TestMusic::Main).on_duplicate(DuplicateMusic::Crossfade),
+        );
+        assert!(app.world().get::<FadeOut>(second).is_some());
+        let mut fading_in = app
+            .world_mut()
+            .query_filtered::<Entity, (With<TestMusic>, With<FadeIn>)>();
+        assert_eq!(fading_in.iter(app.world()).count(), 1);
+    }
+
+    #[test]
+    fn looping_sfx_start_once_and_stop_by_label() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        for label in ["rain", "rain", "wind"] {
+            app.world_mut().write_message(PlayLoopingSfx::new(
+                Handle::default(),
+                TestSfx::UI,
+                label,
+            ));
+        }
+        app.update();
+        app.world_mut()
+            .write_message(PlayLoopingSfx::new(Handle::default(), TestSfx::UI, "rain"));
+        app.update();
+        let loops = |app: &mut App| {
+            app.world_mut()
+                .query_filtered::<&PlaybackLabel, With<LoopingSfx>>()
+                .iter(app.world())
+                .map(|label| label.as_str().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(loops(&mut app).len(), 2);
+
+        app.world_mut()
+            .write_message(StopLoopingSfx::new(TestSfx::UI).with_label("rain"));
+        app.update();
+        assert_eq!(loops(&mut app), ["wind"]);
+    }
+
+    #[test]
+    fn looping_sfx_message_applies_request_builders() {
+        #[derive(Component, Clone)]
+        struct Rain;
+
+        let mut app = test_app(TestPlugin::default());
+        app.world_mut().write_message(
+            PlayLoopingSfx::new(Handle::default(), TestSfx::Ambience, "rain")
+                .volume(0.4)
+                .with_speed(0.9, 0.9)
+                .with_components(Rain),
+        );
+        app.update();
+
+        let mut query = app
+            .world_mut()
+            .query_filtered::<&PlaybackSettings, (With<LoopingSfx>, With<Rain>)>();
+        let playback = query.single(app.world()).unwrap();
+        assert_eq!(playback.volume, Volume::Linear(0.4));
+        assert!((playback.speed - 0.9).abs() < f32::EPSILON);
+        assert!(matches!(playback.mode, bevy::audio::PlaybackMode::Loop));
+    }
+
+    #[test]
+    fn set_audio_volume_targets_entity_or_label() {
+        let mut app = test_app(TestPlugin::default());
+
+        let engine = app
+            .world_mut()
+            .spawn((
+                SfxBundle::new(Handle::default(), TestSfx::UI),
+                PlaybackLabel::new("engine"),
+            ))
+            .id();
+        let crowd = app
+            .world_mut()
+            .spawn(SfxBundle::new(Handle::default(), TestSfx::UI))
+            .id();
+        let music = app
+            .world_mut()
+            .spawn(MusicBundle::new(Handle::default(), TestMusic::Main))
+            .id();
+
+        app.world_mut()
+            .write_message(SetAudioVolume::new("engine", 0.4));
+        app.world_mut()
+            .write_message(SetAudioVolume::new(crowd, 0.8));
+        app.world_mut()
+            .write_message(SetAudioVolume::new(music, 0.2));
+        app.update();
+
+        let gain = |app: &App, entity| app.world().get::<TrackGain>(entity).copied();
+        assert_eq!(gain(&app, engine), Some(TrackGain(0.4)));
+        assert_eq!(gain(&app, crowd), Some(TrackGain(0.8)));
+        assert_eq!(gain(&app, music), Some(TrackGain(0.2)));
+
+        app.world_mut()
+            .write_message(SetAudioVolume::new(crowd, f32::NAN));
+        app.world_mut()
+            .write_message(SetAudioVolume::new(music, -1.0));
+        app.update();
+        assert_eq!(gain(&app, crowd), Some(TrackGain(0.8)));
+        assert_eq!(gain(&app, music), Some(TrackGain(0.0)));
+    }
 }
@@ -0,0 +1,148 @@
+//! Fading audio out when the app exits.
+//!
+//! Quitting mid-music otherwise cuts every sink off abruptly, which is heard
+//! as a click. With [`MsgAudioPlugin::with_exit_fade`](crate::MsgAudioPlugin::with_exit_fade),
+//! [`AppExit`] messages are held back while every playing sound fades to
+//! silence, then sent again so the app closes.
+
+use bevy::prelude::*;
+use std::time::Duration;
+
+use crate::backend::AudioBackend;
+use crate::components::FadeOut;
+use crate::easing::Easing;
+
+/// Default length of the fade played when the app exits.
+pub const DEFAULT_EXIT_FADE: Duration = Duration::from_millis(300);
+
+/// Resource setting how audio fades out when the app exits.
+///
+/// Inserted by [`MsgAudioPlugin::with_exit_fade`](crate::MsgAudioPlugin::with_exit_fade).
+/// Remove it to let [`AppExit`] through immediately again.
+#[derive(Resource, Reflect, Debug, Clone)]
+#[reflect(Resource)]
+pub struct ExitFade {
+    /// How long playing sounds take to fade to silence.
+    pub duration: Duration,
+    /// The curve of the fade.
+    #[reflect(ignore)]
+    pub easing: Easing,
+    /// The held-back exit and the time left until it is sent again.
+    #[reflect(ignore)]
+    pending: Option<(AppExit, Timer)>,
+}
+
+impl Default for ExitFade {
+    fn default() -> Self {
+        Self::new(DEFAULT_EXIT_FADE)
+    }
+}
+
+impl ExitFade {
+    /// Creates an exit fade of the given length.
+    #[must_use]
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            easing: Easing::default(),
+            pending: None,
+        }
+    }
+
+    /// Sets the easing curve of the fade.
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Returns `true` while an exit is held back for the fade.
+    #[must_use]
+    pub fn is_exiting(&self) -> bool {
+        self.pending.is_some()
+    }
+}
+
+/// Holds back [`AppExit`] messages while playing sounds fade out, then sends
+/// the first one again.
+///
+/// Runs in `Last` so exits written anywhere during the frame are caught
+/// before the app runner sees them. The fade and the hold are timed with real
+/// time, so the sounds still fade and the app still closes while
+/// `Time<Virtual>` is paused.
+pub fn fade_out_on_app_exit<B: AudioBackend>(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut fade: ResMut<ExitFade>,
+    mut exits: ResMut<Messages<AppExit>>,
+    sinks: Query<Entity, Or<(With<B::Sink>, With<<B::Spatial as AudioBackend>::Sink>)>>,
+) {
+    if let Some((exit, timer)) = fade.pending.as_mut() {
+        exits.clear();
+        if timer.tick(time.delta()).is_finished() {
+            exits.write(exit.clone());
+        }
+        return;
+    }
+
+    let Some(exit) = exits.drain().next() else {
+        return;
+    };
+    if fade.duration.is_zero() || sinks.is_empty() {
+        exits.write(exit);
+        return;
+    }
+    for entity in &sinks {
+        commands.entity(entity).insert(
+            FadeOut::new(fade.duration)
+                .from_current_volume()
+                .with_easing(fade.easing.clone())
+                .with_real_time(),
+        );
+    }
+    fade.pending = Some((exit, Timer::new(fade.duration, TimerMode::Once)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestConfig, TestMusic, TestPlugin};
+    use crate::{AudioLog, PlayMusic, TestAudioPlugin};
+
+    #[test]
+    fn exit_fade_holds_back_app_exit_until_sounds_faded() {
+        let mut app = test_app((
+            TestPlugin::default()
+                .with_volume_ramp(Duration::ZERO)
+                .with_exit_fade(Duration::from_millis(30)),
+            TestAudioPlugin,
+        ));
+        app.insert_resource(TestConfig::FULL);
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            Duration::from_millis(20),
+        ));
+        app.world_mut()
+            .write_message(PlayMusic::new(Handle::default(), TestMusic::Main));
+        app.update();
+        app.update();
+        let (entity, _) = app.world().resource::<AudioLog>().plays().next().unwrap();
+        let volume = app.world().resource::<AudioLog>().volume(entity).unwrap();
+
+        // The fade runs on real time while the game is paused
+        app.world_mut().resource_mut::<Time<Virtual>>().pause();
+        app.world_mut().write_message(AppExit::Success);
+        app.update();
+        assert!(app.should_exit().is_none());
+        assert!(app.world().get::<FadeOut>(entity).is_some());
+        assert!(app.world().resource::<ExitFade>().is_exiting());
+
+        app.update();
+        assert!(app.should_exit().is_none());
+        let faded = app.world().resource::<AudioLog>().volume(entity).unwrap();
+        assert!(faded < volume, "{faded} should be below {volume}");
+
+        app.update();
+        assert_eq!(app.should_exit(), Some(AppExit::Success));
+        assert!(app.world().get_entity(entity).is_err());
+    }
+}
@@ -0,0 +1,93 @@
+//! User components attached to entities spawned from play messages.
+//!
+//! [`PlayMusic::with_components`](crate::PlayMusic::with_components) and
+//! [`PlaySfx::with_components`](crate::PlaySfx::with_components) carry
+//! markers and other components to the spawned audio entity, so sounds that
+//! need them can still be played through messages.
+
+use bevy::prelude::*;
+use std::fmt;
+use std::sync::Arc;
+
+/// Components inserted into the entity spawned for a play message.
+///
+/// Messages must be cloneable, so the bundle is kept behind a shared closure
+/// and cloned into every entity it is inserted into.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::PlaySfx;
+///
+/// #[derive(Component, Clone)]
+/// struct Footstep;
+///
+/// sfx.write(PlaySfx::new(step, GameSfx::Player).with_components(Footstep));
+/// ```
+#[derive(Clone)]
+pub struct ExtraComponents(Arc<dyn Fn(&mut EntityCommands) + Send + Sync>);
+
+impl ExtraComponents {
+    /// Creates extra components inserting a clone of `bundle`.
+    #[must_use]
+    pub fn new(bundle: impl Bundle + Clone) -> Self {
+        Self::from_fn(move |entity| {
+            entity.insert(bundle.clone());
+        })
+    }
+
+    /// Creates extra components from a function run on the spawned entity.
+    #[must_use]
+    pub fn from_fn(f: impl Fn(&mut EntityCommands) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// Inserts the components into an entity.
+    pub fn insert_into(&self, entity: &mut EntityCommands) {
+        (self.0)(entity);
+    }
+}
+
+impl fmt::Debug for ExtraComponents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ExtraComponents(..)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin, TestSfx};
+    use crate::{PlayMusic, PlaySfx, TestAudioPlugin};
+
+    #[test]
+    fn play_messages_insert_extra_components() {
+        #[derive(Component, Clone)]
+        struct Marker(u32);
+
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.world_mut().write_message(
+            PlayMusic::new(Handle::default(), TestMusic::Main).with_components(Marker(1)),
+        );
+        app.world_mut().write_message(
+            PlaySfx::new(Handle::default(), TestSfx::UI)
+                .with_components(Marker(2))
+                .with_components(Name::new("hit")),
+        );
+        app.update();
+
+        let world = app.world_mut();
+        let music: Vec<_> = world
+            .query_filtered::<&Marker, With<TestMusic>>()
+            .iter(world)
+            .map(|marker| marker.0)
+            .collect();
+        assert_eq!(music, vec![1]);
+        let sfx: Vec<_> = world
+            .query_filtered::<(&Marker, &Name), With<TestSfx>>()
+            .iter(world)
+            .map(|(marker, name)| (marker.0, name.as_str().to_string()))
+            .collect();
+        assert_eq!(sfx, vec![(2, "hit".to_string())]);
+    }
+}
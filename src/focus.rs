@@ -0,0 +1,205 @@
+//! Audio focus handling.
+//!
+//! When the window loses focus or the app is sent to the background, audio
+//! can be paused or ducked until focus returns. Enable it with
+//! [`MsgAudioPlugin::with_focus_loss`](crate::MsgAudioPlugin::with_focus_loss);
+//! categories opt out through [`AudioCategory::plays_in_background`].
+
+use bevy::{
+    prelude::*,
+    window::{AppLifecycle, WindowFocused},
+};
+
+use crate::backend::AudioBackend;
+use crate::mixer::CategoryMixerState;
+use crate::traits::AudioCategory;
+
+/// What happens to audio while the app is out of focus.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub enum FocusLossBehavior {
+    /// Pause every sound, resuming it when focus returns.
+    Pause,
+    /// Scale every sound by a linear gain, e.g. `0.2`.
+    Duck(f32),
+}
+
+/// Resource tracking whether the app has focus.
+///
+/// Updated from [`WindowFocused`] and [`AppLifecycle`] messages. Set
+/// `focused` yourself to drive focus handling from another source.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource)]
+pub struct AudioFocus {
+    /// Whether the window or app currently has focus.
+    pub focused: bool,
+    /// Behavior while out of focus, or `None` to leave audio untouched.
+    pub behavior: Option<FocusLossBehavior>,
+}
+
+impl Default for AudioFocus {
+    fn default() -> Self {
+        Self {
+            focused: true,
+            behavior: None,
+        }
+    }
+}
+
+impl AudioFocus {
+    /// Returns the gain applied to categories that do not play in the
+    /// background.
+    #[must_use]
+    pub fn background_gain(&self) -> f32 {
+        match self.behavior {
+            Some(FocusLossBehavior::Duck(gain)) if !self.focused => gain.clamp(0.0, 1.0),
+            _ => 1.0,
+        }
+    }
+
+    /// Returns `true` if sounds should currently be paused.
+    #[must_use]
+    pub fn pauses(&self) -> bool {
+        !self.focused && self.behavior == Some(FocusLossBehavior::Pause)
+    }
+}
+
+/// Marks a sound that was paused because the app lost focus.
+///
+/// Only sinks carrying this marker are resumed when focus returns, so sounds
+/// paused by other systems stay paused.
+#[derive(Component, Reflect, Debug, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct PausedByFocus;
+
+/// Updates [`AudioFocus`] from window focus and app lifecycle messages.
+pub fn track_app_focus(
+    mut focus: ResMut<AudioFocus>,
+    mut window_focused: MessageReader<WindowFocused>,
+    mut lifecycle: MessageReader<AppLifecycle>,
+) {
+    let mut focused = None;
+    for message in window_focused.read() {
+        focused = Some(message.focused);
+    }
+    for message in lifecycle.read() {
+        match message {
+            AppLifecycle::WillSuspend | AppLifecycle::Suspended => focused = Some(false),
+            AppLifecycle::WillResume | AppLifecycle::Running => focused = Some(true),
+            AppLifecycle::Idle => {}
+        }
+    }
+    if let Some(focused) = focused {
+        if focus.focused != focused {
+            focus.focused = focused;
+        }
+    }
+}
+
+/// Pauses or ducks sounds of category type `A` while the app is out of focus.
+///
+/// Categories returning `true` from [`AudioCategory::plays_in_background`]
+/// are left untouched. Sounds starting while the app is out of focus are
+/// paused as soon as their sink is created.
+pub fn sync_focus_loss<A: AudioCategory, B: AudioBackend>(
+    mut commands: Commands,
+    focus: Res<AudioFocus>,
+    mut mixer: ResMut<CategoryMixerState<A>>,
+    mut query: Query<(Entity, &A, &mut B::Sink, Has<PausedByFocus>)>,
+) {
+    let changed = focus.is_changed();
+    let pause = focus.pauses();
+    if !changed && !pause {
+        return;
+    }
+    let gain = focus.background_gain();
+    if mixer.background_gain() != gain {
+        mixer.set_background_gain(gain);
+    }
+
+    for (entity, category, mut sink, paused_by_focus) in &mut query {
+        if !changed && !sink.is_added() {
+            continue;
+        }
+        if pause {
+            if !category.plays_in_background() && !B::is_paused(&sink) {
+                B::pause(&mut sink);
+                commands.entity(entity).insert(PausedByFocus);
+            }
+        } else if paused_by_focus {
+            B::resume(&mut sink);
+            commands.entity(entity).remove::<PausedByFocus>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestPlugin, TestSfx};
+    use crate::{AudioCall, AudioLog, PlaySfx, TestAudioPlugin};
+
+    #[test]
+    fn focus_loss_pauses_and_resumes_sounds() {
+        let mut app = test_app((
+            TestPlugin::default().with_focus_loss(FocusLossBehavior::Pause),
+            TestAudioPlugin,
+        ));
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI));
+        app.update();
+        app.update();
+        let (entity, _) = app.world().resource::<AudioLog>().plays().next().unwrap();
+
+        let window = Entity::PLACEHOLDER;
+        app.world_mut().write_message(WindowFocused {
+            window,
+            focused: false,
+        });
+        app.update();
+        assert!(app
+            .world()
+            .resource::<AudioLog>()
+            .calls_for(entity)
+            .any(|call| matches!(call, AudioCall::Pause { .. })));
+        assert!(app.world().get::<PausedByFocus>(entity).is_some());
+
+        // Sounds started while unfocused are paused once their sink exists
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI));
+        app.update();
+        app.update();
+        let (late, _) = app.world().resource::<AudioLog>().plays().nth(1).unwrap();
+        assert!(app.world().get::<AudioSink>(late).unwrap().is_paused());
+        assert!(app.world().get::<PausedByFocus>(late).is_some());
+
+        app.world_mut().write_message(WindowFocused {
+            window,
+            focused: true,
+        });
+        app.update();
+        for entity in [entity, late] {
+            assert!(matches!(
+                app.world().resource::<AudioLog>().calls_for(entity).last(),
+                Some(AudioCall::Resume { .. })
+            ));
+            assert!(app.world().get::<PausedByFocus>(entity).is_none());
+        }
+    }
+
+    #[test]
+    fn focus_loss_ducks_categories_not_playing_in_background() {
+        let mut app =
+            test_app(TestPlugin::default().with_focus_loss(FocusLossBehavior::Duck(0.25)));
+        app.update();
+
+        app.world_mut().write_message(AppLifecycle::Suspended);
+        app.update();
+        let mixer = app.world().resource::<CategoryMixerState<TestSfx>>();
+        assert!((mixer.gain(TestSfx::UI) - 0.25).abs() < f32::EPSILON);
+
+        app.world_mut().write_message(AppLifecycle::Running);
+        app.update();
+        let mixer = app.world().resource::<CategoryMixerState<TestSfx>>();
+        assert!((mixer.gain(TestSfx::UI) - 1.0).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,331 @@
+//! Footstep sounds.
+//!
+//! A [`FootstepEmitter`] plays a step from the [`SurfaceSoundMap`] pool of the
+//! surface it stands on, either when a [`FootstepStride`] message arrives (for
+//! example from an animation event) or on a fixed interval while moving. Steps
+//! closer together than the emitter's minimum interval are dropped.
+
+use bevy::prelude::*;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use crate::components::{PlaybackRandomizer, SoundPool};
+use crate::events::PlaySfx;
+use crate::request::AudioRequest;
+use crate::rng::AudioRng;
+use crate::traits::SfxCategory;
+use crate::MsgAudioSet;
+
+/// Default minimum time between two footsteps of the same emitter.
+pub const DEFAULT_FOOTSTEP_MIN_INTERVAL: Duration = Duration::from_millis(150);
+
+/// Plays footsteps for an entity.
+///
+/// Keep `surface` up to date with the surface tag under the entity, e.g. from
+/// a ground raycast, and `moving` with whether it is walking.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::FootstepEmitter;
+///
+/// // Step every 400ms while moving
+/// commands.spawn((Player, FootstepEmitter::every(Duration::from_millis(400))));
+///
+/// fn update_surface(mut emitters: Query<(&mut FootstepEmitter, &GroundHit)>) {
+///     for (mut emitter, hit) in &mut emitters {
+///         emitter.surface = Some(hit.surface.clone().into());
+///         emitter.moving = hit.velocity.length() > 0.1;
+///     }
+/// }
+/// ```
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct FootstepEmitter {
+    /// Surface tag under the emitter.
+    pub surface: Option<Cow<'static, str>>,
+    /// Whether interval steps are played.
+    pub moving: bool,
+    /// Time between steps while moving, or `None` to only step on strides.
+    pub interval: Option<Duration>,
+    /// Minimum time between two steps.
+    pub min_interval: Duration,
+    /// Time since the last step.
+    pub since_last: Duration,
+}
+
+impl Default for FootstepEmitter {
+    fn default() -> Self {
+        Self {
+            surface: None,
+            moving: false,
+            interval: None,
+            min_interval: DEFAULT_FOOTSTEP_MIN_INTERVAL,
+            since_last: Duration::MAX,
+        }
+    }
+}
+
+impl FootstepEmitter {
+    /// Creates an emitter that only steps on [`FootstepStride`] messages.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an emitter that steps on a fixed interval while moving.
+    #[must_use]
+    pub fn every(interval: Duration) -> Self {
+        Self {
+            interval: Some(interval),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the surface tag under the emitter.
+    #[must_use]
+    pub fn with_surface(mut self, surface: impl Into<Cow<'static, str>>) -> Self {
+        self.surface = Some(surface.into());
+        self
+    }
+
+    /// Sets the minimum time between two steps.
+    #[must_use]
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Advances the emitter and returns `true` if an interval step is due.
+    pub(crate) fn tick(&mut self, delta: Duration) -> bool {
+        self.since_last = self.since_last.saturating_add(delta);
+        match self.interval {
+            Some(interval) if self.moving => self.since_last >= interval.max(self.min_interval),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` and restarts the interval if a step may play now.
+    pub(crate) fn try_step(&mut self) -> bool {
+        if self.since_last < self.min_interval {
+            return false;
+        }
+        self.since_last = Duration::ZERO;
+        true
+    }
+}
+
+/// Message requesting a footstep from an emitter, e.g. on a stride animation event.
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FootstepStride {
+    /// The entity carrying the [`FootstepEmitter`].
+    pub emitter: Entity,
+}
+
+/// Resource mapping surface tags to footstep sounds for category `S`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{SoundPool, SurfaceSoundMap};
+///
+/// app.insert_resource(
+///     SurfaceSoundMap::new(GameSfx::Gameplay)
+///         .with_surface("grass", SoundPool::new([grass_1, grass_2, grass_3]))
+///         .with_surface("stone", SoundPool::new([stone_1, stone_2]))
+///         .with_fallback(SoundPool::new([generic_step])),
+/// );
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct SurfaceSoundMap<S: SfxCategory> {
+    /// The sound effect category for volume control.
+    pub category: S,
+    /// Sound pools keyed by surface tag.
+    pub surfaces: Vec<(Cow<'static, str>, SoundPool)>,
+    /// Pool used for unknown or missing surfaces.
+    pub fallback: Option<SoundPool>,
+    /// Variation applied to every step.
+    pub randomizer: PlaybackRandomizer,
+}
+
+impl<S: SfxCategory> SurfaceSoundMap<S> {
+    /// Creates an empty mapping with slight volume and pitch variation.
+    #[must_use]
+    pub fn new(category: S) -> Self {
+        Self {
+            category,
+            surfaces: Vec::new(),
+            fallback: None,
+            randomizer: PlaybackRandomizer::new()
+                .with_volume(0.8, 1.0)
+                .with_speed(0.9, 1.1),
+        }
+    }
+
+    /// Adds or replaces the pool for a surface.
+    #[must_use]
+    pub fn with_surface(mut self, surface: impl Into<Cow<'static, str>>, pool: SoundPool) -> Self {
+        let surface = surface.into();
+        self.surfaces.retain(|(existing, _)| *existing != surface);
+        self.surfaces.push((surface, pool));
+        self
+    }
+
+    /// Sets the pool used for unknown or missing surfaces.
+    #[must_use]
+    pub fn with_fallback(mut self, pool: SoundPool) -> Self {
+        self.fallback = Some(pool);
+        self
+    }
+
+    /// Sets the variation applied to every step.
+    #[must_use]
+    pub fn with_randomizer(mut self, randomizer: PlaybackRandomizer) -> Self {
+        self.randomizer = randomizer;
+        self
+    }
+
+    /// Returns the pool for a surface, or the fallback pool.
+    #[must_use]
+    pub fn pool(&self, surface: Option<&str>) -> Option<&SoundPool> {
+        surface
+            .and_then(|surface| {
+                self.surfaces
+                    .iter()
+                    .find(|(existing, _)| existing == surface)
+                    .map(|(_, pool)| pool)
+            })
+            .or(self.fallback.as_ref())
+    }
+}
+
+/// Optional plugin that plays footsteps of category `S`.
+///
+/// The [`SurfaceSoundMap<S>`] resource must be inserted by the app.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::FootstepAudioPlugin;
+///
+/// app.add_plugins(FootstepAudioPlugin::<GameSfx>::default());
+/// ```
+pub struct FootstepAudioPlugin<S: SfxCategory> {
+    _phantom: PhantomData<S>,
+}
+
+impl<S: SfxCategory> Default for FootstepAudioPlugin<S> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<S: SfxCategory> Plugin for FootstepAudioPlugin<S> {
+    fn build(&self, app: &mut App) {
+        app.register_type::<FootstepEmitter>();
+        app.add_message::<FootstepStride>();
+        app.add_systems(
+            Update,
+            play_footsteps::<S>
+                .run_if(resource_exists::<SurfaceSoundMap<S>>)
+                .before(MsgAudioSet::EventHandling),
+        );
+    }
+}
+
+/// Plays footsteps for strides and interval steps of every [`FootstepEmitter`].
+pub fn play_footsteps<S: SfxCategory>(
+    time: Res<Time>,
+    map: Res<SurfaceSoundMap<S>>,
+    mut strides: MessageReader<FootstepStride>,
+    mut emitters: Query<&mut FootstepEmitter>,
+    mut messages: MessageWriter<PlaySfx<S>>,
+    mut rng: ResMut<AudioRng>,
+) {
+    let mut step = |emitter: &mut FootstepEmitter| {
+        if !emitter.try_step() {
+            return;
+        }
+        let Some(handle) = map
+            .pool(emitter.surface.as_deref())
+            .and_then(|pool| pool.pick_with(&mut *rng))
+        else {
+            return;
+        };
+        messages.write(
+            PlaySfx::new(handle.clone(), map.category).with_randomizer(map.randomizer.clone()),
+        );
+    };
+
+    for mut emitter in &mut emitters {
+        if emitter.tick(time.delta()) {
+            step(&mut emitter);
+        }
+    }
+    for stride in strides.read() {
+        if let Ok(mut emitter) = emitters.get_mut(stride.emitter) {
+            step(&mut emitter);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestPlugin, TestSfx};
+
+    #[test]
+    fn interval_steps_only_while_moving() {
+        let mut emitter = FootstepEmitter::every(Duration::from_millis(400));
+        emitter.since_last = Duration::ZERO;
+        assert!(!emitter.tick(Duration::from_millis(500)));
+
+        emitter.moving = true;
+        assert!(emitter.tick(Duration::ZERO));
+        assert!(emitter.try_step());
+        assert!(!emitter.tick(Duration::from_millis(300)));
+        assert!(emitter.tick(Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn steps_are_rate_limited() {
+        let mut emitter = FootstepEmitter::new().with_min_interval(Duration::from_millis(200));
+        assert!(emitter.try_step());
+        emitter.tick(Duration::from_millis(100));
+        assert!(!emitter.try_step());
+        emitter.tick(Duration::from_millis(100));
+        assert!(emitter.try_step());
+    }
+
+    #[test]
+    fn footstep_stride_plays_surface_sound() {
+        let mut app = test_app((
+            TestPlugin::default(),
+            FootstepAudioPlugin::<TestSfx>::default(),
+        ));
+        app.insert_resource(
+            SurfaceSoundMap::new(TestSfx::UI)
+                .with_surface("grass", SoundPool::new([Handle::default()])),
+        );
+
+        let player = app
+            .world_mut()
+            .spawn(FootstepEmitter::new().with_surface("grass"))
+            .id();
+        let ghost = app
+            .world_mut()
+            .spawn(FootstepEmitter::new().with_surface("void"))
+            .id();
+        app.world_mut()
+            .write_message(FootstepStride { emitter: player });
+        app.world_mut()
+            .write_message(FootstepStride { emitter: ghost });
+        app.update();
+
+        let mut sfx = app.world_mut().query::<&TestSfx>();
+        assert_eq!(sfx.iter(app.world()).count(), 1);
+    }
+}
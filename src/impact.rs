@@ -0,0 +1,282 @@
+//! Collision and impact sounds.
+//!
+//! Physics engines report collisions through their own message types. Implement
+//! [`ImpactMessage`] for that type and add [`ImpactAudioPlugin`] to turn every
+//! collision into a [`PlaySfx`] whose volume and pitch follow the impact speed,
+//! picked from the [`SoundPool`] of the surface material.
+
+use bevy::{audio::Volume, prelude::*};
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use crate::components::SoundPool;
+use crate::events::PlaySfx;
+use crate::request::AudioRequest;
+use crate::rng::AudioRng;
+use crate::traits::SfxCategory;
+use crate::MsgAudioSet;
+
+/// A collision message that can produce an impact sound.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::ImpactMessage;
+///
+/// impl ImpactMessage for CollisionStarted {
+///     fn material(&self) -> Option<&str> {
+///         Some(&self.surface)
+///     }
+///
+///     fn speed(&self) -> f32 {
+///         self.relative_velocity.length()
+///     }
+/// }
+/// ```
+pub trait ImpactMessage: Message {
+    /// Returns the surface material that was hit, or `None` to stay silent.
+    fn material(&self) -> Option<&str>;
+
+    /// Returns the relative speed of the colliding bodies.
+    fn speed(&self) -> f32;
+}
+
+/// Resource mapping surface materials to impact sounds for category `S`.
+///
+/// Impacts slower than `min_speed` are ignored. Between `min_speed` and
+/// `max_speed`, volume and pitch are interpolated across their ranges.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{ImpactSounds, SoundPool};
+///
+/// app.insert_resource(
+///     ImpactSounds::new(GameSfx::Gameplay)
+///         .with_material("metal", SoundPool::new([assets.load("sfx/metal_1.ogg"), assets.load("sfx/metal_2.ogg")]))
+///         .with_material("wood", SoundPool::new([assets.load("sfx/wood.ogg")]))
+///         .with_speed_range(0.5, 12.0),
+/// );
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct ImpactSounds<S: SfxCategory> {
+    /// The sound effect category for volume control.
+    pub category: S,
+    /// Sound pools keyed by material.
+    pub materials: Vec<(Cow<'static, str>, SoundPool)>,
+    /// Pool used for materials without their own pool.
+    pub fallback: Option<SoundPool>,
+    /// Slowest impact that makes a sound.
+    pub min_speed: f32,
+    /// Impact speed at which volume and pitch reach their maximum.
+    pub max_speed: f32,
+    /// Linear volume at the slowest and the fastest impacts.
+    pub volume: (f32, f32),
+    /// Playback speed at the slowest and the fastest impacts.
+    pub pitch: (f32, f32),
+}
+
+impl<S: SfxCategory> ImpactSounds<S> {
+    /// Creates an empty mapping.
+    #[must_use]
+    pub fn new(category: S) -> Self {
+        Self {
+            category,
+            materials: Vec::new(),
+            fallback: None,
+            min_speed: 1.0,
+            max_speed: 10.0,
+            volume: (0.2, 1.0),
+            pitch: (1.1, 0.9),
+        }
+    }
+
+    /// Adds or replaces the pool for a material.
+    #[must_use]
+    pub fn with_material(
+        mut self,
+        material: impl Into<Cow<'static, str>>,
+        pool: SoundPool,
+    ) -> Self {
+        let material = material.into();
+        self.materials.retain(|(existing, _)| *existing != material);
+        self.materials.push((material, pool));
+        self
+    }
+
+    /// Sets the pool used for materials without their own pool.
+    #[must_use]
+    pub fn with_fallback(mut self, pool: SoundPool) -> Self {
+        self.fallback = Some(pool);
+        self
+    }
+
+    /// Sets the impact speeds that map to the quietest and loudest sounds.
+    #[must_use]
+    pub fn with_speed_range(mut self, min: f32, max: f32) -> Self {
+        self.min_speed = min;
+        self.max_speed = max;
+        self
+    }
+
+    /// Sets the volume of the slowest and fastest impacts.
+    #[must_use]
+    pub fn with_volume_range(mut self, min: f32, max: f32) -> Self {
+        self.volume = (min, max);
+        self
+    }
+
+    /// Sets the playback speed of the slowest and fastest impacts.
+    #[must_use]
+    pub fn with_pitch_range(mut self, slow: f32, fast: f32) -> Self {
+        self.pitch = (slow, fast);
+        self
+    }
+
+    /// Returns the pool for a material.
+    #[must_use]
+    pub fn pool(&self, material: &str) -> Option<&SoundPool> {
+        self.materials
+            .iter()
+            .find(|(existing, _)| existing == material)
+            .map(|(_, pool)| pool)
+            .or(self.fallback.as_ref())
+    }
+
+    /// Returns the impact intensity in `[0.0, 1.0]`, or `None` if the
+    /// impact is too slow to be heard.
+    #[must_use]
+    pub fn intensity(&self, speed: f32) -> Option<f32> {
+        if speed < self.min_speed {
+            return None;
+        }
+        let range = self.max_speed - self.min_speed;
+        if range <= 0.0 {
+            return Some(1.0);
+        }
+        Some(((speed - self.min_speed) / range).min(1.0))
+    }
+
+    /// Returns the playback settings for an impact of the given intensity.
+    #[must_use]
+    pub fn playback(&self, intensity: f32) -> PlaybackSettings {
+        let volume = self.volume.0 + (self.volume.1 - self.volume.0) * intensity;
+        let speed = self.pitch.0 + (self.pitch.1 - self.pitch.0) * intensity;
+        PlaybackSettings::DESPAWN
+            .with_volume(Volume::Linear(volume))
+            .with_speed(speed)
+    }
+}
+
+/// Optional plugin that plays [`ImpactSounds`] for every message of type `E`.
+///
+/// The [`ImpactSounds<S>`] resource must be inserted by the app.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::ImpactAudioPlugin;
+///
+/// app.add_plugins(ImpactAudioPlugin::<CollisionStarted, GameSfx>::default());
+/// ```
+pub struct ImpactAudioPlugin<E: ImpactMessage, S: SfxCategory> {
+    _phantom: PhantomData<(E, S)>,
+}
+
+impl<E: ImpactMessage, S: SfxCategory> Default for ImpactAudioPlugin<E, S> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<E: ImpactMessage, S: SfxCategory> Plugin for ImpactAudioPlugin<E, S> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            play_impact_sounds::<E, S>
+                .run_if(resource_exists::<ImpactSounds<S>>)
+                .before(MsgAudioSet::EventHandling),
+        );
+    }
+}
+
+/// Plays an impact sound for each [`ImpactMessage`] that is fast enough.
+pub fn play_impact_sounds<E: ImpactMessage, S: SfxCategory>(
+    sounds: Res<ImpactSounds<S>>,
+    mut impacts: MessageReader<E>,
+    mut messages: MessageWriter<PlaySfx<S>>,
+    mut rng: ResMut<AudioRng>,
+) {
+    for impact in impacts.read() {
+        let Some(material) = impact.material() else {
+            continue;
+        };
+        let Some(intensity) = sounds.intensity(impact.speed()) else {
+            continue;
+        };
+        let Some(handle) = sounds
+            .pool(material)
+            .and_then(|pool| pool.pick_with(&mut *rng))
+        else {
+            continue;
+        };
+        messages.write(
+            PlaySfx::new(handle.clone(), sounds.category).with_playback(sounds.playback(intensity)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::AudioCategory;
+
+    #[derive(Resource, Clone, Default)]
+    struct TestConfig;
+
+    #[derive(Component, Clone, Copy, Default, Debug, PartialEq)]
+    enum TestSfx {
+        #[default]
+        Gameplay,
+    }
+
+    impl AudioCategory for TestSfx {
+        type Config = TestConfig;
+        fn volume_multiplier(&self, _: &Self::Config) -> f32 {
+            1.0
+        }
+    }
+
+    impl SfxCategory for TestSfx {}
+
+    #[test]
+    fn intensity_maps_speed_range() {
+        let sounds = ImpactSounds::new(TestSfx::Gameplay).with_speed_range(2.0, 6.0);
+        assert_eq!(sounds.intensity(1.0), None);
+        assert_eq!(sounds.intensity(4.0), Some(0.5));
+        assert_eq!(sounds.intensity(100.0), Some(1.0));
+    }
+
+    #[test]
+    fn playback_scales_volume_and_pitch() {
+        let sounds = ImpactSounds::new(TestSfx::Gameplay)
+            .with_volume_range(0.0, 1.0)
+            .with_pitch_range(1.2, 0.8);
+        let playback = sounds.playback(0.5);
+        assert!((crate::systems::extract_linear_volume(playback.volume) - 0.5).abs() < 1e-5);
+        assert!((playback.speed - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn pool_falls_back_for_unknown_materials() {
+        let sounds = ImpactSounds::new(TestSfx::Gameplay)
+            .with_material("metal", SoundPool::new([Handle::default()]));
+        assert!(sounds.pool("metal").is_some());
+        assert!(sounds.pool("wood").is_none());
+
+        let sounds = sounds.with_fallback(SoundPool::default());
+        assert!(sounds.pool("wood").is_some());
+    }
+}
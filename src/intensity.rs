@@ -0,0 +1,422 @@
+//! Adaptive music driven by an intensity parameter.
+//!
+//! Game code keeps the [`MusicIntensity`] resource up to date, e.g. from the
+//! number of enemies nearby, and an [`IntensityMap`] declares which track
+//! plays and how loud each layer of a layered track is for every range of
+//! intensity. Crossing into another range crossfades the music; hysteresis
+//! keeps it from flapping while the intensity hovers around a threshold.
+//!
+//! Bands without a track keep the track of the nearest band below them, or
+//! the map's base music below the first track. Falling out of a track band
+//! stops its track and restarts the one underneath.
+
+use bevy::prelude::*;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use crate::components::FadeIn;
+use crate::events::{PlayLayeredMusic, PlayMusic, SetLayerVolume, StopMusic};
+use crate::layers::LayeredMusic;
+use crate::request::AudioRequest;
+use crate::traits::MusicCategory;
+use crate::MsgAudioSet;
+
+/// Default crossfade between intensity bands.
+pub const DEFAULT_INTENSITY_CROSSFADE: Duration = Duration::from_secs(2);
+
+/// Default distance the intensity must move past a threshold before the band
+/// changes.
+pub const DEFAULT_INTENSITY_HYSTERESIS: f32 = 0.05;
+
+/// Resource holding the current music intensity, from `0.0` (calm) to `1.0`
+/// (full action).
+#[derive(Resource, Reflect, Debug, Clone, Copy, Default, PartialEq)]
+#[reflect(Resource)]
+pub struct MusicIntensity(f32);
+
+impl MusicIntensity {
+    /// Creates an intensity, clamped to `0.0..=1.0`.
+    #[must_use]
+    pub fn new(intensity: f32) -> Self {
+        Self(intensity.clamp(0.0, 1.0))
+    }
+
+    /// Returns the current intensity.
+    #[must_use]
+    pub fn get(&self) -> f32 {
+        self.0
+    }
+
+    /// Sets the intensity, clamped to `0.0..=1.0`.
+    pub fn set(&mut self, intensity: f32) {
+        self.0 = intensity.clamp(0.0, 1.0);
+    }
+}
+
+/// Music played from an intensity threshold up to the next band.
+#[derive(Debug, Clone)]
+pub struct IntensityBand {
+    /// Lowest intensity of the band.
+    pub from: f32,
+    /// Track started when the band becomes active, or `None` to keep the
+    /// track of the band below.
+    pub track: Option<Handle<AudioSource>>,
+    /// Linear volume of each layer while the band is active.
+    pub layers: Vec<(Cow<'static, str>, f32)>,
+}
+
+/// Resource mapping [`MusicIntensity`] ranges to music of category `M`.
+///
+/// Bands start at their `from` intensity and end where the next one starts.
+/// When the band changes, its track replaces the music of the category and
+/// its layers are faded to their volumes over the crossfade. Layers used by
+/// other bands but not listed in the active one are faded out.
+///
+/// Bands without a track play the track of the nearest band below. Below the
+/// first track band, the music started by the game plays; set it as the base
+/// with [`with_base`](Self::with_base) so it is restarted when the intensity
+/// falls back from a track band.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::IntensityMap;
+///
+/// app.insert_resource(
+///     IntensityMap::new(GameMusic::Gameplay)
+///         .with_base(forest_layers)
+///         .with_layers(0.0, [("pads", 1.0)])
+///         .with_layers(0.4, [("pads", 1.0), ("drums", 1.0)])
+///         .with_track(0.8, assets.load("music/boss.ogg")),
+/// );
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct IntensityMap<M: MusicCategory> {
+    /// The music category the bands play in.
+    pub category: M,
+    /// Bands sorted by their lowest intensity.
+    pub bands: Vec<IntensityBand>,
+    /// Distance the intensity must move past a threshold before the band
+    /// changes.
+    pub hysteresis: f32,
+    /// Duration of the crossfade between bands.
+    pub crossfade: Duration,
+    /// Layered track restarted when the intensity falls from a track band
+    /// back below the first one.
+    pub base: Option<LayeredMusic<M>>,
+    active: Option<usize>,
+}
+
+impl<M: MusicCategory> IntensityMap<M> {
+    /// Creates a map without bands.
+    #[must_use]
+    pub fn new(category: M) -> Self {
+        Self {
+            category,
+            bands: Vec::new(),
+            hysteresis: DEFAULT_INTENSITY_HYSTERESIS,
+            crossfade: DEFAULT_INTENSITY_CROSSFADE,
+            base: None,
+            active: None,
+        }
+    }
+
+    /// Adds a band playing `track` from intensity `from`.
+    #[must_use]
+    pub fn with_track(self, from: f32, track: Handle<AudioSource>) -> Self {
+        self.with_band(IntensityBand {
+            from,
+            track: Some(track),
+            layers: Vec::new(),
+        })
+    }
+
+    /// Adds a band setting layer volumes from intensity `from`.
+    #[must_use]
+    pub fn with_layers<L: Into<Cow<'static, str>>>(
+        self,
+        from: f32,
+        layers: impl IntoIterator<Item = (L, f32)>,
+    ) -> Self {
+        self.with_band(IntensityBand {
+            from,
+            track: None,
+            layers: layers
+                .into_iter()
+                .map(|(layer, volume)| (layer.into(), volume))
+                .collect(),
+        })
+    }
+
+    /// Adds a band, replacing one starting at the same intensity.
+    #[must_use]
+    pub fn with_band(mut self, band: IntensityBand) -> Self {
+        self.bands.retain(|existing| existing.from != band.from);
+        self.bands.push(band);
+        self.bands.sort_by(|a, b| a.from.total_cmp(&b.from));
+        self
+    }
+
+    /// Sets the distance the intensity must move past a threshold before the
+    /// band changes.
+    #[must_use]
+    pub fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Sets the duration of the crossfade between bands.
+    #[must_use]
+    pub fn with_crossfade(mut self, crossfade: Duration) -> Self {
+        self.crossfade = crossfade;
+        self
+    }
+
+    /// Sets the layered track playing below the first track band.
+    ///
+    /// The map does not start it; it is only restarted when the intensity
+    /// falls back from a track band, with its layers at the volumes of the
+    /// new band.
+    #[must_use]
+    pub fn with_base(mut self, track: LayeredMusic<M>) -> Self {
+        self.base = Some(track);
+        self
+    }
+
+    /// Returns the active band.
+    #[must_use]
+    pub fn active(&self) -> Option<&IntensityBand> {
+        self.active.and_then(|index| self.bands.get(index))
+    }
+
+    /// Returns the band for `intensity`, staying in the active band until the
+    /// intensity leaves it by more than the hysteresis.
+    #[must_use]
+    pub fn band_for(&self, intensity: f32) -> Option<usize> {
+        let below = |offset: f32| {
+            self.bands
+                .iter()
+                .rposition(|band| band.from + offset <= intensity)
+        };
+        let Some(active) = self.active.filter(|&index| index < self.bands.len()) else {
+            return below(0.0);
+        };
+        let lower = self.bands[active].from - self.hysteresis;
+        let upper = self
+            .bands
+            .get(active + 1)
+            .map_or(f32::INFINITY, |next| next.from + self.hysteresis);
+        if intensity >= lower && intensity < upper {
+            Some(active)
+        } else if intensity >= upper {
+            below(self.hysteresis)
+        } else {
+            below(0.0)
+        }
+    }
+
+    /// Returns the track playing in a band, inherited from the nearest band
+    /// below if the band has none.
+    fn track_of(&self, band: usize) -> Option<&Handle<AudioSource>> {
+        self.bands[..=band]
+            .iter()
+            .rev()
+            .find_map(|band| band.track.as_ref())
+    }
+
+    /// Returns the volume of `layer` in a band, `0.0` if it is not listed.
+    fn layer_volume(&self, band: usize, layer: &str) -> f32 {
+        self.bands[band]
+            .layers
+            .iter()
+            .find(|(name, _)| name == layer)
+            .map_or(0.0, |(_, volume)| *volume)
+    }
+}
+
+/// Optional plugin driving music of category type `M` from
+/// [`MusicIntensity`].
+///
+/// Initializes the [`MusicIntensity`] resource. The [`IntensityMap<M>`]
+/// resource must be inserted by the app.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{MusicIntensity, MusicIntensityPlugin};
+///
+/// app.add_plugins(MusicIntensityPlugin::<GameMusic>::default());
+///
+/// fn track_combat(enemies: Query<(), With<Enemy>>, mut intensity: ResMut<MusicIntensity>) {
+///     intensity.set(enemies.iter().count() as f32 / 10.0);
+/// }
+/// ```
+pub struct MusicIntensityPlugin<M: MusicCategory> {
+    _phantom: PhantomData<M>,
+}
+
+impl<M: MusicCategory> Default for MusicIntensityPlugin<M> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: MusicCategory> Plugin for MusicIntensityPlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.register_type::<MusicIntensity>();
+        app.init_resource::<MusicIntensity>();
+        app.add_systems(
+            Update,
+            apply_music_intensity::<M>
+                .run_if(resource_exists::<IntensityMap<M>>)
+                .before(MsgAudioSet::EventHandling),
+        );
+    }
+}
+
+/// Switches the music to the band of the current [`MusicIntensity`].
+pub fn apply_music_intensity<M: MusicCategory>(
+    intensity: Res<MusicIntensity>,
+    mut map: ResMut<IntensityMap<M>>,
+    mut plays: MessageWriter<PlayMusic<M>>,
+    mut stops: MessageWriter<StopMusic<M>>,
+    mut layers: MessageWriter<SetLayerVolume<M>>,
+    mut layered: MessageWriter<PlayLayeredMusic<M>>,
+) {
+    let Some(band) = map.band_for(intensity.get()) else {
+        return;
+    };
+    let previous = map.active.replace(band);
+    if previous == Some(band) {
+        return;
+    }
+
+    let track = map.track_of(band);
+    let current_track = previous.and_then(|index| map.track_of(index));
+    if track != current_track {
+        if let Some(track) = track {
+            stops.write(StopMusic::new(map.category).with_fade(map.crossfade));
+            plays.write(
+                PlayMusic::new(track.clone(), map.category)
+                    .with_fade_in(FadeIn::new(map.crossfade)),
+            );
+        } else if current_track.is_some() {
+            stops.write(StopMusic::new(map.category).with_fade(map.crossfade));
+            if let Some(base) = &map.base {
+                let mut base = base.clone().with_fade_in(FadeIn::new(map.crossfade));
+                for stem in &mut base.stems {
+                    if map
+                        .bands
+                        .iter()
+                        .any(|band| band.layers.iter().any(|(name, _)| *name == stem.layer.name))
+                    {
+                        stem.volume = map.layer_volume(band, &stem.layer.name);
+                    }
+                }
+                layered.write(PlayLayeredMusic::new(base));
+            }
+        }
+    }
+
+    let mut names: Vec<&Cow<'static, str>> = Vec::new();
+    for (name, _) in map.bands.iter().flat_map(|band| &band.layers) {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    for name in names {
+        let volume = map.layer_volume(band, name);
+        if previous.is_some_and(|previous| map.layer_volume(previous, name) == volume) {
+            continue;
+        }
+        layers.write(
+            SetLayerVolume::new(map.category, name.clone(), volume).with_duration(map.crossfade),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin};
+
+    use crate::{MusicLayer, TrackGain};
+
+    #[test]
+    fn bands_change_past_the_hysteresis() {
+        let mut map = IntensityMap::new(TestMusic::Main)
+            .with_layers(0.5, [("drums", 1.0)])
+            .with_layers(0.0, [("pads", 1.0)])
+            .with_hysteresis(0.1);
+        assert_eq!(map.bands[0].from, 0.0);
+        assert_eq!(map.band_for(0.55), Some(1));
+
+        map.active = Some(0);
+        assert_eq!(map.band_for(0.55), Some(0));
+        assert_eq!(map.band_for(0.6), Some(1));
+
+        map.active = Some(1);
+        assert_eq!(map.band_for(0.45), Some(1));
+        assert_eq!(map.band_for(0.35), Some(0));
+    }
+
+    #[test]
+    fn music_intensity_drives_layers_and_tracks() {
+        let boss = bevy::asset::uuid_handle!("3c8e5f10-6a2d-4f7b-8e91-0b5c7d9e1f21");
+        let mut app = test_app((
+            TestPlugin::default(),
+            MusicIntensityPlugin::<TestMusic>::default(),
+        ));
+        let track = LayeredMusic::new(TestMusic::Main)
+            .with_layer("pads", Handle::default(), 1.0)
+            .with_layer("drums", Handle::default(), 0.0);
+        app.insert_resource(
+            IntensityMap::new(TestMusic::Main)
+                .with_base(track.clone())
+                .with_layers(0.0, [("pads", 1.0)])
+                .with_layers(0.5, [("pads", 1.0), ("drums", 0.8)])
+                .with_track(0.9, boss.clone())
+                .with_crossfade(Duration::ZERO),
+        );
+
+        app.world_mut().write_message(PlayLayeredMusic::new(track));
+        app.update();
+
+        app.world_mut().resource_mut::<MusicIntensity>().set(0.6);
+        app.update();
+        let mut layers = app.world_mut().query::<(&MusicLayer, &TrackGain)>();
+        for (layer, gain) in layers.iter(app.world()) {
+            let expected = if layer.name == "drums" { 0.8 } else { 1.0 };
+            assert!((gain.0 - expected).abs() < f32::EPSILON);
+        }
+
+        app.world_mut().resource_mut::<MusicIntensity>().set(1.0);
+        app.update();
+        app.update();
+        let mut players = app
+            .world_mut()
+            .query_filtered::<&AudioPlayer, With<TestMusic>>();
+        let playing: Vec<_> = players.iter(app.world()).map(|player| &player.0).collect();
+        assert_eq!(playing, vec![&boss]);
+        let mut stems = app.world_mut().query::<(&MusicLayer, &TrackGain)>();
+        assert_eq!(stems.iter(app.world()).count(), 0);
+
+        app.world_mut().resource_mut::<MusicIntensity>().set(0.6);
+        app.update();
+        app.update();
+        let playing: Vec<_> = players.iter(app.world()).map(|player| &player.0).collect();
+        assert!(!playing.contains(&&boss));
+        let mut stems: Vec<_> = stems
+            .iter(app.world())
+            .map(|(layer, gain)| (layer.name.to_string(), gain.0))
+            .collect();
+        stems.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            stems,
+            [("drums".to_string(), 0.8), ("pads".to_string(), 1.0)]
+        );
+    }
+}
@@ -0,0 +1,626 @@
+//! `bevy_kira_audio` backend.
+//!
+//! [`KiraBackendPlugin`] plays the crate's sounds through `bevy_kira_audio`
+//! instead of `bevy_audio`. Sounds are still spawned as [`AudioPlayer`]s of
+//! [`AudioSource`]s or [`Tone`]s, which are decoded into kira sounds once
+//! loaded and started on kira's main track. Each playing sound then gets a
+//! [`KiraSink`], or a [`KiraSpatialSink`] for spatial playback, driven by the
+//! crate's systems through [`KiraAudioBackend`] and synced to its kira
+//! [`AudioInstance`] at the end of every frame. Enable the `kira` feature to
+//! use it.
+//!
+//! Spatial sounds are panned by their direction from the [`SpatialListener`],
+//! or by their offset on the 2D plane for [`Spatial2d`] sounds. They get no
+//! distance falloff beyond their [`SpatialSettings`](crate::SpatialSettings).
+//! The `duration` and `spatial_scale` of [`PlaybackSettings`] are ignored.
+
+use bevy::{
+    audio::{AudioLoader, Decodable, PlaybackMode, Sample, Source},
+    platform::collections::HashMap,
+    prelude::*,
+};
+use bevy_kira_audio::{
+    prelude::{Decibels, Frame, StaticSoundData, StaticSoundSettings},
+    Audio, AudioControl, AudioInstance, AudioSource as KiraAudioSource, AudioSystemSet, AudioTween,
+    PlaybackState,
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::backend::AudioBackend;
+use crate::spatial_2d::Spatial2d;
+use crate::systems::extract_linear_volume;
+use crate::tone::Tone;
+
+/// [`AudioBackend`] for `bevy_kira_audio`, added by the [`KiraBackendPlugin`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{KiraAudioBackend, KiraBackendPlugin, ToneAudioPlugin};
+///
+/// app.add_plugins((
+///     DefaultPlugins.build().disable::<bevy::audio::AudioPlugin>(),
+///     bevy_kira_audio::AudioPlugin,
+///     KiraBackendPlugin,
+///     MsgAudioPlugin::<GameMusic, GameSfx, GameAudioConfig, KiraAudioBackend>::default(),
+///     ToneAudioPlugin::<GameSfx, KiraAudioBackend>::default(),
+/// ));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KiraAudioBackend;
+
+/// [`AudioBackend`] for spatial `bevy_kira_audio` sounds, which play through
+/// a [`KiraSpatialSink`] instead of a [`KiraSink`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KiraSpatialAudioBackend;
+
+/// Sink of a sound played through [`KiraAudioBackend`].
+///
+/// Holds the state set by the crate's systems, which is sent to the kira
+/// [`AudioInstance`] at the end of the frame.
+#[derive(Component, Debug)]
+pub struct KiraSink {
+    instance: Handle<AudioInstance>,
+    volume: f32,
+    speed: f32,
+    paused: bool,
+    muted: bool,
+    stopped: bool,
+    panning: f32,
+    seek: Option<Duration>,
+    position: Duration,
+    started: bool,
+    finished: bool,
+    synced: SyncedState,
+}
+
+/// Sink of a sound played through [`KiraSpatialAudioBackend`], panned by its
+/// position relative to the [`SpatialListener`].
+#[derive(Component, Debug, Deref, DerefMut)]
+pub struct KiraSpatialSink(KiraSink);
+
+impl AsRef<KiraSink> for KiraSink {
+    fn as_ref(&self) -> &KiraSink {
+        self
+    }
+}
+
+impl AsRef<KiraSink> for KiraSpatialSink {
+    fn as_ref(&self) -> &KiraSink {
+        &self.0
+    }
+}
+
+/// State last sent to a kira instance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SyncedState {
+    volume: f32,
+    speed: f32,
+    paused: bool,
+    panning: f32,
+}
+
+impl KiraSink {
+    fn new(instance: Handle<AudioInstance>, playback: &PlaybackSettings) -> Self {
+        let mut sink = Self {
+            instance,
+            volume: extract_linear_volume(playback.volume),
+            speed: playback.speed,
+            paused: playback.paused,
+            muted: playback.muted,
+            stopped: false,
+            panning: 0.0,
+            seek: None,
+            position: playback.start_position.unwrap_or_default(),
+            started: false,
+            finished: false,
+            synced: SyncedState {
+                volume: 0.0,
+                speed: 0.0,
+                paused: false,
+                panning: 0.0,
+            },
+        };
+        sink.synced = sink.target();
+        sink
+    }
+
+    /// Returns the kira instance playing the sound.
+    #[must_use]
+    pub fn instance(&self) -> &Handle<AudioInstance> {
+        &self.instance
+    }
+
+    fn target(&self) -> SyncedState {
+        SyncedState {
+            volume: if self.muted { 0.0 } else { self.volume },
+            speed: self.speed,
+            paused: self.paused,
+            panning: self.panning,
+        }
+    }
+}
+
+impl AudioBackend for KiraAudioBackend {
+    type Sink = KiraSink;
+    type Spatial = KiraSpatialAudioBackend;
+
+    fn play<P: Asset + Decodable>(
+        entity: &mut EntityCommands,
+        handle: Handle<P>,
+        playback: PlaybackSettings,
+    ) {
+        entity.insert((AudioPlayer(handle), playback));
+    }
+
+    fn volume(sink: &KiraSink) -> f32 {
+        sink.volume
+    }
+
+    fn set_volume(sink: &mut KiraSink, volume: f32) {
+        sink.volume = volume;
+    }
+
+    fn speed(sink: &KiraSink) -> f32 {
+        sink.speed
+    }
+
+    fn set_speed(sink: &mut KiraSink, speed: f32) {
+        sink.speed = speed;
+    }
+
+    fn is_paused(sink: &KiraSink) -> bool {
+        sink.paused
+    }
+
+    fn pause(sink: &mut KiraSink) {
+        sink.paused = true;
+    }
+
+    fn resume(sink: &mut KiraSink) {
+        sink.paused = false;
+    }
+
+    fn stop(sink: &mut KiraSink) {
+        sink.stopped = true;
+    }
+
+    fn is_finished(sink: &KiraSink) -> bool {
+        sink.stopped || sink.finished
+    }
+
+    fn position(sink: &KiraSink) -> Duration {
+        sink.position
+    }
+
+    fn seek(sink: &mut KiraSink, position: Duration) {
+        sink.seek = Some(position);
+        sink.position = position;
+    }
+}
+
+impl AudioBackend for KiraSpatialAudioBackend {
+    type Sink = KiraSpatialSink;
+    type Spatial = Self;
+
+    fn play<P: Asset + Decodable>(
+        entity: &mut EntityCommands,
+        handle: Handle<P>,
+        playback: PlaybackSettings,
+    ) {
+        entity.insert((AudioPlayer(handle), playback.with_spatial(true)));
+    }
+
+    fn volume(sink: &KiraSpatialSink) -> f32 {
+        KiraAudioBackend::volume(sink)
+    }
+
+    fn set_volume(sink: &mut KiraSpatialSink, volume: f32) {
+        KiraAudioBackend::set_volume(sink, volume);
+    }
+
+    fn speed(sink: &KiraSpatialSink) -> f32 {
+        KiraAudioBackend::speed(sink)
+    }
+
+    fn set_speed(sink: &mut KiraSpatialSink, speed: f32) {
+        KiraAudioBackend::set_speed(sink, speed);
+    }
+
+    fn is_paused(sink: &KiraSpatialSink) -> bool {
+        KiraAudioBackend::is_paused(sink)
+    }
+
+    fn pause(sink: &mut KiraSpatialSink) {
+        KiraAudioBackend::pause(sink);
+    }
+
+    fn resume(sink: &mut KiraSpatialSink) {
+        KiraAudioBackend::resume(sink);
+    }
+
+    fn stop(sink: &mut KiraSpatialSink) {
+        KiraAudioBackend::stop(sink);
+    }
+
+    fn is_finished(sink: &KiraSpatialSink) -> bool {
+        KiraAudioBackend::is_finished(sink)
+    }
+
+    fn position(sink: &KiraSpatialSink) -> Duration {
+        KiraAudioBackend::position(sink)
+    }
+
+    fn seek(sink: &mut KiraSpatialSink, position: Duration) {
+        KiraAudioBackend::seek(sink, position);
+    }
+}
+
+/// Plugin playing [`AudioPlayer`]s of [`AudioSource`]s and [`Tone`]s through
+/// `bevy_kira_audio`, for the [`KiraAudioBackend`].
+///
+/// Requires kira's `AudioPlugin` in place of Bevy's, whose [`AudioSource`]
+/// assets and loader it registers instead. Pass [`KiraAudioBackend`] to the
+/// [`MsgAudioPlugin`](crate::MsgAudioPlugin) and the sub-plugins.
+pub struct KiraBackendPlugin;
+
+impl Plugin for KiraBackendPlugin {
+    fn build(&self, app: &mut App) {
+        if app.is_plugin_added::<bevy::audio::AudioPlugin>() {
+            warn!("KiraBackendPlugin is added next to Bevy's AudioPlugin, sounds will play twice");
+        }
+        if !app.world().contains_resource::<Assets<AudioSource>>() {
+            app.init_asset::<AudioSource>()
+                .init_asset_loader::<AudioLoader>();
+        }
+        app.add_observer(stop_removed_kira_sink::<KiraSink>);
+        app.add_observer(stop_removed_kira_sink::<KiraSpatialSink>);
+        app.add_systems(
+            PostUpdate,
+            (
+                (
+                    play_kira_sounds::<AudioSource>,
+                    play_kira_sounds::<Tone>.run_if(resource_exists::<Assets<Tone>>),
+                )
+                    .before(AudioSystemSet::PlayTypedChannels),
+                pan_kira_spatial_sinks
+                    .after(TransformSystems::Propagate)
+                    .before(sync_kira_sinks),
+                sync_kira_sinks.after(AudioSystemSet::PlayTypedChannels),
+                (
+                    cleanup_finished_kira_sounds::<AudioSource>,
+                    cleanup_finished_kira_sounds::<Tone>,
+                )
+                    .after(sync_kira_sinks),
+            ),
+        );
+    }
+}
+
+/// Decodes a `bevy_audio` source into a kira sound, keeping the first two
+/// channels.
+fn decode<P: Decodable>(source: &P) -> StaticSoundData {
+    let decoder = source.decoder();
+    let channels = usize::from(decoder.channels().max(1));
+    let sample_rate = decoder.sample_rate();
+    let samples: Vec<f32> = decoder.map(Sample::to_f32).collect();
+    let frames: Arc<[Frame]> = samples
+        .chunks(channels)
+        .map(|frame| match *frame {
+            [left, right, ..] => Frame::new(left, right),
+            [mono] => Frame::from_mono(mono),
+            [] => Frame::ZERO,
+        })
+        .collect();
+    StaticSoundData {
+        sample_rate,
+        frames,
+        settings: StaticSoundSettings::default(),
+        slice: None,
+    }
+}
+
+/// Converts a linear volume to kira's decibels, silent at zero.
+fn decibels(volume: f32) -> Decibels {
+    if volume <= 0.0 {
+        Decibels::SILENCE
+    } else {
+        Decibels(20.0 * volume.log10())
+    }
+}
+
+/// Starts the loaded sources of new [`AudioPlayer`]s on kira's main track
+/// and gives them a sink.
+///
+/// Sources are decoded once per asset, and again after they change.
+fn play_kira_sounds<P: Asset + Decodable>(
+    mut commands: Commands,
+    audio: Res<Audio>,
+    sources: Res<Assets<P>>,
+    mut source_events: MessageReader<AssetEvent<P>>,
+    mut kira_sources: ResMut<Assets<KiraAudioSource>>,
+    mut decoded: Local<HashMap<AssetId<P>, Handle<KiraAudioSource>>>,
+    queued: Query<
+        (Entity, &AudioPlayer<P>, &PlaybackSettings),
+        (Without<KiraSink>, Without<KiraSpatialSink>),
+    >,
+) {
+    for event in source_events.read() {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            decoded.remove(id);
+        }
+    }
+    for (entity, player, playback) in &queued {
+        let id = player.0.id();
+        let sound = decoded.get(&id).cloned().or_else(|| {
+            let handle = kira_sources.add(KiraAudioSource {
+                sound: decode(sources.get(id)?),
+            });
+            decoded.insert(id, handle.clone());
+            Some(handle)
+        });
+        let Some(sound) = sound else {
+            continue;
+        };
+
+        let mut command = audio.play(sound);
+        command
+            .with_volume(decibels(if playback.muted {
+                0.0
+            } else {
+                extract_linear_volume(playback.volume)
+            }))
+            .with_playback_rate(f64::from(playback.speed));
+        if playback.paused {
+            command.paused();
+        }
+        if matches!(playback.mode, PlaybackMode::Loop) {
+            command.looped();
+        }
+        if let Some(start) = playback.start_position {
+            command.start_from(start.as_secs_f64());
+        }
+        let sink = KiraSink::new(command.handle(), playback);
+        if playback.spatial {
+            commands.entity(entity).insert(KiraSpatialSink(sink));
+        } else {
+            commands.entity(entity).insert(sink);
+        }
+    }
+}
+
+/// Pans spatial sounds by their direction from the [`SpatialListener`].
+fn pan_kira_spatial_sinks(
+    listeners: Query<&GlobalTransform, With<SpatialListener>>,
+    mut sinks: Query<(&mut KiraSpatialSink, &GlobalTransform, Option<&Spatial2d>)>,
+) {
+    let Some(listener) = listeners.iter().next() else {
+        return;
+    };
+    for (mut sink, transform, spatial_2d) in &mut sinks {
+        let offset = transform.translation() - listener.translation();
+        let panning = match spatial_2d {
+            Some(spatial_2d) => spatial_2d.pan(offset.truncate()),
+            None => offset.normalize_or_zero().dot(*listener.right()),
+        };
+        if sink.panning != panning {
+            sink.panning = panning;
+        }
+    }
+}
+
+/// Sends the state of changed sinks to their kira instances and reads back
+/// the playback position and whether they finished.
+fn sync_kira_sinks(
+    mut instances: ResMut<Assets<AudioInstance>>,
+    mut sinks: Query<&mut KiraSink>,
+    mut spatial_sinks: Query<&mut KiraSpatialSink>,
+) {
+    for sink in &mut sinks {
+        sync_kira_sink(sink, &mut instances);
+    }
+    for sink in &mut spatial_sinks {
+        sync_kira_sink(sink.map_unchanged(|sink| &mut sink.0), &mut instances);
+    }
+}
+
+fn sync_kira_sink(mut sink: Mut<KiraSink>, instances: &mut Assets<AudioInstance>) {
+    // Bookkeeping only, systems reacting to sink changes shouldn't see it
+    let sink = sink.bypass_change_detection();
+    let Some(state) = instances.get(&sink.instance).map(AudioInstance::state) else {
+        // Instances only disappear once stopped
+        sink.finished |= sink.started;
+        return;
+    };
+    sink.started = true;
+    if let Some(position) = state.position() {
+        sink.position = Duration::from_secs_f64(position);
+    }
+    if matches!(state, PlaybackState::Stopped) {
+        sink.finished = true;
+        return;
+    }
+
+    let target = sink.target();
+    if sink.stopped || sink.seek.is_some() || target != sink.synced {
+        let Some(instance) = instances.get_mut(&sink.instance) else {
+            return;
+        };
+        if sink.stopped {
+            instance.stop(AudioTween::default());
+            return;
+        }
+        if let Some(position) = sink.seek.take() {
+            instance.seek_to(position.as_secs_f64());
+            sink.position = position;
+        }
+        if target.volume != sink.synced.volume {
+            instance.set_decibels(decibels(target.volume), AudioTween::default());
+        }
+        if target.speed != sink.synced.speed {
+            instance.set_playback_rate(f64::from(target.speed), AudioTween::default());
+        }
+        if target.panning != sink.synced.panning {
+            instance.set_panning(target.panning, AudioTween::default());
+        }
+        if target.paused != sink.synced.paused {
+            if target.paused {
+                instance.pause(AudioTween::default());
+            } else {
+                instance.resume(AudioTween::default());
+            }
+        }
+        sink.synced = target;
+    }
+}
+
+/// Despawns finished sounds or removes their player, following their
+/// [`PlaybackMode`] like `bevy_audio` does.
+fn cleanup_finished_kira_sounds<P: Asset + Decodable>(
+    mut commands: Commands,
+    sounds: Query<
+        (
+            Entity,
+            &PlaybackSettings,
+            AnyOf<(&KiraSink, &KiraSpatialSink)>,
+        ),
+        With<AudioPlayer<P>>,
+    >,
+) {
+    for (entity, playback, (sink, spatial_sink)) in &sounds {
+        let finished = sink.is_some_and(|sink| sink.finished)
+            || spatial_sink.is_some_and(|sink| sink.finished);
+        if !finished {
+            continue;
+        }
+        match playback.mode {
+            PlaybackMode::Despawn => {
+                commands.entity(entity).despawn();
+            }
+            PlaybackMode::Remove => {
+                commands.entity(entity).remove::<(
+                    AudioPlayer<P>,
+                    KiraSink,
+                    KiraSpatialSink,
+                    PlaybackSettings,
+                )>();
+            }
+            PlaybackMode::Loop | PlaybackMode::Once => {}
+        }
+    }
+}
+
+/// Stops the kira instance of a sink that is removed or despawned, which
+/// would otherwise play on.
+fn stop_removed_kira_sink<T: Component + AsRef<KiraSink>>(
+    remove: On<Remove, T>,
+    sinks: Query<&T>,
+    mut instances: ResMut<Assets<AudioInstance>>,
+) {
+    let Ok(sink) = sinks.get(remove.entity).map(AsRef::as_ref) else {
+        return;
+    };
+    if !sink.finished {
+        if let Some(instance) = instances.get_mut(&sink.instance) {
+            instance.stop(AudioTween::default());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestConfig, TestMusic, TestSfx};
+    use crate::{
+        AudioConfigTrait, AudioRequest, AudioStarted, MsgAudioPlugin, PlayTone, ToneAudioPlugin,
+    };
+
+    type KiraTestPlugin = MsgAudioPlugin<TestMusic, TestSfx, TestConfig, KiraAudioBackend>;
+
+    fn kira_app() -> App {
+        let mut app = test_app((
+            AssetPlugin::default(),
+            bevy_kira_audio::AudioPlugin,
+            KiraBackendPlugin,
+            KiraTestPlugin::default(),
+            ToneAudioPlugin::<TestSfx, KiraAudioBackend>::default(),
+        ));
+        app.finish();
+        app
+    }
+
+    #[test]
+    fn tones_decode_to_kira_frames() {
+        let sound = decode(&Tone::sine(440.0, Duration::from_millis(100)));
+        assert_eq!(sound.sample_rate, crate::TONE_SAMPLE_RATE);
+        assert_eq!(sound.frames.len(), 4_410);
+    }
+
+    #[test]
+    fn decibels_are_silent_at_zero_volume() {
+        assert_eq!(decibels(0.0), Decibels::SILENCE);
+        assert_eq!(decibels(1.0), Decibels::IDENTITY);
+        assert!((decibels(0.5).0 + 6.0206).abs() < 1e-3);
+    }
+
+    #[test]
+    fn play_tone_gets_a_kira_sink_at_category_volume() {
+        let mut app = kira_app();
+        app.insert_resource(TestConfig {
+            sfx: 0.5,
+            ..TestConfig::FULL
+        });
+
+        app.world_mut().write_message(
+            PlayTone::new(Tone::sine(440.0, Duration::from_millis(100)), TestSfx::UI).volume(0.8),
+        );
+        app.update();
+        app.update();
+
+        let config = app.world().resource::<TestConfig>();
+        let expected = config.effective_volume() * config.to_linear_gain(0.5) * 0.8;
+        let (entity, sink) = app
+            .world_mut()
+            .query::<(Entity, &KiraSink)>()
+            .single(app.world())
+            .unwrap();
+        assert!((KiraAudioBackend::volume(sink) - expected).abs() < 1e-5);
+        assert!(!KiraAudioBackend::is_paused(sink));
+
+        let started = app.world().resource::<Messages<AudioStarted<Tone>>>();
+        let started: Vec<_> = started.get_cursor().read(started).cloned().collect();
+        assert_eq!(started.len(), 1);
+        assert_eq!(started[0].entity, entity);
+    }
+
+    #[test]
+    fn spatial_sounds_get_a_spatial_sink() {
+        let mut app = kira_app();
+        let tone = app
+            .world_mut()
+            .resource_mut::<Assets<Tone>>()
+            .add(Tone::sine(440.0, Duration::from_millis(100)));
+        let sound = app
+            .world_mut()
+            .spawn((
+                AudioPlayer(tone),
+                PlaybackSettings::ONCE.with_spatial(true),
+                Transform::default(),
+            ))
+            .id();
+        app.update();
+
+        assert!(app.world().get::<KiraSpatialSink>(sound).is_some());
+        assert!(app.world().get::<KiraSink>(sound).is_none());
+    }
+
+    #[test]
+    fn seeking_moves_the_sink_position() {
+        let mut sink = KiraSink::new(Handle::default(), &PlaybackSettings::ONCE);
+        KiraAudioBackend::seek(&mut sink, Duration::from_secs(2));
+        assert_eq!(KiraAudioBackend::position(&sink), Duration::from_secs(2));
+        KiraAudioBackend::stop(&mut sink);
+        assert!(KiraAudioBackend::is_finished(&sink));
+    }
+}
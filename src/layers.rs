@@ -0,0 +1,375 @@
+//! Layered (vertical) music.
+//!
+//! A layered track is made of several stems that play in sync, such as pads,
+//! percussion, and melody. Individual layers are faded in and out with
+//! [`SetLayerVolume`](crate::SetLayerVolume) to follow the action, for example
+//! adding percussion as tension rises.
+//!
+//! Stems wait until the sources of all stems have loaded and then start in
+//! the same frame. A [`StemSync`] on the track seeks stems that start late to
+//! the position of a master stem, and keeps correcting them if they drift
+//! apart.
+
+use bevy::prelude::*;
+use std::borrow::Cow;
+use std::time::Duration;
+
+use crate::components::{FadeIn, TrackGain, WaitForLoad};
+use crate::traits::MusicCategory;
+
+/// Marks the parent entity of a layered music track.
+///
+/// The stems are spawned as children. The parent is despawned automatically
+/// once all of its stems are gone.
+#[derive(Component, Reflect, Debug, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct LayeredTrack;
+
+/// Names a stem of a layered music track.
+#[derive(Component, Reflect, Debug, Clone, PartialEq, Eq, Hash)]
+#[reflect(Component)]
+pub struct MusicLayer {
+    /// Name used to address the layer.
+    pub name: Cow<'static, str>,
+}
+
+impl MusicLayer {
+    /// Creates a new layer name.
+    #[must_use]
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+/// Default drift between a stem and the master stem that is tolerated
+/// before the stem is seeked.
+pub const DEFAULT_STEM_SYNC_TOLERANCE: Duration = Duration::from_millis(30);
+
+/// Default time between two drift checks of a synchronized track.
+pub const DEFAULT_STEM_SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Keeps the stems of a layered track aligned to a master stem.
+///
+/// Stems that start playing after the master are seeked to its position right
+/// away. Every `interval`, stems that drifted more than `tolerance` from the
+/// master are seeked again. Nothing is corrected while the master is paused or
+/// has not started yet.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{LayeredMusic, StemSync};
+///
+/// let track = LayeredMusic::new(GameMusic::Exploration)
+///     .with_layer("pads", assets.load("music/forest_pads.ogg"), 1.0)
+///     .with_layer("drums", assets.load("music/forest_drums.ogg"), 0.0)
+///     .with_sync(StemSync::new().with_master("pads"));
+/// ```
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+pub struct StemSync {
+    /// Layer the others are aligned to, or `None` for the first stem.
+    pub master: Option<MusicLayer>,
+    /// Drift tolerated before a stem is seeked.
+    pub tolerance: Duration,
+    /// Time between two drift checks.
+    pub interval: Duration,
+    /// Time since the last drift check.
+    pub since_check: Duration,
+}
+
+impl Default for StemSync {
+    fn default() -> Self {
+        Self {
+            master: None,
+            tolerance: DEFAULT_STEM_SYNC_TOLERANCE,
+            interval: DEFAULT_STEM_SYNC_INTERVAL,
+            since_check: Duration::ZERO,
+        }
+    }
+}
+
+impl StemSync {
+    /// Creates a sync aligning the stems to the first stem.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the layer the others are aligned to.
+    #[must_use]
+    pub fn with_master(mut self, layer: impl Into<Cow<'static, str>>) -> Self {
+        self.master = Some(MusicLayer::new(layer));
+        self
+    }
+
+    /// Sets the drift tolerated before a stem is seeked.
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the time between two drift checks.
+    #[must_use]
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Returns the position to seek a stem at `stem` to, or `None` if it is
+    /// within the tolerance of the master at `master`.
+    #[must_use]
+    pub fn correction(&self, master: Duration, stem: Duration) -> Option<Duration> {
+        (master.abs_diff(stem) > self.tolerance).then_some(master)
+    }
+
+    /// Advances the check timer and returns `true` if a drift check is due.
+    pub(crate) fn tick(&mut self, delta: Duration) -> bool {
+        self.since_check = self.since_check.saturating_add(delta);
+        if self.since_check < self.interval {
+            return false;
+        }
+        self.since_check = Duration::ZERO;
+        true
+    }
+}
+
+/// A single stem of a [`LayeredMusic`] track.
+#[derive(Clone, Debug)]
+pub struct LayerStem {
+    /// The layer name.
+    pub layer: MusicLayer,
+    /// Handle to the stem's audio source.
+    pub handle: Handle<AudioSource>,
+    /// Initial linear volume of the layer.
+    pub volume: f32,
+}
+
+/// Builder for a layered music track.
+///
+/// All stems are spawned with identical playback settings and a
+/// [`WaitForLoad`], and start together once every source has loaded. Silent
+/// layers keep playing at zero volume, which keeps them aligned with the
+/// others until they are faded in.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{LayeredMusic, PlayLayeredMusic};
+///
+/// let track = LayeredMusic::new(GameMusic::Exploration)
+///     .with_layer("pads", assets.load("music/forest_pads.ogg"), 1.0)
+///     .with_layer("drums", assets.load("music/forest_drums.ogg"), 0.0);
+/// messages.write(PlayLayeredMusic::new(track));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LayeredMusic<M: MusicCategory> {
+    /// The music category shared by all stems.
+    pub category: M,
+    /// Playback settings shared by all stems (defaults to LOOP).
+    pub playback: PlaybackSettings,
+    /// The stems of the track.
+    pub stems: Vec<LayerStem>,
+    /// Keeps the stems aligned, if set.
+    pub sync: Option<StemSync>,
+    /// Fade applied to every stem as the track starts, if set.
+    pub fade_in: Option<FadeIn>,
+}
+
+impl<M: MusicCategory> LayeredMusic<M> {
+    /// Creates an empty layered track with looping playback.
+    #[must_use]
+    pub fn new(category: M) -> Self {
+        Self {
+            category,
+            playback: PlaybackSettings::LOOP,
+            stems: Vec::new(),
+            sync: None,
+            fade_in: None,
+        }
+    }
+
+    /// Adds a stem with its initial linear volume.
+    #[must_use]
+    pub fn with_layer(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        handle: Handle<AudioSource>,
+        volume: f32,
+    ) -> Self {
+        self.stems.push(LayerStem {
+            layer: MusicLayer::new(name),
+            handle,
+            volume,
+        });
+        self
+    }
+
+    /// Sets the playback settings shared by all stems.
+    #[must_use]
+    pub fn with_playback(mut self, playback: PlaybackSettings) -> Self {
+        self.playback = playback;
+        self
+    }
+
+    /// Keeps the stems aligned to a master stem.
+    #[must_use]
+    pub fn with_sync(mut self, sync: StemSync) -> Self {
+        self.sync = Some(sync);
+        self
+    }
+
+    /// Fades every stem in as the track starts.
+    #[must_use]
+    pub fn with_fade_in(mut self, fade_in: FadeIn) -> Self {
+        self.fade_in = Some(fade_in);
+        self
+    }
+
+    /// Spawns the track and returns the parent entity.
+    pub fn spawn(&self, commands: &mut Commands) -> Entity {
+        let mut parent = commands.spawn((LayeredTrack, Name::new("Layered Music")));
+        if let Some(sync) = &self.sync {
+            parent.insert(sync.clone());
+        }
+        parent.with_children(|children| {
+            for stem in &self.stems {
+                let mut child = children.spawn((
+                    WaitForLoad::new(stem.handle.clone()),
+                    self.playback,
+                    self.category,
+                    stem.layer.clone(),
+                    TrackGain::new(stem.volume),
+                ));
+                if let Some(fade_in) = &self.fade_in {
+                    child.insert(fade_in.clone());
+                }
+            }
+        });
+        parent.id()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::tests::{MockBackend, MockSink};
+    use crate::tests::{test_app, TestMusic, TestPlugin};
+
+    use crate::{PlayLayeredMusic, SetLayerVolume, SourceDurations};
+
+    #[test]
+    fn builder_collects_stems() {
+        let track = LayeredMusic::new(TestMusic::Main)
+            .with_layer("pads", Handle::default(), 1.0)
+            .with_layer("drums", Handle::default(), 0.0);
+
+        assert_eq!(track.stems.len(), 2);
+        assert_eq!(track.stems[1].layer, MusicLayer::new("drums"));
+        assert!(track.stems[1].volume.abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn spawn_creates_children_per_stem() {
+        let mut world = World::new();
+        let track = LayeredMusic::new(TestMusic::Main)
+            .with_layer("pads", Handle::default(), 1.0)
+            .with_layer("drums", Handle::default(), 0.5);
+
+        let mut commands = world.commands();
+        let parent = track.spawn(&mut commands);
+        world.flush();
+
+        let children = world.get::<Children>(parent).unwrap();
+        assert_eq!(children.len(), 2);
+        let drums = children[1];
+        assert_eq!(world.get::<TrackGain>(drums), Some(&TrackGain(0.5)));
+        assert!(world.get::<TestMusic>(drums).is_some());
+    }
+
+    #[test]
+    fn stem_sync_corrects_drift_past_tolerance() {
+        let mut sync = StemSync::new().with_tolerance(Duration::from_millis(20));
+        let master = Duration::from_secs(10);
+        assert_eq!(sync.correction(master, master), None);
+        assert_eq!(
+            sync.correction(master, master + Duration::from_millis(15)),
+            None
+        );
+        assert_eq!(
+            sync.correction(master, master - Duration::from_millis(25)),
+            Some(master)
+        );
+
+        assert!(!sync.tick(Duration::from_millis(600)));
+        assert!(sync.tick(Duration::from_millis(400)));
+        assert!(!sync.tick(Duration::from_millis(400)));
+    }
+
+    #[test]
+    fn set_layer_volume_updates_stem_gain() {
+        let mut app = test_app(TestPlugin::default());
+
+        let track = LayeredMusic::new(TestMusic::Main)
+            .with_layer("pads", Handle::default(), 1.0)
+            .with_layer("drums", Handle::default(), 0.0);
+        app.world_mut().write_message(PlayLayeredMusic::new(track));
+        app.update();
+
+        app.world_mut()
+            .write_message(SetLayerVolume::new(TestMusic::Main, "drums", 0.7));
+        app.update();
+
+        let mut query = app.world_mut().query::<(&MusicLayer, &TrackGain)>();
+        for (layer, gain) in query.iter(app.world()) {
+            let expected = if layer.name == "drums" { 0.7 } else { 1.0 };
+            assert!((gain.0 - expected).abs() < f32::EPSILON);
+        }
+        let mut parents = app.world_mut().query_filtered::<(), With<LayeredTrack>>();
+        assert_eq!(parents.iter(app.world()).count(), 1);
+    }
+
+    #[test]
+    fn synced_stems_follow_the_master_within_the_loop() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.init_resource::<SourceDurations>();
+        app.add_systems(Update, crate::systems::sync_layered_stems::<MockBackend>);
+        let handle = Handle::<AudioSource>::default();
+        app.world_mut()
+            .resource_mut::<SourceDurations>()
+            .insert(&handle, Duration::from_secs(60));
+        let stem = |app: &mut App, layer: &'static str, position: u64| {
+            app.world_mut()
+                .spawn((
+                    MusicLayer::new(layer),
+                    AudioPlayer(handle.clone()),
+                    MockSink {
+                        position: Duration::from_millis(position),
+                        ..default()
+                    },
+                ))
+                .id()
+        };
+        // The master has looped twice and is 10 s into the track
+        let pads = stem(&mut app, "pads", 130_000);
+        let drums = stem(&mut app, "drums", 10_500);
+        let bass = stem(&mut app, "bass", 70_010);
+        app.world_mut()
+            .spawn((
+                LayeredTrack,
+                StemSync::new()
+                    .with_master("pads")
+                    .with_interval(Duration::ZERO),
+            ))
+            .add_children(&[pads, drums, bass]);
+
+        app.update();
+
+        let seeks = |app: &App, stem| app.world().get::<MockSink>(stem).unwrap().seeks.clone();
+        assert_eq!(seeks(&app, drums), [Duration::from_secs(10)]);
+        assert_eq!(seeks(&app, bass), []);
+        assert_eq!(seeks(&app, pads), []);
+    }
+}
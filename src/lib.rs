@@ -96,20 +96,212 @@
 //! - **Volume Management**: Automatic volume application based on master + category
 //! - **Concurrency Limiting**: Prevent audio spam with per-sound limits
 //! - **Randomization**: Built-in volume and pitch randomization for variety
+//! - **Fades**: Fade-in and fade-out with linear, exponential, s-curve, or custom easing
 //! - **Dual API**: Use component bundles or events based on your needs
 
+mod ambience;
+mod app_ext;
+mod attenuation;
+mod audio_query;
+mod backend;
 mod bundles;
+mod clock;
 mod components;
+mod conditions;
+#[cfg(feature = "console")]
+mod console;
+#[cfg(feature = "debug_overlay")]
+mod debug_overlay;
+mod delay;
+mod diagnostics;
+mod duration;
+mod easing;
+mod effects;
+mod environment;
 mod events;
+mod exit;
+mod extra;
+mod focus;
+mod footsteps;
+mod impact;
+mod intensity;
+#[cfg(feature = "kira")]
+mod kira;
+mod layers;
+mod library;
+mod limiter;
+mod listener;
+mod localization;
+mod memory;
+mod mixer;
+mod muffle;
+mod naming;
+mod now_playing;
+#[cfg(any(feature = "ron", feature = "toml"))]
+mod persistence;
+mod player_param;
+mod pool;
+mod preload;
+#[cfg(feature = "serde")]
+mod recorder;
+mod request;
+mod rng;
+mod sequence;
+#[cfg(feature = "serde")]
+mod serialization;
+mod spatial_2d;
+mod state_scoped;
 mod systems;
+mod testing;
+mod tone;
+mod track_info;
 mod traits;
+mod ui;
+mod virtualization;
+mod voice;
+mod volume;
+mod weather;
+mod world_ext;
 
-pub use bundles::{MusicBundle, SfxBundle, DEFAULT_CONCURRENCY_INTERVAL, DEFAULT_MAX_CONCURRENT};
-pub use components::{FadeOut, MaxConcurrent, PlaybackRandomizer, SoundEffectCounter};
-pub use events::{FadeOutMusic, PlayMusic, PlaySfx, StopAllMusic, StopMusic};
-pub use traits::{AudioCategory, AudioConfigTrait, MusicCategory, SfxCategory};
+pub use ambience::{
+    AmbienceLayer, AmbienceListener, AmbienceSource, AmbienceZone, BlendedAmbience,
+    BlendedAmbienceSource, ZoneShape, DEFAULT_AMBIENCE_EDGE_FADE, DEFAULT_AMBIENCE_FADE,
+};
+pub use app_ext::AddAudioCategory;
+pub use attenuation::{Attenuation, SpatialSettings, DEFAULT_MAX_DISTANCE, DEFAULT_MIN_DISTANCE};
+pub use audio_query::AudioQuery;
+pub use backend::{
+    either_sink, has_spatial_sink, with_sink_mut, AnySink, AudioBackend, BevyAudioBackend,
+    BevySpatialAudioBackend, EitherSink,
+};
+pub use bundles::{LoopingSfxBundle, MusicBundle, SfxBundle, DEFAULT_MAX_CONCURRENT};
+pub use clock::{ClockState, MusicClock, MusicTempo, Quantize, QuantizedStop};
+pub use components::{
+    AttachedAudio, AttachedSounds, AudioVolumeState, ConcurrencyPolicy, DuplicateMusic, FadeIn,
+    FadeOut, LoopingSfx, MaxConcurrent, MaxDuration, MusicCrossfade, NonSpatial, PausedByGame,
+    PlayChance, PlaybackLabel, PlaybackRandomizer, RampingVolume, SoundEffectCounter, SoundPool,
+    SpawnRequest, SpeedTween, StartDelay, StartPosition, StereoPan, StereoPanRange, Stinger,
+    TrackGain, TweenCompletion, VolumeRamp, VolumeTween, WaitForLoad, DEFAULT_MUSIC_CROSSFADE,
+    DEFAULT_STINGER_DUCK_VOLUME, DEFAULT_STINGER_FADE, DEFAULT_VOLUME_RAMP,
+};
+pub use conditions::{
+    any_music_playing, any_music_playing_with, category_playing, category_playing_with,
+    no_sfx_active, no_sfx_active_with,
+};
+#[cfg(feature = "console")]
+pub use console::{
+    AudioConsoleCommand, AudioConsoleInput, AudioConsoleOutput, AudioConsolePlugin,
+    ConsoleAudioConfig, ParseAudioCommandError,
+};
+#[cfg(feature = "debug_overlay")]
+pub use debug_overlay::{
+    AudioDebugOverlay, AudioDebugOverlayPlugin, AudioDebugOverlayRoot, PausedByDebugSolo,
+    DEFAULT_DEBUG_OVERLAY_REFRESH,
+};
+pub use delay::DelayedMessages;
+pub use diagnostics::{
+    audio_diagnostics_system, AudioDiagnosticsPlugin, AUDIO_ACTIVE_MUSIC, AUDIO_ACTIVE_SFX,
+    AUDIO_DROPPED_PER_SECOND, AUDIO_PEAK_VOICES, AUDIO_POOL_HIT_RATE,
+};
+pub use duration::SourceDurations;
+pub use easing::Easing;
+pub use effects::{ActiveEffectBus, AudioEffectZone, Echo, EchoTap, EffectBus, EffectSend};
+pub use environment::{
+    ActiveEnvironment, EnvironmentProfile, EnvironmentProfiles, EnvironmentZone,
+    DEFAULT_ENVIRONMENT_TRANSITION,
+};
+pub use events::{
+    AudioDropped, AudioStarted, AudioStopped, AudioTarget, Bar, Beat, CrossfadeMusic, DropReason,
+    FadeOutAllMusic, FadeOutMusic, FadeOutSfx, MuteCategory, PauseAudio, PauseLabeled,
+    PlayLayeredMusic, PlayLoopingSfx, PlayMusic, PlaySfx, PlaySfxBatch, PlayStinger, PlayVoiceLine,
+    PopMixerSnapshot, PushMixerSnapshot, ResumeAudio, ResumeLabeled, SeekMusic, SetAudioVolume,
+    SetLayerVolume, SoloCategory, StopAllMusic, StopLabeled, StopLoopingSfx, StopMusic, StopReason,
+    VoiceLineFinished,
+};
+pub use exit::{ExitFade, DEFAULT_EXIT_FADE};
+pub use extra::ExtraComponents;
+pub use focus::{AudioFocus, FocusLossBehavior, PausedByFocus};
+pub use footsteps::{
+    play_footsteps, FootstepAudioPlugin, FootstepEmitter, FootstepStride, SurfaceSoundMap,
+    DEFAULT_FOOTSTEP_MIN_INTERVAL,
+};
+pub use impact::{play_impact_sounds, ImpactAudioPlugin, ImpactMessage, ImpactSounds};
+pub use intensity::{
+    apply_music_intensity, IntensityBand, IntensityMap, MusicIntensity, MusicIntensityPlugin,
+    DEFAULT_INTENSITY_CROSSFADE, DEFAULT_INTENSITY_HYSTERESIS,
+};
+#[cfg(feature = "kira")]
+pub use kira::{
+    KiraAudioBackend, KiraBackendPlugin, KiraSink, KiraSpatialAudioBackend, KiraSpatialSink,
+};
+pub use layers::{
+    LayerStem, LayeredMusic, LayeredTrack, MusicLayer, StemSync, DEFAULT_STEM_SYNC_INTERVAL,
+    DEFAULT_STEM_SYNC_TOLERANCE,
+};
+pub use library::{ManifestSound, SoundEntry, SoundId, SoundLibrary, SoundManifest};
+pub use limiter::{MixLevel, MixLimiter, DEFAULT_LIMITER_ATTACK, DEFAULT_LIMITER_RELEASE};
+pub use listener::{AudioListenerTarget, DEFAULT_EAR_GAP};
+pub use localization::{CurrentAudioLanguage, LocalizedAudio};
+pub use memory::MusicMemory;
+pub use mixer::{CategoryMixerState, CategorySpeed, MixerSnapshot};
+pub use muffle::{
+    AudioMuffle, MuffleAudio, DEFAULT_MUFFLE_GAIN, DEFAULT_MUFFLE_SPEED, DEFAULT_MUFFLE_TRANSITION,
+};
+pub use naming::{name_audio_entities, AudioEntityNames, AudioNameParts, AudioNamingPlugin};
+pub use now_playing::{NowPlaying, NowPlayingChanged, PlayingTrack};
+#[cfg(any(feature = "ron", feature = "toml"))]
+pub use persistence::{
+    load_config_from_file, save_config_to_file, AudioConfigPersistencePlugin, ConfigFormat,
+    ConfigPersistError, DEFAULT_HOT_RELOAD_INTERVAL, DEFAULT_PERSIST_DELAY,
+};
+pub use player_param::AudioPlayerParam;
+pub use pool::{PoolGeneration, Pooled, SfxPool};
+pub use preload::{audio_assets_loaded, AudioPreloader};
+#[cfg(feature = "serde")]
+pub use recorder::{
+    record_audio_events, replay_audio_timeline, AudioEventRecorder, AudioRecorderPlugin,
+    AudioTimeline, AudioTimelineEntry, AudioTimelinePlayer, RecordedAudioEvent,
+};
+pub use request::{AudioRequest, ConcurrencyRequest, SfxRequest};
+pub use rng::AudioRng;
+pub use sequence::{AudioSequence, SequenceStep};
+#[cfg(feature = "serde")]
+pub use serialization::{SerializedPlayLoopingSfx, SerializedPlayMusic, SerializedPlaySfx};
+pub use spatial_2d::{Spatial2d, DEFAULT_PAN_WIDTH};
+pub use state_scoped::{
+    ScopedAudioCategories, StateScopedAudio, StateScopedAudioExt, DEFAULT_STATE_EXIT_FADE,
+};
+pub use testing::{AudioCall, AudioLog, FinishSound, TestAudioPlugin};
+pub use tone::{
+    handle_play_tone_events, play_tone_echoes, PlayTone, Tone, ToneAudioPlugin, ToneDecoder,
+    Waveform, TONE_SAMPLE_RATE,
+};
+pub use track_info::{TrackInfo, TrackManifest, TrackRegistry, DEFAULT_REFERENCE_LOUDNESS};
+pub use traits::{
+    AudioCategory, AudioConfigTrait, MusicCategory, SfxCategory, VoiceCategory, VolumeCurve,
+    VolumeScale,
+};
+pub use ui::{play_ui_sounds, UiAudioPlugin, UiSoundCategory, UiSounds};
+pub use virtualization::{
+    AudioPriority, GlobalVoiceLimit, VirtualizationMode, Virtualized, VoiceBudget,
+    DEFAULT_GLOBAL_VOICE_LIMIT, DEFAULT_VOICE_HYSTERESIS,
+};
+pub use voice::{
+    ActiveVoiceLine, DialogueDucking, DialogueQueue, VoiceLine, DEFAULT_DIALOGUE_DUCK_ATTACK,
+    DEFAULT_DIALOGUE_DUCK_DB, DEFAULT_DIALOGUE_DUCK_RELEASE,
+};
+pub use volume::{compute_final_volume, VolumeStages};
+pub use weather::{
+    play_thunder, update_weather_loops, Thunder, ThunderStrike, WeatherAudio, WeatherAudioPlugin,
+    WeatherSounds, WeatherSource, WeatherState, DEFAULT_SPEED_OF_SOUND, DEFAULT_WEATHER_FADE,
+};
+pub use world_ext::WorldAudioExt;
 
-use bevy::prelude::*;
+use app_ext::SfxOptions;
+use bevy::{
+    prelude::*,
+    window::{AppLifecycle, WindowFocused},
+};
 
 /// Main plugin for the dmg_audio crate.
 ///
@@ -124,69 +316,566 @@ use bevy::prelude::*;
 /// - `M`: Your music category type implementing [`MusicCategory`]
 /// - `S`: Your sound effect category type implementing [`SfxCategory`]
 /// - `C`: Your audio config type implementing [`AudioConfigTrait`]
+/// - `B`: The [`AudioBackend`] playing the sounds, [`BevyAudioBackend`] by
+///   default
+///
+/// Additional sound effect category types sharing the same config can be
+/// registered with [`with_sfx`](Self::with_sfx). Sub-plugins can register
+/// their own category types later through [`AddAudioCategory`].
 ///
 /// # Example
 ///
 /// ```rust,ignore
 /// app.add_plugins(MsgAudioPlugin::<GameMusic, GameSfx, GameAudioConfig>::default());
+///
+/// // Split sound effects across several category types
+/// app.add_plugins(
+///     MsgAudioPlugin::<GameMusic, UiSfx, GameAudioConfig>::default().with_sfx::<WorldSfx>(),
+/// );
 /// ```
 #[derive(Default)]
-pub struct MsgAudioPlugin<M, S, C>
+pub struct MsgAudioPlugin<M, S, C, B = BevyAudioBackend>
 where
     M: MusicCategory<Config = C>,
     S: SfxCategory<Config = C>,
     C: AudioConfigTrait,
+    B: AudioBackend,
 {
-    _phantom: std::marker::PhantomData<(M, S, C)>,
+    sfx_options: SfxOptions,
+    volume_ramp: VolumeRamp,
+    music_crossfade: MusicCrossfade,
+    duplicate_music: DuplicateMusic,
+    dialogue_ducking: DialogueDucking,
+    mix_limiter: MixLimiter,
+    voice_budget: VoiceBudget,
+    global_voice_limit: GlobalVoiceLimit,
+    focus_loss: Option<FocusLossBehavior>,
+    exit_fade: Option<ExitFade>,
+    sfx_pool: Option<SfxPool>,
+    extra_sfx: Vec<fn(&mut App)>,
+    _phantom: std::marker::PhantomData<(M, S, C, B)>,
+}
+
+impl<M, S, C, B> MsgAudioPlugin<M, S, C, B>
+where
+    M: MusicCategory<Config = C>,
+    S: SfxCategory<Config = C>,
+    C: AudioConfigTrait,
+    B: AudioBackend,
+{
+    /// Registers an additional sound effect category type.
+    ///
+    /// The type gets its own [`PlaySfx`] and [`FadeOutSfx`] messages and
+    /// the same volume, concurrency, and plugin options as `S`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// app.add_plugins(
+    ///     MsgAudioPlugin::<GameMusic, UiSfx, GameAudioConfig>::default()
+    ///         .with_sfx::<WorldSfx>()
+    ///         .with_sfx::<VehicleSfx>(),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_sfx<T: SfxCategory<Config = C>>(mut self) -> Self {
+        self.extra_sfx.push(|app| {
+            app.add_sfx_category_with::<T, B>();
+        });
+        self
+    }
+
+    /// Ties sound effect playback speed to the virtual time's relative speed.
+    ///
+    /// Slow-motion gameplay then automatically lowers the pitch of sound effects.
+    /// Categories can opt out via [`SfxCategory::follows_time_scale`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// app.add_plugins(
+    ///     MsgAudioPlugin::<GameMusic, GameSfx, GameAudioConfig>::default().with_time_scaled_sfx(),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_time_scaled_sfx(mut self) -> Self {
+        self.sfx_options.time_scaled = true;
+        self
+    }
+
+    /// Pauses sound effects while `Time<Virtual>` is paused and resumes them afterwards.
+    ///
+    /// Music keeps playing, and sound effect categories can opt out via
+    /// [`SfxCategory::pauses_with_game`] (e.g. UI sounds in a pause menu).
+    ///
+    /// To pause on a custom condition instead, pipe your own condition into
+    /// [`audio_systems::sync_sfx_game_pause`]:
+    ///
+    /// ```rust,ignore
+    /// app.add_systems(
+    ///     Update,
+    ///     in_state(GameState::Paused)
+    ///         .pipe(audio_systems::sync_sfx_game_pause::<GameSfx, BevyAudioBackend>),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_pause_sfx_on_game_pause(mut self) -> Self {
+        self.sfx_options.pause_on_game_pause = true;
+        self
+    }
+
+    /// Sets how long playing audio takes to reach a new volume after the
+    /// config changes.
+    ///
+    /// Defaults to [`DEFAULT_VOLUME_RAMP`]. Pass [`Duration::ZERO`] to apply
+    /// changes instantly. The [`VolumeRamp`] resource can also be changed at runtime.
+    ///
+    /// [`Duration::ZERO`]: std::time::Duration::ZERO
+    #[must_use]
+    pub fn with_volume_ramp(mut self, duration: std::time::Duration) -> Self {
+        self.volume_ramp = VolumeRamp::new(duration);
+        self
+    }
+
+    /// Sets the crossfade applied when music is stopped and started in the
+    /// same frame.
+    ///
+    /// Defaults to [`DEFAULT_MUSIC_CROSSFADE`]. Pass [`Duration::ZERO`] to cut
+    /// over instantly. The [`MusicCrossfade`] resource can also be changed at
+    /// runtime.
+    ///
+    /// [`Duration::ZERO`]: std::time::Duration::ZERO
+    #[must_use]
+    pub fn with_music_crossfade(mut self, duration: std::time::Duration) -> Self {
+        self.music_crossfade = MusicCrossfade::new(duration);
+        self
+    }
+
+    /// Sets what happens when music is requested that already plays in the
+    /// same category.
+    ///
+    /// Defaults to [`DuplicateMusic::Stack`]. Individual messages can
+    /// override it with [`PlayMusic::on_duplicate`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// app.add_plugins(
+    ///     MsgAudioPlugin::<GameMusic, GameSfx, GameAudioConfig>::default()
+    ///         .with_duplicate_music(DuplicateMusic::Ignore),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_duplicate_music(mut self, policy: DuplicateMusic) -> Self {
+        self.duplicate_music = policy;
+        self
+    }
+
+    /// Sets how music is ducked while voice lines play.
+    ///
+    /// Defaults to [`DEFAULT_DIALOGUE_DUCK_DB`] of attenuation. Pass
+    /// [`DialogueDucking::DISABLED`] to leave music untouched.
+    #[must_use]
+    pub fn with_dialogue_ducking(mut self, ducking: DialogueDucking) -> Self {
+        self.dialogue_ducking = ducking;
+        self
+    }
+
+    /// Attenuates the whole mix while the summed volume of the playing
+    /// sounds exceeds a ceiling, preventing clipping when many loud sounds
+    /// overlap.
+    ///
+    /// Defaults to [`MixLimiter::DISABLED`].
+    #[must_use]
+    pub fn with_mix_limiter(mut self, limiter: MixLimiter) -> Self {
+        self.mix_limiter = limiter;
+        self
+    }
+
+    /// Limits how many sounds play at once.
+    ///
+    /// Defaults to [`VoiceBudget::UNLIMITED`]. See [`VoiceBudget`] for how
+    /// voices over the budget are chosen.
+    #[must_use]
+    pub fn with_voice_budget(mut self, budget: VoiceBudget) -> Self {
+        self.voice_budget = budget;
+        self
+    }
+
+    /// Caps how many sounds exist at once, across all categories.
+    ///
+    /// Defaults to [`DEFAULT_GLOBAL_VOICE_LIMIT`], which depends on the
+    /// target platform. Pass [`GlobalVoiceLimit::UNLIMITED`] to disable the
+    /// cap. See [`GlobalVoiceLimit`] for which sounds are dropped.
+    #[must_use]
+    pub fn with_global_voice_limit(mut self, limit: GlobalVoiceLimit) -> Self {
+        self.global_voice_limit = limit;
+        self
+    }
+
+    /// Pauses or ducks audio while the window is unfocused or the app is in
+    /// the background, restoring it when focus returns.
+    ///
+    /// Categories can keep playing via [`AudioCategory::plays_in_background`]
+    /// (e.g. music in a background music player).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// app.add_plugins(
+    ///     MsgAudioPlugin::<GameMusic, GameSfx, GameAudioConfig>::default()
+    ///         .with_focus_loss(FocusLossBehavior::Duck(0.2)),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_focus_loss(mut self, behavior: FocusLossBehavior) -> Self {
+        self.focus_loss = Some(behavior);
+        self
+    }
+
+    /// Fades every playing sound to silence before the app exits.
+    ///
+    /// [`AppExit`] messages are held back for the length of the fade and then
+    /// sent again, so quitting mid-music does not cut it off with a click.
+    /// See [`DEFAULT_EXIT_FADE`] for a sensible duration.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// app.add_plugins(
+    ///     MsgAudioPlugin::<GameMusic, GameSfx, GameAudioConfig>::default()
+    ///         .with_exit_fade(DEFAULT_EXIT_FADE),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_exit_fade(mut self, duration: std::time::Duration) -> Self {
+        self.exit_fade = Some(ExitFade::new(duration));
+        self
+    }
+
+    /// Reuses the entities of finished one-shot sound effects, keeping up to
+    /// `capacity` idle entities.
+    ///
+    /// Cuts spawn and despawn churn in games firing many short sounds. The
+    /// [`SfxPool`] resource reports the hit rate. See [`SfxPool`] for which
+    /// sounds are pooled.
+    #[must_use]
+    pub fn with_sfx_pool(mut self, capacity: usize) -> Self {
+        self.sfx_pool = Some(SfxPool::new(capacity));
+        self
+    }
 }
 
-impl<M, S, C> Plugin for MsgAudioPlugin<M, S, C>
+impl<M, S, C, B> Plugin for MsgAudioPlugin<M, S, C, B>
 where
     M: MusicCategory<Config = C>,
     S: SfxCategory<Config = C>,
     C: AudioConfigTrait,
+    B: AudioBackend,
 {
     fn build(&self, app: &mut App) {
-        // Register types
-        app.register_type::<MaxConcurrent>();
-        app.register_type::<SoundEffectCounter>();
-        app.register_type::<FadeOut>();
-
-        // Initialize resources
-        app.init_resource::<SoundEffectCounter>();
-
-        // Add messages (renamed from events in Bevy 0.17)
-        app.add_message::<PlayMusic<M>>();
-        app.add_message::<PlaySfx<S>>();
-        app.add_message::<StopMusic<M>>();
-        app.add_message::<StopAllMusic<M>>();
-        app.add_message::<FadeOutMusic<M>>();
-
-        // Add systems
+        register_common(app);
+
+        app.configure_sets(
+            Update,
+            (
+                MsgAudioSet::EventHandling,
+                MsgAudioSet::Concurrency,
+                MsgAudioSet::VolumeApplication,
+                MsgAudioSet::Fades,
+            )
+                .chain(),
+        );
+
+        // Category-independent processing
+        app.add_systems(
+            Update,
+            (
+                (
+                    systems::process_fade_outs::<B>,
+                    systems::process_fade_ins::<B>,
+                    systems::process_volume_tweens::<B>,
+                    systems::process_speed_tweens::<B>,
+                    systems::process_volume_ramps::<B>,
+                ),
+                attenuation::apply_spatial_attenuation::<B>,
+                systems::despawn_empty_layered_tracks,
+                systems::sync_layered_stems::<B>,
+                systems::update_music_clock::<B>,
+                systems::process_quantized_stops::<B>,
+                systems::virtualize_voices::<B>,
+                systems::stop_sounds_past_max_duration::<B>,
+                limiter::update_mix_limiter::<B>,
+                muffle::update_audio_muffle,
+            )
+                .in_set(MsgAudioSet::Fades),
+        );
+        app.add_message::<Beat>();
+        app.add_message::<Bar>();
+        app.add_message::<AudioStarted>();
+        app.add_message::<AudioStopped>();
+        app.add_message::<AudioDropped>();
+        app.add_message::<AudioStarted<Tone>>();
+        app.add_message::<AudioStopped<Tone>>();
+        app.add_message::<AudioDropped<Tone>>();
+        app.add_observer(systems::emit_audio_stopped::<B, AudioSource>);
+        app.add_observer(systems::emit_audio_stopped::<B, Tone>);
+        // Backends without a separate spatial sink get a single set of systems
+        if has_spatial_sink::<B>() {
+            app.add_observer(systems::emit_audio_stopped::<B::Spatial, AudioSource>);
+            app.add_observer(systems::emit_audio_stopped::<B::Spatial, Tone>);
+            app.add_systems(
+                Update,
+                (
+                    (
+                        systems::process_fade_outs::<B::Spatial>,
+                        systems::process_fade_ins::<B::Spatial>,
+                        systems::process_volume_tweens::<B::Spatial>,
+                        systems::process_speed_tweens::<B::Spatial>,
+                        systems::process_volume_ramps::<B::Spatial>,
+                        attenuation::apply_spatial_attenuation::<B::Spatial>,
+                        systems::sync_layered_stems::<B::Spatial>,
+                        systems::update_music_clock::<B::Spatial>,
+                    )
+                        .in_set(MsgAudioSet::Fades),
+                    (
+                        events::handle_pause_labeled_events::<B::Spatial>,
+                        events::handle_resume_labeled_events::<B::Spatial>,
+                        events::handle_pause_audio_events::<B::Spatial>,
+                        events::handle_resume_audio_events::<B::Spatial>,
+                        systems::emit_audio_started::<B::Spatial, AudioSource>,
+                        systems::emit_audio_started::<B::Spatial, Tone>,
+                        systems::reroll_randomized_playback::<B::Spatial>,
+                    )
+                        .in_set(MsgAudioSet::EventHandling),
+                ),
+            );
+            app.add_systems(Last, systems::seek_start_positions::<B::Spatial>);
+        }
+        app.add_observer(systems::roll_stereo_pan_range);
+        app.add_observer(systems::apply_spawn_request);
+        app.add_message::<StopLabeled>();
+        app.add_message::<PauseLabeled>();
+        app.add_message::<ResumeLabeled>();
+        app.add_message::<PauseAudio>();
+        app.add_message::<ResumeAudio>();
         app.add_systems(
             Update,
             (
-                // Apply volume to new audio
-                systems::apply_volume_to_new_music::<M, C>,
-                systems::apply_volume_to_new_sfx::<S, C>,
-                // Update volume when config changes
-                systems::update_music_volume::<M, C>.run_if(resource_changed::<C>),
-                systems::update_sfx_volume::<S, C>.run_if(resource_changed::<C>),
-                // Concurrency limiting
-                systems::enforce_sfx_concurrency::<S>,
-                // Fade processing
-                systems::process_fade_outs,
-                // Event handling
-                events::handle_play_music_events::<M>,
-                events::handle_play_sfx_events::<S>,
-                events::handle_stop_music_events::<M>,
-                events::handle_stop_all_music_events::<M>,
-                events::handle_fade_out_music_events::<M>,
+                events::handle_stop_labeled_events::<B>,
+                events::handle_pause_labeled_events::<B>,
+                events::handle_resume_labeled_events::<B>,
+                events::handle_pause_audio_events::<B>,
+                events::handle_resume_audio_events::<B>,
+                systems::update_active_effect_bus,
+                environment::update_environment_zone,
+                systems::emit_audio_started::<B, AudioSource>,
+                systems::emit_audio_started::<B, Tone>,
+                systems::reroll_randomized_playback::<B>,
+                systems::roll_play_chance,
+            )
+                .in_set(MsgAudioSet::EventHandling),
+        );
+        app.add_systems(
+            Update,
+            systems::enforce_global_voice_limit::<B>.in_set(MsgAudioSet::Concurrency),
+        );
+        app.add_systems(
+            PostUpdate,
+            (
+                listener::sync_audio_listener,
+                systems::follow_attached_audio,
+                systems::place_panned_sounds,
+                spatial_2d::enable_2d_spatial_playback,
+                systems::center_non_spatial_sounds,
+            )
+                .chain()
+                .before(TransformSystems::Propagate),
+        );
+        app.add_systems(
+            Last,
+            (
+                spatial_2d::pan_2d_sounds,
+                systems::seek_start_positions::<B>,
             ),
         );
+        app.add_systems(
+            Update,
+            (
+                systems::start_audio_when_loaded::<B>.run_if(
+                    resource_exists::<AssetServer>.and(resource_exists::<Assets<AudioSource>>),
+                ),
+                systems::start_layered_stems_when_loaded::<B>,
+                systems::start_delayed_audio::<B>,
+            )
+                .in_set(MsgAudioSet::EventHandling),
+        );
+        app.add_systems(
+            Update,
+            duration::measure_source_durations
+                .run_if(resource_exists::<Assets<AudioSource>>)
+                .in_set(MsgAudioSet::EventHandling),
+        );
+
+        app.insert_resource(self.sfx_options);
+        app.insert_resource(self.volume_ramp);
+        app.insert_resource(self.music_crossfade);
+        app.insert_resource(self.duplicate_music);
+        app.insert_resource(self.dialogue_ducking);
+        app.insert_resource(self.mix_limiter);
+        app.insert_resource(self.voice_budget);
+        app.insert_resource(self.global_voice_limit);
+        if let Some(behavior) = self.focus_loss {
+            app.insert_resource(AudioFocus {
+                behavior: Some(behavior),
+                ..default()
+            });
+            app.add_message::<WindowFocused>();
+            app.add_message::<AppLifecycle>();
+            app.add_systems(
+                Update,
+                focus::track_app_focus.in_set(MsgAudioSet::EventHandling),
+            );
+        }
+        if let Some(pool) = &self.sfx_pool {
+            app.insert_resource(pool.clone());
+        }
+        app.add_systems(
+            Update,
+            pool::recycle_pooled_sfx
+                .run_if(resource_exists::<SfxPool>)
+                .in_set(MsgAudioSet::EventHandling),
+        );
+        if let Some(exit_fade) = &self.exit_fade {
+            app.insert_resource(exit_fade.clone());
+            app.add_systems(
+                Last,
+                exit::fade_out_on_app_exit::<B>.run_if(resource_exists::<ExitFade>),
+            );
+        }
+        app.add_music_category_with::<M, B>();
+        app.add_sfx_category_with::<S, B>();
+        app.add_systems(
+            Update,
+            sequence::run_audio_sequences::<M, S>.before(MsgAudioSet::EventHandling),
+        );
+        for add_sfx in &self.extra_sfx {
+            add_sfx(app);
+        }
     }
 }
 
+/// System sets used by [`MsgAudioPlugin`], running in `Update` in declaration order.
+///
+/// Schedule your own systems relative to these sets to run before or after a
+/// specific phase of audio processing.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::MsgAudioSet;
+///
+/// // Queue sounds before the crate handles this frame's play messages
+/// app.add_systems(Update, play_footsteps.before(MsgAudioSet::EventHandling));
+/// ```
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MsgAudioSet {
+    /// Handles play, stop, and fade messages.
+    EventHandling,
+    /// Enforces concurrency limits on sound effects.
+    Concurrency,
+    /// Applies master, category, and playback volume (plus speed and pause state) to sinks.
+    VolumeApplication,
+    /// Advances fades, tweens, and ducking.
+    Fades,
+}
+
+/// Registers types and resources shared by all category types.
+fn register_common(app: &mut App) {
+    app.register_type::<MaxConcurrent>();
+    app.register_type::<ConcurrencyPolicy>();
+    app.register_type::<SoundEffectCounter>();
+    app.register_type::<FadeOut>();
+    app.register_type::<FadeIn>();
+    app.register_type::<VolumeTween>();
+    app.register_type::<SpeedTween>();
+    app.register_type::<Stinger>();
+    app.register_type::<PausedByGame>();
+    app.register_type::<PausedByFocus>();
+    app.register_type::<AudioFocus>();
+    app.register_type::<ExitFade>();
+    app.register_type::<WaitForLoad>();
+    app.register_type::<TrackGain>();
+    app.register_type::<PlaybackLabel>();
+    app.register_type::<AttachedAudio>();
+    app.register_type::<AttachedSounds>();
+    app.register_type::<LayeredTrack>();
+    app.register_type::<MusicLayer>();
+    app.register_type::<StemSync>();
+    app.register_type::<MusicTempo>();
+    app.register_type::<QuantizedStop>();
+    app.register_type::<VoiceLine>();
+    app.register_type::<DialogueDucking>();
+    app.register_type::<CurrentAudioLanguage>();
+    app.register_type::<AmbienceListener>();
+    app.register_type::<AmbienceSource>();
+    app.register_type::<BlendedAmbienceSource>();
+    app.register_type::<ZoneShape>();
+    app.register_type::<AudioEffectZone>();
+    app.register_type::<ActiveEffectBus>();
+    app.register_type::<EnvironmentZone>();
+    app.register_type::<ActiveEnvironment>();
+    app.register_type::<EffectSend>();
+    app.register_type::<Echo>();
+    app.register_type::<EchoTap>();
+    app.register_type::<VolumeRamp>();
+    app.register_type::<RampingVolume>();
+    app.register_type::<VoiceBudget>();
+    app.register_type::<GlobalVoiceLimit>();
+    app.register_type::<SfxPool>();
+    app.register_type::<PoolGeneration>();
+    app.register_type::<PlaybackRandomizer>();
+    app.register_type::<PlayChance>();
+    app.register_type::<StereoPan>();
+    app.register_type::<StereoPanRange>();
+    app.register_type::<SpawnRequest>();
+    app.register_type::<StartDelay>();
+    app.register_type::<StartPosition>();
+    app.register_type::<NonSpatial>();
+    app.register_type::<MaxDuration>();
+    app.register_type::<LoopingSfx>();
+    app.register_type::<AudioVolumeState>();
+    app.register_type::<MusicCrossfade>();
+    app.register_type::<DuplicateMusic>();
+    app.register_type::<Pooled>();
+    app.register_type::<AudioPriority>();
+    app.register_type::<Virtualized>();
+    app.register_type::<MixLimiter>();
+    app.register_type::<MixLevel>();
+    app.register_type::<AudioListenerTarget>();
+    app.register_type::<SpatialSettings>();
+    app.register_type::<Spatial2d>();
+    app.register_type::<MuffleAudio>();
+    app.register_type::<AudioMuffle>();
+    app.init_resource::<SoundEffectCounter>();
+    app.init_resource::<VolumeRamp>();
+    app.init_resource::<MusicCrossfade>();
+    app.init_resource::<DuplicateMusic>();
+    app.init_resource::<AudioPreloader>();
+    app.init_resource::<MusicClock>();
+    app.init_resource::<DialogueDucking>();
+    app.init_resource::<CurrentAudioLanguage>();
+    app.init_resource::<VoiceBudget>();
+    app.init_resource::<ActiveEffectBus>();
+    app.init_resource::<ActiveEnvironment>();
+    app.init_resource::<AudioRng>();
+    app.init_resource::<TrackRegistry>();
+    app.init_resource::<MixLimiter>();
+    app.init_resource::<MixLevel>();
+    app.init_resource::<AudioMuffle>();
+    app.init_resource::<SourceDurations>();
+}
+
 /// Minimal plugin that only registers types and resources.
 ///
 /// Use this when you want more control over system scheduling
@@ -196,32 +885,65 @@ where
 ///
 /// ```rust,ignore
 /// app.add_plugins(MsgAudioMinimalPlugin);
-/// app.add_systems(Update, systems::apply_volume_to_new_music::<MyMusic, MyConfig>);
+/// app.add_systems(
+///     Update,
+///     systems::apply_volume_to_new_music::<MyMusic, MyConfig, BevyAudioBackend>,
+/// );
 /// ```
 pub struct MsgAudioMinimalPlugin;
 
 impl Plugin for MsgAudioMinimalPlugin {
     fn build(&self, app: &mut App) {
-        app.register_type::<MaxConcurrent>();
-        app.register_type::<SoundEffectCounter>();
-        app.register_type::<FadeOut>();
-        app.init_resource::<SoundEffectCounter>();
+        register_common(app);
     }
 }
 
 /// Re-export of system functions for custom scheduling.
 pub mod audio_systems {
+    pub use crate::attenuation::apply_spatial_attenuation;
+    pub use crate::delay::release_delayed_messages;
+    pub use crate::duration::measure_source_durations;
+    pub use crate::environment::{apply_environment_profile, update_environment_zone};
+    pub use crate::exit::fade_out_on_app_exit;
+    pub use crate::focus::{sync_focus_loss, track_app_focus};
+    pub use crate::limiter::{apply_mix_limiter, update_mix_limiter};
+    pub use crate::listener::sync_audio_listener;
+    pub use crate::muffle::{apply_audio_muffle, update_audio_muffle};
+    pub use crate::now_playing::update_now_playing;
+    pub use crate::pool::recycle_pooled_sfx;
+    pub use crate::sequence::run_audio_sequences;
+    pub use crate::spatial_2d::{enable_2d_spatial_playback, pan_2d_sounds};
+    pub use crate::state_scoped::{scope_new_audio, stop_state_scoped_audio};
     pub use crate::systems::{
-        apply_volume_to_new_music, apply_volume_to_new_sfx, enforce_sfx_concurrency,
-        process_fade_outs, update_music_volume, update_sfx_volume,
+        advance_dialogue_queue, advance_mixer_snapshots, apply_category_speed, apply_spawn_request,
+        apply_time_scale_to_sfx, apply_volume_to_new_music, apply_volume_to_new_sfx,
+        apply_volume_to_new_voice, center_non_spatial_sounds, despawn_empty_layered_tracks,
+        duck_music_for_stingers, duck_music_for_voice, emit_audio_started, emit_audio_stopped,
+        enforce_global_voice_limit, enforce_sfx_concurrency, follow_attached_audio,
+        place_panned_sounds, play_sfx_echoes, process_fade_ins, process_fade_outs,
+        process_quantized_stops, process_speed_tweens, process_volume_ramps, process_volume_tweens,
+        remember_music_position, reroll_randomized_playback, route_sfx_to_effect_bus,
+        seek_start_positions, start_audio_when_loaded, start_delayed_audio,
+        start_layered_stems_when_loaded, stop_sounds_past_max_duration, sync_layered_stems,
+        sync_sfx_game_pause, update_active_effect_bus, update_ambience_zones,
+        update_blended_ambience, update_music_clock, update_music_volume, update_sfx_volume,
+        update_voice_volume, virtual_time_paused, virtualize_voices,
     };
 }
 
 /// Re-export of event handler functions for custom scheduling.
 pub mod audio_events {
     pub use crate::events::{
-        handle_fade_out_music_events, handle_play_music_events, handle_play_sfx_events,
-        handle_stop_all_music_events, handle_stop_music_events,
+        handle_crossfade_music_events, handle_fade_out_all_music_events,
+        handle_fade_out_music_events, handle_fade_out_sfx_events, handle_mute_category_events,
+        handle_pause_audio_events, handle_pause_labeled_events, handle_play_layered_music_events,
+        handle_play_looping_sfx_events, handle_play_music_events, handle_play_sfx_batch_events,
+        handle_play_sfx_events, handle_play_stinger_events, handle_play_voice_line_events,
+        handle_pop_mixer_snapshot_events, handle_push_mixer_snapshot_events,
+        handle_resume_audio_events, handle_resume_labeled_events, handle_seek_music_events,
+        handle_set_audio_volume_events, handle_set_layer_volume_events,
+        handle_solo_category_events, handle_stop_all_music_events, handle_stop_labeled_events,
+        handle_stop_looping_sfx_events, handle_stop_music_events,
     };
 }
 
@@ -229,37 +951,118 @@ pub mod audio_events {
 ///
 /// Import with `use msg_audio::prelude::*;` for quick access to all commonly used types.
 pub mod prelude {
-    pub use crate::bundles::{MusicBundle, SfxBundle, DEFAULT_MAX_CONCURRENT};
-    pub use crate::components::{FadeOut, MaxConcurrent, PlaybackRandomizer, SoundEffectCounter};
-    pub use crate::events::{FadeOutMusic, PlayMusic, PlaySfx, StopAllMusic, StopMusic};
-    pub use crate::traits::{AudioCategory, AudioConfigTrait, MusicCategory, SfxCategory};
-    pub use crate::{MsgAudioMinimalPlugin, MsgAudioPlugin};
+    pub use crate::ambience::{AmbienceListener, AmbienceZone, BlendedAmbience, ZoneShape};
+    pub use crate::app_ext::AddAudioCategory;
+    pub use crate::attenuation::{Attenuation, SpatialSettings};
+    pub use crate::audio_query::AudioQuery;
+    pub use crate::bundles::{LoopingSfxBundle, MusicBundle, SfxBundle, DEFAULT_MAX_CONCURRENT};
+    pub use crate::clock::{MusicClock, MusicTempo, Quantize};
+    pub use crate::components::{
+        AttachedAudio, AttachedSounds, AudioVolumeState, ConcurrencyPolicy, DuplicateMusic, FadeIn,
+        FadeOut, LoopingSfx, MaxConcurrent, MaxDuration, MusicCrossfade, NonSpatial, PlayChance,
+        PlaybackLabel, PlaybackRandomizer, SoundEffectCounter, SoundPool, SpeedTween, StereoPan,
+        StereoPanRange, Stinger, TrackGain, TweenCompletion, VolumeRamp, VolumeTween, WaitForLoad,
+    };
+    pub use crate::conditions::{any_music_playing, category_playing, no_sfx_active};
+    pub use crate::duration::SourceDurations;
+    pub use crate::easing::Easing;
+    pub use crate::effects::{AudioEffectZone, Echo, EffectBus};
+    pub use crate::environment::{
+        ActiveEnvironment, EnvironmentProfile, EnvironmentProfiles, EnvironmentZone,
+    };
+    pub use crate::events::{
+        AudioDropped, AudioStarted, AudioStopped, AudioTarget, Bar, Beat, CrossfadeMusic,
+        FadeOutAllMusic, FadeOutMusic, FadeOutSfx, MuteCategory, PauseAudio, PauseLabeled,
+        PlayLayeredMusic, PlayLoopingSfx, PlayMusic, PlaySfx, PlaySfxBatch, PlayStinger,
+        PlayVoiceLine, PopMixerSnapshot, PushMixerSnapshot, ResumeAudio, ResumeLabeled, SeekMusic,
+        SetAudioVolume, SetLayerVolume, SoloCategory, StopAllMusic, StopLabeled, StopLoopingSfx,
+        StopMusic, VoiceLineFinished,
+    };
+    pub use crate::exit::DEFAULT_EXIT_FADE;
+    pub use crate::focus::FocusLossBehavior;
+    pub use crate::footsteps::{
+        FootstepAudioPlugin, FootstepEmitter, FootstepStride, SurfaceSoundMap,
+    };
+    pub use crate::impact::{ImpactAudioPlugin, ImpactMessage, ImpactSounds};
+    pub use crate::intensity::{IntensityMap, MusicIntensity, MusicIntensityPlugin};
+    pub use crate::layers::{LayeredMusic, MusicLayer, StemSync};
+    pub use crate::library::{SoundEntry, SoundId, SoundLibrary};
+    pub use crate::limiter::MixLimiter;
+    pub use crate::listener::AudioListenerTarget;
+    pub use crate::localization::{CurrentAudioLanguage, LocalizedAudio};
+    pub use crate::memory::MusicMemory;
+    pub use crate::mixer::{CategoryMixerState, CategorySpeed, MixerSnapshot};
+    pub use crate::muffle::MuffleAudio;
+    pub use crate::naming::AudioNamingPlugin;
+    pub use crate::now_playing::NowPlaying;
+    pub use crate::player_param::AudioPlayerParam;
+    pub use crate::preload::{audio_assets_loaded, AudioPreloader};
+    pub use crate::request::{AudioRequest, ConcurrencyRequest, SfxRequest};
+    pub use crate::rng::AudioRng;
+    pub use crate::sequence::AudioSequence;
+    pub use crate::spatial_2d::Spatial2d;
+    pub use crate::state_scoped::{StateScopedAudio, StateScopedAudioExt};
+    pub use crate::tone::{PlayTone, Tone, ToneAudioPlugin, Waveform};
+    pub use crate::track_info::{TrackInfo, TrackRegistry};
+    pub use crate::traits::{
+        AudioCategory, AudioConfigTrait, MusicCategory, SfxCategory, VoiceCategory, VolumeCurve,
+        VolumeScale,
+    };
+    pub use crate::ui::{UiAudioPlugin, UiSounds};
+    pub use crate::virtualization::{AudioPriority, VoiceBudget};
+    pub use crate::voice::{DialogueDucking, DialogueQueue, VoiceLine};
+    pub use crate::weather::{
+        Thunder, ThunderStrike, WeatherAudio, WeatherAudioPlugin, WeatherSounds, WeatherState,
+    };
+    pub use crate::world_ext::WorldAudioExt;
+    pub use crate::{MsgAudioMinimalPlugin, MsgAudioPlugin, MsgAudioSet};
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
+    use bevy::app::Plugins;
+    use std::time::Duration;
 
     #[derive(Component, Clone, Copy, Default, Debug, PartialEq, Eq, Reflect)]
     #[reflect(Component)]
-    enum TestMusic {
+    pub(crate) enum TestMusic {
         #[default]
         Main,
+        Combat,
     }
 
     #[derive(Component, Clone, Copy, Default, Debug, PartialEq, Eq, Reflect)]
     #[reflect(Component)]
-    enum TestSfx {
+    pub(crate) enum TestSfx {
         #[default]
         UI,
+        World,
+        Ambience,
+    }
+
+    #[derive(Component, Clone, Copy, Default, Debug, PartialEq, Eq, Reflect)]
+    #[reflect(Component)]
+    pub(crate) enum TestVoice {
+        #[default]
+        Dialogue,
     }
 
     #[derive(Resource, Clone, Default, Reflect)]
     #[reflect(Resource)]
-    struct TestConfig {
-        master: f32,
-        music: f32,
-        sfx: f32,
+    pub(crate) struct TestConfig {
+        pub master: f32,
+        pub music: f32,
+        pub sfx: f32,
+    }
+
+    impl TestConfig {
+        /// Every slider at full volume.
+        pub(crate) const FULL: Self = Self {
+            master: 1.0,
+            music: 1.0,
+            sfx: 1.0,
+        };
     }
 
     impl AudioConfigTrait for TestConfig {
@@ -281,15 +1084,200 @@ mod tests {
         fn volume_multiplier(&self, config: &Self::Config) -> f32 {
             config.sfx
         }
+        fn muffles(&self) -> bool {
+            false
+        }
     }
     impl SfxCategory for TestSfx {}
 
-    #[test]
-    fn plugin_builds_without_panic() {
+    impl AudioCategory for TestVoice {
+        type Config = TestConfig;
+        fn volume_multiplier(&self, config: &Self::Config) -> f32 {
+            config.sfx
+        }
+    }
+    impl VoiceCategory for TestVoice {}
+
+    /// The plugin over the shared test categories.
+    pub(crate) type TestPlugin = MsgAudioPlugin<TestMusic, TestSfx, TestConfig>;
+
+    /// App with [`MinimalPlugins`], a default [`TestConfig`] and `plugins`.
+    ///
+    /// The default config is silent; tests that check volumes insert
+    /// [`TestConfig::FULL`] or their own sliders.
+    pub(crate) fn test_app<M>(plugins: impl Plugins<M>) -> App {
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
         app.init_resource::<TestConfig>();
-        app.add_plugins(MsgAudioPlugin::<TestMusic, TestSfx, TestConfig>::default());
+        app.add_plugins(plugins);
+        app
+    }
+
+    #[test]
+    fn plugin_builds_without_panic() {
+        let mut app = test_app(TestPlugin::default());
+        app.update();
+    }
+
+    #[test]
+    fn plugin_runs_on_a_backend_with_a_single_sink_type() {
+        use crate::backend::tests::{MockBackend, MockSink};
+
+        let mut app = test_app(
+            MsgAudioPlugin::<TestMusic, TestSfx, TestConfig, MockBackend>::default()
+                .with_volume_ramp(Duration::ZERO),
+        );
+        app.insert_resource(TestConfig {
+            sfx: 0.5,
+            ..TestConfig::FULL
+        });
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI).volume(0.8));
+        app.update();
+        app.update();
+
+        let config = app.world().resource::<TestConfig>();
+        let expected = config.effective_volume() * config.to_linear_gain(0.5) * 0.8;
+        let sink = app
+            .world_mut()
+            .query::<&MockSink>()
+            .single(app.world())
+            .unwrap();
+        assert!((sink.volume - expected).abs() < 1e-5);
+        let started = app.world().resource::<Messages<AudioStarted>>();
+        assert_eq!(started.get_cursor().read(started).count(), 1);
+    }
+
+    fn single<C: Component>(app: &mut App) -> Entity {
+        app.world_mut()
+            .query_filtered::<Entity, With<C>>()
+            .single(app.world())
+            .unwrap()
+    }
+
+    #[test]
+    fn plugin_with_time_scaled_sfx_scales_only_sfx_speed() {
+        let mut app = test_app((
+            TestPlugin::default().with_time_scaled_sfx(),
+            TestAudioPlugin,
+        ));
+        app.add_voice_category::<TestVoice>();
+        app.world_mut()
+            .resource_mut::<Time<Virtual>>()
+            .set_relative_speed(0.5);
+
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::World));
+        app.world_mut()
+            .write_message(PlayMusic::new(Handle::default(), TestMusic::Main));
+        app.world_mut()
+            .write_message(PlayVoiceLine::new(Handle::default(), TestVoice::Dialogue));
+        app.update();
+        app.update();
+        app.update();
+
+        let sfx = single::<TestSfx>(&mut app);
+        let music = single::<TestMusic>(&mut app);
+        let voice = single::<TestVoice>(&mut app);
+        let log = app.world().resource::<AudioLog>();
+        assert!(log.calls_for(sfx).any(|call| matches!(
+            call,
+            AudioCall::SetSpeed { speed, .. } if (*speed - 0.5).abs() < f32::EPSILON
+        )));
+        for entity in [music, voice] {
+            assert!(log.calls_for(entity).any(|call| matches!(
+                call,
+                AudioCall::Play { speed, .. } if (*speed - 1.0).abs() < f32::EPSILON
+            )));
+            assert!(!log
+                .calls_for(entity)
+                .any(|call| matches!(call, AudioCall::SetSpeed { .. })));
+        }
+    }
+
+    #[test]
+    fn plugin_with_pause_sfx_on_game_pause_pauses_and_resumes_sfx() {
+        let mut app = test_app((
+            TestPlugin::default().with_pause_sfx_on_game_pause(),
+            TestAudioPlugin,
+        ));
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::World));
+        app.update();
+        app.update();
+        let (entity, _) = app.world().resource::<AudioLog>().plays().next().unwrap();
+
+        app.world_mut().resource_mut::<Time<Virtual>>().pause();
+        app.update();
+        let log = app.world().resource::<AudioLog>();
+        assert_eq!(
+            log.calls_for(entity).last(),
+            Some(&AudioCall::Pause { entity })
+        );
+
+        app.world_mut().resource_mut::<Time<Virtual>>().unpause();
+        app.update();
+        let log = app.world().resource::<AudioLog>();
+        assert_eq!(
+            log.calls_for(entity).last(),
+            Some(&AudioCall::Resume { entity })
+        );
+    }
+
+    #[test]
+    fn plugin_with_volume_ramp_sets_resource() {
+        let mut app = test_app(TestPlugin::default().with_volume_ramp(Duration::ZERO));
+        app.update();
+
+        assert_eq!(*app.world().resource::<VolumeRamp>(), VolumeRamp::INSTANT);
+    }
+
+    #[derive(Component, Clone, Copy, Default, Debug, PartialEq, Eq, Reflect)]
+    #[reflect(Component)]
+    enum TestWorldSfx {
+        #[default]
+        Impact,
+    }
+
+    impl AudioCategory for TestWorldSfx {
+        type Config = TestConfig;
+        fn volume_multiplier(&self, config: &Self::Config) -> f32 {
+            config.sfx
+        }
+    }
+
+    impl SfxCategory for TestWorldSfx {}
+
+    #[test]
+    fn plugin_with_extra_sfx_registers_messages() {
+        let mut app = test_app(
+            TestPlugin::default()
+                .with_sfx::<TestWorldSfx>()
+                .with_time_scaled_sfx(),
+        );
+        app.update();
+
+        assert!(app
+            .world()
+            .contains_resource::<Messages<PlaySfx<TestSfx>>>());
+        assert!(app
+            .world()
+            .contains_resource::<Messages<PlaySfx<TestWorldSfx>>>());
+    }
+
+    #[test]
+    fn user_systems_can_order_against_sets() {
+        fn before_events() {}
+        fn after_fades() {}
+
+        let mut app = test_app(TestPlugin::default());
+        app.add_systems(
+            Update,
+            (
+                before_events.before(MsgAudioSet::EventHandling),
+                after_fades.after(MsgAudioSet::Fades),
+            ),
+        );
         app.update();
     }
 
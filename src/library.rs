@@ -0,0 +1,311 @@
+//! Sounds referred to by name instead of by handle.
+//!
+//! A [`SoundLibrary`] maps [`SoundId`]s to a handle, a category, and default
+//! settings. Gameplay code then plays sounds with
+//! [`PlaySfx::id`](crate::PlaySfx::id) and never touches asset paths or
+//! handles. The library is filled at startup, or from a [`SoundManifest`].
+
+use bevy::{audio::Volume, platform::collections::HashMap, prelude::*};
+use std::borrow::Cow;
+
+use crate::bundles::SfxBundle;
+use crate::components::PlaybackRandomizer;
+use crate::events::PlaySfx;
+use crate::request::AudioRequest;
+use crate::systems::extract_linear_volume;
+use crate::traits::SfxCategory;
+
+/// Name of a sound in a [`SoundLibrary`].
+///
+/// Declare ids as constants to get typo-proof names:
+///
+/// ```rust,ignore
+/// use msg_audio::SoundId;
+///
+/// pub const JUMP: SoundId = SoundId::from_static("jump");
+/// ```
+#[derive(Reflect, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct SoundId(pub Cow<'static, str>);
+
+impl SoundId {
+    /// Creates a new id.
+    #[must_use]
+    pub fn new(id: impl Into<Cow<'static, str>>) -> Self {
+        Self(id.into())
+    }
+
+    /// Creates an id from a static string, usable in constants.
+    #[must_use]
+    pub const fn from_static(id: &'static str) -> Self {
+        Self(Cow::Borrowed(id))
+    }
+
+    /// Returns the id as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&'static str> for SoundId {
+    fn from(id: &'static str) -> Self {
+        Self::from_static(id)
+    }
+}
+
+impl From<String> for SoundId {
+    fn from(id: String) -> Self {
+        Self(Cow::Owned(id))
+    }
+}
+
+/// A sound registered in a [`SoundLibrary`].
+#[derive(Clone, Debug)]
+pub struct SoundEntry<S: SfxCategory> {
+    /// Handle to the audio source.
+    pub handle: Handle<AudioSource>,
+    /// The sound effect category the sound plays in.
+    pub category: S,
+    /// Linear volume multiplied into the volume of every request.
+    pub volume: f32,
+    /// Randomization used by requests without their own.
+    pub randomizer: Option<PlaybackRandomizer>,
+}
+
+impl<S: SfxCategory> SoundEntry<S> {
+    /// Creates an entry at full volume without randomization.
+    #[must_use]
+    pub fn new(handle: Handle<AudioSource>, category: S) -> Self {
+        Self {
+            handle,
+            category,
+            volume: 1.0,
+            randomizer: None,
+        }
+    }
+
+    /// Sets the linear volume of the sound.
+    #[must_use]
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Sets the default randomization of the sound.
+    #[must_use]
+    pub fn with_randomizer(mut self, randomizer: PlaybackRandomizer) -> Self {
+        self.randomizer = Some(randomizer);
+        self
+    }
+}
+
+/// Resource mapping [`SoundId`]s to the sound effects of type `S`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{PlaySfx, SoundEntry, SoundId, SoundLibrary};
+///
+/// const JUMP: SoundId = SoundId::from_static("jump");
+///
+/// fn load_sounds(mut library: ResMut<SoundLibrary<GameSfx>>, assets: Res<AssetServer>) {
+///     library.insert(
+///         JUMP,
+///         SoundEntry::new(assets.load("sfx/jump.ogg"), GameSfx::Player).with_volume(0.8),
+///     );
+/// }
+///
+/// fn jump(mut sfx: MessageWriter<PlaySfx<GameSfx>>) {
+///     sfx.write(PlaySfx::id(JUMP));
+/// }
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct SoundLibrary<S: SfxCategory> {
+    sounds: HashMap<SoundId, SoundEntry<S>>,
+}
+
+impl<S: SfxCategory> Default for SoundLibrary<S> {
+    fn default() -> Self {
+        Self {
+            sounds: HashMap::default(),
+        }
+    }
+}
+
+impl<S: SfxCategory> SoundLibrary<S> {
+    /// Registers a sound, replacing any previous entry with the same id.
+    pub fn insert(&mut self, id: impl Into<SoundId>, entry: SoundEntry<S>) {
+        self.sounds.insert(id.into(), entry);
+    }
+
+    /// Returns the entry of a sound.
+    #[must_use]
+    pub fn get(&self, id: &SoundId) -> Option<&SoundEntry<S>> {
+        self.sounds.get(id)
+    }
+
+    /// Removes a sound.
+    pub fn remove(&mut self, id: &SoundId) -> Option<SoundEntry<S>> {
+        self.sounds.remove(id)
+    }
+
+    /// Returns the number of registered sounds.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.sounds.len()
+    }
+
+    /// Returns `true` if no sounds are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sounds.is_empty()
+    }
+
+    /// Returns a bundle playing a sound with its default settings.
+    #[must_use]
+    pub fn bundle(&self, id: &SoundId) -> Option<SfxBundle<S>> {
+        let entry = self.get(id)?;
+        let bundle = SfxBundle::new(entry.handle.clone(), entry.category).volume(entry.volume);
+        Some(match &entry.randomizer {
+            Some(randomizer) => bundle.with_randomizer(randomizer.clone()),
+            None => bundle,
+        })
+    }
+
+    /// Fills in the handle, category, and defaults of a request made with
+    /// [`PlaySfx::id`], or returns `None` if the id is not registered.
+    ///
+    /// Requests with a handle are returned unchanged.
+    #[must_use]
+    pub fn resolve<'a>(&self, request: &'a PlaySfx<S>) -> Option<Cow<'a, PlaySfx<S>>> {
+        let Some(id) = &request.sound else {
+            return Some(Cow::Borrowed(request));
+        };
+        let entry = self.get(id)?;
+        let mut request = request.clone();
+        request.handle = entry.handle.clone();
+        request.category = entry.category;
+        let volume = extract_linear_volume(request.playback.volume);
+        request.playback.volume = Volume::Linear(volume * entry.volume);
+        if request.randomizer.is_none() {
+            request.randomizer.clone_from(&entry.randomizer);
+        }
+        Some(Cow::Owned(request))
+    }
+
+    /// Loads every sound of a manifest and registers it.
+    pub fn load_manifest(&mut self, manifest: &SoundManifest<S>, assets: &AssetServer) {
+        for sound in &manifest.sounds {
+            let mut entry = SoundEntry::new(assets.load(sound.path.clone()), sound.category)
+                .with_volume(sound.volume);
+            entry.randomizer.clone_from(&sound.randomizer);
+            self.insert(sound.id.clone(), entry);
+        }
+    }
+}
+
+/// A sound listed in a [`SoundManifest`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestSound<S: SfxCategory> {
+    /// Name the sound is played by.
+    pub id: SoundId,
+    /// Asset path of the audio source.
+    pub path: String,
+    /// The sound effect category the sound plays in.
+    pub category: S,
+    /// Linear volume of the sound.
+    #[cfg_attr(feature = "serde", serde(default = "full_volume"))]
+    pub volume: f32,
+    /// Default randomization of the sound.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub randomizer: Option<PlaybackRandomizer>,
+}
+
+#[cfg(feature = "serde")]
+fn full_volume() -> f32 {
+    1.0
+}
+
+/// List of sounds loaded into a [`SoundLibrary`] with
+/// [`SoundLibrary::load_manifest`].
+///
+/// With the `serde` feature the manifest can be read from a file, e.g. RON:
+///
+/// ```ron
+/// (sounds: [
+///     (id: "jump", path: "sfx/jump.ogg", category: Player, volume: 0.8),
+/// ])
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoundManifest<S: SfxCategory> {
+    /// The sounds of the manifest.
+    pub sounds: Vec<ManifestSound<S>>,
+}
+
+impl<S: SfxCategory> Default for SoundManifest<S> {
+    fn default() -> Self {
+        Self { sounds: Vec::new() }
+    }
+}
+
+impl<S: SfxCategory> SoundManifest<S> {
+    /// Adds a sound to the manifest at full volume.
+    #[must_use]
+    pub fn with_sound(
+        mut self,
+        id: impl Into<SoundId>,
+        path: impl Into<String>,
+        category: S,
+    ) -> Self {
+        self.sounds.push(ManifestSound {
+            id: id.into(),
+            path: path.into(),
+            category,
+            volume: 1.0,
+            randomizer: None,
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestPlugin, TestSfx};
+    use crate::TestAudioPlugin;
+
+    #[test]
+    fn sound_library_resolves_ids() {
+        const JUMP: SoundId = SoundId::from_static("jump");
+
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        let handle: Handle<AudioSource> =
+            bevy::asset::uuid_handle!("6f2b1e0c-5d8a-4c3e-9b7f-2a1d4e6c8b90");
+        app.world_mut()
+            .resource_mut::<SoundLibrary<TestSfx>>()
+            .insert(
+                JUMP,
+                SoundEntry::new(handle.clone(), TestSfx::UI).with_volume(0.5),
+            );
+        app.world_mut()
+            .write_message(PlaySfx::<TestSfx>::id(JUMP).volume(0.5));
+        app.world_mut()
+            .write_message(PlaySfx::<TestSfx>::id("missing"));
+        app.update();
+
+        let world = app.world_mut();
+        let sounds: Vec<_> = world
+            .query::<(&TestSfx, &AudioPlayer, &PlaybackSettings)>()
+            .iter(world)
+            .map(|(category, player, playback)| (*category, player.0.clone(), playback.volume))
+            .collect();
+        assert_eq!(
+            sounds,
+            vec![(TestSfx::UI, handle, bevy::audio::Volume::Linear(0.25))]
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//! Soft limiting of the overall mix.
+//!
+//! When many loud sounds overlap, their summed volume can clip the output.
+//! The limiter estimates the mix level as the sum of the volumes the playing
+//! sounds are headed for and, while it exceeds the [`MixLimiter`] ceiling, attenuates every
+//! category through the mixer. The attenuation follows an attack and release
+//! envelope, so chaotic moments get quieter smoothly instead of distorting.
+
+use bevy::prelude::*;
+use std::time::Duration;
+
+use crate::backend::{either_sink, AnySink, AudioBackend};
+use crate::components::{AudioVolumeState, TrackGain};
+use crate::mixer::CategoryMixerState;
+use crate::traits::AudioCategory;
+
+/// Default duration over which the limiter reaches full attenuation.
+pub const DEFAULT_LIMITER_ATTACK: Duration = Duration::from_millis(50);
+
+/// Default duration over which the limiter recovers once the mix is below
+/// the ceiling again.
+pub const DEFAULT_LIMITER_RELEASE: Duration = Duration::from_millis(600);
+
+/// Resource configuring the mix limiter.
+///
+/// Disabled by default. The ceiling is the summed linear volume of all
+/// playing sounds the mix may reach, e.g. `4.0` for four sounds at full
+/// volume.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::MixLimiter;
+///
+/// app.add_plugins(
+///     MsgAudioPlugin::<GameMusic, GameSfx, GameAudioConfig>::default()
+///         .with_mix_limiter(MixLimiter::new(3.0)),
+/// );
+/// ```
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource)]
+pub struct MixLimiter {
+    /// Highest summed linear volume of the mix. Infinite disables the limiter.
+    pub ceiling: f32,
+    /// Duration over which the limiter reaches full attenuation.
+    pub attack: Duration,
+    /// Duration over which the limiter recovers.
+    pub release: Duration,
+}
+
+impl MixLimiter {
+    /// Disables the limiter.
+    pub const DISABLED: Self = Self {
+        ceiling: f32::INFINITY,
+        attack: DEFAULT_LIMITER_ATTACK,
+        release: DEFAULT_LIMITER_RELEASE,
+    };
+
+    /// Creates a limiter with the default attack and release.
+    #[must_use]
+    pub fn new(ceiling: f32) -> Self {
+        Self {
+            ceiling: ceiling.max(0.0),
+            ..Self::DISABLED
+        }
+    }
+
+    /// Sets the duration of the attenuation.
+    #[must_use]
+    pub fn with_attack(mut self, attack: Duration) -> Self {
+        self.attack = attack;
+        self
+    }
+
+    /// Sets the duration of the recovery.
+    #[must_use]
+    pub fn with_release(mut self, release: Duration) -> Self {
+        self.release = release;
+        self
+    }
+
+    /// Returns `true` if the limiter has a finite ceiling.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.ceiling.is_finite()
+    }
+
+    /// Returns the gain keeping a mix at `level` below the ceiling.
+    #[must_use]
+    pub fn target_gain(&self, level: f32) -> f32 {
+        if level <= self.ceiling {
+            1.0
+        } else {
+            self.ceiling / level
+        }
+    }
+
+    /// Moves a limiter gain towards `target` over `delta`.
+    ///
+    /// Attenuation follows the attack time and recovery the release time,
+    /// both measured for the full range between silence and unity gain.
+    #[must_use]
+    pub fn step(&self, current: f32, target: f32, delta: Duration) -> f32 {
+        let duration = if target < current {
+            self.attack
+        } else {
+            self.release
+        };
+        if duration.is_zero() {
+            return target;
+        }
+        let max_step = delta.as_secs_f32() / duration.as_secs_f32();
+        if current > target {
+            (current - max_step).max(target)
+        } else {
+            (current + max_step).min(target)
+        }
+    }
+}
+
+impl Default for MixLimiter {
+    fn default() -> Self {
+        Self::DISABLED
+    }
+}
+
+/// Resource reporting the estimated mix level and the limiter gain.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource)]
+pub struct MixLevel {
+    level: f32,
+    gain: f32,
+}
+
+impl Default for MixLevel {
+    fn default() -> Self {
+        Self {
+            level: 0.0,
+            gain: 1.0,
+        }
+    }
+}
+
+impl MixLevel {
+    /// Returns the summed linear volume of the playing sounds, before the
+    /// limiter.
+    #[must_use]
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    /// Returns the gain the limiter currently applies to every category.
+    #[must_use]
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+}
+
+/// Estimates the mix level from the volume states of the unpaused sounds and
+/// steps the limiter gain towards the [`MixLimiter`] target.
+///
+/// The level sums the volumes the sounds are headed for rather than what
+/// their sinks play right now, so ramps, fades, and tweens in progress do not
+/// skew it. Runs on real time, so the limiter keeps up while virtual time is
+/// paused or scaled.
+pub fn update_mix_limiter<B: AudioBackend>(
+    time: Res<Time<Real>>,
+    limiter: Res<MixLimiter>,
+    mut mix: ResMut<MixLevel>,
+    sounds: Query<(&AudioVolumeState, Option<&TrackGain>, AnySink<B>)>,
+) {
+    if !limiter.is_enabled() && mix.gain == 1.0 {
+        return;
+    }
+    let limited: f32 = sounds
+        .iter()
+        .filter(|(.., sinks)| !either_sink::<B>(*sinks).is_paused())
+        .map(|(state, gain, _)| state.volume(gain))
+        .sum();
+    // The volume states include the limiter gain applied last frame, which
+    // is divided out again
+    let level = if mix.gain > 0.0 {
+        limited / mix.gain
+    } else {
+        limited
+    };
+    let gain = limiter.step(mix.gain, limiter.target_gain(level), time.delta());
+    mix.set_if_neq(MixLevel { level, gain });
+}
+
+/// Applies the limiter gain of [`MixLevel`] to the mixer of category type
+/// `A`, writing it only while it changes.
+///
+/// Runs right after [`update_mix_limiter`], so the volume states hold the
+/// gain in [`MixLevel`] by the time the limiter next measures them.
+pub fn apply_mix_limiter<A: AudioCategory>(
+    mix: Res<MixLevel>,
+    mut mixer: ResMut<CategoryMixerState<A>>,
+) {
+    if mixer.limiter_gain() != mix.gain() {
+        mixer.set_limiter_gain(mix.gain());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestConfig, TestPlugin, TestSfx};
+    use crate::{PlaySfx, TestAudioPlugin, VolumeRamp};
+
+    #[test]
+    fn target_gain_keeps_mix_below_ceiling() {
+        let limiter = MixLimiter::new(2.0);
+        assert!((limiter.target_gain(1.5) - 1.0).abs() < f32::EPSILON);
+        assert!((limiter.target_gain(4.0) - 0.5).abs() < f32::EPSILON);
+        assert!((MixLimiter::DISABLED.target_gain(100.0) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn step_follows_attack_and_release() {
+        let limiter = MixLimiter::new(1.0)
+            .with_attack(Duration::from_millis(100))
+            .with_release(Duration::from_secs(1));
+        let delta = Duration::from_millis(50);
+        assert!((limiter.step(1.0, 0.2, delta) - 0.5).abs() < 0.0001);
+        assert!((limiter.step(0.5, 1.0, delta) - 0.55).abs() < 0.0001);
+        assert!((limiter.step(0.99, 1.0, delta) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn mix_limiter_attenuates_overlapping_sounds() {
+        let mut app = test_app((
+            TestPlugin::default()
+                .with_mix_limiter(MixLimiter::new(2.0).with_attack(Duration::ZERO)),
+            TestAudioPlugin,
+        ));
+        app.insert_resource(TestConfig::FULL);
+        app.insert_resource(VolumeRamp::INSTANT);
+        for _ in 0..4 {
+            app.world_mut()
+                .write_message(PlaySfx::new(Handle::default(), TestSfx::UI));
+        }
+        for _ in 0..4 {
+            app.update();
+        }
+
+        let mix = *app.world().resource::<MixLevel>();
+        assert!((mix.level() - 4.0).abs() < 0.0001);
+        assert!((mix.gain() - 0.5).abs() < 0.0001);
+        let world = app.world_mut();
+        let mut sinks = world.query_filtered::<&AudioSink, With<TestSfx>>();
+        assert_eq!(sinks.iter(world).count(), 4);
+        for sink in sinks.iter(world) {
+            assert!((crate::systems::extract_linear_volume(sink.volume()) - 0.5).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn mix_limiter_measures_volume_targets_while_sinks_ramp() {
+        let mut app = test_app((
+            TestPlugin::default().with_mix_limiter(
+                MixLimiter::new(2.0)
+                    .with_attack(Duration::ZERO)
+                    .with_release(Duration::ZERO),
+            ),
+            TestAudioPlugin,
+        ));
+        app.insert_resource(TestConfig::FULL);
+        for _ in 0..4 {
+            app.world_mut()
+                .write_message(PlaySfx::new(Handle::default(), TestSfx::UI));
+        }
+        app.update();
+        app.update();
+
+        for _ in 0..5 {
+            app.update();
+            let mix = *app.world().resource::<MixLevel>();
+            assert!((mix.level() - 4.0).abs() < 0.0001);
+            assert!((mix.gain() - 0.5).abs() < 0.0001);
+        }
+    }
+}
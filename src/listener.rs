@@ -0,0 +1,138 @@
+//! Spatial listener placement.
+//!
+//! Spatial sounds are heard from the entity with the [`SpatialListener`].
+//! Mark the camera, the player, or both with an [`AudioListenerTarget`] and
+//! the listener is kept on the best active one, following camera switches.
+
+use bevy::prelude::*;
+
+/// Default distance between the ears of the listener, matching
+/// [`SpatialListener::default`].
+pub const DEFAULT_EAR_GAP: f32 = 4.0;
+
+/// Marks an entity that can carry the [`SpatialListener`].
+///
+/// Every frame the listener is placed on the target with the highest
+/// priority, skipping cameras that are not active, and removed from the
+/// other targets. On a tie the current holder keeps it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::AudioListenerTarget;
+///
+/// commands.spawn((Camera3d::default(), AudioListenerTarget::default()));
+/// // A cutscene camera takes over the listener while it is active.
+/// commands.spawn((
+///     Camera3d::default(),
+///     Camera { is_active: false, ..default() },
+///     AudioListenerTarget::default().with_priority(1),
+/// ));
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+#[require(Transform)]
+pub struct AudioListenerTarget {
+    /// Distance between the ears of the listener.
+    pub ear_gap: f32,
+    /// Targets with a higher priority take the listener first.
+    pub priority: i32,
+}
+
+impl Default for AudioListenerTarget {
+    fn default() -> Self {
+        Self {
+            ear_gap: DEFAULT_EAR_GAP,
+            priority: 0,
+        }
+    }
+}
+
+impl AudioListenerTarget {
+    /// Creates a target with the given ear gap.
+    #[must_use]
+    pub fn new(ear_gap: f32) -> Self {
+        Self {
+            ear_gap,
+            ..default()
+        }
+    }
+
+    /// Sets the priority of the target.
+    #[must_use]
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+/// Moves the [`SpatialListener`] to the active [`AudioListenerTarget`] with
+/// the highest priority.
+pub fn sync_audio_listener(
+    mut commands: Commands,
+    targets: Query<(
+        Entity,
+        Ref<AudioListenerTarget>,
+        Option<&Camera>,
+        Has<SpatialListener>,
+    )>,
+) {
+    let chosen = targets
+        .iter()
+        .filter(|(_, _, camera, _)| camera.is_none_or(|camera| camera.is_active))
+        .max_by_key(|(_, target, _, listening)| (target.priority, *listening))
+        .map(|(entity, ..)| entity);
+    for (entity, target, _, listening) in &targets {
+        if Some(entity) == chosen {
+            if !listening || target.is_changed() {
+                commands
+                    .entity(entity)
+                    .insert(SpatialListener::new(target.ear_gap));
+            }
+        } else if listening {
+            commands.entity(entity).remove::<SpatialListener>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestPlugin};
+    use crate::TestAudioPlugin;
+
+    #[test]
+    fn listener_follows_active_target() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        let player = app.world_mut().spawn(AudioListenerTarget::default()).id();
+        let camera = app
+            .world_mut()
+            .spawn((
+                Camera {
+                    is_active: false,
+                    ..default()
+                },
+                AudioListenerTarget::new(2.0).with_priority(1),
+            ))
+            .id();
+        let listening = |app: &App| {
+            (
+                app.world().entity(player).contains::<SpatialListener>(),
+                app.world().entity(camera).contains::<SpatialListener>(),
+            )
+        };
+
+        app.update();
+        assert_eq!(listening(&app), (true, false));
+
+        app.world_mut().get_mut::<Camera>(camera).unwrap().is_active = true;
+        app.update();
+        assert_eq!(listening(&app), (false, true));
+        let listener = app.world().get::<SpatialListener>(camera).unwrap();
+        assert_eq!(listener.right_ear_offset.x, 1.0);
+
+        app.world_mut().get_mut::<Camera>(camera).unwrap().is_active = false;
+        app.update();
+        assert_eq!(listening(&app), (true, false));
+    }
+}
@@ -0,0 +1,139 @@
+//! Localized audio variants.
+//!
+//! Voice-over and some sound effects exist once per language. A
+//! [`LocalizedAudio`] bundles the variants of one sound, and
+//! [`PlayVoiceLine`](crate::PlayVoiceLine) and [`PlaySfx`](crate::PlaySfx)
+//! pick the variant for the [`CurrentAudioLanguage`] when they play.
+
+use bevy::prelude::*;
+use std::borrow::Cow;
+
+/// Resource selecting the language of localized audio.
+///
+/// Defaults to `"en"`. Changing it affects sounds played afterwards; sounds
+/// that are already playing keep their language.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::CurrentAudioLanguage;
+///
+/// fn apply_settings(settings: Res<Settings>, mut language: ResMut<CurrentAudioLanguage>) {
+///     *language = CurrentAudioLanguage::new(settings.voice_language.clone());
+/// }
+/// ```
+#[derive(Resource, Reflect, Debug, Clone, PartialEq, Eq, Hash)]
+#[reflect(Resource)]
+pub struct CurrentAudioLanguage(pub Cow<'static, str>);
+
+impl CurrentAudioLanguage {
+    /// Creates a new language selection.
+    #[must_use]
+    pub fn new(language: impl Into<Cow<'static, str>>) -> Self {
+        Self(language.into())
+    }
+
+    /// Returns the language code.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for CurrentAudioLanguage {
+    fn default() -> Self {
+        Self::new("en")
+    }
+}
+
+/// The per-language variants of a sound.
+///
+/// Languages without a variant fall back to the default handle.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{LocalizedAudio, PlayVoiceLine};
+///
+/// let greeting = LocalizedAudio::new(assets.load("vo/en/greeting.ogg"))
+///     .with_language("de", assets.load("vo/de/greeting.ogg"))
+///     .with_language("fr", assets.load("vo/fr/greeting.ogg"));
+/// messages.write(PlayVoiceLine::localized(greeting, GameVoice::Dialogue));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LocalizedAudio {
+    /// Handle used for languages without a variant.
+    pub default: Handle<AudioSource>,
+    /// Handles keyed by language code.
+    pub variants: Vec<(Cow<'static, str>, Handle<AudioSource>)>,
+}
+
+impl LocalizedAudio {
+    /// Creates a localized sound with its fallback handle.
+    #[must_use]
+    pub fn new(default: Handle<AudioSource>) -> Self {
+        Self {
+            default,
+            variants: Vec::new(),
+        }
+    }
+
+    /// Adds or replaces the variant for a language.
+    #[must_use]
+    pub fn with_language(
+        mut self,
+        language: impl Into<Cow<'static, str>>,
+        handle: Handle<AudioSource>,
+    ) -> Self {
+        let language = language.into();
+        self.variants.retain(|(existing, _)| *existing != language);
+        self.variants.push((language, handle));
+        self
+    }
+
+    /// Returns the handle for a language, or the default handle.
+    #[must_use]
+    pub fn get(&self, language: &str) -> &Handle<AudioSource> {
+        self.variants
+            .iter()
+            .find(|(existing, _)| existing == language)
+            .map_or(&self.default, |(_, handle)| handle)
+    }
+
+    /// Returns the handle for the current language.
+    #[must_use]
+    pub fn resolve(&self, language: &CurrentAudioLanguage) -> &Handle<AudioSource> {
+        self.get(language.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_language_with_fallback() {
+        let mut assets = Assets::<AudioSource>::default();
+        let mut source = || {
+            assets.add(AudioSource {
+                bytes: Vec::new().into(),
+            })
+        };
+        let english = source();
+        let german = source();
+
+        let audio = LocalizedAudio::new(english.clone()).with_language("de", german.clone());
+
+        assert_eq!(audio.resolve(&CurrentAudioLanguage::new("de")), &german);
+        assert_eq!(audio.resolve(&CurrentAudioLanguage::default()), &english);
+        assert_eq!(audio.get("ja"), &english);
+    }
+
+    #[test]
+    fn with_language_replaces_variant() {
+        let audio = LocalizedAudio::new(Handle::default())
+            .with_language("de", Handle::default())
+            .with_language("de", Handle::default());
+        assert_eq!(audio.variants.len(), 1);
+    }
+}
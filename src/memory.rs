@@ -0,0 +1,247 @@
+//! Playback position memory for music.
+//!
+//! When a music track stops or fades out, its position is stored per category
+//! so that [`PlayMusic::resume_from_memory`](crate::PlayMusic::resume_from_memory)
+//! can continue it later, for example when returning from a battle to the
+//! exploration theme.
+
+use bevy::prelude::*;
+use std::time::Duration;
+
+use crate::traits::MusicCategory;
+
+/// Remembered playback positions for music of category type `M`.
+///
+/// Positions are recorded automatically when a music entity is despawned
+/// while its sink still has audio left, and are keyed by category and audio
+/// source so a different track in the same category starts from the beginning.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::MusicMemory;
+///
+/// fn forget_exploration(mut memory: ResMut<MusicMemory<GameMusic>>) {
+///     memory.forget_category(GameMusic::Exploration);
+/// }
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct MusicMemory<M: MusicCategory> {
+    entries: Vec<MemoryEntry<M>>,
+}
+
+#[derive(Clone, Debug)]
+struct MemoryEntry<M> {
+    category: M,
+    source: AssetId<AudioSource>,
+    position: Duration,
+}
+
+impl<M: MusicCategory> Default for MusicMemory<M> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<M: MusicCategory> MusicMemory<M> {
+    /// Stores the position of a track, replacing any previous entry.
+    pub fn remember(
+        &mut self,
+        category: M,
+        source: impl Into<AssetId<AudioSource>>,
+        position: Duration,
+    ) {
+        let source = source.into();
+        self.forget(category, source);
+        self.entries.push(MemoryEntry {
+            category,
+            source,
+            position,
+        });
+    }
+
+    /// Returns the remembered position of a track.
+    #[must_use]
+    pub fn position(
+        &self,
+        category: M,
+        source: impl Into<AssetId<AudioSource>>,
+    ) -> Option<Duration> {
+        let source = source.into();
+        self.entries
+            .iter()
+            .find(|entry| entry.category == category && entry.source == source)
+            .map(|entry| entry.position)
+    }
+
+    /// Forgets the position of a track.
+    pub fn forget(&mut self, category: M, source: impl Into<AssetId<AudioSource>>) {
+        let source = source.into();
+        self.entries
+            .retain(|entry| entry.category != category || entry.source != source);
+    }
+
+    /// Forgets the positions of every track in a category.
+    pub fn forget_category(&mut self, category: M) {
+        self.entries.retain(|entry| entry.category != category);
+    }
+
+    /// Forgets all positions.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::tests::{MockBackend, MockSink};
+    use crate::tests::{test_app, TestMusic, TestPlugin};
+
+    use crate::{PlayMusic, SourceDurations, StartPosition};
+
+    #[test]
+    fn remembers_per_category_and_source() {
+        let assets = Assets::<AudioSource>::default();
+        let forest = assets.reserve_handle();
+        let cave = assets.reserve_handle();
+
+        let mut memory = MusicMemory::default();
+        memory.remember(TestMusic::Main, &forest, Duration::from_secs(42));
+
+        assert_eq!(
+            memory.position(TestMusic::Main, &forest),
+            Some(Duration::from_secs(42))
+        );
+        assert_eq!(memory.position(TestMusic::Main, &cave), None);
+        assert_eq!(memory.position(TestMusic::Combat, &forest), None);
+    }
+
+    #[test]
+    fn remember_replaces_previous_position() {
+        let mut memory = MusicMemory::default();
+        let source = AssetId::<AudioSource>::default();
+        memory.remember(TestMusic::Main, source, Duration::from_secs(1));
+        memory.remember(TestMusic::Main, source, Duration::from_secs(2));
+
+        assert_eq!(
+            memory.position(TestMusic::Main, source),
+            Some(Duration::from_secs(2))
+        );
+    }
+
+    #[test]
+    fn forget_category_removes_all_tracks() {
+        let mut memory = MusicMemory::default();
+        let source = AssetId::<AudioSource>::default();
+        memory.remember(TestMusic::Main, source, Duration::from_secs(1));
+        memory.remember(TestMusic::Combat, source, Duration::from_secs(1));
+
+        memory.forget_category(TestMusic::Main);
+        assert_eq!(memory.position(TestMusic::Main, source), None);
+        assert!(memory.position(TestMusic::Combat, source).is_some());
+    }
+
+    #[test]
+    fn play_music_resumes_from_memory() {
+        let mut app = test_app(TestPlugin::default());
+
+        let handle = Handle::<AudioSource>::default();
+        let position = Duration::from_secs(30);
+        app.world_mut()
+            .resource_mut::<MusicMemory<TestMusic>>()
+            .remember(TestMusic::Main, &handle, position);
+
+        app.world_mut()
+            .write_message(PlayMusic::new(handle, TestMusic::Main).resume_from_memory());
+        app.update();
+
+        let mut query = app
+            .world_mut()
+            .query::<(&PlaybackSettings, &StartPosition)>();
+        let (playback, start) = query.single(app.world()).unwrap();
+        assert_eq!(playback.start_position, None);
+        assert_eq!(*start, StartPosition(position));
+    }
+
+    /// App playing music through [`MockBackend`] sinks, which tests insert by
+    /// hand and move to any position.
+    fn mock_music_app() -> App {
+        let mut app = test_app(TestPlugin::default());
+        app.add_systems(Last, crate::systems::seek_start_positions::<MockBackend>);
+        app.add_observer(crate::systems::remember_music_position::<TestMusic, MockBackend>);
+        app
+    }
+
+    /// Plays a track resumed from memory and gives it a sink that has been
+    /// seeked to its start position.
+    fn play_mock_music(app: &mut App, handle: &Handle<AudioSource>) -> Entity {
+        app.world_mut()
+            .write_message(PlayMusic::new(handle.clone(), TestMusic::Main).resume_from_memory());
+        app.update();
+        let mut query = app
+            .world_mut()
+            .query_filtered::<Entity, (With<TestMusic>, Without<MockSink>)>();
+        let entity = query.single(app.world()).unwrap();
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(MockSink::default());
+        app.update();
+        entity
+    }
+
+    #[test]
+    fn resumed_music_remembers_its_full_position_again() {
+        let mut app = mock_music_app();
+        let handle = Handle::<AudioSource>::default();
+        let memory = |app: &App| {
+            app.world()
+                .resource::<MusicMemory<TestMusic>>()
+                .position(TestMusic::Main, &handle)
+        };
+
+        let entity = play_mock_music(&mut app, &handle);
+        app.world_mut()
+            .get_mut::<MockSink>(entity)
+            .unwrap()
+            .position = Duration::from_secs(10);
+        app.world_mut().despawn(entity);
+        assert_eq!(memory(&app), Some(Duration::from_secs(10)));
+
+        // The resumed sink starts at the remembered position and keeps counting
+        let entity = play_mock_music(&mut app, &handle);
+        let mut sink = app.world_mut().get_mut::<MockSink>(entity).unwrap();
+        assert_eq!(sink.seeks, [Duration::from_secs(10)]);
+        sink.position += Duration::from_secs(5);
+        app.world_mut().despawn(entity);
+        assert_eq!(memory(&app), Some(Duration::from_secs(15)));
+
+        let entity = play_mock_music(&mut app, &handle);
+        let sink = app.world().get::<MockSink>(entity).unwrap();
+        assert_eq!(sink.seeks, [Duration::from_secs(15)]);
+    }
+
+    #[test]
+    fn looped_music_remembers_its_position_in_the_loop() {
+        let mut app = mock_music_app();
+        let handle = Handle::<AudioSource>::default();
+        app.world_mut()
+            .resource_mut::<SourceDurations>()
+            .insert(&handle, Duration::from_secs(60));
+
+        let entity = play_mock_music(&mut app, &handle);
+        app.world_mut()
+            .get_mut::<MockSink>(entity)
+            .unwrap()
+            .position = Duration::from_secs(130);
+        app.world_mut().despawn(entity);
+
+        let memory = app.world().resource::<MusicMemory<TestMusic>>();
+        assert_eq!(
+            memory.position(TestMusic::Main, &handle),
+            Some(Duration::from_secs(10))
+        );
+    }
+}
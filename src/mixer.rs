@@ -0,0 +1,657 @@
+//! Runtime mixer state layered on top of the user's audio config.
+//!
+//! The mixer lets categories be muted, soloed, or attenuated by snapshots
+//! without touching the config resource, which is usually persisted as the
+//! player's settings. This is handy for debugging, accessibility toggles, and
+//! situational mixes such as a muffled pause menu.
+
+use bevy::prelude::*;
+use std::borrow::Cow;
+use std::time::Duration;
+
+use crate::environment::EnvironmentProfile;
+use crate::traits::AudioCategory;
+
+/// Per-category mute, solo, and snapshot state for the category type `A`.
+///
+/// Registered for every music and sound effect category type, and updated by
+/// the [`MuteCategory`](crate::MuteCategory),
+/// [`SoloCategory`](crate::SoloCategory),
+/// [`PushMixerSnapshot`](crate::PushMixerSnapshot), and
+/// [`PopMixerSnapshot`](crate::PopMixerSnapshot) messages. Solo is scoped to the
+/// category type: soloing a music category silences the other music
+/// categories but leaves sound effects untouched.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::CategoryMixerState;
+///
+/// fn mute_ambience(mut mixer: ResMut<CategoryMixerState<GameSfx>>) {
+///     mixer.set_muted(GameSfx::Ambience, true);
+/// }
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct CategoryMixerState<A: AudioCategory> {
+    muted: Vec<A>,
+    soloed: Vec<A>,
+    snapshots: Vec<ActiveSnapshot<A>>,
+    duck: f32,
+    stinger_duck: f32,
+    stinger_duck_volume: f32,
+    background: f32,
+    muffle: f32,
+    environment: EnvironmentProfile<A>,
+    limiter: f32,
+}
+
+impl<A: AudioCategory> Default for CategoryMixerState<A> {
+    fn default() -> Self {
+        Self {
+            muted: Vec::new(),
+            soloed: Vec::new(),
+            snapshots: Vec::new(),
+            duck: 1.0,
+            stinger_duck: 0.0,
+            stinger_duck_volume: 1.0,
+            background: 1.0,
+            muffle: 1.0,
+            environment: EnvironmentProfile::default(),
+            limiter: 1.0,
+        }
+    }
+}
+
+impl<A: AudioCategory> CategoryMixerState<A> {
+    /// Returns `true` if the category is muted.
+    #[must_use]
+    pub fn is_muted(&self, category: A) -> bool {
+        self.muted.contains(&category)
+    }
+
+    /// Returns `true` if the category is soloed.
+    #[must_use]
+    pub fn is_soloed(&self, category: A) -> bool {
+        self.soloed.contains(&category)
+    }
+
+    /// Returns `true` if any category of this type is soloed.
+    #[must_use]
+    pub fn has_solo(&self) -> bool {
+        !self.soloed.is_empty()
+    }
+
+    /// Mutes or unmutes a category.
+    pub fn set_muted(&mut self, category: A, muted: bool) {
+        set_member(&mut self.muted, category, muted);
+    }
+
+    /// Solos or unsolos a category.
+    pub fn set_soloed(&mut self, category: A, soloed: bool) {
+        set_member(&mut self.soloed, category, soloed);
+    }
+
+    /// Clears all mute and solo state.
+    pub fn clear(&mut self) {
+        self.muted.clear();
+        self.soloed.clear();
+    }
+
+    /// Pushes a snapshot, blending it in over `transition`.
+    pub fn push_snapshot(&mut self, snapshot: MixerSnapshot<A>, transition: Duration) {
+        self.snapshots.push(ActiveSnapshot {
+            snapshot,
+            from: 0.0,
+            to: 1.0,
+            timer: Timer::new(transition, TimerMode::Once),
+        });
+    }
+
+    /// Pops the most recently pushed snapshot, blending it out over `transition`.
+    ///
+    /// Returns `false` if no snapshot was active.
+    pub fn pop_snapshot(&mut self, transition: Duration) -> bool {
+        let index = self.snapshots.iter().rposition(|active| active.to > 0.0);
+        self.release(index, transition)
+    }
+
+    /// Removes the most recently pushed snapshot with the given name, blending
+    /// it out over `transition`.
+    ///
+    /// Returns `false` if no such snapshot was active.
+    pub fn remove_snapshot(&mut self, name: &str, transition: Duration) -> bool {
+        let index = self
+            .snapshots
+            .iter()
+            .rposition(|active| active.to > 0.0 && active.snapshot.name == name);
+        self.release(index, transition)
+    }
+
+    /// Returns the names of active snapshots, oldest first.
+    ///
+    /// Snapshots that are blending out are not included.
+    pub fn snapshot_names(&self) -> impl Iterator<Item = &str> {
+        self.snapshots
+            .iter()
+            .filter(|active| active.to > 0.0)
+            .map(|active| active.snapshot.name.as_ref())
+    }
+
+    /// Returns `true` while any snapshot is blending in or out.
+    #[must_use]
+    pub fn is_transitioning(&self) -> bool {
+        self.snapshots
+            .iter()
+            .any(|active| active.to <= 0.0 || !active.timer.is_finished())
+    }
+
+    /// Advances snapshot transitions and drops snapshots that finished blending out.
+    pub fn tick(&mut self, delta: Duration) {
+        for active in &mut self.snapshots {
+            active.timer.tick(delta);
+        }
+        self.snapshots
+            .retain(|active| active.to > 0.0 || !active.timer.is_finished());
+    }
+
+    /// Returns the gain currently applied by dialogue ducking.
+    #[must_use]
+    pub fn duck_gain(&self) -> f32 {
+        self.duck
+    }
+
+    /// Sets the gain applied by dialogue ducking to every category.
+    pub fn set_duck_gain(&mut self, gain: f32) {
+        self.duck = gain.clamp(0.0, 1.0);
+    }
+
+    /// Returns how far music is ducked for stingers, from `0.0` (not ducked)
+    /// to `1.0` (fully ducked).
+    #[must_use]
+    pub fn stinger_duck(&self) -> f32 {
+        self.stinger_duck
+    }
+
+    /// Sets how far music is ducked for stingers.
+    pub fn set_stinger_duck(&mut self, progress: f32) {
+        self.stinger_duck = progress.clamp(0.0, 1.0);
+    }
+
+    /// Returns the volume multiplier of fully ducked music without a
+    /// [`TrackInfo::duck_db`](crate::TrackInfo::duck_db) of its own.
+    #[must_use]
+    pub fn stinger_duck_volume(&self) -> f32 {
+        self.stinger_duck_volume
+    }
+
+    /// Sets the volume multiplier of fully ducked music, usually the
+    /// [`Stinger::duck_volume`](crate::Stinger::duck_volume) of the strongest
+    /// playing stinger.
+    pub fn set_stinger_duck_volume(&mut self, volume: f32) {
+        self.stinger_duck_volume = volume.clamp(0.0, 1.0);
+    }
+
+    /// Returns the gain stinger ducking currently applies to music fully
+    /// ducked to `volume`, or to the [`stinger_duck_volume`](Self::stinger_duck_volume)
+    /// when `None`.
+    #[must_use]
+    pub fn stinger_duck_gain(&self, volume: Option<f32>) -> f32 {
+        let volume = volume.unwrap_or(self.stinger_duck_volume);
+        1.0 + (volume - 1.0) * self.stinger_duck
+    }
+
+    /// Returns the gain applied while the app is out of focus.
+    #[must_use]
+    pub fn background_gain(&self) -> f32 {
+        self.background
+    }
+
+    /// Sets the gain applied while the app is out of focus to categories
+    /// that do not [play in the background](AudioCategory::plays_in_background).
+    pub fn set_background_gain(&mut self, gain: f32) {
+        self.background = gain.clamp(0.0, 1.0);
+    }
+
+    /// Returns the gain applied while audio is muffled.
+    #[must_use]
+    pub fn muffle_gain(&self) -> f32 {
+        self.muffle
+    }
+
+    /// Sets the gain applied while audio is muffled to categories that
+    /// [muffle](AudioCategory::muffles).
+    pub fn set_muffle_gain(&mut self, gain: f32) {
+        self.muffle = gain.clamp(0.0, 1.0);
+    }
+
+    /// Returns the environment profile whose gains are applied.
+    #[must_use]
+    pub fn environment(&self) -> &EnvironmentProfile<A> {
+        &self.environment
+    }
+
+    /// Sets the environment profile whose gains are applied, usually part
+    /// way through a ramp between two profiles.
+    pub fn set_environment(&mut self, profile: EnvironmentProfile<A>) {
+        self.environment = profile;
+    }
+
+    /// Returns the gain applied by the [`MixLimiter`](crate::MixLimiter).
+    #[must_use]
+    pub fn limiter_gain(&self) -> f32 {
+        self.limiter
+    }
+
+    /// Sets the gain applied by the mix limiter to every category.
+    pub fn set_limiter_gain(&mut self, gain: f32) {
+        self.limiter = gain.clamp(0.0, 1.0);
+    }
+
+    fn release(&mut self, index: Option<usize>, transition: Duration) -> bool {
+        let Some(active) = index.and_then(|index| self.snapshots.get_mut(index)) else {
+            return false;
+        };
+        active.from = active.weight();
+        active.to = 0.0;
+        active.timer = Timer::new(transition, TimerMode::Once);
+        true
+    }
+
+    /// Returns `true` if the category can currently be heard.
+    ///
+    /// A category is audible when it is not muted and either nothing is
+    /// soloed or it is soloed itself.
+    #[must_use]
+    pub fn is_audible(&self, category: A) -> bool {
+        !self.is_muted(category) && (!self.has_solo() || self.is_soloed(category))
+    }
+
+    /// Returns the gain the mixer applies to the category.
+    ///
+    /// This is `0.0` for inaudible categories, otherwise the product of the
+    /// gains of all active snapshots, weighted by their blend progress, the
+    /// dialogue duck gain, the background gain, the muffle gain, the
+    /// environment gain, and the limiter gain.
+    #[must_use]
+    pub fn gain(&self, category: A) -> f32 {
+        self.snapshot_gain(category) * self.duck
+    }
+
+    /// Returns the gain the mixer applies to the category, without the
+    /// dialogue duck gain.
+    ///
+    /// This is `0.0` for inaudible categories, otherwise the product of the
+    /// gains of all active snapshots, weighted by their blend progress, the
+    /// background gain, the muffle gain, the environment gain, and the
+    /// limiter gain.
+    #[must_use]
+    pub fn snapshot_gain(&self, category: A) -> f32 {
+        if !self.is_audible(category) {
+            return 0.0;
+        }
+        let snapshots: f32 = self
+            .snapshots
+            .iter()
+            .map(|active| 1.0 + (active.snapshot.gain(category) - 1.0) * active.weight())
+            .product();
+        let background = if category.plays_in_background() {
+            1.0
+        } else {
+            self.background
+        };
+        let muffle = if category.muffles() { self.muffle } else { 1.0 };
+        snapshots * background * muffle * self.environment.gain(category) * self.limiter
+    }
+}
+
+/// A named set of per-category gain offsets.
+///
+/// Snapshots are pushed onto a [`CategoryMixerState`] and applied on top of
+/// the user config until popped. Categories without an explicit gain use the
+/// snapshot's default gain.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{MixerSnapshot, PushMixerSnapshot};
+///
+/// // Dampen everything except UI sounds while the pause menu is open
+/// let pause_menu = MixerSnapshot::new("PauseMenu")
+///     .with_default_gain(0.3)
+///     .with_gain(GameSfx::UI, 1.0);
+/// messages.write(PushMixerSnapshot::new(pause_menu, Duration::from_millis(300)));
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MixerSnapshot<A: AudioCategory> {
+    /// Name used to remove the snapshot later.
+    pub name: Cow<'static, str>,
+    /// Linear gain for categories without an explicit entry.
+    pub default_gain: f32,
+    /// Linear gain per category.
+    pub gains: Vec<(A, f32)>,
+}
+
+impl<A: AudioCategory> MixerSnapshot<A> {
+    /// Creates a snapshot that leaves every category unchanged.
+    #[must_use]
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            default_gain: 1.0,
+            gains: Vec::new(),
+        }
+    }
+
+    /// Sets the gain for categories without an explicit entry.
+    #[must_use]
+    pub fn with_default_gain(mut self, gain: f32) -> Self {
+        self.default_gain = gain.max(0.0);
+        self
+    }
+
+    /// Sets the gain for a category.
+    #[must_use]
+    pub fn with_gain(mut self, category: A, gain: f32) -> Self {
+        self.gains.retain(|(c, _)| *c != category);
+        self.gains.push((category, gain.max(0.0)));
+        self
+    }
+
+    /// Returns the gain this snapshot applies to the category.
+    #[must_use]
+    pub fn gain(&self, category: A) -> f32 {
+        self.gains
+            .iter()
+            .find(|(c, _)| *c == category)
+            .map_or(self.default_gain, |(_, gain)| *gain)
+    }
+}
+
+/// Runtime playback speed multipliers for the category type `A`.
+///
+/// Registered for every music, sound effect, and voice category type. Changes
+/// are applied to every playing sound of the category, e.g. slowing gameplay
+/// sounds during bullet-time while UI sounds keep their speed. Categories
+/// without a multiplier play at `1.0`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::CategorySpeed;
+///
+/// fn enter_bullet_time(mut speed: ResMut<CategorySpeed<GameSfx>>) {
+///     speed.set(GameSfx::Gameplay, 0.8);
+/// }
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct CategorySpeed<A: AudioCategory> {
+    speeds: Vec<(A, f32)>,
+    muffle: f32,
+    environment: EnvironmentProfile<A>,
+}
+
+impl<A: AudioCategory> Default for CategorySpeed<A> {
+    fn default() -> Self {
+        Self {
+            speeds: Vec::new(),
+            muffle: 1.0,
+            environment: EnvironmentProfile::default(),
+        }
+    }
+}
+
+impl<A: AudioCategory> CategorySpeed<A> {
+    /// Returns the speed multiplier of a category, including the
+    /// environment speed and the muffle speed for categories that
+    /// [muffle](AudioCategory::muffles).
+    #[must_use]
+    pub fn get(&self, category: A) -> f32 {
+        let speed = self
+            .speeds
+            .iter()
+            .find(|(c, _)| *c == category)
+            .map_or(1.0, |(_, speed)| *speed)
+            * self.environment.speed(category);
+        if category.muffles() {
+            speed * self.muffle
+        } else {
+            speed
+        }
+    }
+
+    /// Returns the speed multiplier applied while audio is muffled.
+    #[must_use]
+    pub fn muffle_speed(&self) -> f32 {
+        self.muffle
+    }
+
+    /// Sets the speed multiplier applied while audio is muffled.
+    pub fn set_muffle_speed(&mut self, speed: f32) {
+        self.muffle = speed;
+    }
+
+    /// Returns the environment profile whose speeds are applied.
+    #[must_use]
+    pub fn environment(&self) -> &EnvironmentProfile<A> {
+        &self.environment
+    }
+
+    /// Sets the environment profile whose speeds are applied.
+    pub fn set_environment(&mut self, profile: EnvironmentProfile<A>) {
+        self.environment = profile;
+    }
+
+    /// Sets the speed multiplier of a category.
+    pub fn set(&mut self, category: A, speed: f32) {
+        self.speeds.retain(|(c, _)| *c != category);
+        if speed != 1.0 {
+            self.speeds.push((category, speed));
+        }
+    }
+
+    /// Resets every category to normal speed.
+    pub fn clear(&mut self) {
+        self.speeds.clear();
+    }
+}
+
+/// A snapshot on the mixer stack together with its blend state.
+#[derive(Clone, Debug)]
+struct ActiveSnapshot<A: AudioCategory> {
+    snapshot: MixerSnapshot<A>,
+    from: f32,
+    to: f32,
+    timer: Timer,
+}
+
+impl<A: AudioCategory> ActiveSnapshot<A> {
+    fn weight(&self) -> f32 {
+        self.from + (self.to - self.from) * self.timer.fraction()
+    }
+}
+
+fn set_member<A: PartialEq>(list: &mut Vec<A>, category: A, present: bool) {
+    let contains = list.contains(&category);
+    if present && !contains {
+        list.push(category);
+    } else if !present && contains {
+        list.retain(|c| *c != category);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin, TestSfx};
+    use crate::{MusicBundle, MuteCategory, SfxBundle, TestAudioPlugin};
+
+    #[test]
+    fn everything_audible_by_default() {
+        let mixer = CategoryMixerState::<TestSfx>::default();
+        assert!(mixer.is_audible(TestSfx::UI));
+        assert!((mixer.gain(TestSfx::World) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn mute_silences_category() {
+        let mut mixer = CategoryMixerState::default();
+        mixer.set_muted(TestSfx::World, true);
+        mixer.set_muted(TestSfx::World, true);
+
+        assert!(!mixer.is_audible(TestSfx::World));
+        assert!(mixer.is_audible(TestSfx::UI));
+
+        mixer.set_muted(TestSfx::World, false);
+        assert!(mixer.is_audible(TestSfx::World));
+    }
+
+    #[test]
+    fn solo_silences_other_categories() {
+        let mut mixer = CategoryMixerState::default();
+        mixer.set_soloed(TestSfx::UI, true);
+        mixer.set_soloed(TestSfx::Ambience, true);
+
+        assert!(mixer.is_audible(TestSfx::UI));
+        assert!(mixer.is_audible(TestSfx::Ambience));
+        assert!(!mixer.is_audible(TestSfx::World));
+
+        mixer.clear();
+        assert!(mixer.is_audible(TestSfx::World));
+    }
+
+    #[test]
+    fn mute_wins_over_solo() {
+        let mut mixer = CategoryMixerState::default();
+        mixer.set_soloed(TestSfx::UI, true);
+        mixer.set_muted(TestSfx::UI, true);
+
+        assert!(!mixer.is_audible(TestSfx::UI));
+    }
+
+    #[test]
+    fn snapshot_gain_falls_back_to_default() {
+        let snapshot = MixerSnapshot::new("PauseMenu")
+            .with_default_gain(0.3)
+            .with_gain(TestSfx::UI, 1.0);
+
+        assert!((snapshot.gain(TestSfx::UI) - 1.0).abs() < f32::EPSILON);
+        assert!((snapshot.gain(TestSfx::World) - 0.3).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn snapshot_blends_in_and_out() {
+        let mut mixer = CategoryMixerState::default();
+        let snapshot = MixerSnapshot::new("PauseMenu").with_default_gain(0.0);
+        mixer.push_snapshot(snapshot, Duration::from_millis(100));
+
+        assert!((mixer.gain(TestSfx::World) - 1.0).abs() < f32::EPSILON);
+        assert!(mixer.is_transitioning());
+
+        mixer.tick(Duration::from_millis(50));
+        assert!((mixer.gain(TestSfx::World) - 0.5).abs() < 0.001);
+
+        mixer.tick(Duration::from_millis(50));
+        assert!(mixer.gain(TestSfx::World).abs() < f32::EPSILON);
+        assert!(!mixer.is_transitioning());
+
+        assert!(mixer.remove_snapshot("PauseMenu", Duration::from_millis(100)));
+        assert_eq!(mixer.snapshot_names().count(), 0);
+        mixer.tick(Duration::from_millis(50));
+        assert!((mixer.gain(TestSfx::World) - 0.5).abs() < 0.001);
+
+        mixer.tick(Duration::from_millis(50));
+        assert!((mixer.gain(TestSfx::World) - 1.0).abs() < f32::EPSILON);
+        assert!(!mixer.is_transitioning());
+    }
+
+    #[test]
+    fn stacked_snapshots_multiply() {
+        let mut mixer = CategoryMixerState::default();
+        mixer.push_snapshot(
+            MixerSnapshot::new("A").with_default_gain(0.5),
+            Duration::ZERO,
+        );
+        mixer.push_snapshot(
+            MixerSnapshot::new("B").with_default_gain(0.5),
+            Duration::ZERO,
+        );
+        assert!((mixer.gain(TestSfx::UI) - 0.25).abs() < f32::EPSILON);
+
+        assert!(mixer.pop_snapshot(Duration::ZERO));
+        mixer.tick(Duration::ZERO);
+        assert_eq!(mixer.snapshot_names().collect::<Vec<_>>(), vec!["A"]);
+        assert!((mixer.gain(TestSfx::UI) - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn duck_gain_scales_every_category() {
+        let mut mixer = CategoryMixerState::default();
+        mixer.set_duck_gain(0.5);
+        assert!((mixer.gain(TestSfx::UI) - 0.5).abs() < f32::EPSILON);
+        mixer.set_muted(TestSfx::UI, true);
+        assert!(mixer.gain(TestSfx::UI).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn stinger_duck_blends_towards_duck_volume() {
+        let mut mixer = CategoryMixerState::<TestMusic>::default();
+        mixer.set_stinger_duck_volume(0.2);
+        assert!((mixer.stinger_duck_gain(None) - 1.0).abs() < f32::EPSILON);
+
+        mixer.set_stinger_duck(0.5);
+        assert!((mixer.stinger_duck_gain(None) - 0.6).abs() < 1e-6);
+        assert!((mixer.stinger_duck_gain(Some(0.0)) - 0.5).abs() < 1e-6);
+        assert!((mixer.stinger_duck_gain(Some(1.0)) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn pop_without_snapshots_returns_false() {
+        let mut mixer = CategoryMixerState::<TestSfx>::default();
+        assert!(!mixer.pop_snapshot(Duration::ZERO));
+    }
+
+    #[test]
+    fn mute_message_updates_mixer_state() {
+        let mut app = test_app(TestPlugin::default());
+
+        app.world_mut()
+            .write_message(MuteCategory::mute(TestSfx::UI));
+        app.update();
+
+        assert!(app
+            .world()
+            .resource::<CategoryMixerState<TestSfx>>()
+            .is_muted(TestSfx::UI));
+    }
+
+    #[test]
+    fn category_speed_scales_only_its_category() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        let sfx = app
+            .world_mut()
+            .spawn(SfxBundle::new(Handle::default(), TestSfx::UI))
+            .id();
+        let music = app
+            .world_mut()
+            .spawn(MusicBundle::new(Handle::default(), TestMusic::Main))
+            .id();
+        app.update();
+        app.update();
+
+        app.world_mut()
+            .resource_mut::<CategorySpeed<TestSfx>>()
+            .set(TestSfx::UI, 0.8);
+        app.update();
+        let speed = |entity| app.world().get::<AudioSink>(entity).unwrap().speed();
+        assert!((speed(sfx) - 0.8).abs() < f32::EPSILON);
+        assert!((speed(music) - 1.0).abs() < f32::EPSILON);
+
+        app.world_mut()
+            .resource_mut::<CategorySpeed<TestSfx>>()
+            .clear();
+        app.update();
+        let speed = |entity| app.world().get::<AudioSink>(entity).unwrap().speed();
+        assert!((speed(sfx) - 1.0).abs() < f32::EPSILON);
+    }
+}
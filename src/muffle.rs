@@ -0,0 +1,247 @@
+//! Muffled audio behind menus.
+//!
+//! While any entity carries a [`MuffleAudio`] component, e.g. the root of a
+//! pause menu, the categories that [muffle](AudioCategory::muffles) are
+//! turned down and slowed slightly through the mixer, approximating the
+//! low-pass sound of a game heard through a menu. `bevy_audio` has no
+//! filters, so the effect is made of gain and speed only. Despawning the last
+//! [`MuffleAudio`] blends the effect out again.
+
+use bevy::prelude::*;
+use std::time::Duration;
+
+use crate::mixer::{CategoryMixerState, CategorySpeed};
+use crate::traits::AudioCategory;
+
+/// Default gain applied to muffled categories.
+pub const DEFAULT_MUFFLE_GAIN: f32 = 0.35;
+
+/// Default playback speed of muffled categories.
+pub const DEFAULT_MUFFLE_SPEED: f32 = 0.95;
+
+/// Default time the muffle takes to blend in or out.
+pub const DEFAULT_MUFFLE_TRANSITION: Duration = Duration::from_millis(300);
+
+/// Muffles audio while the entity exists.
+///
+/// When several entities muffle at once, the strongest gain and speed apply.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::MuffleAudio;
+///
+/// commands.spawn((PauseMenu, Node::default(), MuffleAudio::default()));
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+pub struct MuffleAudio {
+    /// Linear gain applied to muffled categories.
+    pub gain: f32,
+    /// Playback speed multiplier of muffled categories.
+    pub speed: f32,
+    /// Time the muffle takes to blend in or out.
+    pub transition: Duration,
+}
+
+impl Default for MuffleAudio {
+    fn default() -> Self {
+        Self {
+            gain: DEFAULT_MUFFLE_GAIN,
+            speed: DEFAULT_MUFFLE_SPEED,
+            transition: DEFAULT_MUFFLE_TRANSITION,
+        }
+    }
+}
+
+impl MuffleAudio {
+    /// Creates a muffle with the given gain and the default speed and
+    /// transition.
+    #[must_use]
+    pub fn new(gain: f32) -> Self {
+        Self {
+            gain: gain.clamp(0.0, 1.0),
+            ..default()
+        }
+    }
+
+    /// Sets the playback speed multiplier of muffled categories.
+    #[must_use]
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Sets the time the muffle takes to blend in or out.
+    #[must_use]
+    pub fn with_transition(mut self, transition: Duration) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    /// Combines two muffles, keeping the stronger gain, speed change, and
+    /// the longer transition.
+    #[must_use]
+    fn strongest(self, other: Self) -> Self {
+        Self {
+            gain: self.gain.min(other.gain),
+            speed: if (self.speed - 1.0).abs() >= (other.speed - 1.0).abs() {
+                self.speed
+            } else {
+                other.speed
+            },
+            transition: self.transition.max(other.transition),
+        }
+    }
+}
+
+/// Resource reporting how far the muffle is blended in.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource)]
+pub struct AudioMuffle {
+    amount: f32,
+    settings: MuffleAudio,
+}
+
+impl Default for AudioMuffle {
+    fn default() -> Self {
+        Self {
+            amount: 0.0,
+            settings: MuffleAudio::default(),
+        }
+    }
+}
+
+impl AudioMuffle {
+    /// Returns how far the muffle is blended in, from `0.0` to `1.0`.
+    #[must_use]
+    pub fn amount(&self) -> f32 {
+        self.amount
+    }
+
+    /// Returns the gain currently applied to muffled categories.
+    #[must_use]
+    pub fn gain(&self) -> f32 {
+        1.0 + (self.settings.gain - 1.0) * self.amount
+    }
+
+    /// Returns the speed multiplier currently applied to muffled categories.
+    #[must_use]
+    pub fn speed(&self) -> f32 {
+        1.0 + (self.settings.speed - 1.0) * self.amount
+    }
+}
+
+/// Blends the muffle in while any [`MuffleAudio`] exists and out once none
+/// is left.
+///
+/// The settings of the last muffle are kept while blending out.
+pub fn update_audio_muffle(
+    time: Res<Time<Real>>,
+    muffles: Query<&MuffleAudio>,
+    mut muffle: ResMut<AudioMuffle>,
+) {
+    let active = muffles.iter().copied().reduce(MuffleAudio::strongest);
+    let settings = active.unwrap_or(muffle.settings);
+    let target = if active.is_some() { 1.0 } else { 0.0 };
+    let amount = if settings.transition.is_zero() {
+        target
+    } else {
+        let max_step = time.delta_secs() / settings.transition.as_secs_f32();
+        if muffle.amount > target {
+            (muffle.amount - max_step).max(target)
+        } else {
+            (muffle.amount + max_step).min(target)
+        }
+    };
+    muffle.set_if_neq(AudioMuffle { amount, settings });
+}
+
+/// Applies the [`AudioMuffle`] gain and speed to the mixer and speeds of
+/// category type `A`.
+pub fn apply_audio_muffle<A: AudioCategory>(
+    muffle: Res<AudioMuffle>,
+    mut mixer: ResMut<CategoryMixerState<A>>,
+    mut speeds: ResMut<CategorySpeed<A>>,
+) {
+    if mixer.muffle_gain() != muffle.gain() {
+        mixer.set_muffle_gain(muffle.gain());
+    }
+    if speeds.muffle_speed() != muffle.speed() {
+        speeds.set_muffle_speed(muffle.speed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestConfig, TestMusic, TestPlugin, TestSfx};
+    use crate::{PlayMusic, PlaySfx, TestAudioPlugin, VolumeRamp};
+
+    #[test]
+    fn strongest_muffle_wins() {
+        let light = MuffleAudio::new(0.8).with_speed(1.0);
+        let heavy = MuffleAudio::new(0.2)
+            .with_speed(0.9)
+            .with_transition(Duration::from_millis(100));
+        let combined = light.strongest(heavy);
+        assert!((combined.gain - 0.2).abs() < f32::EPSILON);
+        assert!((combined.speed - 0.9).abs() < f32::EPSILON);
+        assert_eq!(combined.transition, DEFAULT_MUFFLE_TRANSITION);
+
+        let half = AudioMuffle {
+            amount: 0.5,
+            settings: heavy,
+        };
+        assert!((half.gain() - 0.6).abs() < 0.0001);
+        assert!((half.speed() - 0.95).abs() < 0.0001);
+    }
+
+    #[test]
+    fn muffle_audio_dampens_non_ui_categories() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.insert_resource(TestConfig::FULL);
+        app.insert_resource(VolumeRamp::INSTANT);
+        app.world_mut()
+            .write_message(PlayMusic::new(Handle::default(), TestMusic::Main));
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI));
+        app.update();
+        app.update();
+
+        let menu = app
+            .world_mut()
+            .spawn(
+                MuffleAudio::new(0.25)
+                    .with_speed(0.9)
+                    .with_transition(Duration::ZERO),
+            )
+            .id();
+        app.update();
+        app.update();
+        let volumes = |app: &mut App| {
+            let world = app.world_mut();
+            let mut music = world.query_filtered::<&AudioSink, With<TestMusic>>();
+            let music = music.single(world).unwrap();
+            let music = (
+                crate::systems::extract_linear_volume(music.volume()),
+                music.speed(),
+            );
+            let mut sfx = world.query_filtered::<&AudioSink, With<TestSfx>>();
+            let sfx = crate::systems::extract_linear_volume(sfx.single(world).unwrap().volume());
+            (music, sfx)
+        };
+        let ((music, speed), sfx) = volumes(&mut app);
+        assert!((music - 0.25).abs() < 0.0001);
+        assert!((speed - 0.9).abs() < 0.0001);
+        assert!((sfx - 1.0).abs() < 0.0001);
+
+        app.world_mut().despawn(menu);
+        app.update();
+        app.update();
+        let ((music, speed), sfx) = volumes(&mut app);
+        assert!((music - 1.0).abs() < 0.0001);
+        assert!((speed - 1.0).abs() < 0.0001);
+        assert!((sfx - 1.0).abs() < 0.0001);
+    }
+}
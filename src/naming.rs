@@ -0,0 +1,203 @@
+//! Readable names for audio entities.
+//!
+//! [`AudioNamingPlugin`] gives every music and sound effect entity a [`Name`]
+//! built from its source path and category, so inspectors and logs show
+//! `Sfx: ui/click.ogg (UI)` instead of anonymous entities.
+
+use bevy::prelude::*;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::components::WaitForLoad;
+use crate::tone::Tone;
+use crate::traits::{MusicCategory, SfxCategory};
+use crate::MsgAudioSet;
+
+/// Plugin naming the audio entities of music type `M` and sound effect type
+/// `S`.
+///
+/// Entities that already have a [`Name`], e.g. one added with
+/// [`PlaySfx::with_components`](crate::PlaySfx::with_components), keep it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::AudioNamingPlugin;
+///
+/// app.add_plugins(AudioNamingPlugin::<GameMusic, GameSfx>::default());
+/// ```
+pub struct AudioNamingPlugin<M, S> {
+    _phantom: PhantomData<(M, S)>,
+}
+
+impl<M, S> Default for AudioNamingPlugin<M, S> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M, S> Plugin for AudioNamingPlugin<M, S>
+where
+    M: MusicCategory + Debug,
+    S: SfxCategory + Debug,
+{
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AudioEntityNames>();
+        app.add_systems(
+            Update,
+            (
+                name_audio_entities::<M>("Music"),
+                name_audio_entities::<S>("Sfx"),
+            )
+                .after(MsgAudioSet::EventHandling)
+                .before(MsgAudioSet::Concurrency),
+        );
+    }
+}
+
+/// What an audio entity's name is built from.
+#[derive(Debug, Clone, Copy)]
+pub struct AudioNameParts<'a> {
+    /// The audio entity.
+    pub entity: Entity,
+    /// `"Music"` or `"Sfx"`.
+    pub kind: &'static str,
+    /// Asset path of the source, or its asset id if it was not loaded from a
+    /// path.
+    pub source: &'a str,
+    /// Debug name of the category.
+    pub category: &'a str,
+}
+
+/// Resource holding the hook that names audio entities.
+///
+/// Replace it to customize the names:
+///
+/// ```rust,ignore
+/// use msg_audio::AudioEntityNames;
+///
+/// app.insert_resource(AudioEntityNames::new(|parts| {
+///     format!("[{}] {}", parts.category, parts.source)
+/// }));
+/// ```
+#[derive(Resource, Clone)]
+pub struct AudioEntityNames(Arc<dyn Fn(&AudioNameParts) -> String + Send + Sync>);
+
+impl AudioEntityNames {
+    /// Creates a naming hook from a function or closure.
+    #[must_use]
+    pub fn new(f: impl Fn(&AudioNameParts) -> String + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// Returns the name of an audio entity.
+    #[must_use]
+    pub fn name(&self, parts: &AudioNameParts) -> String {
+        (self.0)(parts)
+    }
+}
+
+impl Default for AudioEntityNames {
+    /// Names entities `"{kind}: {source} ({category})"`.
+    fn default() -> Self {
+        Self::new(|parts| format!("{}: {} ({})", parts.kind, parts.source, parts.category))
+    }
+}
+
+impl Debug for AudioEntityNames {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AudioEntityNames(..)")
+    }
+}
+
+/// Returns a system naming the unnamed audio entities of category `A`.
+pub fn name_audio_entities<A: Component + Debug>(
+    kind: &'static str,
+) -> impl FnMut(
+    Commands,
+    Res<AudioEntityNames>,
+    Query<
+        (Entity, &A, Option<&AudioPlayer>, Option<&WaitForLoad>),
+        (Without<Name>, Or<(With<AudioPlayer>, With<WaitForLoad>)>),
+    >,
+    Query<(Entity, &A, &AudioPlayer<Tone>), Without<Name>>,
+) {
+    move |mut commands, names, query, tones| {
+        let mut name = |entity, category: &A, source: String| {
+            let name = names.name(&AudioNameParts {
+                entity,
+                kind,
+                source: &source,
+                category: &format!("{category:?}"),
+            });
+            commands.entity(entity).insert(Name::new(name));
+        };
+        for (entity, category, player, pending) in &query {
+            let Some(handle) = player
+                .map(|player| &player.0)
+                .or(pending.map(|pending| &pending.handle))
+            else {
+                continue;
+            };
+            name(entity, category, source_name(handle));
+        }
+        for (entity, category, tone) in &tones {
+            name(entity, category, source_name(&tone.0));
+        }
+    }
+}
+
+/// Returns the asset path of a source, or its asset id if it was not loaded
+/// from a path.
+fn source_name<A: Asset>(handle: &Handle<A>) -> String {
+    handle
+        .path()
+        .map_or_else(|| format!("{:?}", handle.id()), ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin, TestSfx};
+    use crate::{PlayMusic, PlaySfx, TestAudioPlugin};
+
+    #[test]
+    fn audio_naming_plugin_names_entities() {
+        let mut app = test_app((
+            AssetPlugin::default(),
+            TestPlugin::default(),
+            AudioNamingPlugin::<TestMusic, TestSfx>::default(),
+            TestAudioPlugin,
+        ));
+        app.init_asset::<AudioSource>();
+        app.world_mut()
+            .write_message(PlaySfx::path("ui/click.ogg", TestSfx::UI));
+        app.world_mut().write_message(
+            PlayMusic::path("music/theme.ogg", TestMusic::Main).with_components(Name::new("Theme")),
+        );
+        app.update();
+        app.update();
+
+        let world = app.world_mut();
+        let mut names: Vec<_> = world
+            .query::<&Name>()
+            .iter(world)
+            .map(|name| name.as_str().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["Sfx: ui/click.ogg (UI)", "Theme"]);
+
+        app.insert_resource(AudioEntityNames::new(|parts| parts.source.to_string()));
+        app.world_mut()
+            .write_message(PlaySfx::path("ui/hover.ogg", TestSfx::UI));
+        app.update();
+        let world = app.world_mut();
+        assert!(world
+            .query::<&Name>()
+            .iter(world)
+            .any(|name| name.as_str() == "ui/hover.ogg"));
+    }
+}
@@ -0,0 +1,237 @@
+//! The music currently playing in each category.
+//!
+//! [`NowPlaying`] mirrors the playing music sinks into a resource, so UIs can
+//! show the track name and a progress bar without querying sinks directly.
+//! [`NowPlayingChanged`] is emitted when the track of a category changes.
+
+use bevy::prelude::*;
+use std::time::Duration;
+
+use crate::backend::{either_sink, AnySink, AudioBackend};
+use crate::components::{FadeOut, Stinger};
+use crate::duration::SourceDurations;
+use crate::track_info::{TrackInfo, TrackRegistry};
+use crate::traits::MusicCategory;
+
+/// The track playing in one music category.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlayingTrack<M: MusicCategory> {
+    /// The music entity.
+    pub entity: Entity,
+    /// The audio source being played.
+    pub handle: Handle<AudioSource>,
+    /// The music category.
+    pub category: M,
+    /// Position within the track, counted from the start of the source even
+    /// if it started at a [`StartPosition`](crate::StartPosition), and wrapped
+    /// by the [`SourceDurations`] while it loops.
+    pub elapsed: Duration,
+    /// Whether the track loops.
+    pub looping: bool,
+    /// Whether the sink is paused.
+    pub paused: bool,
+    /// Metadata of the track from the [`TrackRegistry`].
+    pub info: Option<TrackInfo>,
+}
+
+/// Resource listing the track playing in each category of type `M`.
+///
+/// Updated every frame after [`MsgAudioSet::Fades`](crate::MsgAudioSet::Fades).
+/// Tracks fading out are left out, so a crossfade switches to the new track
+/// right away. Stingers are not listed.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::NowPlaying;
+///
+/// fn progress_bar(now_playing: Res<NowPlaying<GameMusic>>, mut bar: Single<&mut Node, With<ProgressBar>>) {
+///     if let Some(track) = now_playing.get(GameMusic::Exploration) {
+///         bar.width = Val::Px(track.elapsed.as_secs_f32());
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct NowPlaying<M: MusicCategory> {
+    tracks: Vec<PlayingTrack<M>>,
+}
+
+impl<M: MusicCategory> Default for NowPlaying<M> {
+    fn default() -> Self {
+        Self { tracks: Vec::new() }
+    }
+}
+
+impl<M: MusicCategory> NowPlaying<M> {
+    /// Returns the track playing in a category.
+    #[must_use]
+    pub fn get(&self, category: M) -> Option<&PlayingTrack<M>> {
+        self.tracks.iter().find(|track| track.category == category)
+    }
+
+    /// Returns `true` if a track is playing in the category.
+    #[must_use]
+    pub fn is_playing(&self, category: M) -> bool {
+        self.get(category).is_some()
+    }
+
+    /// Returns the playing tracks, one per category.
+    pub fn iter(&self) -> impl Iterator<Item = &PlayingTrack<M>> {
+        self.tracks.iter()
+    }
+}
+
+/// Message emitted when the track playing in a music category changes.
+#[derive(Message, Clone, Debug, PartialEq)]
+pub struct NowPlayingChanged<M: MusicCategory> {
+    /// The music category.
+    pub category: M,
+    /// The track that was playing, if any.
+    pub previous: Option<Handle<AudioSource>>,
+    /// The track now playing, if any.
+    pub current: Option<Handle<AudioSource>>,
+}
+
+/// Updates [`NowPlaying`] from the music sinks of type `M` and emits
+/// [`NowPlayingChanged`] for categories whose track changed.
+///
+/// The track already listed for a category stays listed while it plays, so
+/// layered stems do not flip between each other. A new music entity counts as
+/// a change even if it plays the same source, such as a restarted track.
+pub fn update_now_playing<M: MusicCategory, B: AudioBackend>(
+    query: Query<
+        (Entity, &M, &AudioPlayer, &PlaybackSettings, AnySink<B>),
+        (Without<FadeOut>, Without<Stinger>),
+    >,
+    registry: Res<TrackRegistry>,
+    durations: Res<SourceDurations>,
+    mut now_playing: ResMut<NowPlaying<M>>,
+    mut changed: MessageWriter<NowPlayingChanged<M>>,
+) {
+    let mut tracks: Vec<PlayingTrack<M>> = Vec::with_capacity(now_playing.tracks.len());
+    for (entity, category, player, playback, sink) in &query {
+        let listed = now_playing
+            .get(*category)
+            .is_some_and(|track| track.entity == entity);
+        let sink = either_sink::<B>(sink);
+        let track = PlayingTrack {
+            entity,
+            handle: player.0.clone(),
+            category: *category,
+            elapsed: durations.wrap(&player.0, sink.position()),
+            looping: matches!(playback.mode, bevy::audio::PlaybackMode::Loop),
+            paused: sink.is_paused(),
+            info: registry.get(&player.0).cloned(),
+        };
+        match tracks.iter_mut().find(|track| track.category == *category) {
+            Some(existing) if listed => *existing = track,
+            Some(_) => {}
+            None => tracks.push(track),
+        }
+    }
+
+    for track in &tracks {
+        let previous = now_playing.get(track.category);
+        if previous.map(|t| t.entity) != Some(track.entity) {
+            changed.write(NowPlayingChanged {
+                category: track.category,
+                previous: previous.map(|t| t.handle.clone()),
+                current: Some(track.handle.clone()),
+            });
+        }
+    }
+    for track in &now_playing.tracks {
+        if !tracks.iter().any(|t| t.category == track.category) {
+            changed.write(NowPlayingChanged {
+                category: track.category,
+                previous: Some(track.handle.clone()),
+                current: None,
+            });
+        }
+    }
+
+    now_playing.set_if_neq(NowPlaying { tracks });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin};
+    use crate::{DuplicateMusic, PlayMusic, StopMusic, TestAudioPlugin};
+
+    #[test]
+    fn now_playing_tracks_music_per_category() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        let changes = |app: &App| {
+            let messages = app
+                .world()
+                .resource::<Messages<NowPlayingChanged<TestMusic>>>();
+            messages
+                .get_cursor()
+                .read(messages)
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let handle = Handle::<AudioSource>::default();
+        app.world_mut()
+            .write_message(PlayMusic::new(handle.clone(), TestMusic::Main));
+        app.update();
+        app.update();
+
+        let now_playing = app.world().resource::<NowPlaying<TestMusic>>();
+        let track = now_playing.get(TestMusic::Main).unwrap();
+        assert_eq!(track.handle, handle);
+        assert!(track.looping);
+        assert!(!track.paused);
+        let started = changes(&app);
+        assert_eq!(started.len(), 1);
+        assert_eq!(started[0].previous, None);
+        assert_eq!(started[0].current, Some(handle.clone()));
+
+        app.world_mut()
+            .write_message(StopMusic::new(TestMusic::Main));
+        app.update();
+        assert!(!app
+            .world()
+            .resource::<NowPlaying<TestMusic>>()
+            .is_playing(TestMusic::Main));
+        let stopped = changes(&app);
+        assert_eq!(stopped.last().unwrap().previous, Some(handle));
+        assert_eq!(stopped.last().unwrap().current, None);
+    }
+
+    #[test]
+    fn now_playing_changes_when_another_instance_takes_over() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        let handle = Handle::<AudioSource>::default();
+        app.world_mut()
+            .write_message(PlayMusic::new(handle.clone(), TestMusic::Main));
+        app.update();
+        app.update();
+        app.world_mut().write_message(
+            PlayMusic::new(handle.clone(), TestMusic::Main).on_duplicate(DuplicateMusic::Stack),
+        );
+        app.update();
+        app.update();
+        let listed = |app: &App| {
+            app.world()
+                .resource::<NowPlaying<TestMusic>>()
+                .get(TestMusic::Main)
+                .unwrap()
+                .entity
+        };
+        let first = listed(&app);
+
+        app.world_mut().despawn(first);
+        app.update();
+
+        assert_ne!(listed(&app), first);
+        let messages = app
+            .world()
+            .resource::<Messages<NowPlayingChanged<TestMusic>>>();
+        let changed: Vec<_> = messages.iter_current_update_messages().collect();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].previous, Some(handle.clone()));
+        assert_eq!(changed[0].current, Some(handle));
+    }
+}
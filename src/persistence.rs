@@ -0,0 +1,501 @@
+//! Audio config persistence.
+//!
+//! [`save_config_to_file`] and [`load_config_from_file`] store any
+//! serializable [`AudioConfigTrait`] resource as RON or TOML, and
+//! [`AudioConfigPersistencePlugin`] loads it at startup, writes it back
+//! whenever it changes, and can reload it when the file is edited while the
+//! game runs. Enable the `ron` or `toml` feature to use them; the format is
+//! chosen from the file extension, see [`ConfigFormat`].
+
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt;
+use std::fs;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::traits::AudioConfigTrait;
+
+/// Default time a config has to stay unchanged before it is saved.
+pub const DEFAULT_PERSIST_DELAY: Duration = Duration::from_millis(500);
+
+/// Default time between two checks of the config file for edits.
+pub const DEFAULT_HOT_RELOAD_INTERVAL: Duration = Duration::from_secs(1);
+
+/// File format of a stored config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// [RON](https://github.com/ron-rs/ron), enabled by the `ron` feature.
+    #[cfg(feature = "ron")]
+    Ron,
+    /// [TOML](https://toml.io), enabled by the `toml` feature.
+    #[cfg(feature = "toml")]
+    Toml,
+}
+
+impl ConfigFormat {
+    /// Returns the format of a file from its extension: TOML for `.toml`,
+    /// RON for `.ron`, and RON (or TOML, if only that feature is enabled)
+    /// for any other extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigPersistError::UnsupportedFormat`] if the extension
+    /// names a format whose feature is disabled.
+    pub fn from_path(path: &Path) -> Result<Self, ConfigPersistError> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            #[cfg(feature = "toml")]
+            Some("toml") => Ok(Self::Toml),
+            #[cfg(not(feature = "toml"))]
+            Some("toml") => Err(ConfigPersistError::UnsupportedFormat(path.to_path_buf())),
+            #[cfg(not(feature = "ron"))]
+            Some("ron") => Err(ConfigPersistError::UnsupportedFormat(path.to_path_buf())),
+            #[cfg(feature = "ron")]
+            _ => Ok(Self::Ron),
+            #[cfg(not(feature = "ron"))]
+            _ => Ok(Self::Toml),
+        }
+    }
+}
+
+/// Error returned when a config cannot be saved or loaded.
+#[derive(Debug)]
+pub enum ConfigPersistError {
+    /// The file could not be read or written.
+    Io(std::io::Error),
+    /// The file extension names a format whose feature is disabled.
+    UnsupportedFormat(PathBuf),
+    /// The config could not be serialized as RON.
+    #[cfg(feature = "ron")]
+    SerializeRon(ron::Error),
+    /// The file does not contain a valid RON config.
+    #[cfg(feature = "ron")]
+    ParseRon(ron::error::SpannedError),
+    /// The config could not be serialized as TOML.
+    #[cfg(feature = "toml")]
+    SerializeToml(toml::ser::Error),
+    /// The file does not contain a valid TOML config.
+    #[cfg(feature = "toml")]
+    ParseToml(toml::de::Error),
+}
+
+impl fmt::Display for ConfigPersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "audio config file error: {err}"),
+            Self::UnsupportedFormat(path) => write!(
+                f,
+                "no enabled feature supports the format of {}",
+                path.display()
+            ),
+            #[cfg(feature = "ron")]
+            Self::SerializeRon(err) => write!(f, "failed to serialize audio config: {err}"),
+            #[cfg(feature = "ron")]
+            Self::ParseRon(err) => write!(f, "invalid audio config file: {err}"),
+            #[cfg(feature = "toml")]
+            Self::SerializeToml(err) => write!(f, "failed to serialize audio config: {err}"),
+            #[cfg(feature = "toml")]
+            Self::ParseToml(err) => write!(f, "invalid audio config file: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigPersistError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::UnsupportedFormat(_) => None,
+            #[cfg(feature = "ron")]
+            Self::SerializeRon(err) => Some(err),
+            #[cfg(feature = "ron")]
+            Self::ParseRon(err) => Some(err),
+            #[cfg(feature = "toml")]
+            Self::SerializeToml(err) => Some(err),
+            #[cfg(feature = "toml")]
+            Self::ParseToml(err) => Some(err),
+        }
+    }
+}
+
+impl From<std::io::Error> for ConfigPersistError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Writes a config to `path` in the [`ConfigFormat`] of its extension,
+/// pretty-printed, creating missing parent directories.
+///
+/// # Errors
+///
+/// Returns an error if the format is not enabled, the config cannot be
+/// serialized, or the file cannot be written.
+pub fn save_config_to_file<C: Serialize>(
+    config: &C,
+    path: impl AsRef<Path>,
+) -> Result<(), ConfigPersistError> {
+    let path = path.as_ref();
+    let text = match ConfigFormat::from_path(path)? {
+        #[cfg(feature = "ron")]
+        ConfigFormat::Ron => ron::ser::to_string_pretty(config, ron::ser::PrettyConfig::default())
+            .map_err(ConfigPersistError::SerializeRon)?,
+        #[cfg(feature = "toml")]
+        ConfigFormat::Toml => {
+            toml::to_string_pretty(config).map_err(ConfigPersistError::SerializeToml)?
+        }
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, text)?;
+    Ok(())
+}
+
+/// Reads a config written by [`save_config_to_file`].
+///
+/// # Errors
+///
+/// Returns an error if the format is not enabled, or the file cannot be
+/// read or does not hold a valid config.
+pub fn load_config_from_file<C: DeserializeOwned>(
+    path: impl AsRef<Path>,
+) -> Result<C, ConfigPersistError> {
+    let path = path.as_ref();
+    let format = ConfigFormat::from_path(path)?;
+    let text = fs::read_to_string(path)?;
+    match format {
+        #[cfg(feature = "ron")]
+        ConfigFormat::Ron => ron::from_str(&text).map_err(ConfigPersistError::ParseRon),
+        #[cfg(feature = "toml")]
+        ConfigFormat::Toml => toml::from_str(&text).map_err(ConfigPersistError::ParseToml),
+    }
+}
+
+/// Plugin loading the audio config `C` from a file at startup and saving it
+/// whenever it changes.
+///
+/// The file is read in `PreStartup`, so it overrides a config resource
+/// inserted before or after the plugin, and `Startup` systems see the loaded
+/// values. If the file does not exist or cannot be read, the config keeps its
+/// current value (or its default when not inserted yet). Saves wait until
+/// the config has stayed unchanged for [`with_delay`](Self::with_delay), so
+/// dragging a volume slider writes the file once.
+///
+/// With [`with_hot_reload`](Self::with_hot_reload), edits made to the file
+/// while the game runs are applied to the config. Reloaded values are not
+/// saved back, so comments and formatting in the file are kept.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::AudioConfigPersistencePlugin;
+///
+/// app.add_plugins(
+///     AudioConfigPersistencePlugin::<GameAudioConfig>::new("settings/audio.ron")
+///         .with_hot_reload(Duration::from_millis(250)),
+/// );
+/// ```
+pub struct AudioConfigPersistencePlugin<C> {
+    /// File the config is stored in.
+    pub path: PathBuf,
+    /// Time the config has to stay unchanged before it is saved.
+    pub delay: Duration,
+    /// Time between two checks of the file for edits, or `None` to only
+    /// read it at startup.
+    pub hot_reload: Option<Duration>,
+    _phantom: PhantomData<C>,
+}
+
+impl<C> AudioConfigPersistencePlugin<C> {
+    /// Creates a plugin storing the config in `path`.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            delay: DEFAULT_PERSIST_DELAY,
+            hot_reload: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Applies edits made to the file while the game runs, checking it
+    /// every `interval`.
+    ///
+    /// See [`DEFAULT_HOT_RELOAD_INTERVAL`] for a sensible interval.
+    #[must_use]
+    pub fn with_hot_reload(mut self, interval: Duration) -> Self {
+        self.hot_reload = Some(interval);
+        self
+    }
+
+    /// Sets the time the config has to stay unchanged before it is saved.
+    #[must_use]
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+impl<C> Plugin for AudioConfigPersistencePlugin<C>
+where
+    C: AudioConfigTrait + Serialize + DeserializeOwned,
+{
+    fn build(&self, app: &mut App) {
+        app.init_resource::<C>();
+        app.insert_resource(ConfigPersistence::<C> {
+            path: self.path.clone(),
+            timer: Timer::new(self.delay, TimerMode::Once),
+            pending: false,
+            reload: self
+                .hot_reload
+                .map(|interval| Timer::new(interval, TimerMode::Repeating)),
+            modified: modified_time(&self.path),
+            reloaded: false,
+            _phantom: PhantomData,
+        });
+        app.add_systems(PreStartup, load_audio_config::<C>);
+        app.add_systems(Last, persist_audio_config::<C>);
+        if self.hot_reload.is_some() {
+            app.add_systems(PreUpdate, reload_audio_config::<C>);
+        }
+    }
+}
+
+/// Where and when the config `C` is saved and reloaded.
+#[derive(Resource)]
+struct ConfigPersistence<C> {
+    path: PathBuf,
+    timer: Timer,
+    pending: bool,
+    /// Interval between checks for edits, when hot reload is enabled.
+    reload: Option<Timer>,
+    /// Modification time of the file when it was last read or written.
+    modified: Option<SystemTime>,
+    /// The config was just reloaded, so its change must not be saved.
+    reloaded: bool,
+    _phantom: PhantomData<C>,
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// Replaces the config `C` with the contents of its file, if there is one.
+///
+/// The config is then newly changed, which the first run of
+/// [`persist_audio_config`] skips, so loading does not save it back.
+fn load_audio_config<C>(mut config: ResMut<C>, persistence: Res<ConfigPersistence<C>>)
+where
+    C: AudioConfigTrait + DeserializeOwned,
+{
+    if !persistence.path.exists() {
+        return;
+    }
+    match load_config_from_file::<C>(&persistence.path) {
+        Ok(loaded) => *config = loaded,
+        Err(err) => warn!("Failed to load {}: {err}", persistence.path.display()),
+    }
+}
+
+/// Applies edits made to the config file since it was last read or written.
+fn reload_audio_config<C>(
+    time: Res<Time<Real>>,
+    mut config: ResMut<C>,
+    mut persistence: ResMut<ConfigPersistence<C>>,
+) where
+    C: AudioConfigTrait + DeserializeOwned,
+{
+    let persistence = &mut *persistence;
+    let Some(timer) = persistence.reload.as_mut() else {
+        return;
+    };
+    if !timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let modified = modified_time(&persistence.path);
+    if modified.is_none() || modified == persistence.modified {
+        return;
+    }
+    persistence.modified = modified;
+    match load_config_from_file::<C>(&persistence.path) {
+        Ok(loaded) => {
+            *config = loaded;
+            persistence.reloaded = true;
+        }
+        Err(err) => warn!("Failed to reload {}: {err}", persistence.path.display()),
+    }
+}
+
+/// Saves the config `C` once it has stayed unchanged for the plugin's delay.
+fn persist_audio_config<C>(
+    time: Res<Time<Real>>,
+    config: Res<C>,
+    mut persistence: ResMut<ConfigPersistence<C>>,
+) where
+    C: AudioConfigTrait + Serialize,
+{
+    if config.is_changed() && !config.is_added() {
+        if persistence.reloaded {
+            persistence.reloaded = false;
+        } else {
+            persistence.pending = true;
+            persistence.timer.reset();
+        }
+    }
+    if !persistence.pending || !persistence.timer.tick(time.delta()).is_finished() {
+        return;
+    }
+    persistence.pending = false;
+    if let Err(err) = save_config_to_file(&*config, &persistence.path) {
+        warn!("Failed to save {}: {err}", persistence.path.display());
+    }
+    persistence.modified = modified_time(&persistence.path);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Resource, Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+    struct TestConfig {
+        master: f32,
+        muted: bool,
+    }
+
+    impl AudioConfigTrait for TestConfig {
+        fn master_volume(&self) -> f32 {
+            self.master
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("msg_audio_{}", std::process::id()))
+            .join(name)
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn config_round_trips_through_file() {
+        let path = temp_path("round_trip.ron");
+        let config = TestConfig {
+            master: 0.7,
+            muted: true,
+        };
+
+        save_config_to_file(&config, &path).unwrap();
+        assert_eq!(load_config_from_file::<TestConfig>(&path).unwrap(), config);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn plugin_loads_and_saves_changed_config() {
+        let path = temp_path("plugin.ron");
+        save_config_to_file(
+            &TestConfig {
+                master: 0.4,
+                muted: false,
+            },
+            &path,
+        )
+        .unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(
+            AudioConfigPersistencePlugin::<TestConfig>::new(&path).with_delay(Duration::ZERO),
+        );
+        app.update();
+        assert_eq!(app.world().resource::<TestConfig>().master, 0.4);
+
+        app.world_mut().resource_mut::<TestConfig>().master = 0.9;
+        app.update();
+        assert_eq!(
+            load_config_from_file::<TestConfig>(&path).unwrap().master,
+            0.9
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn hot_reload_applies_file_edits_without_saving_them() {
+        let path = temp_path("hot_reload.ron");
+        save_config_to_file(&TestConfig::default(), &path).unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(
+            AudioConfigPersistencePlugin::<TestConfig>::new(&path)
+                .with_delay(Duration::ZERO)
+                .with_hot_reload(Duration::ZERO),
+        );
+        app.update();
+
+        let edited = "// tweaked by hand\n(master: 0.25, muted: false)";
+        let saved = modified_time(&path).unwrap();
+        fs::write(&path, edited).unwrap();
+        // Filesystems with coarse timestamps may not move the time on their own
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(saved + Duration::from_secs(1))
+            .unwrap();
+        app.update();
+        app.update();
+
+        assert_eq!(app.world().resource::<TestConfig>().master, 0.25);
+        assert_eq!(fs::read_to_string(&path).unwrap(), edited);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn config_round_trips_through_toml_file() {
+        let path = temp_path("round_trip.toml");
+        let config = TestConfig {
+            master: 0.5,
+            muted: true,
+        };
+
+        save_config_to_file(&config, &path).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.contains("master = 0.5"), "{text}");
+        assert_eq!(load_config_from_file::<TestConfig>(&path).unwrap(), config);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn file_overrides_config_inserted_after_plugin() {
+        let path = temp_path("insert_order.toml");
+        save_config_to_file(
+            &TestConfig {
+                master: 0.3,
+                muted: false,
+            },
+            &path,
+        )
+        .unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(
+            AudioConfigPersistencePlugin::<TestConfig>::new(&path).with_delay(Duration::ZERO),
+        );
+        app.insert_resource(TestConfig {
+            master: 1.0,
+            muted: true,
+        });
+        app.update();
+
+        assert_eq!(app.world().resource::<TestConfig>().master, 0.3);
+        // Loading is not a change to save back
+        assert!(!load_config_from_file::<TestConfig>(&path).unwrap().muted);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,121 @@
+//! One system parameter for playing sounds.
+//!
+//! [`AudioPlayerParam`] bundles the message writers for music and sound
+//! effects, so a system that plays several kinds of sounds takes a single
+//! parameter instead of one [`MessageWriter`] per message type.
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use std::time::Duration;
+
+use crate::events::{FadeOutMusic, PlayMusic, PlaySfx, StopAllMusic, StopMusic};
+use crate::traits::{AudioCategory, MusicCategory, SfxCategory};
+
+/// System parameter for playing music of type `M` and sound effects of
+/// type `S`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::AudioPlayerParam;
+///
+/// fn on_level_start(mut audio: AudioPlayerParam<GameMusic, GameSfx>, assets: Res<LevelAssets>) {
+///     audio.music(assets.theme.clone(), GameMusic::Exploration);
+///     audio.sfx(assets.fanfare.clone(), GameSfx::UI);
+///     audio.play_sfx(PlaySfx::new(assets.wind.clone(), GameSfx::Ambience).randomized());
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct AudioPlayerParam<'w, M, S>
+where
+    M: MusicCategory,
+    S: SfxCategory<Config = M::Config>,
+{
+    config: Res<'w, <M as AudioCategory>::Config>,
+    play_music: MessageWriter<'w, PlayMusic<M>>,
+    play_sfx: MessageWriter<'w, PlaySfx<S>>,
+    stop_music: MessageWriter<'w, StopMusic<M>>,
+    stop_all_music: MessageWriter<'w, StopAllMusic<M>>,
+    fade_out_music: MessageWriter<'w, FadeOutMusic<M>>,
+}
+
+impl<M, S> AudioPlayerParam<'_, M, S>
+where
+    M: MusicCategory,
+    S: SfxCategory<Config = M::Config>,
+{
+    /// Returns the audio config.
+    #[must_use]
+    pub fn config(&self) -> &M::Config {
+        &self.config
+    }
+
+    /// Plays a sound effect with the default settings.
+    pub fn sfx(&mut self, handle: Handle<AudioSource>, category: S) {
+        self.play_sfx(PlaySfx::new(handle, category));
+    }
+
+    /// Plays looping music with the default settings.
+    pub fn music(&mut self, handle: Handle<AudioSource>, category: M) {
+        self.play_music(PlayMusic::new(handle, category));
+    }
+
+    /// Sends a configured [`PlaySfx`] message.
+    pub fn play_sfx(&mut self, message: PlaySfx<S>) {
+        self.play_sfx.write(message);
+    }
+
+    /// Sends a configured [`PlayMusic`] message.
+    pub fn play_music(&mut self, message: PlayMusic<M>) {
+        self.play_music.write(message);
+    }
+
+    /// Stops the music of a category.
+    pub fn stop_music(&mut self, category: M) {
+        self.stop_music.write(StopMusic::new(category));
+    }
+
+    /// Fades out the music of a category over `duration`.
+    pub fn fade_out_music(&mut self, category: M, duration: Duration) {
+        self.fade_out_music
+            .write(FadeOutMusic::new(category, duration));
+    }
+
+    /// Stops all music.
+    pub fn stop_all_music(&mut self) {
+        self.stop_all_music.write(StopAllMusic::default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin, TestSfx};
+    use crate::TestAudioPlugin;
+
+    #[test]
+    fn audio_player_param_sends_play_messages() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.world_mut()
+            .run_system_once(|mut audio: AudioPlayerParam<TestMusic, TestSfx>| {
+                audio.music(Handle::default(), TestMusic::Main);
+                audio.sfx(Handle::default(), TestSfx::UI);
+            })
+            .unwrap();
+        app.update();
+
+        let world = app.world_mut();
+        assert_eq!(world.query::<&TestMusic>().iter(world).count(), 1);
+        assert_eq!(world.query::<&TestSfx>().iter(world).count(), 1);
+
+        app.world_mut()
+            .run_system_once(|mut audio: AudioPlayerParam<TestMusic, TestSfx>| {
+                audio.stop_all_music();
+            })
+            .unwrap();
+        app.update();
+        let world = app.world_mut();
+        assert_eq!(world.query::<&TestMusic>().iter(world).count(), 0);
+    }
+}
@@ -0,0 +1,260 @@
+//! Entity pooling for one-shot sound effects.
+//!
+//! Games firing many short sounds spawn and despawn an audio entity per
+//! shot. With [`MsgAudioPlugin::with_sfx_pool`](crate::MsgAudioPlugin::with_sfx_pool),
+//! one-shots played through [`PlaySfx`](crate::PlaySfx) are stripped and kept
+//! in an [`SfxPool`] when they finish, and later sounds reuse those entities
+//! instead of allocating new ones.
+
+use bevy::{ecs::entity::Entities, prelude::*};
+
+use crate::components::WaitForLoad;
+
+/// Resource holding finished one-shot entities for reuse.
+///
+/// Only [`PlaySfx`](crate::PlaySfx) requests with
+/// [`PlaybackMode::Despawn`](bevy::audio::PlaybackMode::Despawn) are pooled.
+/// Their playback mode is switched to
+/// [`PlaybackMode::Remove`](bevy::audio::PlaybackMode::Remove) so the entity
+/// survives the end of the sound. Sounds that are despawned before finishing,
+/// e.g. by a fade-out or a stop message, are not returned to the pool.
+///
+/// A reused entity keeps its id but moves to the next [`PoolGeneration`].
+/// Target a pooled sound with [`AudioStarted::target`](crate::AudioStarted::target)
+/// rather than its bare [`Entity`], so messages kept from an earlier sound
+/// do not reach the newer one.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::SfxPool;
+///
+/// fn log_pool(pool: Res<SfxPool>) {
+///     info!("sfx pool hit rate: {:.0}%", pool.hit_rate() * 100.0);
+/// }
+/// ```
+#[derive(Resource, Reflect, Debug, Clone, Default)]
+#[reflect(Resource)]
+pub struct SfxPool {
+    /// Maximum number of idle entities kept for reuse.
+    pub capacity: usize,
+    idle: Vec<Entity>,
+    hits: u64,
+    misses: u64,
+}
+
+impl SfxPool {
+    /// Creates a pool keeping up to `capacity` idle entities.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..default()
+        }
+    }
+
+    /// Returns the number of idle entities waiting for reuse.
+    #[must_use]
+    pub fn idle(&self) -> usize {
+        self.idle.len()
+    }
+
+    /// Returns how many pooled sounds reused an idle entity.
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns how many pooled sounds had to spawn a new entity.
+    #[must_use]
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Returns the share of pooled sounds that reused an idle entity, from
+    /// `0.0` to `1.0`.
+    #[must_use]
+    pub fn hit_rate(&self) -> f32 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f32 / total as f32
+        }
+    }
+
+    /// Takes an idle entity that still exists, recording a hit or a miss.
+    pub(crate) fn take(&mut self, entities: &Entities) -> Option<Entity> {
+        while let Some(entity) = self.idle.pop() {
+            if entities.contains(entity) {
+                self.hits += 1;
+                return Some(entity);
+            }
+        }
+        self.misses += 1;
+        None
+    }
+}
+
+/// Marks a one-shot sound that returns to the [`SfxPool`] when it finishes.
+#[derive(Component, Reflect, Debug, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct Pooled;
+
+/// How many times a pooled entity was recycled for a new sound.
+///
+/// Entity-targeted messages built with
+/// [`AudioTarget::Instance`](crate::AudioTarget::Instance) only apply while the
+/// entity is still on the generation they name. Entities that were never
+/// recycled have no component and count as generation `0`.
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[reflect(Component)]
+pub struct PoolGeneration(pub u32);
+
+impl PoolGeneration {
+    /// Returns the generation of an entity, `0` without the component.
+    #[must_use]
+    pub fn of(generation: Option<&Self>) -> Self {
+        generation.copied().unwrap_or_default()
+    }
+}
+
+/// Strips pooled sounds that finished playing and returns their entities to
+/// the [`SfxPool`] on their next [`PoolGeneration`], or despawns them once
+/// the pool is full.
+pub fn recycle_pooled_sfx(
+    mut commands: Commands,
+    mut pool: ResMut<SfxPool>,
+    finished: Query<
+        (Entity, Option<&PoolGeneration>),
+        (With<Pooled>, Without<AudioPlayer>, Without<WaitForLoad>),
+    >,
+) {
+    for (entity, generation) in &finished {
+        if pool.idle.len() < pool.capacity {
+            let next = PoolGeneration(PoolGeneration::of(generation).0.wrapping_add(1));
+            commands.entity(entity).retain::<()>().insert(next);
+            pool.idle.push(entity);
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin, TestSfx};
+    use crate::AUDIO_POOL_HIT_RATE;
+    use crate::{
+        AudioDiagnosticsPlugin, AudioLog, AudioStarted, FinishSound, PauseAudio, PlaySfx,
+        TestAudioPlugin,
+    };
+    use bevy::diagnostic::{Diagnostic, DiagnosticsStore};
+
+    #[test]
+    fn sfx_pool_reuses_finished_one_shots() {
+        let mut app = test_app((
+            TestPlugin::default().with_sfx_pool(4),
+            AudioDiagnosticsPlugin::<TestMusic, TestSfx>::default(),
+            TestAudioPlugin,
+        ));
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI));
+        app.update();
+        app.update();
+        let (entity, _) = app.world().resource::<AudioLog>().plays().next().unwrap();
+
+        app.world_mut().write_message(FinishSound { entity });
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<SfxPool>().idle(), 1);
+        assert!(app.world().get::<TestSfx>(entity).is_none());
+
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI));
+        app.update();
+        app.update();
+        let plays: Vec<_> = app.world().resource::<AudioLog>().plays().collect();
+        assert_eq!(plays.len(), 2);
+        assert_eq!(plays[1].0, entity);
+        let pool = app.world().resource::<SfxPool>();
+        assert_eq!((pool.hits(), pool.misses()), (1, 1));
+
+        let hit_rate = app
+            .world()
+            .resource::<DiagnosticsStore>()
+            .get(&AUDIO_POOL_HIT_RATE)
+            .and_then(Diagnostic::value);
+        assert_eq!(hit_rate, Some(0.5));
+    }
+
+    #[test]
+    fn targets_kept_from_a_recycled_sound_skip_the_next_one() {
+        let mut app = test_app((TestPlugin::default().with_sfx_pool(4), TestAudioPlugin));
+        let mut started = Vec::new();
+        for _ in 0..2 {
+            app.world_mut()
+                .write_message(PlaySfx::new(Handle::default(), TestSfx::UI));
+            app.update();
+            app.update();
+            let messages = app.world().resource::<Messages<AudioStarted>>();
+            let mut cursor = messages.get_cursor();
+            started.push(cursor.read(messages).last().unwrap().clone());
+            app.world_mut().write_message(FinishSound {
+                entity: started.last().unwrap().entity,
+            });
+            app.update();
+            app.update();
+        }
+        let (first, second) = (&started[0], &started[1]);
+        assert_eq!(first.entity, second.entity);
+        assert_ne!(first.generation, second.generation);
+
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI));
+        app.update();
+        app.update();
+        let entity = first.entity;
+        let paused = |app: &App| app.world().get::<AudioSink>(entity).unwrap().is_paused();
+
+        app.world_mut()
+            .write_message(PauseAudio::new(first.target()));
+        app.update();
+        assert!(!paused(&app));
+
+        let messages = app.world().resource::<Messages<AudioStarted>>();
+        let mut cursor = messages.get_cursor();
+        let third = cursor.read(messages).last().unwrap().target();
+        app.world_mut().write_message(PauseAudio::new(third));
+        app.update();
+        assert!(paused(&app));
+    }
+
+    #[test]
+    fn recycled_sounds_detach_from_their_entity() {
+        let mut app = test_app((TestPlugin::default().with_sfx_pool(4), TestAudioPlugin));
+        let car = app.world_mut().spawn(Transform::default()).id();
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI).attached_to(car));
+        app.update();
+        app.update();
+        let (entity, _) = app.world().resource::<AudioLog>().plays().next().unwrap();
+        app.world_mut().write_message(FinishSound { entity });
+        app.update();
+        app.update();
+
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI));
+        app.update();
+        app.update();
+        assert_eq!(
+            app.world().resource::<AudioLog>().plays().last().unwrap().0,
+            entity
+        );
+
+        app.world_mut().despawn(car);
+        app.update();
+        assert!(app.world().get::<AudioSink>(entity).is_some());
+    }
+}
@@ -0,0 +1,177 @@
+//! Preloading of audio assets.
+//!
+//! Loading screens usually need a handful of sounds to be resident before
+//! gameplay starts. Queue them on the [`AudioPreloader`] and gate the next
+//! state transition with the [`audio_assets_loaded`] run condition.
+
+use bevy::{asset::LoadState, prelude::*};
+
+/// Tracks audio assets that should be loaded before they are needed.
+///
+/// Queued handles are strong, so the assets stay resident for as long as
+/// they remain in the preloader.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{audio_assets_loaded, AudioPreloader};
+///
+/// fn queue_sounds(mut preloader: ResMut<AudioPreloader>, assets: Res<AssetServer>) {
+///     preloader
+///         .queue(assets.load("music/boss.ogg"))
+///         .queue(assets.load("sfx/explosion.ogg"));
+/// }
+///
+/// app.add_systems(Update, finish_loading.run_if(audio_assets_loaded));
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct AudioPreloader {
+    handles: Vec<Handle<AudioSource>>,
+}
+
+impl AudioPreloader {
+    /// Adds an audio asset to the set that must be loaded.
+    pub fn queue(&mut self, handle: Handle<AudioSource>) -> &mut Self {
+        self.handles.push(handle);
+        self
+    }
+
+    /// Returns the queued handles.
+    #[must_use]
+    pub fn handles(&self) -> &[Handle<AudioSource>] {
+        &self.handles
+    }
+
+    /// Returns the number of queued assets.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Returns `true` if no assets are queued.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Removes all queued handles, allowing unused assets to be unloaded.
+    pub fn clear(&mut self) {
+        self.handles.clear();
+    }
+
+    /// Returns the number of queued assets that are loaded.
+    #[must_use]
+    pub fn loaded_count(&self, sources: &Assets<AudioSource>) -> usize {
+        self.handles
+            .iter()
+            .filter(|handle| sources.contains(*handle))
+            .count()
+    }
+
+    /// Returns loading progress in `[0.0, 1.0]`.
+    ///
+    /// An empty preloader reports `1.0`.
+    #[must_use]
+    pub fn progress(&self, sources: &Assets<AudioSource>) -> f32 {
+        if self.handles.is_empty() {
+            return 1.0;
+        }
+        self.loaded_count(sources) as f32 / self.handles.len() as f32
+    }
+
+    /// Returns `true` if every queued asset is loaded.
+    #[must_use]
+    pub fn all_loaded(&self, sources: &Assets<AudioSource>) -> bool {
+        self.handles.iter().all(|handle| sources.contains(handle))
+    }
+
+    /// Returns `true` if any queued asset failed to load.
+    #[must_use]
+    pub fn any_failed(&self, asset_server: &AssetServer) -> bool {
+        self.handles.iter().any(|handle| {
+            matches!(
+                asset_server.get_load_state(handle),
+                Some(LoadState::Failed(_))
+            )
+        })
+    }
+}
+
+/// Run condition that is `true` once every asset queued on the
+/// [`AudioPreloader`] is loaded.
+///
+/// Without an audio asset store only an empty preloader counts as loaded.
+pub fn audio_assets_loaded(
+    preloader: Res<AudioPreloader>,
+    sources: Option<Res<Assets<AudioSource>>>,
+) -> bool {
+    match sources {
+        Some(sources) => preloader.all_loaded(&sources),
+        None => preloader.is_empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> AudioSource {
+        AudioSource {
+            bytes: Vec::new().into(),
+        }
+    }
+
+    #[test]
+    fn empty_preloader_is_loaded() {
+        let sources = Assets::<AudioSource>::default();
+        let preloader = AudioPreloader::default();
+        assert!(preloader.all_loaded(&sources));
+        assert!((preloader.progress(&sources) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn tracks_loaded_and_pending_assets() {
+        let mut sources = Assets::<AudioSource>::default();
+        let loaded = sources.add(source());
+
+        let mut preloader = AudioPreloader::default();
+        preloader
+            .queue(loaded)
+            .queue(Handle::<AudioSource>::default());
+
+        assert_eq!(preloader.len(), 2);
+        assert_eq!(preloader.loaded_count(&sources), 1);
+        assert!((preloader.progress(&sources) - 0.5).abs() < f32::EPSILON);
+        assert!(!preloader.all_loaded(&sources));
+
+        preloader.clear();
+        assert!(preloader.is_empty());
+    }
+
+    #[test]
+    fn run_condition_waits_for_queued_assets() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<AudioSource>();
+        app.init_resource::<AudioPreloader>();
+
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<AudioSource>>()
+            .reserve_handle();
+        app.world_mut()
+            .resource_mut::<AudioPreloader>()
+            .queue(handle.clone());
+
+        let ready = app.world_mut().run_system_cached(audio_assets_loaded);
+        assert!(!ready.unwrap());
+
+        app.world_mut()
+            .resource_mut::<Assets<AudioSource>>()
+            .insert(&handle, source())
+            .unwrap();
+
+        let ready = app.world_mut().run_system_cached(audio_assets_loaded);
+        assert!(ready.unwrap());
+    }
+}
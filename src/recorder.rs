@@ -0,0 +1,730 @@
+//! Recording and replaying audio messages.
+//!
+//! [`AudioEventRecorder`] captures the play, loop, stop, and fade messages
+//! written during a session into an [`AudioTimeline`], which can be saved
+//! with serde and fed to an [`AudioTimelinePlayer`] to write the same
+//! messages again at the same times, e.g. for replays or audio regression
+//! tests. Enable the `serde` feature to use them.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use crate::events::{
+    FadeOutAllMusic, FadeOutMusic, FadeOutSfx, PlayLoopingSfx, PlayMusic, PlaySfx, PlaySfxBatch,
+    StopAllMusic, StopLoopingSfx, StopMusic,
+};
+use crate::serialization::{SerializedPlayLoopingSfx, SerializedPlayMusic, SerializedPlaySfx};
+use crate::traits::{MusicCategory, SfxCategory};
+use crate::MsgAudioSet;
+
+/// A recorded audio message.
+#[derive(Serialize, Deserialize, Clone)]
+pub enum RecordedAudioEvent<M: MusicCategory, S: SfxCategory> {
+    /// A [`PlayMusic`] message.
+    PlayMusic(SerializedPlayMusic<M>),
+    /// A [`PlaySfx`] message.
+    PlaySfx(SerializedPlaySfx<S>),
+    /// A [`PlaySfxBatch`] message.
+    PlaySfxBatch(Vec<SerializedPlaySfx<S>>),
+    /// A [`PlayLoopingSfx`] message.
+    PlayLoopingSfx(SerializedPlayLoopingSfx<S>),
+    /// A [`StopLoopingSfx`] message.
+    StopLoopingSfx(StopLoopingSfx<S>),
+    /// A [`StopMusic`] message.
+    StopMusic(StopMusic<M>),
+    /// A [`StopAllMusic`] message.
+    StopAllMusic(StopAllMusic<M>),
+    /// A [`FadeOutMusic`] message.
+    FadeOutMusic(FadeOutMusic<M>),
+    /// A [`FadeOutAllMusic`] message.
+    FadeOutAllMusic(FadeOutAllMusic<M>),
+    /// A [`FadeOutSfx`] message.
+    FadeOutSfx(FadeOutSfx<S>),
+}
+
+/// A recorded message and when it was written.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AudioTimelineEntry<M: MusicCategory, S: SfxCategory> {
+    /// Game time since the recording started.
+    pub at: Duration,
+    /// The recorded message.
+    pub event: RecordedAudioEvent<M, S>,
+}
+
+/// Audio messages in the order they were written.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AudioTimeline<M: MusicCategory, S: SfxCategory> {
+    /// The recorded messages, oldest first.
+    pub entries: Vec<AudioTimelineEntry<M, S>>,
+}
+
+impl<M: MusicCategory, S: SfxCategory> Default for AudioTimeline<M, S> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<M: MusicCategory, S: SfxCategory> AudioTimeline<M, S> {
+    /// Returns the number of recorded messages.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if nothing was recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the time of the last recorded message.
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        self.entries.last().map_or(Duration::ZERO, |entry| entry.at)
+    }
+}
+
+/// Resource recording audio messages into an [`AudioTimeline`].
+///
+/// Sounds whose source was not loaded from a path cannot be replayed and
+/// are left out of the timeline. Delayed requests are recorded once, when
+/// their delay has passed and they are written again to be played. Taps of
+/// an [`Echo`](crate::Echo) are left out, as the replayed sound echoes again.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::AudioEventRecorder;
+///
+/// fn start_recording(mut recorder: ResMut<AudioEventRecorder<GameMusic, GameSfx>>) {
+///     recorder.start();
+/// }
+///
+/// fn save_recording(mut recorder: ResMut<AudioEventRecorder<GameMusic, GameSfx>>) {
+///     let timeline = recorder.stop();
+///     std::fs::write("replay_audio.ron", ron::to_string(&timeline).unwrap()).unwrap();
+/// }
+/// ```
+#[derive(Resource)]
+pub struct AudioEventRecorder<M: MusicCategory, S: SfxCategory> {
+    recording: bool,
+    elapsed: Duration,
+    timeline: AudioTimeline<M, S>,
+}
+
+impl<M: MusicCategory, S: SfxCategory> Default for AudioEventRecorder<M, S> {
+    fn default() -> Self {
+        Self {
+            recording: false,
+            elapsed: Duration::ZERO,
+            timeline: AudioTimeline::default(),
+        }
+    }
+}
+
+impl<M: MusicCategory, S: SfxCategory> AudioEventRecorder<M, S> {
+    /// Starts a new recording, discarding the previous timeline.
+    pub fn start(&mut self) {
+        self.recording = true;
+        self.elapsed = Duration::ZERO;
+        self.timeline = AudioTimeline::default();
+    }
+
+    /// Stops recording and returns the recorded timeline.
+    pub fn stop(&mut self) -> AudioTimeline<M, S> {
+        self.recording = false;
+        std::mem::take(&mut self.timeline)
+    }
+
+    /// Returns `true` while recording.
+    #[must_use]
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Returns the timeline recorded so far.
+    #[must_use]
+    pub fn timeline(&self) -> &AudioTimeline<M, S> {
+        &self.timeline
+    }
+
+    fn push(&mut self, event: RecordedAudioEvent<M, S>) {
+        self.timeline.entries.push(AudioTimelineEntry {
+            at: self.elapsed,
+            event,
+        });
+    }
+}
+
+/// Resource writing the messages of an [`AudioTimeline`] at their recorded
+/// times.
+///
+/// Time is game time, so pausing or slowing down the game delays the
+/// replay the same way it delayed the recording.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{AudioTimeline, AudioTimelinePlayer};
+///
+/// fn start_replay(mut player: ResMut<AudioTimelinePlayer<GameMusic, GameSfx>>) {
+///     let timeline: AudioTimeline<GameMusic, GameSfx> =
+///         ron::from_str(&std::fs::read_to_string("replay_audio.ron").unwrap()).unwrap();
+///     player.play(timeline);
+/// }
+/// ```
+#[derive(Resource)]
+pub struct AudioTimelinePlayer<M: MusicCategory, S: SfxCategory> {
+    timeline: AudioTimeline<M, S>,
+    elapsed: Duration,
+    next: usize,
+}
+
+impl<M: MusicCategory, S: SfxCategory> Default for AudioTimelinePlayer<M, S> {
+    fn default() -> Self {
+        Self {
+            timeline: AudioTimeline::default(),
+            elapsed: Duration::ZERO,
+            next: 0,
+        }
+    }
+}
+
+impl<M: MusicCategory, S: SfxCategory> AudioTimelinePlayer<M, S> {
+    /// Starts replaying a timeline from its beginning.
+    pub fn play(&mut self, timeline: AudioTimeline<M, S>) {
+        self.timeline = timeline;
+        self.elapsed = Duration::ZERO;
+        self.next = 0;
+    }
+
+    /// Stops the replay.
+    pub fn stop(&mut self) {
+        self.next = self.timeline.len();
+    }
+
+    /// Returns `true` while messages are left to replay.
+    #[must_use]
+    pub fn is_playing(&self) -> bool {
+        self.next < self.timeline.len()
+    }
+}
+
+/// Plugin adding the [`AudioEventRecorder`] and [`AudioTimelinePlayer`] for
+/// music type `M` and sound effect type `S`.
+///
+/// Messages are recorded in `Last`, so replayed messages are recorded again
+/// when both run at once.
+pub struct AudioRecorderPlugin<M, S> {
+    _phantom: PhantomData<(M, S)>,
+}
+
+impl<M, S> Default for AudioRecorderPlugin<M, S> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<M: MusicCategory, S: SfxCategory> Plugin for AudioRecorderPlugin<M, S> {
+    fn build(&self, app: &mut App) {
+        app.add_message::<PlayMusic<M>>()
+            .add_message::<PlaySfx<S>>()
+            .add_message::<PlaySfxBatch<S>>()
+            .add_message::<PlayLoopingSfx<S>>()
+            .add_message::<StopLoopingSfx<S>>()
+            .add_message::<StopMusic<M>>()
+            .add_message::<StopAllMusic<M>>()
+            .add_message::<FadeOutMusic<M>>()
+            .add_message::<FadeOutAllMusic<M>>()
+            .add_message::<FadeOutSfx<S>>()
+            .init_resource::<AudioEventRecorder<M, S>>()
+            .init_resource::<AudioTimelinePlayer<M, S>>()
+            .add_systems(Last, record_audio_events::<M, S>)
+            .add_systems(
+                Update,
+                replay_audio_timeline::<M, S>.before(MsgAudioSet::EventHandling),
+            );
+    }
+}
+
+/// Appends the messages written this frame to the recording.
+pub fn record_audio_events<M: MusicCategory, S: SfxCategory>(
+    time: Res<Time>,
+    mut recorder: ResMut<AudioEventRecorder<M, S>>,
+    mut play_music: MessageReader<PlayMusic<M>>,
+    mut play_sfx: MessageReader<PlaySfx<S>>,
+    mut play_sfx_batch: MessageReader<PlaySfxBatch<S>>,
+    mut play_looping_sfx: MessageReader<PlayLoopingSfx<S>>,
+    mut stop_looping_sfx: MessageReader<StopLoopingSfx<S>>,
+    mut stop_music: MessageReader<StopMusic<M>>,
+    mut stop_all_music: MessageReader<StopAllMusic<M>>,
+    mut fade_out_music: MessageReader<FadeOutMusic<M>>,
+    mut fade_out_all_music: MessageReader<FadeOutAllMusic<M>>,
+    mut fade_out_sfx: MessageReader<FadeOutSfx<S>>,
+) {
+    if !recorder.recording {
+        // Skip what was written while not recording
+        play_music.clear();
+        play_sfx.clear();
+        play_sfx_batch.clear();
+        play_looping_sfx.clear();
+        stop_looping_sfx.clear();
+        stop_music.clear();
+        stop_all_music.clear();
+        fade_out_music.clear();
+        fade_out_all_music.clear();
+        fade_out_sfx.clear();
+        return;
+    }
+    recorder.elapsed += time.delta();
+
+    // Delayed requests are written again once due, record only that copy
+    for message in play_music.read().filter(|message| message.delay.is_none()) {
+        if let Some(serialized) = message.to_serialized() {
+            recorder.push(RecordedAudioEvent::PlayMusic(serialized));
+        }
+    }
+    // Echo taps are played again by the replayed original
+    for message in play_sfx
+        .read()
+        .filter(|message| message.delay.is_none() && !message.echo_tap)
+    {
+        if let Some(serialized) = message.to_serialized() {
+            recorder.push(RecordedAudioEvent::PlaySfx(serialized));
+        }
+    }
+    // Delayed requests of a batch are written again as single `PlaySfx`
+    for batch in play_sfx_batch.read() {
+        let requests: Vec<_> = batch
+            .requests
+            .iter()
+            .filter(|message| message.delay.is_none() && !message.echo_tap)
+            .filter_map(PlaySfx::to_serialized)
+            .collect();
+        if !requests.is_empty() {
+            recorder.push(RecordedAudioEvent::PlaySfxBatch(requests));
+        }
+    }
+    for message in play_looping_sfx
+        .read()
+        .filter(|message| message.delay.is_none())
+    {
+        if let Some(serialized) = message.to_serialized() {
+            recorder.push(RecordedAudioEvent::PlayLoopingSfx(serialized));
+        }
+    }
+    for message in stop_looping_sfx.read() {
+        recorder.push(RecordedAudioEvent::StopLoopingSfx(message.clone()));
+    }
+    for message in stop_music.read() {
+        recorder.push(RecordedAudioEvent::StopMusic(message.clone()));
+    }
+    for message in stop_all_music.read() {
+        recorder.push(RecordedAudioEvent::StopAllMusic(message.clone()));
+    }
+    for message in fade_out_music.read() {
+        recorder.push(RecordedAudioEvent::FadeOutMusic(message.clone()));
+    }
+    for message in fade_out_all_music.read() {
+        recorder.push(RecordedAudioEvent::FadeOutAllMusic(message.clone()));
+    }
+    for message in fade_out_sfx.read() {
+        recorder.push(RecordedAudioEvent::FadeOutSfx(message.clone()));
+    }
+}
+
+/// Writes the timeline messages that are due.
+pub fn replay_audio_timeline<M: MusicCategory, S: SfxCategory>(
+    time: Res<Time>,
+    asset_server: Option<Res<AssetServer>>,
+    mut player: ResMut<AudioTimelinePlayer<M, S>>,
+    mut play_music: MessageWriter<PlayMusic<M>>,
+    mut play_sfx: MessageWriter<PlaySfx<S>>,
+    mut play_sfx_batch: MessageWriter<PlaySfxBatch<S>>,
+    mut play_looping_sfx: MessageWriter<PlayLoopingSfx<S>>,
+    mut stop_looping_sfx: MessageWriter<StopLoopingSfx<S>>,
+    mut stop_music: MessageWriter<StopMusic<M>>,
+    mut stop_all_music: MessageWriter<StopAllMusic<M>>,
+    mut fade_out_music: MessageWriter<FadeOutMusic<M>>,
+    mut fade_out_all_music: MessageWriter<FadeOutAllMusic<M>>,
+    mut fade_out_sfx: MessageWriter<FadeOutSfx<S>>,
+) {
+    if !player.is_playing() {
+        return;
+    }
+    let player = &mut *player;
+    player.elapsed += time.delta();
+
+    while let Some(entry) = player.timeline.entries.get(player.next) {
+        if entry.at > player.elapsed {
+            break;
+        }
+        player.next += 1;
+        match entry.event.clone() {
+            RecordedAudioEvent::PlayMusic(serialized) => match &asset_server {
+                Some(server) => {
+                    play_music.write(serialized.into_message(server));
+                }
+                None => warn!("Cannot replay music without an AssetServer"),
+            },
+            RecordedAudioEvent::PlaySfx(serialized) => match &asset_server {
+                Some(server) => {
+                    play_sfx.write(serialized.into_message(server));
+                }
+                None => warn!("Cannot replay a sound effect without an AssetServer"),
+            },
+            RecordedAudioEvent::PlaySfxBatch(requests) => match &asset_server {
+                Some(server) => {
+                    play_sfx_batch.write(
+                        requests
+                            .into_iter()
+                            .map(|serialized| serialized.into_message(server))
+                            .collect(),
+                    );
+                }
+                None => warn!("Cannot replay sound effects without an AssetServer"),
+            },
+            RecordedAudioEvent::PlayLoopingSfx(serialized) => match &asset_server {
+                Some(server) => {
+                    play_looping_sfx.write(serialized.into_message(server));
+                }
+                None => warn!("Cannot replay a looping sound effect without an AssetServer"),
+            },
+            RecordedAudioEvent::StopLoopingSfx(message) => {
+                stop_looping_sfx.write(message);
+            }
+            RecordedAudioEvent::StopMusic(message) => {
+                stop_music.write(message);
+            }
+            RecordedAudioEvent::StopAllMusic(message) => {
+                stop_all_music.write(message);
+            }
+            RecordedAudioEvent::FadeOutMusic(message) => {
+                fade_out_music.write(message);
+            }
+            RecordedAudioEvent::FadeOutAllMusic(message) => {
+                fade_out_all_music.write(message);
+            }
+            RecordedAudioEvent::FadeOutSfx(message) => {
+                fade_out_sfx.write(message);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::AudioRequest;
+    use crate::AudioConfigTrait;
+
+    #[derive(Component, Clone, Copy, Default, PartialEq, Debug, Serialize, Deserialize)]
+    enum TestMusic {
+        #[default]
+        Theme,
+        Combat,
+    }
+
+    impl crate::AudioCategory for TestMusic {
+        type Config = TestConfig;
+
+        fn volume_multiplier(&self, _config: &Self::Config) -> f32 {
+            1.0
+        }
+    }
+
+    impl MusicCategory for TestMusic {}
+
+    #[derive(Component, Clone, Copy, Default, PartialEq, Debug, Serialize, Deserialize)]
+    struct TestSfx;
+
+    impl crate::AudioCategory for TestSfx {
+        type Config = TestConfig;
+
+        fn volume_multiplier(&self, _config: &Self::Config) -> f32 {
+            1.0
+        }
+    }
+
+    impl SfxCategory for TestSfx {}
+
+    #[derive(Resource, Clone, Default)]
+    struct TestConfig;
+
+    impl AudioConfigTrait for TestConfig {
+        fn master_volume(&self) -> f32 {
+            1.0
+        }
+    }
+
+    fn test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(AudioRecorderPlugin::<TestMusic, TestSfx>::default());
+        app
+    }
+
+    #[test]
+    fn records_messages_only_while_recording() {
+        let mut app = test_app();
+        app.world_mut()
+            .write_message(StopMusic::new(TestMusic::Theme));
+        app.update();
+
+        app.world_mut()
+            .resource_mut::<AudioEventRecorder<TestMusic, TestSfx>>()
+            .start();
+        app.world_mut()
+            .write_message(StopMusic::new(TestMusic::Combat));
+        // No asset path, so it cannot be replayed
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx));
+        app.update();
+
+        let timeline = app
+            .world_mut()
+            .resource_mut::<AudioEventRecorder<TestMusic, TestSfx>>()
+            .stop();
+        assert_eq!(timeline.len(), 1);
+        assert!(matches!(
+            timeline.entries[0].event,
+            RecordedAudioEvent::StopMusic(StopMusic {
+                category: TestMusic::Combat,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn delayed_requests_are_recorded_once_released() {
+        use crate::tests::{TestMusic as AppMusic, TestPlugin, TestSfx as AppSfx};
+        use bevy::time::TimeUpdateStrategy;
+
+        let mut app = crate::tests::test_app((
+            TestPlugin::default(),
+            crate::TestAudioPlugin,
+            AudioRecorderPlugin::<AppMusic, AppSfx>::default(),
+        ));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+        app.world_mut()
+            .resource_mut::<AudioEventRecorder<AppMusic, AppSfx>>()
+            .start();
+        app.world_mut().write_message(
+            PlaySfx::path("thunder.ogg", AppSfx::UI).after(Duration::from_millis(200)),
+        );
+        app.update();
+        assert!(app
+            .world()
+            .resource::<AudioEventRecorder<AppMusic, AppSfx>>()
+            .timeline()
+            .is_empty());
+
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        for _ in 0..4 {
+            app.update();
+        }
+        let timeline = app
+            .world_mut()
+            .resource_mut::<AudioEventRecorder<AppMusic, AppSfx>>()
+            .stop();
+        assert_eq!(timeline.len(), 1);
+        assert!(matches!(
+            &timeline.entries[0].event,
+            RecordedAudioEvent::PlaySfx(sfx) if sfx.delay.is_none()
+        ));
+    }
+
+    #[test]
+    fn stop_all_music_keeps_its_fade() {
+        let mut app = test_app();
+        app.world_mut()
+            .resource_mut::<AudioEventRecorder<TestMusic, TestSfx>>()
+            .start();
+        let fade = Duration::from_secs(2);
+        app.world_mut()
+            .write_message(StopAllMusic::<TestMusic>::default().with_fade(fade));
+        app.update();
+        let timeline = app
+            .world_mut()
+            .resource_mut::<AudioEventRecorder<TestMusic, TestSfx>>()
+            .stop();
+
+        app.world_mut()
+            .resource_mut::<AudioTimelinePlayer<TestMusic, TestSfx>>()
+            .play(timeline);
+        app.update();
+        let stops = app.world().resource::<Messages<StopAllMusic<TestMusic>>>();
+        let mut cursor = stops.get_cursor();
+        let fades: Vec<_> = cursor.read(stops).map(|stop| stop.fade).collect();
+        assert_eq!(fades.last(), Some(&Some(fade)));
+    }
+
+    #[test]
+    fn sound_id_requests_round_trip() {
+        let mut app = test_app();
+        app.add_plugins(AssetPlugin::default());
+        app.world_mut()
+            .resource_mut::<AudioEventRecorder<TestMusic, TestSfx>>()
+            .start();
+        app.world_mut()
+            .write_message(PlaySfx::<TestSfx>::id("jump").volume(0.5));
+        app.update();
+        let timeline = app
+            .world_mut()
+            .resource_mut::<AudioEventRecorder<TestMusic, TestSfx>>()
+            .stop();
+        assert_eq!(timeline.len(), 1);
+        let text = ron::to_string(&timeline).unwrap();
+        let timeline: AudioTimeline<TestMusic, TestSfx> = ron::from_str(&text).unwrap();
+
+        app.world_mut()
+            .resource_mut::<AudioTimelinePlayer<TestMusic, TestSfx>>()
+            .play(timeline);
+        app.update();
+        let plays = app.world().resource::<Messages<PlaySfx<TestSfx>>>();
+        let mut cursor = plays.get_cursor();
+        let replayed = cursor.read(plays).last().unwrap();
+        assert_eq!(replayed.sound, Some(crate::SoundId::from_static("jump")));
+        assert_eq!(replayed.playback.volume, bevy::audio::Volume::Linear(0.5));
+    }
+
+    #[test]
+    fn echo_taps_are_not_recorded() {
+        let mut app = test_app();
+        app.world_mut()
+            .resource_mut::<AudioEventRecorder<TestMusic, TestSfx>>()
+            .start();
+        let shot = PlaySfx::path("shot.ogg", TestSfx);
+        let mut tap = shot.clone();
+        tap.echo_tap = true;
+        app.world_mut().write_message(shot);
+        app.world_mut().write_message(tap);
+        app.update();
+
+        let timeline = app
+            .world_mut()
+            .resource_mut::<AudioEventRecorder<TestMusic, TestSfx>>()
+            .stop();
+        assert_eq!(timeline.len(), 1);
+    }
+
+    #[test]
+    fn sfx_batches_round_trip() {
+        let mut app = test_app();
+        app.add_plugins(AssetPlugin::default())
+            .init_asset::<AudioSource>();
+        app.world_mut()
+            .resource_mut::<AudioEventRecorder<TestMusic, TestSfx>>()
+            .start();
+        app.world_mut().write_message(
+            PlaySfxBatch::new()
+                .with(PlaySfx::path("debris_1.ogg", TestSfx))
+                .with(PlaySfx::path("debris_2.ogg", TestSfx))
+                .with(PlaySfx::path("debris_3.ogg", TestSfx).after(Duration::from_secs(1))),
+        );
+        app.update();
+        let timeline = app
+            .world_mut()
+            .resource_mut::<AudioEventRecorder<TestMusic, TestSfx>>()
+            .stop();
+        assert_eq!(timeline.len(), 1);
+        assert!(matches!(
+            &timeline.entries[0].event,
+            RecordedAudioEvent::PlaySfxBatch(requests) if requests.len() == 2
+        ));
+
+        app.world_mut()
+            .resource_mut::<AudioTimelinePlayer<TestMusic, TestSfx>>()
+            .play(timeline);
+        app.update();
+        let batches = app.world().resource::<Messages<PlaySfxBatch<TestSfx>>>();
+        let mut cursor = batches.get_cursor();
+        assert_eq!(cursor.read(batches).last().map(PlaySfxBatch::len), Some(2));
+    }
+
+    #[test]
+    fn looping_sfx_round_trip() {
+        let mut app = test_app();
+        app.add_plugins(AssetPlugin::default())
+            .init_asset::<AudioSource>();
+        app.world_mut()
+            .resource_mut::<AudioEventRecorder<TestMusic, TestSfx>>()
+            .start();
+        let rain = app
+            .world()
+            .resource::<AssetServer>()
+            .load::<AudioSource>("rain.ogg");
+        app.world_mut()
+            .write_message(PlayLoopingSfx::new(rain, TestSfx, "rain"));
+        app.world_mut()
+            .write_message(StopLoopingSfx::new(TestSfx).with_label("rain"));
+        app.update();
+        let timeline = app
+            .world_mut()
+            .resource_mut::<AudioEventRecorder<TestMusic, TestSfx>>()
+            .stop();
+        assert_eq!(timeline.len(), 2);
+        let text = ron::to_string(&timeline).unwrap();
+        let timeline: AudioTimeline<TestMusic, TestSfx> = ron::from_str(&text).unwrap();
+
+        app.world_mut()
+            .resource_mut::<AudioTimelinePlayer<TestMusic, TestSfx>>()
+            .play(timeline);
+        app.update();
+        let loops = app.world().resource::<Messages<PlayLoopingSfx<TestSfx>>>();
+        let mut cursor = loops.get_cursor();
+        let replayed = cursor.read(loops).last().unwrap();
+        assert_eq!(
+            replayed.handle.path().map(ToString::to_string).as_deref(),
+            Some("rain.ogg")
+        );
+        let stops = app.world().resource::<Messages<StopLoopingSfx<TestSfx>>>();
+        let mut cursor = stops.get_cursor();
+        let labels: Vec<_> = cursor.read(stops).map(|stop| stop.label.clone()).collect();
+        assert_eq!(labels.last(), Some(&Some("rain".into())));
+    }
+
+    #[test]
+    fn player_writes_due_messages() {
+        let mut app = test_app();
+        let timeline = AudioTimeline::<TestMusic, TestSfx> {
+            entries: vec![
+                AudioTimelineEntry {
+                    at: Duration::ZERO,
+                    event: RecordedAudioEvent::StopMusic(StopMusic::new(TestMusic::Combat)),
+                },
+                AudioTimelineEntry {
+                    at: Duration::from_secs(3600),
+                    event: RecordedAudioEvent::StopAllMusic(StopAllMusic::default()),
+                },
+            ],
+        };
+        let text = ron::to_string(&timeline).unwrap();
+        let timeline: AudioTimeline<TestMusic, TestSfx> = ron::from_str(&text).unwrap();
+        app.world_mut()
+            .resource_mut::<AudioTimelinePlayer<TestMusic, TestSfx>>()
+            .play(timeline);
+        app.update();
+
+        let stops = app.world().resource::<Messages<StopMusic<TestMusic>>>();
+        let mut cursor = stops.get_cursor();
+        let stopped: Vec<_> = cursor.read(stops).map(|stop| stop.category).collect();
+        assert_eq!(stopped, [TestMusic::Combat]);
+        assert!(app
+            .world()
+            .resource::<Messages<StopAllMusic<TestMusic>>>()
+            .is_empty());
+        assert!(app
+            .world()
+            .resource::<AudioTimelinePlayer<TestMusic, TestSfx>>()
+            .is_playing());
+    }
+}
@@ -0,0 +1,285 @@
+//! Builder methods shared by audio bundles and play messages.
+//!
+//! [`AudioRequest`] and [`ConcurrencyRequest`] are implemented by
+//! [`MusicBundle`](crate::MusicBundle), [`SfxBundle`](crate::SfxBundle),
+//! [`PlayMusic`](crate::PlayMusic), and [`PlaySfx`](crate::PlaySfx), and
+//! [`SfxRequest`] by the sound effect ones, so the component-based and
+//! message-based APIs offer the same builders.
+
+use bevy::{audio::Volume, prelude::*};
+use std::time::Duration;
+
+use crate::components::{ConcurrencyPolicy, FadeIn, PlaybackRandomizer};
+
+/// Builders shared by every way of requesting a sound.
+///
+/// Randomization is rolled from the [`AudioRng`](crate::AudioRng), by
+/// messages when they are handled and by bundles when they are spawned.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::AudioRequest;
+///
+/// fn quiet<R: AudioRequest>(request: R) -> R {
+///     request.volume(0.3).with_speed(0.95, 1.05)
+/// }
+///
+/// commands.spawn(quiet(SfxBundle::new(click, GameSfx::UI)));
+/// sfx.write(quiet(PlaySfx::new(click, GameSfx::UI)));
+/// ```
+pub trait AudioRequest: Sized {
+    /// Returns the playback settings the sound starts with.
+    fn playback_mut(&mut self) -> &mut PlaybackSettings;
+
+    /// Returns the randomization still to be rolled, or `None` if there is
+    /// none.
+    fn randomizer_mut(&mut self) -> Option<&mut PlaybackRandomizer>;
+
+    /// Returns the fade-in the sound starts with.
+    fn fade_in_mut(&mut self) -> &mut Option<FadeIn>;
+
+    /// Returns the time the sound waits before it starts.
+    fn delay_mut(&mut self) -> &mut Option<Duration>;
+
+    /// Sets the randomization of the sound, replacing any previous one.
+    #[must_use]
+    fn with_randomizer(self, randomizer: PlaybackRandomizer) -> Self;
+
+    /// Sets custom playback settings.
+    #[must_use]
+    fn with_playback(mut self, playback: PlaybackSettings) -> Self {
+        *self.playback_mut() = playback;
+        self
+    }
+
+    /// Sets a fixed linear volume, replacing any volume randomization.
+    #[must_use]
+    fn volume(mut self, volume: f32) -> Self {
+        self.playback_mut().volume = Volume::Linear(volume);
+        if let Some(randomizer) = self.randomizer_mut() {
+            randomizer.volume_range = None;
+        }
+        self
+    }
+
+    /// Sets a fixed speed (pitch), replacing any speed randomization.
+    #[must_use]
+    fn speed(mut self, speed: f32) -> Self {
+        self.playback_mut().speed = speed;
+        if let Some(randomizer) = self.randomizer_mut() {
+            randomizer.speed_range = None;
+        }
+        self
+    }
+
+    /// Sets the volume randomization range.
+    ///
+    /// The actual volume will be randomly chosen between `min` and `max`.
+    #[must_use]
+    fn with_volume(mut self, min: f32, max: f32) -> Self {
+        if let Some(randomizer) = self.randomizer_mut() {
+            randomizer.volume_range = Some((min, max));
+            return self;
+        }
+        self.with_randomizer(PlaybackRandomizer::new().with_volume(min, max))
+    }
+
+    /// Sets the speed (pitch) randomization range.
+    ///
+    /// The actual speed will be randomly chosen between `min` and `max`.
+    #[must_use]
+    fn with_speed(mut self, min: f32, max: f32) -> Self {
+        if let Some(randomizer) = self.randomizer_mut() {
+            randomizer.speed_range = Some((min, max));
+            return self;
+        }
+        self.with_randomizer(PlaybackRandomizer::new().with_speed(min, max))
+    }
+
+    /// Applies standard randomization (speed 0.7-1.3, volume 0.6-1.0).
+    #[must_use]
+    fn randomized(self) -> Self {
+        self.with_randomizer(PlaybackRandomizer::standard())
+    }
+
+    /// Plays the sound spatially, positioned by the entity's [`Transform`].
+    #[must_use]
+    fn spatial(mut self) -> Self {
+        self.playback_mut().spatial = true;
+        self
+    }
+
+    /// Fades the sound in from silence when it starts.
+    #[must_use]
+    fn with_fade_in(mut self, fade_in: FadeIn) -> Self {
+        *self.fade_in_mut() = Some(fade_in);
+        self
+    }
+
+    /// Starts the sound after `delay` instead of right away.
+    ///
+    /// Messages are sent again once they are due, see
+    /// [`DelayedMessages`](crate::DelayedMessages). Bundles spawn right away
+    /// and hold the sound back with a [`StartDelay`](crate::StartDelay).
+    #[must_use]
+    fn after(mut self, delay: Duration) -> Self {
+        *self.delay_mut() = Some(delay);
+        self
+    }
+}
+
+/// Concurrency limit builders shared by music and sound effect requests.
+///
+/// See [`MaxConcurrent`](crate::MaxConcurrent) for how the limit is enforced.
+pub trait ConcurrencyRequest: AudioRequest {
+    /// Sets the maximum number of concurrent instances of this sound.
+    #[must_use]
+    fn with_max_concurrent(self, max: u32) -> Self;
+
+    /// Sets which instances are stopped when the concurrency limit is exceeded.
+    #[must_use]
+    fn with_concurrency_policy(self, policy: ConcurrencyPolicy) -> Self;
+
+    /// Keeps the sound from being started twice, e.g. by two systems
+    /// requesting the same track in one frame.
+    ///
+    /// Shorthand for a limit of one instance, rejecting new ones.
+    #[must_use]
+    fn unique(self) -> Self {
+        self.with_max_concurrent(1)
+            .with_concurrency_policy(ConcurrencyPolicy::RejectNew)
+    }
+}
+
+/// Builders shared by the sound effect bundle and message.
+pub trait SfxRequest: ConcurrencyRequest {
+    /// Plays the sound only with the given probability, from `0.0` to `1.0`.
+    ///
+    /// See [`PlayChance`](crate::PlayChance) for details.
+    #[must_use]
+    fn with_chance(self, chance: f32) -> Self;
+
+    /// Places the sound in the stereo field, from `-1.0` (left) to `1.0` (right).
+    ///
+    /// See [`StereoPan`](crate::StereoPan) for details.
+    #[must_use]
+    fn with_pan(self, pan: f32) -> Self;
+
+    /// Sets the stereo pan randomization range.
+    ///
+    /// The actual pan will be randomly chosen between `min` and `max`, which
+    /// may be given in either order.
+    #[must_use]
+    fn with_pan_range(self, min: f32, max: f32) -> Self;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin, TestSfx};
+    use crate::{
+        AudioLog, AudioRng, PlayMusic, PlaySfx, SfxBundle, SpawnRequest, StartDelay, StereoPan,
+        StereoPanRange, TestAudioPlugin,
+    };
+
+    #[test]
+    fn play_chance_skips_losing_sounds() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI).with_chance(0.0));
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI).with_chance(1.0));
+        app.world_mut()
+            .spawn(SfxBundle::new(Handle::default(), TestSfx::UI).with_chance(0.0));
+        app.update();
+        app.update();
+
+        assert_eq!(app.world().resource::<AudioLog>().plays().count(), 1);
+    }
+
+    #[test]
+    fn pan_ranges_roll_from_audio_rng_in_either_order() {
+        let pans = |seed| {
+            let mut app = test_app(TestPlugin::default());
+            app.insert_resource(AudioRng::seeded(seed));
+            app.world_mut().write_message(
+                PlaySfx::new(Handle::default(), TestSfx::UI).with_pan_range(0.5, -0.5),
+            );
+            app.update();
+            let bundle = app
+                .world_mut()
+                .spawn(SfxBundle::new(Handle::default(), TestSfx::UI).with_pan_range(0.5, -0.5))
+                .id();
+            let message = app
+                .world_mut()
+                .query_filtered::<&StereoPan, Without<StereoPanRange>>()
+                .single(app.world())
+                .unwrap()
+                .0;
+            (message, app.world().get::<StereoPan>(bundle).unwrap().0)
+        };
+
+        let (message, bundle) = pans(7);
+        assert!((-0.5..=0.5).contains(&message));
+        assert!((-0.5..=0.5).contains(&bundle));
+        assert_eq!(pans(7), (message, bundle));
+    }
+
+    #[test]
+    fn audio_request_builders_apply_to_bundles_and_messages() {
+        fn quiet<R: AudioRequest>(request: R) -> R {
+            request.volume(0.25).with_speed(1.5, 1.5)
+        }
+
+        let bundle = quiet(SfxBundle::new(Handle::default(), TestSfx::UI));
+        assert_eq!(bundle.playback.volume, bevy::audio::Volume::Linear(0.25));
+
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.world_mut().write_message(
+            quiet(PlaySfx::new(Handle::default(), TestSfx::UI))
+                .with_fade_in(FadeIn::new(Duration::from_secs(1))),
+        );
+        app.world_mut()
+            .write_message(quiet(PlayMusic::new(Handle::default(), TestMusic::Main)));
+        app.update();
+
+        let delay = Duration::from_millis(100);
+        let spawned = app
+            .world_mut()
+            .spawn(
+                bundle
+                    .with_fade_in(FadeIn::new(Duration::from_secs(1)))
+                    .after(delay),
+            )
+            .id();
+        let spawned = app.world().entity(spawned);
+        assert!((spawned.get::<PlaybackSettings>().unwrap().speed - 1.5).abs() < f32::EPSILON);
+        assert!(spawned.contains::<FadeIn>());
+        assert!(spawned.contains::<StartDelay>());
+        assert!(!spawned.contains::<AudioPlayer>());
+        assert!(!spawned.contains::<SpawnRequest>());
+        let spawned = spawned.id();
+
+        let world = app.world_mut();
+        let (playback, fade_in) = world
+            .query_filtered::<(&PlaybackSettings, Option<&FadeIn>), (With<TestSfx>, With<AudioPlayer>)>()
+            .single(world)
+            .unwrap();
+        assert_eq!(playback.volume, bevy::audio::Volume::Linear(0.25));
+        assert!((playback.speed - 1.5).abs() < f32::EPSILON);
+        assert!(fade_in.is_some());
+        let playback = world
+            .query_filtered::<&PlaybackSettings, With<TestMusic>>()
+            .single(world)
+            .unwrap();
+        assert!((playback.speed - 1.5).abs() < f32::EPSILON);
+
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(delay));
+        app.update();
+        app.update();
+        let spawned = app.world().entity(spawned);
+        assert!(spawned.contains::<AudioPlayer>());
+        assert!(!spawned.contains::<StartDelay>());
+    }
+}
@@ -0,0 +1,104 @@
+//! Seedable random number source for audio randomization.
+//!
+//! Randomized playback rolled by the plugin's systems, such as
+//! [`PlaySfx::randomized`](crate::PlaySfx::randomized) and the
+//! [`PlaybackRandomizer`](crate::PlaybackRandomizer) component, draws from the
+//! [`AudioRng`] resource. Seeding it makes replays and tests reproducible.
+
+use bevy::prelude::*;
+use rand::{distr::uniform::SampleUniform, rngs::StdRng, Rng, RngCore, SeedableRng};
+
+/// Resource providing the random numbers used for audio randomization.
+///
+/// Seeded from the thread-local generator by default. Insert a seeded one,
+/// before or after adding the plugin, to make the rolled values reproducible.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::AudioRng;
+///
+/// app.insert_resource(AudioRng::seeded(replay.seed));
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct AudioRng(StdRng);
+
+impl Default for AudioRng {
+    fn default() -> Self {
+        Self(StdRng::from_rng(&mut rand::rng()))
+    }
+}
+
+impl AudioRng {
+    /// Creates a generator producing the same values for the same seed.
+    #[must_use]
+    pub fn seeded(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+
+    /// Restarts the generator from a seed.
+    pub fn reseed(&mut self, seed: u64) {
+        self.0 = StdRng::seed_from_u64(seed);
+    }
+}
+
+/// Returns a random value between `a` and `b` inclusive, with the bounds in
+/// either order.
+pub(crate) fn random_between<T, R>(rng: &mut R, a: T, b: T) -> T
+where
+    T: SampleUniform + PartialOrd,
+    R: Rng + ?Sized,
+{
+    if b < a {
+        rng.random_range(b..=a)
+    } else {
+        rng.random_range(a..=b)
+    }
+}
+
+impl RngCore for AudioRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.0.fill_bytes(dst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestPlugin, TestSfx};
+    use crate::{AudioRequest, PlaySfx, TestAudioPlugin};
+
+    #[test]
+    fn seeded_audio_rng_rolls_reproducible_playback() {
+        let roll = || {
+            let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+            app.insert_resource(AudioRng::seeded(7));
+            for _ in 0..3 {
+                app.world_mut()
+                    .write_message(PlaySfx::new(Handle::default(), TestSfx::UI).randomized());
+            }
+            app.update();
+            let mut speeds: Vec<f32> = app
+                .world_mut()
+                .query::<&PlaybackSettings>()
+                .iter(app.world())
+                .map(|playback| playback.speed)
+                .collect();
+            speeds.sort_by(f32::total_cmp);
+            speeds
+        };
+
+        let speeds = roll();
+        assert_eq!(speeds.len(), 3);
+        assert!(speeds.iter().all(|speed| (0.7..=1.3).contains(speed)));
+        assert_eq!(speeds, roll());
+    }
+}
@@ -0,0 +1,201 @@
+//! Scripted audio sequences.
+//!
+//! An [`AudioSequence`] lists audio requests and waits, such as a jingle
+//! followed by the level music. It is spawned as an entity and stepped
+//! through by [`run_audio_sequences`], which sends each request as its
+//! regular message. The entity despawns itself once the last step ran.
+
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::events::{FadeOutMusic, PlayMusic, PlaySfx, StopMusic};
+use crate::traits::{MusicCategory, SfxCategory};
+
+/// A single step of an [`AudioSequence`].
+#[derive(Clone)]
+pub enum SequenceStep<M: MusicCategory, S: SfxCategory> {
+    /// Sends a [`PlayMusic`] message.
+    PlayMusic(PlayMusic<M>),
+    /// Sends a [`PlaySfx`] message.
+    PlaySfx(PlaySfx<S>),
+    /// Sends a [`FadeOutMusic`] message.
+    FadeOutMusic(FadeOutMusic<M>),
+    /// Sends a [`StopMusic`] message.
+    StopMusic(StopMusic<M>),
+    /// Waits before running the next step.
+    Wait(Duration),
+}
+
+/// Component running a list of audio steps in order, with music type `M` and
+/// sound effect type `S`.
+///
+/// Steps without a wait in between run in the same frame. Waits count in
+/// [`Time<Virtual>`], so a sequence pauses with the game. Despawn the entity
+/// to cancel the rest of the sequence.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{AudioSequence, FadeOutMusic, PlayMusic, PlaySfx};
+///
+/// commands.spawn(
+///     AudioSequence::new()
+///         .fade_out_music(FadeOutMusic::new(GameMusic::Menu, Duration::from_secs(1)))
+///         .play_sfx(PlaySfx::new(jingle, GameSfx::UI))
+///         .wait(Duration::from_secs(3))
+///         .play_music(PlayMusic::new(level_theme, GameMusic::Exploration)),
+/// );
+/// ```
+#[derive(Component, Clone)]
+pub struct AudioSequence<M: MusicCategory, S: SfxCategory> {
+    steps: VecDeque<SequenceStep<M, S>>,
+    wait: Option<Timer>,
+}
+
+impl<M: MusicCategory, S: SfxCategory> Default for AudioSequence<M, S> {
+    fn default() -> Self {
+        Self {
+            steps: VecDeque::new(),
+            wait: None,
+        }
+    }
+}
+
+impl<M: MusicCategory, S: SfxCategory> AudioSequence<M, S> {
+    /// Creates an empty sequence.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a step.
+    #[must_use]
+    pub fn then(mut self, step: SequenceStep<M, S>) -> Self {
+        self.steps.push_back(step);
+        self
+    }
+
+    /// Appends a music track to start.
+    #[must_use]
+    pub fn play_music(self, message: PlayMusic<M>) -> Self {
+        self.then(SequenceStep::PlayMusic(message))
+    }
+
+    /// Appends a sound effect to play.
+    #[must_use]
+    pub fn play_sfx(self, message: PlaySfx<S>) -> Self {
+        self.then(SequenceStep::PlaySfx(message))
+    }
+
+    /// Appends a music fade-out.
+    #[must_use]
+    pub fn fade_out_music(self, message: FadeOutMusic<M>) -> Self {
+        self.then(SequenceStep::FadeOutMusic(message))
+    }
+
+    /// Appends a music stop.
+    #[must_use]
+    pub fn stop_music(self, message: StopMusic<M>) -> Self {
+        self.then(SequenceStep::StopMusic(message))
+    }
+
+    /// Appends a wait before the following steps.
+    #[must_use]
+    pub fn wait(self, duration: Duration) -> Self {
+        self.then(SequenceStep::Wait(duration))
+    }
+
+    /// Returns the number of steps that have not run yet.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.steps.len()
+    }
+}
+
+/// Runs the due steps of every [`AudioSequence`] and despawns finished ones.
+///
+/// Runs before [`MsgAudioSet::EventHandling`](crate::MsgAudioSet::EventHandling),
+/// so the requests of a step are handled in the same frame.
+pub fn run_audio_sequences<M: MusicCategory, S: SfxCategory>(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut sequences: Query<(Entity, &mut AudioSequence<M, S>)>,
+    mut play_music: MessageWriter<PlayMusic<M>>,
+    mut play_sfx: MessageWriter<PlaySfx<S>>,
+    mut fade_out_music: MessageWriter<FadeOutMusic<M>>,
+    mut stop_music: MessageWriter<StopMusic<M>>,
+) {
+    for (entity, mut sequence) in &mut sequences {
+        if let Some(wait) = sequence.wait.as_mut() {
+            if !wait.tick(time.delta()).is_finished() {
+                continue;
+            }
+            sequence.wait = None;
+        }
+        while sequence.wait.is_none() {
+            let Some(step) = sequence.steps.pop_front() else {
+                commands.entity(entity).despawn();
+                break;
+            };
+            match step {
+                SequenceStep::PlayMusic(message) => {
+                    play_music.write(message);
+                }
+                SequenceStep::PlaySfx(message) => {
+                    play_sfx.write(message);
+                }
+                SequenceStep::FadeOutMusic(message) => {
+                    fade_out_music.write(message);
+                }
+                SequenceStep::StopMusic(message) => {
+                    stop_music.write(message);
+                }
+                SequenceStep::Wait(duration) => {
+                    sequence.wait = Some(Timer::new(duration, TimerMode::Once));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin, TestSfx};
+    use crate::{AudioLog, TestAudioPlugin};
+
+    #[test]
+    fn audio_sequence_runs_steps_in_order() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        // Frozen time, so slow frames can't run out the wait early
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            Duration::ZERO,
+        ));
+        let wait = Duration::from_millis(100);
+        let sequence = app
+            .world_mut()
+            .spawn(
+                AudioSequence::new()
+                    .play_sfx(PlaySfx::new(Handle::default(), TestSfx::UI))
+                    .wait(wait)
+                    .play_music(PlayMusic::new(Handle::default(), TestMusic::Main)),
+            )
+            .id();
+        app.update();
+        app.update();
+        let music = |app: &mut App| {
+            app.world_mut()
+                .query::<&TestMusic>()
+                .iter(app.world())
+                .count()
+        };
+        assert_eq!(app.world().resource::<AudioLog>().plays().count(), 1);
+        assert_eq!(music(&mut app), 0);
+
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(wait));
+        app.update();
+        assert_eq!(music(&mut app), 1);
+        assert!(app.world().get_entity(sequence).is_err());
+    }
+}
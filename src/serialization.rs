@@ -0,0 +1,558 @@
+//! Serializable audio messages.
+//!
+//! Messages without asset handles, such as [`StopMusic`](crate::StopMusic) or
+//! [`FadeOutSfx`](crate::FadeOutSfx), implement `Serialize` and `Deserialize`
+//! directly when the `serde` feature is enabled. Messages that play a sound
+//! hold a [`Handle`], which only means something inside one running app, so
+//! they are serialized as [`SerializedPlayMusic`], [`SerializedPlaySfx`],
+//! and [`SerializedPlayLoopingSfx`], which name their source by asset path
+//! instead.
+
+use bevy::{
+    audio::{PlaybackMode, Volume},
+    prelude::*,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::time::Duration;
+
+use crate::components::{
+    ConcurrencyPolicy, DuplicateMusic, FadeIn, PlaybackLabel, PlaybackRandomizer, TrackGain,
+};
+use crate::easing::Easing;
+use crate::events::{PlayLoopingSfx, PlayMusic, PlaySfx};
+use crate::library::SoundId;
+use crate::request::{AudioRequest, ConcurrencyRequest, SfxRequest};
+use crate::systems::extract_linear_volume;
+use crate::traits::{MusicCategory, SfxCategory};
+use crate::MusicTempo;
+
+/// [`PlayMusic`] with its source stored as an asset path.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::SerializedPlayMusic;
+///
+/// let request: SerializedPlayMusic<GameMusic> =
+///     ron::from_str(r#"(path: "music/forest.ogg", category: Exploration)"#)?;
+/// messages.write(request.into_message(&asset_server));
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SerializedPlayMusic<M: MusicCategory> {
+    /// Asset path of the audio source.
+    pub path: String,
+    /// The music category for volume control.
+    pub category: M,
+    /// Playback settings (defaults to LOOP).
+    #[serde(with = "playback_settings", default = "default_music_playback")]
+    pub playback: PlaybackSettings,
+    /// Optional fade-in applied when the track starts.
+    #[serde(default)]
+    pub fade_in: Option<FadeIn>,
+    /// Defer playback until the audio source has finished loading.
+    #[serde(default)]
+    pub wait_for_load: bool,
+    /// Optional per-track gain applied on top of the category volume.
+    #[serde(default)]
+    pub gain: Option<TrackGain>,
+    /// Optional label identifying the spawned instance.
+    #[serde(default)]
+    pub label: Option<PlaybackLabel>,
+    /// Continue from the position stored in [`MusicMemory`](crate::MusicMemory).
+    #[serde(default)]
+    pub resume: bool,
+    /// Optional tempo that makes the track drive the [`MusicClock`](crate::MusicClock).
+    #[serde(default)]
+    pub tempo: Option<MusicTempo>,
+    /// Time to wait before the track starts.
+    #[serde(default)]
+    pub delay: Option<Duration>,
+    /// Randomization rolled when the track is spawned.
+    #[serde(default)]
+    pub randomizer: Option<PlaybackRandomizer>,
+    /// Maximum concurrent instances of this track.
+    #[serde(default = "unlimited_concurrent")]
+    pub max_concurrent: u32,
+    /// Which instances are stopped when `max_concurrent` is exceeded.
+    #[serde(default)]
+    pub concurrency_policy: ConcurrencyPolicy,
+    /// What happens if the track already plays in the category.
+    #[serde(default)]
+    pub on_duplicate: Option<DuplicateMusic>,
+}
+
+impl<M: MusicCategory> SerializedPlayMusic<M> {
+    /// Loads the source and builds the message.
+    #[must_use]
+    pub fn into_message(self, asset_server: &AssetServer) -> PlayMusic<M> {
+        PlayMusic {
+            handle: asset_server.load(self.path),
+            category: self.category,
+            playback: self.playback,
+            fade_in: self.fade_in,
+            wait_for_load: self.wait_for_load,
+            gain: self.gain,
+            label: self.label,
+            resume: self.resume,
+            tempo: self.tempo,
+            delay: self.delay,
+            randomizer: self.randomizer,
+            max_concurrent: self.max_concurrent,
+            concurrency_policy: self.concurrency_policy,
+            on_duplicate: self.on_duplicate,
+            path: None,
+            components: Vec::new(),
+        }
+    }
+}
+
+impl<M: MusicCategory> PlayMusic<M> {
+    /// Returns the serializable form of the message, or `None` if its
+    /// source was not loaded from a path or created with an asset path.
+    #[must_use]
+    pub fn to_serialized(&self) -> Option<SerializedPlayMusic<M>> {
+        Some(SerializedPlayMusic {
+            path: self.path.as_ref().or(self.handle.path())?.to_string(),
+            category: self.category,
+            playback: self.playback,
+            fade_in: self.fade_in.clone(),
+            wait_for_load: self.wait_for_load,
+            gain: self.gain,
+            label: self.label.clone(),
+            resume: self.resume,
+            tempo: self.tempo,
+            delay: self.delay,
+            randomizer: self.randomizer.clone(),
+            max_concurrent: self.max_concurrent,
+            concurrency_policy: self.concurrency_policy,
+            on_duplicate: self.on_duplicate,
+        })
+    }
+}
+
+/// [`PlaySfx`] with its source stored as an asset path or [`SoundId`].
+///
+/// Localized variants and the attached entity are not serialized, as both
+/// only make sense inside the running app.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SerializedPlaySfx<S: SfxCategory> {
+    /// Asset path of the audio source, empty for a `sound` request.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub path: String,
+    /// Sound of the [`SoundLibrary`](crate::SoundLibrary) played in place of
+    /// `path`, as with [`PlaySfx::id`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sound: Option<SoundId>,
+    /// The sound effect category for volume control.
+    pub category: S,
+    /// Playback settings (defaults to DESPAWN).
+    #[serde(with = "playback_settings", default = "default_sfx_playback")]
+    pub playback: PlaybackSettings,
+    /// Maximum concurrent instances of this sound.
+    #[serde(default = "default_max_concurrent")]
+    pub max_concurrent: u32,
+    /// Which instances are stopped when `max_concurrent` is exceeded.
+    #[serde(default)]
+    pub concurrency_policy: ConcurrencyPolicy,
+    /// Defer playback until the audio source has finished loading.
+    #[serde(default)]
+    pub wait_for_load: bool,
+    /// Optional label identifying the spawned instance.
+    #[serde(default)]
+    pub label: Option<PlaybackLabel>,
+    /// Priority when the [`VoiceBudget`](crate::VoiceBudget) is exceeded.
+    #[serde(default)]
+    pub priority: i32,
+    /// Randomization rolled when the sound is spawned.
+    #[serde(default)]
+    pub randomizer: Option<PlaybackRandomizer>,
+    /// Probability that the sound plays.
+    #[serde(default = "default_chance")]
+    pub chance: f32,
+    /// Range the stereo pan is rolled from.
+    #[serde(default)]
+    pub pan: Option<(f32, f32)>,
+    /// Playing time after which the sound is force-stopped.
+    #[serde(default)]
+    pub max_duration: Option<Duration>,
+    /// Time to wait before the sound starts.
+    #[serde(default)]
+    pub delay: Option<Duration>,
+    /// Optional fade-in applied when the sound starts.
+    #[serde(default)]
+    pub fade_in: Option<FadeIn>,
+    /// Play centered and without spatial attenuation.
+    #[serde(default)]
+    pub non_spatial: bool,
+}
+
+impl<S: SfxCategory> SerializedPlaySfx<S> {
+    /// Loads the source and builds the message.
+    ///
+    /// Requests for a `sound` are left to the sound library and load nothing.
+    #[must_use]
+    pub fn into_message(self, asset_server: &AssetServer) -> PlaySfx<S> {
+        let mut message = match self.sound {
+            Some(sound) => PlaySfx {
+                category: self.category,
+                ..PlaySfx::id(sound)
+            },
+            None => PlaySfx::new(asset_server.load(self.path), self.category),
+        };
+        message = message
+            .with_playback(self.playback)
+            .with_max_concurrent(self.max_concurrent)
+            .with_concurrency_policy(self.concurrency_policy)
+            .with_priority(self.priority);
+        message.wait_for_load = self.wait_for_load;
+        message.label = self.label;
+        message.randomizer = self.randomizer;
+        message.pan = self.pan;
+        message.max_duration = self.max_duration;
+        message.delay = self.delay;
+        message.fade_in = self.fade_in;
+        message.non_spatial = self.non_spatial;
+        message.with_chance(self.chance)
+    }
+}
+
+impl<S: SfxCategory> PlaySfx<S> {
+    /// Returns the serializable form of the message, or `None` if it neither
+    /// plays a [`SoundId`] nor has a source loaded from or created with an
+    /// asset path.
+    #[must_use]
+    pub fn to_serialized(&self) -> Option<SerializedPlaySfx<S>> {
+        let path = match &self.sound {
+            Some(_) => String::new(),
+            None => self.path.as_ref().or(self.handle.path())?.to_string(),
+        };
+        Some(SerializedPlaySfx {
+            path,
+            sound: self.sound.clone(),
+            category: self.category,
+            playback: self.playback,
+            max_concurrent: self.max_concurrent,
+            concurrency_policy: self.concurrency_policy,
+            wait_for_load: self.wait_for_load,
+            label: self.label.clone(),
+            priority: self.priority,
+            randomizer: self.randomizer.clone(),
+            chance: self.chance,
+            pan: self.pan,
+            max_duration: self.max_duration,
+            delay: self.delay,
+            fade_in: self.fade_in.clone(),
+            non_spatial: self.non_spatial,
+        })
+    }
+}
+
+/// [`PlayLoopingSfx`] with its source stored as an asset path.
+///
+/// User components are not serialized.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SerializedPlayLoopingSfx<S: SfxCategory> {
+    /// Asset path of the audio source.
+    pub path: String,
+    /// The sound effect category for volume control.
+    pub category: S,
+    /// Label identifying the loop.
+    pub label: PlaybackLabel,
+    /// Playback settings (defaults to LOOP).
+    #[serde(with = "playback_settings", default = "default_music_playback")]
+    pub playback: PlaybackSettings,
+    /// Randomization rolled when the loop is spawned.
+    #[serde(default)]
+    pub randomizer: Option<PlaybackRandomizer>,
+    /// Optional fade-in applied when the loop starts.
+    #[serde(default)]
+    pub fade_in: Option<FadeIn>,
+    /// Time to wait before the loop starts.
+    #[serde(default)]
+    pub delay: Option<Duration>,
+    /// Defer playback until the audio source has finished loading.
+    #[serde(default)]
+    pub wait_for_load: bool,
+}
+
+impl<S: SfxCategory> SerializedPlayLoopingSfx<S> {
+    /// Loads the source and builds the message.
+    #[must_use]
+    pub fn into_message(self, asset_server: &AssetServer) -> PlayLoopingSfx<S> {
+        PlayLoopingSfx {
+            handle: asset_server.load(self.path),
+            category: self.category,
+            label: self.label,
+            playback: self.playback,
+            randomizer: self.randomizer,
+            fade_in: self.fade_in,
+            delay: self.delay,
+            wait_for_load: self.wait_for_load,
+            components: Vec::new(),
+        }
+    }
+}
+
+impl<S: SfxCategory> PlayLoopingSfx<S> {
+    /// Returns the serializable form of the message, or `None` if its
+    /// source was not loaded from a path.
+    #[must_use]
+    pub fn to_serialized(&self) -> Option<SerializedPlayLoopingSfx<S>> {
+        Some(SerializedPlayLoopingSfx {
+            path: self.handle.path()?.to_string(),
+            category: self.category,
+            label: self.label.clone(),
+            playback: self.playback,
+            randomizer: self.randomizer.clone(),
+            fade_in: self.fade_in.clone(),
+            delay: self.delay,
+            wait_for_load: self.wait_for_load,
+        })
+    }
+}
+
+fn default_music_playback() -> PlaybackSettings {
+    PlaybackSettings::LOOP
+}
+
+fn default_sfx_playback() -> PlaybackSettings {
+    PlaybackSettings::DESPAWN
+}
+
+fn default_max_concurrent() -> u32 {
+    crate::bundles::DEFAULT_MAX_CONCURRENT
+}
+
+fn unlimited_concurrent() -> u32 {
+    u32::MAX
+}
+
+fn default_chance() -> f32 {
+    1.0
+}
+
+/// Serialized form of a [`FadeIn`]: its duration, target, and curve.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "FadeIn")]
+struct FadeInDef {
+    duration: Duration,
+    #[serde(default = "full_volume")]
+    target_volume: f32,
+    #[serde(default)]
+    easing: Easing,
+}
+
+fn full_volume() -> f32 {
+    1.0
+}
+
+impl Serialize for FadeIn {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        FadeInDef {
+            duration: self.timer.duration(),
+            target_volume: self.target_volume,
+            easing: self.easing.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FadeIn {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let def = FadeInDef::deserialize(deserializer)?;
+        let mut fade_in = FadeIn::new(def.duration).with_easing(def.easing);
+        fade_in.target_volume = def.target_volume;
+        Ok(fade_in)
+    }
+}
+
+/// `serde(with)` module for [`PlaybackSettings`].
+///
+/// Volumes are stored as linear gains. Spatial scale is not serialized.
+mod playback_settings {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "PlaybackMode")]
+    enum PlaybackModeDef {
+        Once,
+        Loop,
+        Despawn,
+        Remove,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "PlaybackSettings", default)]
+    struct PlaybackDef {
+        #[serde(with = "PlaybackModeDef")]
+        mode: PlaybackMode,
+        volume: f32,
+        speed: f32,
+        paused: bool,
+        muted: bool,
+        spatial: bool,
+        start_position: Option<Duration>,
+        duration: Option<Duration>,
+    }
+
+    impl Default for PlaybackDef {
+        fn default() -> Self {
+            let playback = PlaybackSettings::default();
+            Self {
+                mode: playback.mode,
+                volume: extract_linear_volume(playback.volume),
+                speed: playback.speed,
+                paused: playback.paused,
+                muted: playback.muted,
+                spatial: playback.spatial,
+                start_position: playback.start_position,
+                duration: playback.duration,
+            }
+        }
+    }
+
+    pub fn serialize<S: Serializer>(
+        playback: &PlaybackSettings,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        PlaybackDef {
+            mode: playback.mode,
+            volume: extract_linear_volume(playback.volume),
+            speed: playback.speed,
+            paused: playback.paused,
+            muted: playback.muted,
+            spatial: playback.spatial,
+            start_position: playback.start_position,
+            duration: playback.duration,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<PlaybackSettings, D::Error> {
+        let def = PlaybackDef::deserialize(deserializer)?;
+        Ok(PlaybackSettings {
+            mode: def.mode,
+            volume: Volume::Linear(def.volume),
+            speed: def.speed,
+            paused: def.paused,
+            muted: def.muted,
+            spatial: def.spatial,
+            start_position: def.start_position,
+            duration: def.duration,
+            ..default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AudioConfigTrait, StopMusic};
+
+    #[derive(Resource, Clone, Default)]
+    struct TestConfig;
+
+    impl AudioConfigTrait for TestConfig {
+        fn master_volume(&self) -> f32 {
+            1.0
+        }
+    }
+
+    #[derive(Component, Clone, Copy, Default, PartialEq, Debug, Serialize, Deserialize)]
+    enum TestMusic {
+        #[default]
+        Theme,
+    }
+
+    impl crate::AudioCategory for TestMusic {
+        type Config = TestConfig;
+
+        fn volume_multiplier(&self, _config: &Self::Config) -> f32 {
+            1.0
+        }
+    }
+
+    impl MusicCategory for TestMusic {}
+
+    #[test]
+    fn play_music_round_trips_through_ron() {
+        let serialized = SerializedPlayMusic {
+            path: "music/theme.ogg".to_string(),
+            category: TestMusic::Theme,
+            playback: PlaybackSettings::LOOP.with_speed(1.5),
+            fade_in: Some(FadeIn::from_secs(2.0).with_easing(Easing::SCurve)),
+            wait_for_load: true,
+            gain: Some(TrackGain::new(0.5)),
+            label: Some(PlaybackLabel::new("theme")),
+            resume: false,
+            tempo: None,
+            delay: Some(Duration::from_millis(250)),
+            randomizer: None,
+            max_concurrent: 1,
+            concurrency_policy: ConcurrencyPolicy::RejectNew,
+            on_duplicate: Some(DuplicateMusic::Crossfade),
+        };
+        let text = ron::to_string(&serialized).unwrap();
+        let parsed: SerializedPlayMusic<TestMusic> = ron::from_str(&text).unwrap();
+
+        assert_eq!(parsed.path, "music/theme.ogg");
+        assert!(matches!(parsed.playback.mode, PlaybackMode::Loop));
+        assert_eq!(parsed.playback.speed, 1.5);
+        let fade_in = parsed.fade_in.unwrap();
+        assert_eq!(fade_in.timer.duration(), Duration::from_secs(2));
+        assert!(matches!(fade_in.easing, Easing::SCurve));
+        assert_eq!(parsed.delay, Some(Duration::from_millis(250)));
+        assert_eq!(parsed.gain, Some(TrackGain(0.5)));
+        assert_eq!(parsed.label, Some(PlaybackLabel::new("theme")));
+        assert_eq!(parsed.on_duplicate, Some(DuplicateMusic::Crossfade));
+    }
+
+    #[test]
+    fn missing_fields_use_message_defaults() {
+        let parsed: SerializedPlaySfx<TestSfx> =
+            ron::from_str(r#"(path: "sfx/hit.ogg", category: Hit)"#).unwrap();
+
+        assert!(matches!(parsed.playback.mode, PlaybackMode::Despawn));
+        assert_eq!(parsed.max_concurrent, crate::DEFAULT_MAX_CONCURRENT);
+        assert_eq!(parsed.priority, 0);
+    }
+
+    #[test]
+    fn sound_ids_serialize_without_a_path() {
+        let request = PlaySfx::<TestSfx>::id("jump").volume(0.5);
+        let text = ron::to_string(&request.to_serialized().unwrap()).unwrap();
+        assert!(!text.contains("path"));
+
+        let parsed: SerializedPlaySfx<TestSfx> = ron::from_str(&text).unwrap();
+        assert_eq!(parsed.sound, Some(SoundId::from_static("jump")));
+        assert!(parsed.path.is_empty());
+        let parsed: SerializedPlaySfx<TestSfx> =
+            ron::from_str(r#"(sound: Some("jump"), category: Hit)"#).unwrap();
+        assert_eq!(parsed.sound, Some(SoundId::from_static("jump")));
+    }
+
+    #[test]
+    fn custom_easing_fails_to_serialize() {
+        let stop = StopMusic::new(TestMusic::Theme);
+        assert!(ron::to_string(&stop).is_ok());
+        assert!(ron::to_string(&Easing::custom(|t| t)).is_err());
+    }
+
+    #[derive(Component, Clone, Copy, Default, PartialEq, Debug, Serialize, Deserialize)]
+    enum TestSfx {
+        #[default]
+        Hit,
+    }
+
+    impl crate::AudioCategory for TestSfx {
+        type Config = TestConfig;
+
+        fn volume_multiplier(&self, _config: &Self::Config) -> f32 {
+            1.0
+        }
+    }
+
+    impl SfxCategory for TestSfx {}
+}
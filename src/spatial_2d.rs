@@ -0,0 +1,191 @@
+//! Spatial audio for 2D games.
+//!
+//! `bevy_audio` places emitters and ears in 3D, so a 2D camera far above the
+//! sprite plane hears every sound from straight ahead and attenuated by the
+//! camera's depth. Sounds with a [`Spatial2d`] are instead panned by their
+//! horizontal offset from the [`SpatialListener`] on the 2D plane, and
+//! [`SpatialSettings`](crate::SpatialSettings) attenuate them by their planar
+//! distance.
+
+use bevy::prelude::*;
+
+use crate::components::NonSpatial;
+
+/// Default horizontal distance at which a 2D sound is panned fully to one
+/// side.
+pub const DEFAULT_PAN_WIDTH: f32 = 500.0;
+
+/// Plays a sound positioned on the 2D plane by its [`GlobalTransform`].
+///
+/// The sound is switched to spatial playback. Its pan follows the horizontal
+/// offset from the [`SpatialListener`], usually on the 2D camera, and the `z`
+/// axis is ignored. Add [`SpatialSettings`](crate::SpatialSettings) for
+/// distance attenuation.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{AudioListenerTarget, Spatial2d, SpatialSettings};
+///
+/// commands.spawn((Camera2d, AudioListenerTarget::default()));
+/// sfx.write(
+///     PlaySfx::new(assets.load("sfx/coin.ogg"), GameSfx::Gameplay)
+///         .attached_to(coin)
+///         .with_components(Spatial2d::default())
+///         .with_components(SpatialSettings::default().with_distance(100.0, 1000.0)),
+/// );
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+#[require(Transform)]
+pub struct Spatial2d {
+    /// Horizontal distance at which the sound is panned fully to one side.
+    pub pan_width: f32,
+}
+
+impl Default for Spatial2d {
+    fn default() -> Self {
+        Self {
+            pan_width: DEFAULT_PAN_WIDTH,
+        }
+    }
+}
+
+impl Spatial2d {
+    /// Creates a 2D sound panned fully to one side at `pan_width`.
+    #[must_use]
+    pub fn new(pan_width: f32) -> Self {
+        Self { pan_width }
+    }
+
+    /// Returns the pan for an offset from the listener, from `-1.0` (left)
+    /// to `1.0` (right).
+    #[must_use]
+    pub fn pan(&self, offset: Vec2) -> f32 {
+        if self.pan_width <= 0.0 {
+            return 0.0;
+        }
+        (offset.x / self.pan_width).clamp(-1.0, 1.0)
+    }
+}
+
+/// Switches new [`Spatial2d`] sounds to spatial playback.
+pub fn enable_2d_spatial_playback(
+    mut sounds: Query<&mut PlaybackSettings, (Added<Spatial2d>, Without<NonSpatial>)>,
+) {
+    for mut playback in &mut sounds {
+        if !playback.spatial {
+            playback.spatial = true;
+        }
+    }
+}
+
+/// Places the ears and emitter of [`Spatial2d`] sounds for their pan.
+///
+/// The ears are kept two units apart around the origin and the emitter on
+/// the ear axis, so the pan does not attenuate centered sounds. Runs in
+/// `Last` to override the 3D positions `bevy_audio` writes in `PostUpdate`.
+pub fn pan_2d_sounds(
+    listeners: Query<&GlobalTransform, With<SpatialListener>>,
+    sounds: Query<(&Spatial2d, &GlobalTransform, &SpatialAudioSink), Without<NonSpatial>>,
+) {
+    let listener = listeners
+        .iter()
+        .next()
+        .map_or(Vec2::ZERO, |transform| transform.translation().truncate());
+    for (spatial, transform, sink) in &sounds {
+        let pan = spatial.pan(transform.translation().truncate() - listener);
+        sink.set_ears_position(Vec3::NEG_X, Vec3::X);
+        sink.set_emitter_position(Vec3::new(pan, 0.0, 0.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestConfig, TestPlugin, TestSfx};
+    use crate::{
+        Attenuation, AudioRequest, PlaySfx, SfxBundle, SfxRequest, SpatialSettings, StereoPan,
+        TestAudioPlugin,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn spatial_2d_sounds_attenuate_on_the_plane() {
+        let mut app = test_app((
+            TestPlugin::default().with_volume_ramp(Duration::ZERO),
+            TestAudioPlugin,
+        ));
+        app.insert_resource(TestConfig::FULL);
+        app.world_mut().spawn((
+            SpatialListener::default(),
+            GlobalTransform::from_translation(Vec3::new(0.0, 0.0, 1000.0)),
+        ));
+        let sound = app
+            .world_mut()
+            .spawn((
+                SfxBundle::new(Handle::default(), TestSfx::UI)
+                    .with_playback(PlaybackSettings::LOOP),
+                Spatial2d::default(),
+                SpatialSettings::new(Attenuation::Linear).with_distance(100.0, 300.0),
+                GlobalTransform::from_translation(Vec3::new(120.0, 160.0, 0.0)),
+            ))
+            .id();
+        app.update();
+        app.update();
+
+        let world = app.world();
+        assert!(world.get::<PlaybackSettings>(sound).unwrap().spatial);
+        let volume = world.get::<AudioSink>(sound).unwrap().volume();
+        assert!((crate::systems::extract_linear_volume(volume) - 0.5).abs() < 1e-5);
+        assert_eq!(Spatial2d::new(100.0).pan(Vec2::new(-50.0, 300.0)), -0.5);
+    }
+
+    #[test]
+    fn non_spatial_sounds_stay_centered() {
+        let mut app = test_app((
+            TestPlugin::default().with_volume_ramp(Duration::ZERO),
+            TestAudioPlugin,
+        ));
+        app.insert_resource(TestConfig::FULL);
+        app.world_mut()
+            .spawn((SpatialListener::default(), GlobalTransform::IDENTITY));
+        let coin = app
+            .world_mut()
+            .spawn(GlobalTransform::from_translation(Vec3::new(50.0, 0.0, 0.0)))
+            .id();
+        app.world_mut().write_message(
+            PlaySfx::new(Handle::default(), TestSfx::UI)
+                .with_playback(PlaybackSettings::LOOP.with_spatial(true))
+                .with_pan(-0.5)
+                .attached_to(coin)
+                .with_components(SpatialSettings::new(Attenuation::Linear).with_distance(1.0, 10.0))
+                .non_spatial(),
+        );
+        let bundle = app
+            .world_mut()
+            .spawn((
+                SfxBundle::new(Handle::default(), TestSfx::UI).spatial(),
+                Spatial2d::default(),
+                NonSpatial,
+            ))
+            .id();
+        app.update();
+        app.update();
+
+        let world = app.world_mut();
+        let sounds: Vec<_> = world
+            .query_filtered::<(&PlaybackSettings, &AudioSink, Option<&StereoPan>), With<NonSpatial>>()
+            .iter(world)
+            .map(|(playback, sink, pan)| {
+                (
+                    playback.spatial,
+                    crate::systems::extract_linear_volume(sink.volume()),
+                    pan.map_or(0.0, |pan| pan.0),
+                )
+            })
+            .collect();
+        assert_eq!(sounds, vec![(false, 1.0, 0.0); 2]);
+        assert!(!world.get::<PlaybackSettings>(bundle).unwrap().spatial);
+    }
+}
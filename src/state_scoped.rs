@@ -0,0 +1,269 @@
+//! Audio tied to the lifetime of a state.
+//!
+//! [`StateScopedAudio`] mirrors Bevy's
+//! [`DespawnOnExit`](bevy::state::state_scoped::DespawnOnExit), but fades the
+//! sound out instead of cutting it off when the state is exited. Sounds
+//! started through messages can be scoped per category with
+//! [`StateScopedAudioExt::scope_audio_category`], which tags every sound of
+//! the category spawned while the state is active.
+
+use bevy::prelude::*;
+use bevy::state::state::{StateTransition, StateTransitionEvent, StateTransitionSystems};
+use std::time::Duration;
+
+use crate::backend::{AudioBackend, BevyAudioBackend};
+use crate::easing::Easing;
+use crate::events::start_fade_out;
+use crate::traits::AudioCategory;
+use crate::MsgAudioSet;
+
+/// Default fade-out of state scoped audio when its state is exited.
+pub const DEFAULT_STATE_EXIT_FADE: Duration = Duration::from_millis(500);
+
+/// Component fading out and despawning an audio entity when state `S`
+/// leaves the given variant.
+///
+/// Requires [`StateScopedAudioExt::add_state_scoped_audio`] for the state
+/// type. Sounds that have not started playing yet are despawned right away.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::StateScopedAudio;
+///
+/// commands.spawn((
+///     MusicBundle::new(level_theme, GameMusic::Exploration),
+///     StateScopedAudio::new(GameState::InGame),
+/// ));
+/// ```
+#[derive(Component, Clone, Debug)]
+pub struct StateScopedAudio<S: States> {
+    /// The state variant the sound belongs to.
+    pub state: S,
+    /// Duration of the fade-out on exit, zero to stop immediately.
+    pub fade: Duration,
+    /// Curve applied to the fade-out.
+    pub easing: Easing,
+}
+
+impl<S: States> StateScopedAudio<S> {
+    /// Scopes a sound to a state variant, fading out over
+    /// [`DEFAULT_STATE_EXIT_FADE`] on exit.
+    #[must_use]
+    pub fn new(state: S) -> Self {
+        Self {
+            state,
+            fade: DEFAULT_STATE_EXIT_FADE,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Sets the fade-out duration on exit.
+    #[must_use]
+    pub fn with_fade(mut self, fade: Duration) -> Self {
+        self.fade = fade;
+        self
+    }
+
+    /// Sets the easing curve for the fade.
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// Resource listing the categories of type `A` whose sounds are scoped to a
+/// variant of state `S` while it is active.
+#[derive(Resource, Clone, Debug)]
+pub struct ScopedAudioCategories<S: States, A: AudioCategory> {
+    rules: Vec<(A, StateScopedAudio<S>)>,
+}
+
+impl<S: States, A: AudioCategory> Default for ScopedAudioCategories<S, A> {
+    fn default() -> Self {
+        Self { rules: Vec::new() }
+    }
+}
+
+impl<S: States, A: AudioCategory> ScopedAudioCategories<S, A> {
+    /// Scopes sounds of `category` spawned while `scope.state` is active.
+    pub fn insert(&mut self, category: A, scope: StateScopedAudio<S>) {
+        self.rules.push((category, scope));
+    }
+
+    /// Returns the scope for a sound of `category` spawned in `state`.
+    #[must_use]
+    pub fn scope(&self, category: A, state: &S) -> Option<&StateScopedAudio<S>> {
+        self.rules
+            .iter()
+            .find(|(rule, scope)| *rule == category && scope.state == *state)
+            .map(|(_, scope)| scope)
+    }
+}
+
+/// App extension for scoping audio to states.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{StateScopedAudio, StateScopedAudioExt};
+///
+/// app.init_state::<GameState>()
+///     .add_state_scoped_audio::<GameState>()
+///     .scope_audio_category(
+///         GameSfx::Combat,
+///         StateScopedAudio::new(GameState::InGame).with_fade(Duration::from_secs(1)),
+///     );
+/// ```
+pub trait StateScopedAudioExt {
+    /// Fades out [`StateScopedAudio`] entities of state `S` on exit.
+    ///
+    /// Call after the state has been initialized.
+    fn add_state_scoped_audio<S: States>(&mut self) -> &mut Self {
+        self.add_state_scoped_audio_with::<S, BevyAudioBackend>()
+    }
+
+    /// Fades out [`StateScopedAudio`] entities of state `S` played through
+    /// the backend `B` on exit.
+    ///
+    /// Call after the state has been initialized.
+    fn add_state_scoped_audio_with<S: States, B: AudioBackend>(&mut self) -> &mut Self;
+
+    /// Scopes every sound of `category` spawned while `scope.state` is
+    /// active, including sounds started through messages.
+    fn scope_audio_category<S: States, A: AudioCategory>(
+        &mut self,
+        category: A,
+        scope: StateScopedAudio<S>,
+    ) -> &mut Self;
+}
+
+impl StateScopedAudioExt for App {
+    fn add_state_scoped_audio_with<S: States, B: AudioBackend>(&mut self) -> &mut Self {
+        self.add_systems(
+            StateTransition,
+            stop_state_scoped_audio::<S, B>.in_set(StateTransitionSystems::ExitSchedules),
+        )
+    }
+
+    fn scope_audio_category<S: States, A: AudioCategory>(
+        &mut self,
+        category: A,
+        scope: StateScopedAudio<S>,
+    ) -> &mut Self {
+        if !self
+            .world()
+            .contains_resource::<ScopedAudioCategories<S, A>>()
+        {
+            self.init_resource::<ScopedAudioCategories<S, A>>();
+            self.add_systems(
+                Update,
+                scope_new_audio::<S, A>
+                    .after(MsgAudioSet::EventHandling)
+                    .before(MsgAudioSet::Concurrency),
+            );
+        }
+        self.world_mut()
+            .resource_mut::<ScopedAudioCategories<S, A>>()
+            .insert(category, scope);
+        self
+    }
+}
+
+/// Tags sounds of category type `A` spawned while a scoped state is active.
+pub fn scope_new_audio<S: States, A: AudioCategory>(
+    mut commands: Commands,
+    categories: Res<ScopedAudioCategories<S, A>>,
+    state: Option<Res<State<S>>>,
+    query: Query<(Entity, &A), (Added<A>, Without<StateScopedAudio<S>>)>,
+) {
+    let Some(state) = state else {
+        return;
+    };
+    for (entity, category) in &query {
+        if let Some(scope) = categories.scope(*category, state.get()) {
+            commands.entity(entity).insert(scope.clone());
+        }
+    }
+}
+
+/// Fades out and despawns the [`StateScopedAudio`] entities of the exited
+/// state.
+pub fn stop_state_scoped_audio<S: States, B: AudioBackend>(
+    mut commands: Commands,
+    mut transitions: MessageReader<StateTransitionEvent<S>>,
+    query: Query<(
+        Entity,
+        &StateScopedAudio<S>,
+        Has<B::Sink>,
+        Has<<B::Spatial as AudioBackend>::Sink>,
+    )>,
+) {
+    // At most one transition per state type happens each frame
+    let Some(transition) = transitions.read().last() else {
+        return;
+    };
+    if transition.entered == transition.exited {
+        return;
+    }
+    let Some(exited) = &transition.exited else {
+        return;
+    };
+    for (entity, scope, has_sink, has_spatial_sink) in &query {
+        if scope.state != *exited {
+            continue;
+        }
+        if (has_sink || has_spatial_sink) && !scope.fade.is_zero() {
+            start_fade_out(&mut commands, entity, scope.fade, &scope.easing);
+            commands.entity(entity).remove::<StateScopedAudio<S>>();
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin};
+    use crate::{FadeOut, PlayMusic, TestAudioPlugin};
+
+    #[test]
+    fn state_scoped_audio_fades_out_on_exit() {
+        #[derive(States, Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+        enum TestState {
+            #[default]
+            Playing,
+            Menu,
+        }
+
+        let mut app = test_app((
+            bevy::state::app::StatesPlugin,
+            TestPlugin::default(),
+            TestAudioPlugin,
+        ));
+        app.init_state::<TestState>()
+            .add_state_scoped_audio::<TestState>()
+            .scope_audio_category(TestMusic::Main, StateScopedAudio::new(TestState::Playing));
+        app.world_mut()
+            .write_message(PlayMusic::new(Handle::default(), TestMusic::Main));
+        app.update();
+        app.update();
+        let music = app
+            .world_mut()
+            .query_filtered::<Entity, With<TestMusic>>()
+            .single(app.world())
+            .unwrap();
+        assert!(app
+            .world()
+            .get::<StateScopedAudio<TestState>>(music)
+            .is_some());
+
+        app.world_mut()
+            .resource_mut::<NextState<TestState>>()
+            .set(TestState::Menu);
+        app.update();
+        assert!(app.world().get::<FadeOut>(music).is_some());
+    }
+}
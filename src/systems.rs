@@ -1,138 +1,1834 @@
 //! Audio systems for volume management and concurrency limiting.
 
-use bevy::{audio::Volume, platform::collections::HashMap, prelude::*};
+use bevy::{
+    asset::{LoadState, UntypedAssetId},
+    audio::{Decodable, PlaybackMode, SpatialScale, Volume},
+    ecs::{component::Tick, query::QueryFilter, system::SystemChangeTick},
+    platform::collections::HashMap,
+    prelude::*,
+};
+use std::cmp::Ordering;
+use std::time::Duration;
 
-use crate::components::{MaxConcurrent, SoundEffectCounter};
-use crate::traits::{AudioConfigTrait, MusicCategory, SfxCategory};
+use crate::ambience::{
+    AmbienceListener, AmbienceSource, AmbienceZone, BlendedAmbience, BlendedAmbienceSource,
+};
+use crate::backend::{either_sink, with_sink_mut, AnySink, AudioBackend};
+use crate::clock::{MusicClock, MusicTempo, QuantizedStop};
+use crate::components::{
+    AttachedAudio, AudioVolumeState, ConcurrencyPolicy, FadeIn, FadeOut, MaxConcurrent,
+    MaxDuration, NonSpatial, PausedByGame, PlayChance, PlaybackRandomizer, RampingVolume,
+    SoundEffectCounter, SpawnRequest, SpeedTween, StartDelay, StartPosition, StereoPan,
+    StereoPanRange, Stinger, TrackGain, TweenCompletion, VolumeRamp, VolumeTween, WaitForLoad,
+};
+use crate::duration::SourceDurations;
+use crate::effects::{ActiveEffectBus, AudioEffectZone, Echo, EchoTap, EffectSend};
+use crate::events::{
+    AudioDropped, AudioStarted, AudioStopped, Bar, Beat, DropReason, PlaySfx, StopReason,
+    VoiceLineFinished,
+};
+use crate::layers::{LayeredTrack, MusicLayer, StemSync};
+use crate::localization::CurrentAudioLanguage;
+use crate::memory::MusicMemory;
+use crate::mixer::{CategoryMixerState, CategorySpeed};
+use crate::pool::PoolGeneration;
+use crate::request::AudioRequest;
+use crate::rng::AudioRng;
+use crate::tone::Tone;
+use crate::track_info::{TrackInfo, TrackRegistry};
+use crate::traits::{AudioCategory, AudioConfigTrait, MusicCategory, SfxCategory, VoiceCategory};
+use crate::virtualization::{
+    rank_voices, AudioPriority, GlobalVoiceLimit, VirtualizationMode, Virtualized, VoiceBudget,
+    VoiceRank,
+};
+use crate::voice::{ActiveVoiceLine, DialogueDucking, DialogueQueue, VoiceLine};
+use crate::volume::{compute_final_volume, VolumeStages};
 
 /// Applies volume settings to newly spawned music entities.
 ///
-/// This system runs on `Added<AudioSink>` to apply the correct volume
+/// This system runs on `Added<B::Sink>` to apply the correct volume
 /// based on the music category, master volume, and mute state.
-pub fn apply_volume_to_new_music<M, C>(
+pub fn apply_volume_to_new_music<M, C, B>(
+    mut commands: Commands,
     config: Res<C>,
-    mut query: Query<(&M, &PlaybackSettings, &mut AudioSink), Added<AudioSink>>,
+    mixer: Res<CategoryMixerState<M>>,
+    registry: Res<TrackRegistry>,
+    mut query: Query<
+        (
+            Entity,
+            &M,
+            &AudioPlayer,
+            &PlaybackSettings,
+            Option<&TrackGain>,
+            &mut B::Sink,
+            Option<&mut FadeIn>,
+            Has<Stinger>,
+        ),
+        Added<B::Sink>,
+    >,
 ) where
     M: MusicCategory<Config = C>,
     C: AudioConfigTrait,
+    B: AudioBackend,
 {
-    for (category, playback, mut sink) in &mut query {
-        let category_volume = category.volume_multiplier(&config);
-        let playback_volume = extract_linear_volume(playback.volume);
-        let final_volume = config.effective_volume() * category_volume * playback_volume;
-        sink.set_volume(Volume::Linear(final_volume));
+    for (entity, category, player, playback, gain, mut sink, fade_in, stinger) in &mut query {
+        let stages = VolumeStages::for_category(&*config, &mixer, *category)
+            .with_stinger_duck(&mixer, stinger_duck_volume(&registry, player, stinger))
+            .with_loudness(registry.loudness_gain(&player.0));
+        start_sink_volume::<B>(
+            &mut commands,
+            entity,
+            stages,
+            playback,
+            gain,
+            &mut sink,
+            fade_in,
+        );
     }
 }
 
 /// Applies volume settings to newly spawned sound effect entities.
 ///
-/// This system runs on `Added<AudioSink>` to apply the correct volume
+/// This system runs on `Added<B::Sink>` to apply the correct volume
 /// based on the sound effect category, master volume, and mute state.
-pub fn apply_volume_to_new_sfx<S, C>(
+pub fn apply_volume_to_new_sfx<S, C, B>(
+    mut commands: Commands,
     config: Res<C>,
-    mut query: Query<(&S, &PlaybackSettings, &mut AudioSink), Added<AudioSink>>,
+    mixer: Res<CategoryMixerState<S>>,
+    registry: Res<TrackRegistry>,
+    mut query: Query<
+        (
+            Entity,
+            &S,
+            Option<&AudioPlayer>,
+            &PlaybackSettings,
+            Option<&TrackGain>,
+            &mut B::Sink,
+            Option<&mut FadeIn>,
+        ),
+        Added<B::Sink>,
+    >,
 ) where
     S: SfxCategory<Config = C>,
     C: AudioConfigTrait,
+    B: AudioBackend,
 {
-    for (category, playback, mut sink) in &mut query {
-        let category_volume = category.volume_multiplier(&config);
-        let playback_volume = extract_linear_volume(playback.volume);
-        let final_volume = config.effective_volume() * category_volume * playback_volume;
-        sink.set_volume(Volume::Linear(final_volume));
+    for (entity, category, player, playback, gain, mut sink, fade_in) in &mut query {
+        // Generated sources, like tones, have no measured loudness
+        let loudness = player.map_or(1.0, |player| registry.loudness_gain(&player.0));
+        let stages =
+            VolumeStages::for_category(&*config, &mixer, *category).with_loudness(loudness);
+        start_sink_volume::<B>(
+            &mut commands,
+            entity,
+            stages,
+            playback,
+            gain,
+            &mut sink,
+            fade_in,
+        );
     }
 }
 
-/// Updates volume on all active music entities when config changes.
+/// Updates volume on active music entities when config changes.
 ///
-/// This system should be run with `run_if(resource_changed::<C>)`.
+/// This system should be run with `run_if(resource_changed::<C>)`. Only
+/// sinks whose category gain changed since it was last applied are touched.
 /// Respects the mute state via [`AudioConfigTrait::effective_volume`].
-pub fn update_music_volume<M, C>(
+/// Sinks ramp to the new volume over the [`VolumeRamp`] duration.
+///
+/// The category gain includes the stinger duck, which depends on the track,
+/// so it is compared per sink rather than per category.
+pub fn update_music_volume<M, C, B>(
+    mut commands: Commands,
     config: Res<C>,
-    mut query: Query<(&M, &PlaybackSettings, &mut AudioSink)>,
+    mixer: Res<CategoryMixerState<M>>,
+    registry: Res<TrackRegistry>,
+    ramp: Res<VolumeRamp>,
+    mut query: Query<(
+        Entity,
+        &M,
+        &AudioPlayer,
+        &mut AudioVolumeState,
+        Option<&TrackGain>,
+        &mut B::Sink,
+        Option<&mut FadeIn>,
+        Has<Stinger>,
+        Has<VolumeTween>,
+        Has<FadeOut>,
+    )>,
 ) where
     M: MusicCategory<Config = C>,
     C: AudioConfigTrait,
+    B: AudioBackend,
 {
-    for (category, playback, mut sink) in &mut query {
-        let category_volume = category.volume_multiplier(&config);
-        let playback_volume = extract_linear_volume(playback.volume);
-        let final_volume = config.effective_volume() * category_volume * playback_volume;
-        sink.set_volume(Volume::Linear(final_volume));
+    for (entity, category, player, mut state, gain, mut sink, fade_in, stinger, tweening, fading) in
+        &mut query
+    {
+        let stages = VolumeStages::for_category(&*config, &mixer, *category)
+            .with_stinger_duck(&mixer, stinger_duck_volume(&registry, player, stinger));
+        let category_gain = stages.category_gain();
+        if category_gain == state.category {
+            continue;
+        }
+        state.category = category_gain;
+        if fading {
+            continue;
+        }
+        let instant = fade_in.is_some() || tweening;
+        ramp_sink_volume::<B>(
+            &mut commands,
+            entity,
+            &mut sink,
+            fade_in,
+            instant,
+            compute_final_volume(&stages.with_state(&state, gain)),
+            &ramp,
+        );
+    }
+}
+
+/// Returns the volume a music track is ducked to while stingers play, or
+/// `None` for the mixer's stinger duck volume.
+///
+/// Stingers are not ducked themselves, and tracks with a
+/// [`TrackInfo::duck_db`](crate::TrackInfo::duck_db) use their own.
+fn stinger_duck_volume(
+    registry: &TrackRegistry,
+    player: &AudioPlayer,
+    stinger: bool,
+) -> Option<f32> {
+    if stinger {
+        return Some(1.0);
     }
+    registry.get(&player.0).and_then(TrackInfo::duck_gain)
 }
 
-/// Updates volume on all active sound effect entities when config changes.
+/// Updates volume on active sound effect entities when config changes.
 ///
-/// This system should be run with `run_if(resource_changed::<C>)`.
+/// This system should be run with `run_if(resource_changed::<C>)`. Only
+/// sinks whose category gain changed since the last run are touched.
 /// Respects the mute state via [`AudioConfigTrait::effective_volume`].
-pub fn update_sfx_volume<S, C>(
+/// Sinks ramp to the new volume over the [`VolumeRamp`] duration.
+pub fn update_sfx_volume<S, C, B>(
+    commands: Commands,
+    config: Res<C>,
+    mixer: Res<CategoryMixerState<S>>,
+    ramp: Res<VolumeRamp>,
+    applied: Local<Vec<(S, f32)>>,
+    query: Query<CategorySinkData<S, B>>,
+) where
+    S: SfxCategory<Config = C>,
+    C: AudioConfigTrait,
+    B: AudioBackend,
+{
+    update_category_volume::<_, B>(commands, &*config, &mixer, &ramp, applied, query);
+}
+
+/// Applies volume settings to newly spawned voice line entities.
+pub fn apply_volume_to_new_voice<V, C, B>(
+    mut commands: Commands,
+    config: Res<C>,
+    mixer: Res<CategoryMixerState<V>>,
+    registry: Res<TrackRegistry>,
+    mut query: Query<
+        (
+            Entity,
+            &V,
+            &AudioPlayer,
+            &PlaybackSettings,
+            Option<&TrackGain>,
+            &mut B::Sink,
+            Option<&mut FadeIn>,
+        ),
+        Added<B::Sink>,
+    >,
+) where
+    V: VoiceCategory<Config = C>,
+    C: AudioConfigTrait,
+    B: AudioBackend,
+{
+    for (entity, category, player, playback, gain, mut sink, fade_in) in &mut query {
+        let stages = VolumeStages::for_category(&*config, &mixer, *category)
+            .with_loudness(registry.loudness_gain(&player.0));
+        start_sink_volume::<B>(
+            &mut commands,
+            entity,
+            stages,
+            playback,
+            gain,
+            &mut sink,
+            fade_in,
+        );
+    }
+}
+
+/// Updates volume on active voice line entities when config changes.
+///
+/// This system should be run with `run_if(resource_changed::<C>)`. Only
+/// sinks whose category gain changed since the last run are touched.
+/// Sinks ramp to the new volume over the [`VolumeRamp`] duration.
+pub fn update_voice_volume<V, C, B>(
+    commands: Commands,
+    config: Res<C>,
+    mixer: Res<CategoryMixerState<V>>,
+    ramp: Res<VolumeRamp>,
+    applied: Local<Vec<(V, f32)>>,
+    query: Query<CategorySinkData<V, B>>,
+) where
+    V: VoiceCategory<Config = C>,
+    C: AudioConfigTrait,
+    B: AudioBackend,
+{
+    update_category_volume::<_, B>(commands, &*config, &mixer, &ramp, applied, query);
+}
+
+/// Query data of the sounds [`update_category_volume`] ramps.
+type CategorySinkData<A, B> = (
+    Entity,
+    &'static A,
+    &'static mut AudioVolumeState,
+    Option<&'static TrackGain>,
+    &'static mut <B as AudioBackend>::Sink,
+    Option<&'static mut FadeIn>,
+    Has<VolumeTween>,
+    Has<FadeOut>,
+);
+
+/// Ramps the sinks of every category whose gain changed since the last run.
+fn update_category_volume<A, B>(
+    mut commands: Commands,
+    config: &A::Config,
+    mixer: &CategoryMixerState<A>,
+    ramp: &VolumeRamp,
+    mut applied: Local<Vec<(A, f32)>>,
+    mut query: Query<CategorySinkData<A, B>>,
+) where
+    A: AudioCategory,
+    A::Config: AudioConfigTrait,
+    B: AudioBackend,
+{
+    let mut gains = CategoryGains::new(&mut applied);
+    for (entity, category, mut state, gain, mut sink, fade_in, tweening, fading) in &mut query {
+        if let Some(stages) = gains.changed(config, mixer, *category) {
+            state.category = stages.category_gain();
+            if fading {
+                continue;
+            }
+            let instant = fade_in.is_some() || tweening;
+            ramp_sink_volume::<B>(
+                &mut commands,
+                entity,
+                &mut sink,
+                fade_in,
+                instant,
+                compute_final_volume(&stages.with_state(&state, gain)),
+                ramp,
+            );
+        }
+    }
+    gains.finish();
+}
+
+/// Fades the loops of [`AmbienceZone`]s with the listener's depth into each zone.
+///
+/// Loops are spawned as children of their zone when the listener enters and
+/// despawned once they have faded out after it leaves.
+pub fn update_ambience_zones<S, C, B>(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<C>,
+    mixer: Res<CategoryMixerState<S>>,
+    listeners: Query<&GlobalTransform, With<AmbienceListener>>,
+    mut zones: Query<(Entity, &mut AmbienceZone<S>, &GlobalTransform)>,
+    mut sources: Query<LoopSourceData<B>, With<AmbienceSource>>,
+) where
+    S: SfxCategory<Config = C>,
+    C: AudioConfigTrait,
+    B: AudioBackend,
+{
+    let listener = listeners.iter().next().map(GlobalTransform::translation);
+
+    for (entity, mut zone, transform) in &mut zones {
+        let target = listener.map_or(0.0, |point| zone.weight(transform, point) * zone.volume);
+        let gain = zone.step_gain(target, time.delta());
+        let zone = &mut *zone;
+        drive_loop_source::<B, _>(
+            &mut commands,
+            &mut zone.source,
+            gain,
+            &mut sources,
+            VolumeStages::for_category(&*config, &mixer, zone.category),
+            |commands| {
+                let mut source = commands.spawn((
+                    zone.category,
+                    TrackGain::new(gain),
+                    AmbienceSource { zone: entity },
+                    ChildOf(entity),
+                ));
+                B::play(&mut source, zone.handle.clone(), PlaybackSettings::LOOP);
+                source.id()
+            },
+        );
+    }
+}
+
+/// Crossfades the layers of [`BlendedAmbience`]s towards their weights at the
+/// current parameter.
+///
+/// Layers are spawned as children of their blend while their gain is above
+/// zero and despawned once they have faded out.
+pub fn update_blended_ambience<S, C, B>(
+    mut commands: Commands,
+    time: Res<Time>,
     config: Res<C>,
-    mut query: Query<(&S, &PlaybackSettings, &mut AudioSink)>,
+    mixer: Res<CategoryMixerState<S>>,
+    mut blends: Query<(Entity, &mut BlendedAmbience<S>)>,
+    mut sources: Query<LoopSourceData<B>, With<BlendedAmbienceSource>>,
 ) where
     S: SfxCategory<Config = C>,
     C: AudioConfigTrait,
+    B: AudioBackend,
 {
+    for (entity, mut blend) in &mut blends {
+        let targets = blend.targets();
+        for (layer, target) in targets.into_iter().enumerate() {
+            let gain = blend.step_gain(layer, target, time.delta());
+            let category = blend.category;
+            let ambience = &mut blend.layers[layer];
+            drive_loop_source::<B, _>(
+                &mut commands,
+                &mut ambience.source,
+                gain,
+                &mut sources,
+                VolumeStages::for_category(&*config, &mixer, category),
+                |commands| {
+                    let mut source = commands.spawn((
+                        category,
+                        TrackGain::new(gain),
+                        BlendedAmbienceSource {
+                            ambience: entity,
+                            layer,
+                        },
+                        ChildOf(entity),
+                    ));
+                    B::play(&mut source, ambience.handle.clone(), PlaybackSettings::LOOP);
+                    source.id()
+                },
+            );
+        }
+    }
+}
+
+/// Query data of a looping source whose gain is driven by
+/// [`drive_loop_source`].
+pub(crate) type LoopSourceData<B> = (
+    &'static mut TrackGain,
+    &'static PlaybackSettings,
+    Option<&'static AudioVolumeState>,
+    Option<&'static mut <B as AudioBackend>::Sink>,
+);
+
+/// Keeps the source of a crossfaded loop in line with its `gain`.
+///
+/// Forgets a source that no longer exists, spawns one with `spawn` once the
+/// gain rises above zero, despawns it once the gain is back at zero, and
+/// otherwise applies the gain to its [`TrackGain`] and sink. `stages` hold
+/// the category gains of the loop, for sources without a volume state yet.
+pub(crate) fn drive_loop_source<B: AudioBackend, F: QueryFilter>(
+    commands: &mut Commands,
+    source: &mut Option<Entity>,
+    gain: f32,
+    sources: &mut Query<LoopSourceData<B>, F>,
+    stages: VolumeStages,
+    spawn: impl FnOnce(&mut Commands) -> Entity,
+) {
+    if source.is_some_and(|entity| !sources.contains(entity)) {
+        *source = None;
+    }
+    match *source {
+        None if gain > 0.0 => {
+            *source = Some(spawn(commands));
+        }
+        Some(entity) if gain <= 0.0 => {
+            commands.entity(entity).despawn();
+            *source = None;
+        }
+        Some(entity) => {
+            let Ok((mut track_gain, playback, state, sink)) = sources.get_mut(entity) else {
+                return;
+            };
+            track_gain.0 = gain;
+            if let Some(mut sink) = sink {
+                let volume = match state {
+                    Some(state) => state.volume(Some(&track_gain)),
+                    None => compute_final_volume(&stages.with_base(playback, Some(&track_gain))),
+                };
+                B::set_volume(&mut sink, volume);
+            }
+        }
+        None => {}
+    }
+}
+
+/// Detects finished voice lines and starts the next queued line.
+///
+/// Emits [`VoiceLineFinished`] once the current line's entity is gone.
+pub fn advance_dialogue_queue<V: VoiceCategory, B: AudioBackend>(
+    mut commands: Commands,
+    mut queue: ResMut<DialogueQueue<V>>,
+    language: Res<CurrentAudioLanguage>,
+    lines: Query<(), With<VoiceLine>>,
+    mut finished: MessageWriter<VoiceLineFinished<V>>,
+) {
+    let done = queue
+        .current()
+        .is_some_and(|current| !lines.contains(current.entity));
+    if done {
+        if let Some(current) = queue.take_current() {
+            finished.write(VoiceLineFinished {
+                entity: current.entity,
+                category: current.category,
+                label: current.label,
+                interrupted: false,
+            });
+        }
+    }
+
+    if queue.is_playing() {
+        return;
+    }
+    let Some(line) = queue.pop_next() else {
+        return;
+    };
+    let mut entity = commands.spawn((
+        line.category,
+        VoiceLine {
+            priority: line.priority,
+        },
+    ));
+    B::play(
+        &mut entity,
+        line.resolve_handle(&language).clone(),
+        PlaybackSettings::DESPAWN,
+    );
+    if let Some(label) = &line.label {
+        entity.insert(label.clone());
+    }
+    queue.set_current(Some(ActiveVoiceLine {
+        entity: entity.id(),
+        category: line.category,
+        priority: line.priority,
+        label: line.label,
+    }));
+}
+
+/// Scales sound effect playback speed by the virtual time's relative speed.
+///
+/// Categories returning `false` from [`SfxCategory::follows_time_scale`]
+/// keep their original playback speed.
+pub fn apply_time_scale_to_sfx<S: SfxCategory, B: AudioBackend>(
+    time: Res<Time<Virtual>>,
+    speeds: Res<CategorySpeed<S>>,
+    mut query: Query<(&S, &PlaybackSettings, &mut B::Sink), Without<SpeedTween>>,
+) {
+    let relative_speed = time.relative_speed();
+    for (category, playback, mut sink) in &mut query {
+        let speed = playback.speed * speeds.get(*category);
+        if category.follows_time_scale() {
+            B::set_speed(&mut sink, speed * relative_speed);
+        } else {
+            B::set_speed(&mut sink, speed);
+        }
+    }
+}
+
+/// Applies the [`CategorySpeed`] of category type `A` to new sounds, and to
+/// every sound of the type when the multipliers change.
+///
+/// Sounds running a [`SpeedTween`] are left to the tween.
+pub fn apply_category_speed<A: AudioCategory, B: AudioBackend>(
+    speeds: Res<CategorySpeed<A>>,
+    mut query: Query<(&A, &PlaybackSettings, Mut<B::Sink>), Without<SpeedTween>>,
+) {
+    let changed = speeds.is_changed();
     for (category, playback, mut sink) in &mut query {
-        let category_volume = category.volume_multiplier(&config);
-        let playback_volume = extract_linear_volume(playback.volume);
-        let final_volume = config.effective_volume() * category_volume * playback_volume;
-        sink.set_volume(Volume::Linear(final_volume));
+        if changed || sink.is_added() {
+            B::set_speed(&mut sink, playback.speed * speeds.get(*category));
+        }
     }
 }
 
-/// Enforces maximum concurrent sound effect instances.
+/// Run condition that returns true while `Time<Virtual>` is paused.
+pub fn virtual_time_paused(time: Res<Time<Virtual>>) -> bool {
+    time.is_paused()
+}
+
+/// Pauses or resumes sound effects based on the piped-in game pause state.
 ///
-/// This system periodically resets counts and despawns excess sounds
-/// to prevent audio spam.
-pub fn enforce_sfx_concurrency<S: SfxCategory>(
+/// Pipe a condition into this system to drive it. Categories returning `false`
+/// from [`SfxCategory::pauses_with_game`] are left untouched.
+pub fn sync_sfx_game_pause<S: SfxCategory, B: AudioBackend>(
+    In(paused): In<bool>,
+    mut commands: Commands,
+    mut query: Query<(Entity, &S, &mut B::Sink, Has<PausedByGame>)>,
+) {
+    for (entity, category, mut sink, paused_by_game) in &mut query {
+        if paused {
+            if category.pauses_with_game() && !B::is_paused(&sink) {
+                B::pause(&mut sink);
+                commands.entity(entity).insert(PausedByGame);
+            }
+        } else if paused_by_game {
+            B::resume(&mut sink);
+            commands.entity(entity).remove::<PausedByGame>();
+        }
+    }
+}
+
+/// Starts deferred audio entities once their source has loaded.
+///
+/// Plays the source through `B` for entities with a [`WaitForLoad`]
+/// component whose source is available, and despawns those whose load
+/// failed. Stems of layered tracks are left to
+/// [`start_layered_stems_when_loaded`].
+pub fn start_audio_when_loaded<B: AudioBackend>(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    sources: Res<Assets<AudioSource>>,
+    mut dropped: MessageWriter<AudioDropped>,
+    query: Query<(Entity, &WaitForLoad, Option<&PlaybackSettings>), Without<MusicLayer>>,
+) {
+    for (entity, wait, playback) in &query {
+        if sources.contains(&wait.handle) {
+            let mut entity = commands.entity(entity);
+            entity.remove::<WaitForLoad>();
+            B::play(
+                &mut entity,
+                wait.handle.clone(),
+                playback.copied().unwrap_or_default(),
+            );
+        } else if let Some(LoadState::Failed(err)) = asset_server.get_load_state(&wait.handle) {
+            warn!("Audio source failed to load, dropping playback: {err}");
+            commands.entity(entity).despawn();
+            dropped.write(AudioDropped {
+                entity,
+                handle: wait.handle.clone(),
+                reason: DropReason::LoadFailed,
+            });
+        }
+    }
+}
+
+/// Despawns sounds that played longer than their [`MaxDuration`].
+pub fn stop_sounds_past_max_duration<B: AudioBackend>(
     mut commands: Commands,
     time: Res<Time>,
-    mut counter: ResMut<SoundEffectCounter>,
-    query: Query<(Entity, &AudioPlayer, &MaxConcurrent), With<S>>,
+    mut query: Query<(Entity, &mut MaxDuration, AnySink<B>)>,
 ) {
-    // Reset counts periodically to prevent stale data
-    if counter.timer.tick(time.delta()).just_finished() {
-        counter.counts.clear();
+    for (entity, mut limit, sink) in &mut query {
+        if either_sink::<B>(sink).is_paused() {
+            continue;
+        }
+        if limit.timer.tick(time.delta()).is_finished() {
+            commands.entity(entity).despawn();
+        }
     }
+}
 
-    // Track and limit concurrent sounds
-    let mut kept_counts: HashMap<Handle<AudioSource>, u32> = HashMap::new();
-    for (entity, audio_player, max) in &query {
-        let kept_so_far = kept_counts.entry(audio_player.0.clone()).or_insert(0);
-        if *kept_so_far >= max.max {
+/// Despawns newly spawned sounds that lose their [`PlayChance`] roll.
+pub fn roll_play_chance(
+    mut commands: Commands,
+    mut rng: ResMut<AudioRng>,
+    query: Query<(Entity, &PlayChance), Added<PlayChance>>,
+) {
+    for (entity, chance) in &query {
+        if !chance.roll(&mut *rng) {
             commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Rolls the [`StereoPan`] of sounds spawned with a [`StereoPanRange`].
+pub fn roll_stereo_pan_range(
+    add: On<Add, StereoPanRange>,
+    mut commands: Commands,
+    mut rng: ResMut<AudioRng>,
+    query: Query<&StereoPanRange>,
+) {
+    if let Some(pan) = query
+        .get(add.entity)
+        .ok()
+        .and_then(|range| range.roll(&mut *rng))
+    {
+        commands.entity(add.entity).insert(StereoPan(pan));
+    }
+}
+
+/// Applies the [`SpawnRequest`] of a spawned bundle and removes it.
+///
+/// Rolls the randomization from the [`AudioRng`], moving a rolled start
+/// offset into a [`StartPosition`], inserts the fade-in, and swaps the
+/// [`AudioPlayer`] of a delayed sound for a [`StartDelay`].
+pub fn apply_spawn_request(
+    add: On<Add, SpawnRequest>,
+    mut commands: Commands,
+    mut rng: ResMut<AudioRng>,
+    mut query: Query<(&SpawnRequest, &mut PlaybackSettings, Option<&AudioPlayer>)>,
+) {
+    let mut entity = commands.entity(add.entity);
+    entity.remove::<SpawnRequest>();
+    let Ok((request, mut playback, player)) = query.get_mut(add.entity) else {
+        return;
+    };
+    if let Some(randomizer) = &request.randomizer {
+        randomizer.apply_with(&mut playback, &mut *rng);
+        if randomizer.start_offset_range.is_some() {
+            if let Some(start) = StartPosition::take_from(&mut playback) {
+                entity.insert(start);
+            }
+        }
+    }
+    if let Some(fade_in) = &request.fade_in {
+        entity.insert(fade_in.clone());
+    }
+    if let (Some(delay), Some(player)) = (request.delay, player) {
+        if !delay.is_zero() {
+            entity
+                .remove::<AudioPlayer>()
+                .insert(StartDelay::new(player.0.clone(), delay));
+        }
+    }
+}
+
+/// Starts sounds held back by a [`StartDelay`] once their delay has elapsed.
+pub fn start_delayed_audio<B: AudioBackend>(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut StartDelay, Option<&PlaybackSettings>)>,
+) {
+    for (entity, mut delay, playback) in &mut query {
+        if delay.timer.tick(time.delta()).is_finished() {
+            let mut entity = commands.entity(entity);
+            entity.remove::<StartDelay>();
+            B::play(
+                &mut entity,
+                delay.handle.clone(),
+                playback.copied().unwrap_or_default(),
+            );
+        }
+    }
+}
+
+/// Seeks sounds to their [`StartPosition`] once their sink exists, then
+/// removes the component.
+///
+/// Runs in `Last`, right after `bevy_audio` creates sinks in `PostUpdate`, so
+/// next to none of the skipped part is heard.
+pub fn seek_start_positions<B: AudioBackend>(
+    mut commands: Commands,
+    mut query: Query<(Entity, &StartPosition, &mut B::Sink)>,
+) {
+    for (entity, start, mut sink) in &mut query {
+        B::seek(&mut sink, start.0);
+        commands.entity(entity).remove::<StartPosition>();
+    }
+}
+
+/// Rolls new values for sounds with a [`PlaybackRandomizer`] component each
+/// time they start or a loop wraps around.
+///
+/// The rolled volume is written to the [`PlaybackSettings`], so the volume
+/// systems keep applying it. Runs before volume application, so new sinks
+/// start at their rolled volume.
+///
+/// A loop counts as wrapped once its sink position, which keeps growing while
+/// it loops, passes another multiple of the length in [`SourceDurations`].
+pub fn reroll_randomized_playback<B: AudioBackend>(
+    mut query: Query<(
+        Entity,
+        &PlaybackRandomizer,
+        &mut PlaybackSettings,
+        &mut B::Sink,
+        Option<&AudioPlayer>,
+        Option<&mut FadeIn>,
+        Option<&mut AudioVolumeState>,
+    )>,
+    durations: Res<SourceDurations>,
+    mut rng: ResMut<AudioRng>,
+    mut positions: Local<HashMap<Entity, (Duration, u128)>>,
+) {
+    let mut seen = HashMap::with_capacity(positions.len());
+    for (entity, randomizer, mut playback, mut sink, player, fade_in, state) in &mut query {
+        let loops = |position| player.map_or(0, |player| durations.loops(&player.0, position));
+        let mut position = B::position(&sink);
+        let first_start = !positions.contains_key(&entity);
+        // A new sink starts over from zero, a looping one wraps into its next pass
+        let restarted = positions
+            .get(&entity)
+            .is_none_or(|&(previous, pass)| position < previous || loops(position) != pass);
+        if restarted {
+            let previous_volume = extract_linear_volume(playback.volume);
+            randomizer.apply_with(&mut playback, &mut *rng);
+            if randomizer.speed_range.is_some() {
+                B::set_speed(&mut sink, playback.speed);
+            }
+            // The sink is already playing, so the start offset is seeked to
+            // once and not re-applied when a loop wraps
+            if first_start && randomizer.start_offset_range.is_some() {
+                if let Some(offset) = playback.start_position {
+                    B::seek(&mut sink, offset);
+                    position = offset;
+                }
+            }
+            // Loops keep their sink, so scale it by the change in playback volume
+            if let Some(mut state) = state {
+                state.base = extract_linear_volume(playback.volume);
+            }
+            if !first_start && previous_volume > 0.0 {
+                let ratio = extract_linear_volume(playback.volume) / previous_volume;
+                match fade_in {
+                    Some(mut fade_in) => fade_in.target_volume *= ratio,
+                    None => {
+                        let volume = B::volume(&sink);
+                        B::set_volume(&mut sink, volume * ratio);
+                    }
+                }
+            }
+        }
+        seen.insert(entity, (position, loops(position)));
+    }
+    *positions = seen;
+}
+
+/// Keeps at most [`VoiceBudget::max_voices`] sounds audible, virtualizing the rest.
+///
+/// Sounds playing through a sink of `B` and of its spatial twin share one
+/// budget.
+pub fn virtualize_voices<B: AudioBackend>(
+    mut commands: Commands,
+    budget: Res<VoiceBudget>,
+    listeners: Query<&GlobalTransform, With<SpatialListener>>,
+    mut voices: ParamSet<(
+        Query<
+            (
+                Entity,
+                AnySink<B>,
+                Option<&AudioPriority>,
+                Option<&GlobalTransform>,
+                Has<Virtualized>,
+            ),
+            Without<PausedByGame>,
+        >,
+        Query<&mut B::Sink>,
+        Query<&mut <B::Spatial as AudioBackend>::Sink>,
+    )>,
+) {
+    let listener = listeners.iter().next().map(GlobalTransform::translation);
+    let mut ranked: Vec<VoiceRank> = voices
+        .p0()
+        .iter()
+        .filter(|(_, sink, _, _, virtualized)| *virtualized || !either_sink::<B>(*sink).is_paused())
+        .map(|(entity, sink, priority, transform, virtualized)| {
+            let distance = listener
+                .zip(transform)
+                .map_or(0.0, |(listener, transform)| {
+                    listener.distance(transform.translation())
+                });
+            let audibility = either_sink::<B>(sink).volume() / (1.0 + distance);
+            VoiceRank {
+                entity,
+                priority: priority.map_or(0, |priority| priority.0),
+                score: budget.score(audibility, virtualized),
+                virtualized,
+            }
+        })
+        .collect();
+    if ranked.len() <= budget.max_voices && ranked.iter().all(|voice| !voice.virtualized) {
+        return;
+    }
+    rank_voices(&mut ranked);
+
+    for (index, voice) in ranked.iter().enumerate() {
+        let audible = index < budget.max_voices;
+        if audible && voice.virtualized {
+            with_sink_mut::<B, _>(&mut voices, voice.entity, B::resume, B::Spatial::resume);
+            commands.entity(voice.entity).remove::<Virtualized>();
+        } else if !audible && !voice.virtualized {
+            match budget.mode {
+                VirtualizationMode::Pause => {
+                    with_sink_mut::<B, _>(&mut voices, voice.entity, B::pause, B::Spatial::pause);
+                    commands.entity(voice.entity).insert(Virtualized);
+                }
+                VirtualizationMode::Despawn => commands.entity(voice.entity).despawn(),
+            }
+        }
+    }
+}
+
+/// Selects the [`ActiveEffectBus`] from the effect zones containing the listener.
+pub fn update_active_effect_bus(
+    mut active: ResMut<ActiveEffectBus>,
+    listeners: Query<&GlobalTransform, With<AmbienceListener>>,
+    zones: Query<(&AudioEffectZone, &GlobalTransform)>,
+) {
+    let bus = listeners
+        .iter()
+        .next()
+        .and_then(|listener| {
+            let point = listener.translation();
+            zones
+                .iter()
+                .map(|(zone, transform)| (zone, zone.shape.depth(transform, point)))
+                .filter(|(_, depth)| *depth > 0.0)
+                .max_by(|(a, a_depth), (b, b_depth)| {
+                    a.priority.cmp(&b.priority).then(a_depth.total_cmp(b_depth))
+                })
+                .map(|(zone, _)| zone.bus.clone())
+        })
+        .unwrap_or_default();
+    active.set_if_neq(ActiveEffectBus(bus));
+}
+
+/// Routes newly spawned sound effects through the [`ActiveEffectBus`].
+pub fn route_sfx_to_effect_bus<S: SfxCategory>(
+    mut commands: Commands,
+    active: Res<ActiveEffectBus>,
+    query: Query<Entity, (Added<S>, Without<EffectSend>)>,
+) {
+    if active.0.is_dry() {
+        return;
+    }
+    for entity in &query {
+        commands.entity(entity).insert(EffectSend(active.0.clone()));
+    }
+}
+
+/// Plays the delayed taps of one-shot sound effects that started since the
+/// last run, using their own [`Echo`] or the one of their [`EffectSend`].
+pub fn play_sfx_echoes<S: SfxCategory>(
+    query: Query<
+        (
+            &S,
+            &AudioPlayer,
+            &PlaybackSettings,
+            Option<&Transform>,
+            Option<&Echo>,
+            Option<&EffectSend>,
+        ),
+        (Added<AudioPlayer>, Without<EchoTap>),
+    >,
+    mut messages: MessageWriter<PlaySfx<S>>,
+) {
+    for (category, player, playback, transform, echo, send) in &query {
+        let Some(echo) = echo_of(playback, echo, send) else {
+            continue;
+        };
+        for tap in 1..=echo.taps {
+            let mut request = PlaySfx::new(player.0.clone(), *category);
+            request.playback = echo_tap_playback(echo, tap, playback);
+            request.echo_tap = true;
+            let request = request.after(echo.tap_delay(tap));
+            messages.write(match transform {
+                Some(transform) => request.with_components(*transform),
+                None => request,
+            });
+        }
+    }
+}
+
+/// Returns the [`Echo`] a new sound plays with, its own or the one of its
+/// [`EffectSend`]. Looping sounds are never echoed.
+pub(crate) fn echo_of<'a>(
+    playback: &PlaybackSettings,
+    echo: Option<&'a Echo>,
+    send: Option<&'a EffectSend>,
+) -> Option<&'a Echo> {
+    if matches!(playback.mode, PlaybackMode::Loop) {
+        return None;
+    }
+    echo.or_else(|| send.and_then(|send| send.0.echo.as_ref()))
+}
+
+/// Returns the playback settings of the `tap`-th echo of a sound.
+pub(crate) fn echo_tap_playback(
+    echo: &Echo,
+    tap: u32,
+    playback: &PlaybackSettings,
+) -> PlaybackSettings {
+    PlaybackSettings {
+        mode: PlaybackMode::Despawn,
+        volume: Volume::Linear(playback.volume.to_linear() * echo.tap_gain(tap)),
+        ..*playback
+    }
+}
+
+/// Emits an [`AudioStarted`] message for every sink of a source of type `P`
+/// created since the last run.
+pub fn emit_audio_started<B, P>(
+    query: Query<(Entity, &AudioPlayer<P>, Option<&PoolGeneration>), Added<B::Sink>>,
+    mut started: MessageWriter<AudioStarted<P>>,
+) where
+    B: AudioBackend,
+    P: Asset + Decodable,
+{
+    for (entity, player, generation) in &query {
+        started.write(AudioStarted {
+            entity,
+            generation: PoolGeneration::of(generation),
+            handle: player.0.clone(),
+        });
+    }
+}
+
+/// Observer that emits an [`AudioStopped`] message when the sink of a source
+/// of type `P` is removed.
+pub fn emit_audio_stopped<B, P>(
+    remove: On<Remove, B::Sink>,
+    query: Query<(&B::Sink, &AudioPlayer<P>, Option<&PoolGeneration>)>,
+    mut stopped: MessageWriter<AudioStopped<P>>,
+) where
+    B: AudioBackend,
+    P: Asset + Decodable,
+{
+    let Ok((sink, player, generation)) = query.get(remove.entity) else {
+        return;
+    };
+    stopped.write(AudioStopped {
+        entity: remove.entity,
+        generation: PoolGeneration::of(generation),
+        handle: player.0.clone(),
+        reason: if B::is_finished(sink) {
+            StopReason::Finished
         } else {
-            *kept_so_far += 1;
+            StopReason::Stopped
+        },
+    });
+}
+
+/// Moves sounds with an [`AttachedAudio`] to the position of their entity.
+///
+/// Runs in `PostUpdate` before transform propagation, so sounds follow the
+/// entity's global transform from the previous frame.
+pub fn follow_attached_audio(
+    targets: Query<&GlobalTransform>,
+    mut sounds: Query<(&AttachedAudio, &mut Transform)>,
+) {
+    for (attached, mut transform) in &mut sounds {
+        if let Ok(target) = targets.get(attached.0) {
+            transform.set_if_neq(target.compute_transform());
+        }
+    }
+}
+
+/// Keeps sounds with a [`StereoPan`] on the ear axis of the listener.
+///
+/// Panned sounds are switched to spatial playback, scaled so the listener's
+/// ears are two units apart and centered sounds are not attenuated by
+/// distance. Runs in `PostUpdate` after [`follow_attached_audio`] and before
+/// transform propagation.
+pub fn place_panned_sounds(
+    mut commands: Commands,
+    listeners: Query<(&GlobalTransform, &SpatialListener)>,
+    mut sounds: Query<
+        (
+            Entity,
+            &StereoPan,
+            &mut PlaybackSettings,
+            Option<&mut Transform>,
+        ),
+        Without<NonSpatial>,
+    >,
+) {
+    let (listener, ears) = listeners.iter().next().map_or_else(
+        || (GlobalTransform::IDENTITY, SpatialListener::default()),
+        |(transform, ears)| (*transform, ears.clone()),
+    );
+    let gap = ears.left_ear_offset.distance(ears.right_ear_offset);
+    if gap <= 0.0 {
+        return;
+    }
+    for (entity, pan, mut playback, transform) in &mut sounds {
+        if pan.0 == 0.0 {
+            continue;
+        }
+        if !playback.spatial {
+            playback.spatial = true;
+            playback.spatial_scale = Some(SpatialScale::new(2.0 / gap));
+        }
+        let t = (pan.0.clamp(-1.0, 1.0) + 1.0) / 2.0;
+        let position =
+            listener.transform_point(ears.left_ear_offset.lerp(ears.right_ear_offset, t));
+        match transform {
+            Some(mut transform) => {
+                if transform.translation != position {
+                    transform.translation = position;
+                }
+            }
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(Transform::from_translation(position));
+            }
+        }
+    }
+}
+
+/// Switches new [`NonSpatial`] sounds back to non-spatial playback.
+///
+/// Runs in `PostUpdate` after the systems enabling spatial playback.
+pub fn center_non_spatial_sounds(mut sounds: Query<&mut PlaybackSettings, Added<NonSpatial>>) {
+    for mut playback in &mut sounds {
+        if playback.spatial {
+            playback.spatial = false;
         }
     }
 }
 
+/// Enforces maximum concurrent instances of sounds of category type `A`
+/// playing sources of type `P`.
+///
+/// Registered for sound effect and music category types.
+/// Only limits of sounds whose [`MaxConcurrent`] was added this frame are
+/// checked against the [`SoundEffectCounter`]. When a limit is exceeded, the
+/// [`ConcurrencyPolicy`] of the newest sound picks which instances are
+/// despawned.
+pub fn enforce_sfx_concurrency<A, B, P>(
+    mut commands: Commands,
+    counter: Res<SoundEffectCounter>,
+    ticks: SystemChangeTick,
+    mut dropped: MessageWriter<AudioDropped<P>>,
+    added: Query<&MaxConcurrent, (With<A>, Added<MaxConcurrent>)>,
+    instances: Query<
+        (
+            Entity,
+            &AudioPlayer<P>,
+            Ref<MaxConcurrent>,
+            Option<AnySink<B>>,
+        ),
+        With<A>,
+    >,
+) where
+    A: AudioCategory,
+    B: AudioBackend,
+    P: Asset + Decodable,
+{
+    let mut limits: HashMap<UntypedAssetId, (u32, ConcurrencyPolicy)> = HashMap::new();
+    for max in &added {
+        if counter.count(max.source) > max.max {
+            limits.insert(max.source, (max.max, max.policy));
+        }
+    }
+    if limits.is_empty() {
+        return;
+    }
+
+    // Group the instances of every exceeded source in a single pass
+    let mut grouped: HashMap<UntypedAssetId, Vec<_>> = HashMap::new();
+    for instance in &instances {
+        if limits.contains_key(&instance.2.source) {
+            grouped.entry(instance.2.source).or_default().push(instance);
+        }
+    }
+
+    let this_run = ticks.this_run();
+    let newest_first = |a: &Ref<MaxConcurrent>, b: &Ref<MaxConcurrent>| {
+        newest_first(a.added(), b.added(), this_run)
+    };
+    for (source, (max, policy)) in limits {
+        let excess = counter.count(source).saturating_sub(max) as usize;
+        let Some(mut candidates) = grouped.remove(&source) else {
+            continue;
+        };
+        // Candidates in spawn order, reversed so that ties keep the newest first
+        candidates.reverse();
+        match policy {
+            ConcurrencyPolicy::RejectNew => {
+                candidates.retain(|(_, _, max, _)| max.is_added());
+                candidates.sort_by(|a, b| newest_first(&a.2, &b.2));
+            }
+            ConcurrencyPolicy::StealOldest => {
+                candidates.reverse();
+                candidates.sort_by(|a, b| newest_first(&b.2, &a.2));
+            }
+            ConcurrencyPolicy::StealQuietest => {
+                let volume = |sink: Option<_>| {
+                    sink.map_or(f32::INFINITY, |sink| either_sink::<B>(sink).volume())
+                };
+                candidates.sort_by(|a, b| {
+                    volume(a.3)
+                        .total_cmp(&volume(b.3))
+                        .then_with(|| newest_first(&a.2, &b.2))
+                });
+            }
+        }
+
+        for (entity, audio_player, _, sink) in candidates.into_iter().take(excess) {
+            commands.entity(entity).despawn();
+            // Sounds that already play report an `AudioStopped` instead
+            if sink.is_none() {
+                dropped.write(AudioDropped {
+                    entity,
+                    handle: audio_player.0.clone(),
+                    reason: DropReason::Concurrency,
+                });
+            }
+        }
+    }
+}
+
+/// Despawns sounds over the [`GlobalVoiceLimit`] when new sounds are added.
+///
+/// Sounds with the lowest [`AudioPriority`] are despawned first, and the
+/// newest among equal priorities. Generated [`Tone`]s count like any other
+/// sound.
+pub fn enforce_global_voice_limit<B: AudioBackend>(
+    mut commands: Commands,
+    limit: Res<GlobalVoiceLimit>,
+    ticks: SystemChangeTick,
+    mut dropped: MessageWriter<AudioDropped>,
+    mut dropped_tones: MessageWriter<AudioDropped<Tone>>,
+    added: Query<(), Or<(Added<AudioPlayer>, Added<AudioPlayer<Tone>>)>>,
+    sounds: Query<(
+        Entity,
+        AnyOf<(Ref<AudioPlayer>, Ref<AudioPlayer<Tone>>)>,
+        Option<&AudioPriority>,
+        Has<B::Sink>,
+        Has<<B::Spatial as AudioBackend>::Sink>,
+    )>,
+) {
+    if added.is_empty() {
+        return;
+    }
+    let count = sounds.iter().count();
+    if count <= limit.max_voices {
+        return;
+    }
+
+    let this_run = ticks.this_run();
+    let added_tick =
+        |(player, tone): &(Option<Ref<AudioPlayer>>, Option<Ref<AudioPlayer<Tone>>>)| {
+            player
+                .as_ref()
+                .map(Ref::added)
+                .or_else(|| tone.as_ref().map(Ref::added))
+                .unwrap_or(this_run)
+        };
+    // Spawn order, reversed so that ties keep the newest first
+    let mut sounds: Vec<_> = sounds.iter().collect();
+    sounds.reverse();
+    sounds.sort_by(|a, b| {
+        let priority = |priority: Option<&AudioPriority>| priority.map_or(0, |priority| priority.0);
+        priority(a.2)
+            .cmp(&priority(b.2))
+            .then_with(|| newest_first(added_tick(&a.1), added_tick(&b.1), this_run))
+    });
+    for (entity, (player, tone), _, has_sink, has_spatial_sink) in
+        sounds.into_iter().take(count - limit.max_voices)
+    {
+        commands.entity(entity).despawn();
+        // Sounds that already play report an `AudioStopped` instead
+        if has_sink || has_spatial_sink {
+            continue;
+        }
+        if let Some(player) = player {
+            dropped.write(AudioDropped {
+                entity,
+                handle: player.0.clone(),
+                reason: DropReason::VoiceLimit,
+            });
+        } else if let Some(tone) = tone {
+            dropped_tones.write(AudioDropped {
+                entity,
+                handle: tone.0.clone(),
+                reason: DropReason::VoiceLimit,
+            });
+        }
+    }
+}
+
+/// Orders change ticks so that the most recent comes first.
+fn newest_first(a: Tick, b: Tick, this_run: Tick) -> Ordering {
+    if a.is_newer_than(b, this_run) {
+        Ordering::Less
+    } else if b.is_newer_than(a, this_run) {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// Processes volume tweens.
+///
+/// This system updates the volume of entities with [`VolumeTween`] components
+/// and applies the tween's [`TweenCompletion`] action when it finishes.
+pub fn process_volume_tweens<B: AudioBackend>(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut VolumeTween, &mut B::Sink)>,
+) {
+    for (entity, mut tween, mut sink) in &mut query {
+        step_volume_tween::<VolumeTween, B>(
+            &mut commands,
+            entity,
+            &mut tween,
+            &mut sink,
+            time.delta(),
+            1.0,
+        );
+    }
+}
+
+/// Processes speed tweens.
+///
+/// This system updates the playback speed of entities with [`SpeedTween`]
+/// components and applies the tween's [`TweenCompletion`] action when it finishes.
+pub fn process_speed_tweens<B: AudioBackend>(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut SpeedTween, &mut B::Sink)>,
+) {
+    for (entity, mut tween, mut sink) in &mut query {
+        tween.timer.tick(time.delta());
+
+        if !tween.is_finished() {
+            B::set_speed(&mut sink, tween.current_speed());
+            continue;
+        }
+
+        match tween.on_complete {
+            TweenCompletion::Keep => {
+                B::set_speed(&mut sink, tween.target);
+                commands.entity(entity).remove::<SpeedTween>();
+            }
+            TweenCompletion::Despawn => commands.entity(entity).despawn(),
+            TweenCompletion::Pause => {
+                B::set_speed(&mut sink, tween.target);
+                B::pause(&mut sink);
+                commands.entity(entity).remove::<SpeedTween>();
+            }
+        }
+    }
+}
+
+/// Processes volume ramps started by config changes.
+///
+/// This system advances [`RampingVolume`] components and removes them once
+/// the sink has reached its new volume.
+pub fn process_volume_ramps<B: AudioBackend>(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut RampingVolume, &mut B::Sink)>,
+) {
+    for (entity, mut ramp, mut sink) in &mut query {
+        step_volume_tween::<RampingVolume, B>(
+            &mut commands,
+            entity,
+            &mut ramp.tween,
+            &mut sink,
+            time.delta(),
+            1.0,
+        );
+    }
+}
+
+/// Advances mixer snapshot transitions for category type `A`.
+///
+/// The mixer resource is only marked as changed while a snapshot is
+/// blending in or out, so volumes are not reapplied every frame.
+///
+/// Transitions run on real time so they keep blending while the game is
+/// paused, which is when pause menu snapshots are usually pushed.
+pub fn advance_mixer_snapshots<A: AudioCategory>(
+    time: Res<Time<Real>>,
+    mut mixer: ResMut<CategoryMixerState<A>>,
+) {
+    if mixer.is_transitioning() {
+        mixer.tick(time.delta());
+    }
+}
+
 /// Processes audio fade-outs.
 ///
 /// This system updates the volume of entities with [`FadeOut`](crate::components::FadeOut)
 /// components, gradually reducing volume and despawning when complete.
-pub fn process_fade_outs(
+///
+/// The fade progress is multiplied into the volume computed from the
+/// entity's [`AudioVolumeState`] each frame, so volume changes made during
+/// the fade carry through.
+pub fn process_fade_outs<B: AudioBackend>(
     mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<(Entity, &mut crate::components::FadeOut, &mut AudioSink)>,
+    real_time: Res<Time<Real>>,
+    mut query: Query<(
+        Entity,
+        &mut FadeOut,
+        &mut B::Sink,
+        Option<&AudioVolumeState>,
+        Option<&TrackGain>,
+    )>,
+) {
+    for (entity, mut fade, mut sink, state, gain) in &mut query {
+        let full_volume = state.map_or(1.0, |state| state.volume(gain));
+        if fade.from_current {
+            fade.from_current = false;
+            fade.tween.start = if full_volume > 0.0 {
+                B::volume(&sink) / full_volume
+            } else {
+                0.0
+            };
+        }
+        let delta = if fade.real_time {
+            real_time.delta()
+        } else {
+            time.delta()
+        };
+        step_volume_tween::<FadeOut, B>(
+            &mut commands,
+            entity,
+            &mut fade.tween,
+            &mut sink,
+            delta,
+            full_volume,
+        );
+    }
+}
+
+/// Advances a tween and applies its volume, multiplied by `scale`.
+///
+/// Once the tween completes, its completion action is applied and the
+/// driving component `T` is removed from entities that are kept alive.
+fn step_volume_tween<T: Component, B: AudioBackend>(
+    commands: &mut Commands,
+    entity: Entity,
+    tween: &mut VolumeTween,
+    sink: &mut B::Sink,
+    delta: Duration,
+    scale: f32,
+) {
+    tween.timer.tick(delta);
+
+    if !tween.is_finished() {
+        B::set_volume(sink, tween.current_volume() * scale);
+        return;
+    }
+
+    match tween.on_complete {
+        TweenCompletion::Keep => {
+            B::set_volume(sink, tween.target * scale);
+            commands.entity(entity).remove::<T>();
+        }
+        TweenCompletion::Despawn => commands.entity(entity).despawn(),
+        TweenCompletion::Pause => {
+            B::set_volume(sink, tween.target * scale);
+            B::pause(sink);
+            commands.entity(entity).remove::<T>();
+        }
+    }
+}
+
+/// Observer that stores the position of music entities as they are removed.
+///
+/// Positions of looping tracks are wrapped by the [`SourceDurations`], so a
+/// track resumes where it was in the loop. Tracks that finished playing are
+/// forgotten instead, so resuming them starts from the beginning.
+pub fn remember_music_position<M: MusicCategory, B: AudioBackend>(
+    remove: On<Remove, M>,
+    query: Query<(&M, &AudioPlayer, &B::Sink)>,
+    durations: Res<SourceDurations>,
+    mut memory: ResMut<MusicMemory<M>>,
+) {
+    let Ok((category, player, sink)) = query.get(remove.entity) else {
+        return;
+    };
+    if B::is_finished(sink) {
+        memory.forget(*category, &player.0);
+    } else {
+        let position = durations.wrap(&player.0, B::position(sink));
+        memory.remember(*category, &player.0, position);
+    }
+}
+
+/// Follows tracks with a [`MusicTempo`] and emits [`Beat`] and [`Bar`] messages.
+///
+/// Every beat crossed since the last update is emitted, so a long frame does
+/// not skip any. Looping tracks are wrapped by the length in
+/// [`SourceDurations`].
+pub fn update_music_clock<B: AudioBackend>(
+    mut clock: ResMut<MusicClock>,
+    query: Query<(Entity, &MusicTempo, &B::Sink, Option<&AudioPlayer>)>,
+    tempos: Query<(), With<MusicTempo>>,
+    durations: Res<SourceDurations>,
+    mut beats: MessageWriter<Beat>,
+    mut bars: MessageWriter<Bar>,
+) {
+    clock.retain(|entity| tempos.contains(entity));
+
+    for (entity, tempo, sink, player) in &query {
+        let length = player.and_then(|player| durations.get(&player.0));
+        let beats_per_bar = u64::from(tempo.beats_per_bar);
+        for beat in clock.advance(entity, *tempo, B::position(sink), length) {
+            let bar = beat / beats_per_bar;
+            let beat_in_bar = (beat % beats_per_bar) as u32;
+            beats.write(Beat {
+                entity,
+                beat,
+                bar,
+                beat_in_bar,
+            });
+            if beat_in_bar == 0 {
+                bars.write(Bar { entity, bar });
+            }
+        }
+    }
+}
+
+/// Performs [`QuantizedStop`] transitions once their beat or bar boundary is reached.
+pub fn process_quantized_stops<B: AudioBackend>(
+    mut commands: Commands,
+    query: Query<(Entity, &QuantizedStop, AnySink<B>)>,
+) {
+    for (entity, stop, sink) in &query {
+        if !stop.is_due(either_sink::<B>(sink).position()) {
+            continue;
+        }
+        match stop.fade {
+            Some(duration) => {
+                commands.entity(entity).remove::<QuantizedStop>();
+                crate::events::start_fade_out(&mut commands, entity, duration, &stop.easing);
+            }
+            None => commands.entity(entity).despawn(),
+        }
+    }
+}
+
+/// Starts the stems of layered tracks together once every stem's source has
+/// loaded, so they begin in the same frame and stay in step.
+///
+/// Stems whose load failed are despawned and reported as dropped, and the
+/// others start without them. Without an [`Assets<AudioSource>`] resource
+/// there is nothing to wait for, and stems start right away.
+pub fn start_layered_stems_when_loaded<B: AudioBackend>(
+    mut commands: Commands,
+    asset_server: Option<Res<AssetServer>>,
+    sources: Option<Res<Assets<AudioSource>>>,
+    mut dropped: MessageWriter<AudioDropped>,
+    tracks: Query<&Children, With<LayeredTrack>>,
+    stems: Query<(&WaitForLoad, Option<&PlaybackSettings>), With<MusicLayer>>,
+) {
+    for children in &tracks {
+        let mut waiting = Vec::with_capacity(children.len());
+        let mut loaded = true;
+        for stem in children.iter() {
+            let Ok((wait, playback)) = stems.get(stem) else {
+                continue;
+            };
+            if sources
+                .as_ref()
+                .is_none_or(|sources| sources.contains(&wait.handle))
+            {
+                waiting.push((stem, wait, playback));
+                continue;
+            }
+            let state = asset_server
+                .as_ref()
+                .and_then(|server| server.get_load_state(&wait.handle));
+            if let Some(LoadState::Failed(err)) = state {
+                warn!("Music stem failed to load, dropping it: {err}");
+                commands.entity(stem).despawn();
+                dropped.write(AudioDropped {
+                    entity: stem,
+                    handle: wait.handle.clone(),
+                    reason: DropReason::LoadFailed,
+                });
+            } else {
+                loaded = false;
+            }
+        }
+        if !loaded {
+            continue;
+        }
+        for (stem, wait, playback) in waiting {
+            let mut stem = commands.entity(stem);
+            stem.remove::<WaitForLoad>();
+            B::play(
+                &mut stem,
+                wait.handle.clone(),
+                playback.copied().unwrap_or_default(),
+            );
+        }
+    }
+}
+
+/// Despawns layered music parents whose stems have all been removed.
+pub fn despawn_empty_layered_tracks(
+    mut commands: Commands,
+    query: Query<Entity, (With<LayeredTrack>, Without<Children>)>,
+) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Seeks the stems of layered tracks with a [`StemSync`] to the position of
+/// their master stem when they start late or drift apart.
+///
+/// Sink positions keep growing while the stems loop, so they are compared
+/// within the track, wrapped by the lengths in [`SourceDurations`].
+pub fn sync_layered_stems<B: AudioBackend>(
+    time: Res<Time<Real>>,
+    durations: Res<SourceDurations>,
+    mut tracks: Query<(&mut StemSync, &Children), With<LayeredTrack>>,
+    mut stems: Query<(&MusicLayer, &mut B::Sink, Option<&AudioPlayer>)>,
+) {
+    let wrap = |player: Option<&AudioPlayer>, position| match player {
+        Some(player) => durations.wrap(&player.0, position),
+        None => position,
+    };
+    for (mut sync, children) in &mut tracks {
+        let due = sync.tick(time.delta());
+        let master = children.iter().find_map(|child| {
+            let (layer, sink, player) = stems.get(child).ok()?;
+            sync.master
+                .as_ref()
+                .is_none_or(|master| master == layer)
+                .then(|| (child, B::is_paused(sink), wrap(player, B::position(sink))))
+        });
+        let Some((master, false, position)) = master else {
+            continue;
+        };
+        for child in children.iter().filter(|child| *child != master) {
+            let Ok((_, mut sink, player)) = stems.get_mut(child) else {
+                continue;
+            };
+            if !(due || sink.is_added()) {
+                continue;
+            }
+            if let Some(target) = sync.correction(position, wrap(player, B::position(&sink))) {
+                B::seek(&mut sink, target);
+            }
+        }
+    }
+}
+
+/// Ducks music while stingers play and restores it afterwards.
+///
+/// While any [`Stinger`] of music type `M` is alive, the stinger duck on the
+/// mixer ramps in over the stinger's fade, ducking the other music entities
+/// of that type to the stinger's duck volume, or to the
+/// [`TrackInfo::duck_db`](crate::TrackInfo::duck_db) of their track. Once all
+/// stingers have finished, it ramps out over the fade of the last one.
+///
+/// Like dialogue ducking, the duck is a gain in the volume pipeline, so
+/// config, mixer, and fade changes made meanwhile are kept.
+pub fn duck_music_for_stingers<M: MusicCategory>(
+    time: Res<Time>,
+    stingers: Query<&Stinger, With<M>>,
+    mut fade: Local<Duration>,
+    mut mixer: ResMut<CategoryMixerState<M>>,
+) {
+    let strongest = stingers
+        .iter()
+        .min_by(|a, b| a.duck_volume.total_cmp(&b.duck_volume));
+    let target = match strongest {
+        Some(stinger) => {
+            *fade = stinger.fade;
+            if mixer.stinger_duck_volume() != stinger.duck_volume {
+                mixer.set_stinger_duck_volume(stinger.duck_volume);
+            }
+            1.0
+        }
+        None => 0.0,
+    };
+
+    let current = mixer.stinger_duck();
+    let next = if fade.is_zero() {
+        target
+    } else {
+        let max_step = time.delta_secs() / fade.as_secs_f32();
+        if current > target {
+            (current - max_step).max(target)
+        } else {
+            (current + max_step).min(target)
+        }
+    };
+    if next != current {
+        mixer.set_stinger_duck(next);
+    }
+}
+
+/// Ducks music of type `M` while any voice line plays.
+///
+/// The duck gain on the mixer follows the [`DialogueDucking`] attack and
+/// release times, and is only written while it changes.
+pub fn duck_music_for_voice<M: MusicCategory>(
+    time: Res<Time>,
+    ducking: Res<DialogueDucking>,
+    voices: Query<(), With<VoiceLine>>,
+    mut mixer: ResMut<CategoryMixerState<M>>,
+) {
+    let current = mixer.duck_gain();
+    let next = ducking.step(current, !voices.is_empty(), time.delta());
+    if next != current {
+        mixer.set_duck_gain(next);
+    }
+}
+
+/// Processes audio fade-ins.
+///
+/// This system updates the volume of entities with [`FadeIn`] components,
+/// gradually raising volume and removing the component when complete.
+pub fn process_fade_ins<B: AudioBackend>(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut FadeIn, &mut B::Sink)>,
 ) {
     for (entity, mut fade, mut sink) in &mut query {
         fade.timer.tick(time.delta());
 
+        B::set_volume(&mut sink, fade.current_volume());
         if fade.is_finished() {
-            commands.entity(entity).despawn();
-        } else {
-            sink.set_volume(Volume::Linear(fade.current_volume()));
+            commands.entity(entity).remove::<FadeIn>();
+        }
+    }
+}
+
+/// Computes the volume an entity should play at from the config, the mixer
+/// state, its category, its playback settings, and its optional [`TrackGain`].
+pub(crate) fn target_volume<A: AudioCategory>(
+    config: &A::Config,
+    mixer: &CategoryMixerState<A>,
+    category: &A,
+    playback: &PlaybackSettings,
+    gain: Option<&TrackGain>,
+) -> f32
+where
+    A::Config: AudioConfigTrait,
+{
+    compute_final_volume(
+        &VolumeStages::for_category(config, mixer, *category).with_base(playback, gain),
+    )
+}
+
+/// Tracks which category gains changed during one run of a volume update
+/// system.
+///
+/// Categories are compared by equality, so gains are kept in small vectors
+/// instead of a map.
+struct CategoryGains<'a, A> {
+    /// Last gain applied to each category, kept across runs.
+    applied: &'a mut Vec<(A, f32)>,
+    /// Categories checked during this run, with their stages if the gain
+    /// changed.
+    checked: Vec<(A, Option<VolumeStages>)>,
+}
+
+impl<'a, A> CategoryGains<'a, A> {
+    fn new(applied: &'a mut Vec<(A, f32)>) -> Self {
+        Self {
+            applied,
+            checked: Vec::new(),
         }
     }
 }
 
+impl<A: AudioCategory> CategoryGains<'_, A>
+where
+    A::Config: AudioConfigTrait,
+{
+    /// Returns the category stages if the category gain changed since it
+    /// was last applied, recording it as applied.
+    fn changed(
+        &mut self,
+        config: &A::Config,
+        mixer: &CategoryMixerState<A>,
+        category: A,
+    ) -> Option<VolumeStages> {
+        if let Some((_, stages)) = self.checked.iter().find(|(c, _)| *c == category) {
+            return *stages;
+        }
+        let stages = VolumeStages::for_category(config, mixer, category);
+        let gain = stages.category_gain();
+        let changed = match self.applied.iter_mut().find(|(c, _)| *c == category) {
+            Some((_, applied)) if *applied == gain => None,
+            Some((_, applied)) => {
+                *applied = gain;
+                Some(stages)
+            }
+            None => {
+                self.applied.push((category, gain));
+                Some(stages)
+            }
+        };
+        self.checked.push((category, changed));
+        changed
+    }
+
+    /// Ends the run, forgetting categories without sinks as their gain may
+    /// change before their next sink is spawned.
+    fn finish(self) {
+        let checked = self.checked;
+        self.applied
+            .retain(|(category, _)| checked.iter().any(|(c, _)| c == category));
+    }
+}
+
+/// Sets the volume of a new sink and records its [`AudioVolumeState`].
+fn start_sink_volume<B: AudioBackend>(
+    commands: &mut Commands,
+    entity: Entity,
+    stages: VolumeStages,
+    playback: &PlaybackSettings,
+    gain: Option<&TrackGain>,
+    sink: &mut B::Sink,
+    fade_in: Option<Mut<FadeIn>>,
+) {
+    let stages = stages.with_base(playback, gain);
+    set_sink_volume::<B>(sink, fade_in, compute_final_volume(&stages));
+    commands.entity(entity).insert(AudioVolumeState {
+        base: extract_linear_volume(playback.volume),
+        loudness: stages.loudness,
+        attenuation: stages.attenuation,
+        category: stages.category_gain(),
+    });
+}
+
+/// Moves a sink to `volume` over the [`VolumeRamp`], or sets it at once when
+/// `instant` or the ramp is zero.
+fn ramp_sink_volume<B: AudioBackend>(
+    commands: &mut Commands,
+    entity: Entity,
+    sink: &mut B::Sink,
+    fade_in: Option<Mut<FadeIn>>,
+    instant: bool,
+    volume: f32,
+    ramp: &VolumeRamp,
+) {
+    if instant || ramp.duration.is_zero() {
+        set_sink_volume::<B>(sink, fade_in, volume);
+        return;
+    }
+    commands.entity(entity).insert(RampingVolume {
+        tween: VolumeTween::new(B::volume(sink), volume, ramp.duration),
+    });
+}
+
+/// Sets the sink volume, or retargets the fade-in if one is in progress.
+fn set_sink_volume<B: AudioBackend>(sink: &mut B::Sink, fade_in: Option<Mut<FadeIn>>, volume: f32) {
+    match fade_in {
+        Some(mut fade) => {
+            fade.target_volume = volume;
+            B::set_volume(sink, fade.current_volume());
+        }
+        None => B::set_volume(sink, volume),
+    }
+}
+
 /// Extracts linear volume from a Volume enum.
 ///
 /// Converts decibel values to linear using the formula: 10^(db/20)
 #[inline]
-fn extract_linear_volume(volume: Volume) -> f32 {
+pub(crate) fn extract_linear_volume(volume: Volume) -> f32 {
     match volume {
         Volume::Linear(v) => v,
         Volume::Decibels(db) => 10_f32.powf(db / 20.0),
@@ -142,6 +1838,125 @@ fn extract_linear_volume(volume: Volume) -> f32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend::tests::{MockBackend, MockSink};
+    use crate::backend::BevyAudioBackend;
+    use crate::tests::{test_app, TestConfig, TestMusic, TestPlugin, TestSfx};
+    use crate::traits::{VolumeCurve, VolumeScale};
+    use crate::{
+        AudioLog, ConcurrencyRequest, FinishSound, PlayMusic, SfxBundle, SfxRequest,
+        TestAudioPlugin,
+    };
+
+    #[derive(Resource, Clone, Default)]
+    struct TestLinearConfig;
+
+    impl AudioConfigTrait for TestLinearConfig {
+        fn master_volume(&self) -> f32 {
+            0.5
+        }
+
+        fn volume_curve(&self) -> VolumeCurve {
+            VolumeCurve::Linear
+        }
+    }
+
+    #[derive(Component, Clone, Copy, Default, PartialEq)]
+    struct TestLinearMusic;
+
+    impl AudioCategory for TestLinearMusic {
+        type Config = TestLinearConfig;
+        fn volume_multiplier(&self, _: &Self::Config) -> f32 {
+            0.8
+        }
+    }
+
+    #[test]
+    fn target_volume_combines_all_factors() {
+        let playback = PlaybackSettings::LOOP.with_volume(Volume::Linear(0.5));
+        let volume = target_volume(
+            &TestLinearConfig,
+            &CategoryMixerState::default(),
+            &TestLinearMusic,
+            &playback,
+            None,
+        );
+        assert!((volume - 0.2).abs() < 0.0001);
+    }
+
+    #[derive(Resource, Clone, Default)]
+    struct TestDecibelConfig;
+
+    impl AudioConfigTrait for TestDecibelConfig {
+        fn master_volume(&self) -> f32 {
+            0.0
+        }
+
+        fn volume_scale(&self) -> VolumeScale {
+            VolumeScale::Decibels
+        }
+    }
+
+    #[derive(Component, Clone, Copy, Default, PartialEq)]
+    struct TestDecibelMusic;
+
+    impl AudioCategory for TestDecibelMusic {
+        type Config = TestDecibelConfig;
+        fn volume_multiplier(&self, _: &Self::Config) -> f32 {
+            -20.0
+        }
+    }
+
+    #[test]
+    fn target_volume_converts_decibel_config() {
+        let volume = target_volume(
+            &TestDecibelConfig,
+            &CategoryMixerState::default(),
+            &TestDecibelMusic,
+            &PlaybackSettings::LOOP,
+            None,
+        );
+        assert!((volume - 0.1).abs() < 0.001);
+    }
+
+    #[test]
+    fn target_volume_applies_track_gain() {
+        let gain = TrackGain::new(0.5);
+        let volume = target_volume(
+            &TestLinearConfig,
+            &CategoryMixerState::default(),
+            &TestLinearMusic,
+            &PlaybackSettings::LOOP,
+            Some(&gain),
+        );
+        assert!((volume - 0.2).abs() < 0.0001);
+    }
+
+    #[test]
+    fn target_volume_applies_mixer_state() {
+        let mut mixer = CategoryMixerState::default();
+        mixer.set_muted(TestLinearMusic, true);
+        let volume = target_volume(
+            &TestLinearConfig,
+            &mixer,
+            &TestLinearMusic,
+            &PlaybackSettings::LOOP,
+            None,
+        );
+        assert!(volume.abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn volume_stages_split_category_and_sound_gain() {
+        let mut mixer = CategoryMixerState::default();
+        mixer.set_duck_gain(0.5);
+        let stages = VolumeStages::for_category(&TestLinearConfig, &mixer, TestLinearMusic)
+            .with_base(&PlaybackSettings::LOOP, Some(&TrackGain::new(0.5)))
+            .with_tween(0.5);
+        assert!((stages.duck - 0.5).abs() < f32::EPSILON);
+        assert!((stages.snapshot - 1.0).abs() < f32::EPSILON);
+        assert!((stages.category_gain() - 0.2).abs() < 0.0001);
+        assert!((compute_final_volume(&stages) - 0.05).abs() < 0.0001);
+    }
 
     #[test]
     fn extract_linear_volume_from_linear() {
@@ -164,4 +1979,558 @@ mod tests {
         let linear = extract_linear_volume(volume);
         assert!((linear - 1.0).abs() < f32::EPSILON);
     }
+
+    #[test]
+    fn start_audio_when_loaded_inserts_player() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<AudioSource>();
+        app.add_message::<AudioDropped>();
+        app.add_systems(Update, start_audio_when_loaded::<BevyAudioBackend>);
+
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<AudioSource>>()
+            .add(AudioSource {
+                bytes: Vec::new().into(),
+            });
+        let entity = app.world_mut().spawn(WaitForLoad::new(handle)).id();
+
+        app.update();
+
+        let entity = app.world().entity(entity);
+        assert!(entity.contains::<AudioPlayer>());
+        assert!(!entity.contains::<WaitForLoad>());
+    }
+
+    #[test]
+    fn start_audio_when_loaded_waits_for_missing_source() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<AudioSource>();
+        app.add_message::<AudioDropped>();
+        app.add_systems(Update, start_audio_when_loaded::<BevyAudioBackend>);
+
+        let entity = app
+            .world_mut()
+            .spawn(WaitForLoad::new(Handle::<AudioSource>::default()))
+            .id();
+
+        app.update();
+
+        let entity = app.world().entity(entity);
+        assert!(!entity.contains::<AudioPlayer>());
+        assert!(entity.contains::<WaitForLoad>());
+    }
+
+    #[test]
+    fn layered_stems_start_together_once_all_are_loaded() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<AudioSource>();
+        app.add_message::<AudioDropped>();
+        app.add_systems(Update, start_layered_stems_when_loaded::<BevyAudioBackend>);
+
+        let mut sources = app.world_mut().resource_mut::<Assets<AudioSource>>();
+        let pads = sources.add(AudioSource {
+            bytes: Vec::new().into(),
+        });
+        let drums = sources.reserve_handle();
+        let track = app
+            .world_mut()
+            .spawn(LayeredTrack)
+            .with_children(|stems| {
+                stems.spawn((MusicLayer::new("pads"), WaitForLoad::new(pads)));
+                stems.spawn((MusicLayer::new("drums"), WaitForLoad::new(drums.clone())));
+            })
+            .id();
+        let started = |app: &App| {
+            let stems: Vec<_> = app.world().get::<Children>(track).unwrap().to_vec();
+            stems
+                .into_iter()
+                .filter(|stem| app.world().entity(*stem).contains::<AudioPlayer>())
+                .count()
+        };
+
+        app.update();
+        assert_eq!(started(&app), 0);
+
+        app.world_mut()
+            .resource_mut::<Assets<AudioSource>>()
+            .insert(
+                &drums,
+                AudioSource {
+                    bytes: Vec::new().into(),
+                },
+            )
+            .unwrap();
+        app.update();
+        assert_eq!(started(&app), 2);
+    }
+
+    #[test]
+    fn start_positions_are_seeked_once() {
+        let mut app = App::new();
+        app.add_systems(Update, seek_start_positions::<MockBackend>);
+        let position = Duration::from_secs(12);
+        let entity = app
+            .world_mut()
+            .spawn((StartPosition(position), MockSink::default()))
+            .id();
+
+        app.update();
+        app.update();
+
+        assert_eq!(
+            app.world().get::<MockSink>(entity).unwrap().seeks,
+            [position]
+        );
+        assert!(app.world().get::<StartPosition>(entity).is_none());
+    }
+
+    #[test]
+    fn randomized_loops_reroll_when_the_position_crosses_the_source_length() {
+        let mut app = App::new();
+        let handle = Handle::<AudioSource>::default();
+        let mut durations = SourceDurations::default();
+        durations.insert(&handle, Duration::from_secs(10));
+        app.insert_resource(durations);
+        app.insert_resource(AudioRng::seeded(7));
+        app.add_systems(Update, reroll_randomized_playback::<MockBackend>);
+        let entity = app
+            .world_mut()
+            .spawn((
+                AudioPlayer(handle),
+                PlaybackSettings::LOOP,
+                PlaybackRandomizer::new().with_speed(0.5, 2.0),
+                MockSink {
+                    speed: 1.0,
+                    ..default()
+                },
+            ))
+            .id();
+        let mut step = |position: u64| {
+            let mut sink = app.world_mut().get_mut::<MockSink>(entity).unwrap();
+            sink.position = Duration::from_secs(position);
+            app.update();
+            app.world().get::<MockSink>(entity).unwrap().speed
+        };
+
+        let first = step(0);
+        assert_ne!(first, 1.0);
+        assert_eq!(step(9), first);
+        // The sink position keeps growing past the end of the looping source
+        let second = step(11);
+        assert_ne!(second, first);
+        assert_eq!(step(19), second);
+        assert_ne!(step(21), second);
+    }
+
+    #[test]
+    fn music_clock_emits_every_beat_of_a_long_frame() {
+        let mut app = App::new();
+        app.init_resource::<MusicClock>();
+        app.init_resource::<SourceDurations>();
+        app.add_message::<Beat>();
+        app.add_message::<Bar>();
+        app.add_systems(Update, update_music_clock::<MockBackend>);
+        let entity = app
+            .world_mut()
+            .spawn((MusicTempo::new(120.0, 4), MockSink::default()))
+            .id();
+        app.update();
+
+        app.world_mut()
+            .get_mut::<MockSink>(entity)
+            .unwrap()
+            .position = Duration::from_millis(2100);
+        app.update();
+
+        let beats: Vec<_> = app
+            .world()
+            .resource::<Messages<Beat>>()
+            .iter_current_update_messages()
+            .map(|beat| (beat.beat, beat.beat_in_bar))
+            .collect();
+        assert_eq!(beats, [(1, 1), (2, 2), (3, 3), (4, 0)]);
+        let bars: Vec<_> = app
+            .world()
+            .resource::<Messages<Bar>>()
+            .iter_current_update_messages()
+            .map(|bar| bar.bar)
+            .collect();
+        assert_eq!(bars, [1]);
+    }
+
+    #[test]
+    fn lifecycle_messages_report_start_finish_and_drops() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+
+        for _ in 0..2 {
+            app.world_mut()
+                .write_message(PlaySfx::new(Handle::default(), TestSfx::UI).with_max_concurrent(1));
+        }
+        app.update();
+        let dropped = app.world().resource::<Messages<AudioDropped>>();
+        let dropped: Vec<_> = dropped.get_cursor().read(dropped).cloned().collect();
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].reason, DropReason::Concurrency);
+
+        app.update();
+        let started = app.world().resource::<Messages<AudioStarted>>();
+        let entity = started.get_cursor().read(started).next().unwrap().entity;
+
+        app.world_mut().write_message(FinishSound { entity });
+        app.update();
+        let stopped = app.world().resource::<Messages<AudioStopped>>();
+        let stopped: Vec<_> = stopped.get_cursor().read(stopped).cloned().collect();
+        assert_eq!(stopped.len(), 1);
+        assert_eq!(stopped[0].entity, entity);
+        assert_eq!(stopped[0].reason, StopReason::Finished);
+    }
+
+    #[test]
+    fn lifecycle_messages_report_spatial_sounds() {
+        // Spatial sinks need an audio device, so the spatial sound plays
+        // through the mock backend the spatial systems are generic over
+        let mut app = test_app(TestPlugin::default());
+        app.add_systems(Update, emit_audio_started::<MockBackend, AudioSource>);
+        app.add_observer(emit_audio_stopped::<MockBackend, AudioSource>);
+
+        let entity = app
+            .world_mut()
+            .spawn((
+                AudioPlayer::<AudioSource>(Handle::default()),
+                PlaybackSettings::ONCE.with_spatial(true),
+                Transform::default(),
+                MockSink::default(),
+            ))
+            .id();
+        app.update();
+        let started = app.world().resource::<Messages<AudioStarted>>();
+        let started: Vec<_> = started.get_cursor().read(started).cloned().collect();
+        assert_eq!(started.len(), 1);
+        assert_eq!(started[0].entity, entity);
+
+        app.world_mut().entity_mut(entity).despawn();
+        let stopped = app.world().resource::<Messages<AudioStopped>>();
+        let stopped: Vec<_> = stopped.get_cursor().read(stopped).cloned().collect();
+        assert_eq!(stopped.len(), 1);
+        assert_eq!(stopped[0].entity, entity);
+        assert_eq!(stopped[0].reason, StopReason::Stopped);
+    }
+
+    #[test]
+    fn custom_backend_plays_sfx_at_category_volume() {
+        let mut app = test_app(TestPlugin::default());
+        app.insert_resource(TestConfig {
+            sfx: 0.5,
+            ..TestConfig::FULL
+        });
+        app.add_systems(
+            Update,
+            (
+                crate::events::handle_play_sfx_events::<TestSfx, MockBackend>
+                    .in_set(crate::MsgAudioSet::EventHandling),
+                apply_volume_to_new_sfx::<TestSfx, TestConfig, MockBackend>
+                    .in_set(crate::MsgAudioSet::VolumeApplication),
+            ),
+        );
+
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI).volume(0.5));
+        app.update();
+
+        let mut sinks = app
+            .world_mut()
+            .query_filtered::<(&MockSink, &AudioVolumeState), With<TestSfx>>();
+        let (sink, state) = sinks.single(app.world()).unwrap();
+        assert!((state.base - 0.5).abs() < f32::EPSILON);
+        assert!(state.category < 1.0);
+        assert!((sink.volume - state.volume(None)).abs() < 0.0001);
+    }
+
+    #[test]
+    fn steal_oldest_policy_replaces_playing_sound() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        let play = || {
+            PlaySfx::new(Handle::default(), TestSfx::UI)
+                .with_max_concurrent(1)
+                .with_concurrency_policy(ConcurrencyPolicy::StealOldest)
+        };
+
+        app.world_mut().write_message(play());
+        app.update();
+        app.update();
+        let (oldest, _) = app.world().resource::<AudioLog>().plays().next().unwrap();
+
+        app.world_mut().write_message(play());
+        app.update();
+        assert!(app.world().get_entity(oldest).is_err());
+        let mut sounds = app.world_mut().query::<&MaxConcurrent>();
+        assert_eq!(sounds.iter(app.world()).count(), 1);
+        assert_eq!(
+            app.world()
+                .resource::<SoundEffectCounter>()
+                .count(&Handle::<AudioSource>::default()),
+            1
+        );
+    }
+
+    #[test]
+    fn global_voice_limit_drops_lowest_priority_sounds() {
+        let mut app = test_app((
+            TestPlugin::default().with_global_voice_limit(GlobalVoiceLimit::new(2)),
+            TestAudioPlugin,
+        ));
+        let play = |priority| PlaySfx::new(Handle::default(), TestSfx::UI).with_priority(priority);
+
+        app.world_mut().write_message(play(1));
+        app.world_mut().write_message(play(1));
+        app.update();
+        app.world_mut().write_message(play(0));
+        app.update();
+        let dropped = app.world().resource::<Messages<AudioDropped>>();
+        let dropped: Vec<_> = dropped.get_cursor().read(dropped).cloned().collect();
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].reason, DropReason::VoiceLimit);
+
+        app.world_mut().write_message(play(2));
+        app.update();
+        let mut priorities: Vec<_> = app
+            .world_mut()
+            .query::<&AudioPriority>()
+            .iter(app.world())
+            .map(|priority| priority.0)
+            .collect();
+        priorities.sort_unstable();
+        assert_eq!(priorities, [1, 2]);
+    }
+
+    #[test]
+    fn config_changes_only_touch_changed_categories() {
+        let mut app = test_app((
+            TestPlugin::default().with_volume_ramp(Duration::ZERO),
+            TestAudioPlugin,
+        ));
+        app.insert_resource(TestConfig::FULL);
+        app.world_mut()
+            .write_message(PlayMusic::new(Handle::default(), TestMusic::Main));
+        app.world_mut().write_message(
+            PlaySfx::new(Handle::default(), TestSfx::UI).with_playback(PlaybackSettings::LOOP),
+        );
+        app.update();
+        app.update();
+        let world = app.world_mut();
+        let music = world
+            .query_filtered::<Entity, With<TestMusic>>()
+            .single(world)
+            .unwrap();
+        let sfx = world
+            .query_filtered::<Entity, With<TestSfx>>()
+            .single(world)
+            .unwrap();
+
+        // Scripted sink changes survive config changes of other categories
+        app.world_mut()
+            .get_mut::<AudioSink>(music)
+            .unwrap()
+            .set_volume(bevy::audio::Volume::Linear(0.3));
+        app.world_mut().resource_mut::<TestConfig>().sfx = 0.5;
+        app.update();
+        let volume = |entity| {
+            crate::systems::extract_linear_volume(
+                app.world().get::<AudioSink>(entity).unwrap().volume(),
+            )
+        };
+        assert!((volume(music) - 0.3).abs() < f32::EPSILON);
+        assert!((volume(sfx) - 0.125).abs() < 0.001);
+    }
+
+    #[test]
+    fn config_changes_keep_captured_volume_state() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.insert_resource(TestConfig::FULL);
+        app.insert_resource(VolumeRamp::INSTANT);
+        let entity = app
+            .world_mut()
+            .spawn(SfxBundle {
+                playback: PlaybackSettings::LOOP.with_volume(bevy::audio::Volume::Linear(0.5)),
+                ..SfxBundle::new(Handle::default(), TestSfx::UI)
+            })
+            .id();
+        app.update();
+        app.update();
+        let state = *app.world().get::<AudioVolumeState>(entity).unwrap();
+        assert!((state.base - 0.5).abs() < f32::EPSILON);
+
+        // Playback settings are not read again once the sound plays
+        app.world_mut()
+            .get_mut::<PlaybackSettings>(entity)
+            .unwrap()
+            .volume = bevy::audio::Volume::Linear(1.0);
+        app.world_mut().resource_mut::<TestConfig>().sfx = 0.5;
+        app.update();
+
+        let state = *app.world().get::<AudioVolumeState>(entity).unwrap();
+        let sink = app.world().get::<AudioSink>(entity).unwrap();
+        let expected = state.category * 0.5;
+        assert!((crate::systems::extract_linear_volume(sink.volume()) - expected).abs() < 0.0001);
+        assert!(state.category < 1.0);
+    }
+
+    #[test]
+    fn randomizer_component_rolls_when_sink_starts() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.insert_resource(TestConfig::FULL);
+        let entity = app
+            .world_mut()
+            .spawn((
+                SfxBundle::new(Handle::default(), TestSfx::UI),
+                PlaybackRandomizer::new()
+                    .with_volume(0.5, 0.5)
+                    .with_speed(1.5, 1.5),
+            ))
+            .id();
+        app.update();
+        app.update();
+
+        let sink = app.world().get::<AudioSink>(entity).unwrap();
+        assert!((sink.speed() - 1.5).abs() < f32::EPSILON);
+        assert!((crate::systems::extract_linear_volume(sink.volume()) - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn panned_sounds_sit_on_the_listener_ear_axis() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.world_mut().spawn((
+            SpatialListener::new(4.0),
+            GlobalTransform::from_xyz(10.0, 0.0, 0.0),
+        ));
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI).with_pan(1.0));
+        let centered = app
+            .world_mut()
+            .spawn(SfxBundle::new(Handle::default(), TestSfx::UI))
+            .id();
+        app.update();
+        app.update();
+
+        let (playback, transform) = app
+            .world_mut()
+            .query_filtered::<(&PlaybackSettings, &Transform), With<StereoPan>>()
+            .iter(app.world())
+            .find(|(playback, _)| playback.spatial)
+            .unwrap();
+        assert_eq!(transform.translation, Vec3::new(12.0, 0.0, 0.0));
+        assert_eq!(playback.spatial_scale.unwrap().0, Vec3::splat(0.5));
+        assert!(
+            !app.world()
+                .get::<PlaybackSettings>(centered)
+                .unwrap()
+                .spatial
+        );
+    }
+
+    #[test]
+    fn max_duration_stops_runaway_sounds() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            Duration::from_millis(40),
+        ));
+        app.world_mut().write_message(
+            PlaySfx::new(Handle::default(), TestSfx::UI)
+                .with_playback(PlaybackSettings::LOOP)
+                .with_max_duration(Duration::from_millis(100)),
+        );
+        app.update();
+        app.update();
+        let (entity, _) = app.world().resource::<AudioLog>().plays().next().unwrap();
+
+        // The sink exists from the first update, so 80ms have been played
+        app.update();
+        assert!(app.world().get_entity(entity).is_ok());
+
+        app.update();
+        assert!(app.world().get_entity(entity).is_err());
+    }
+
+    #[test]
+    fn fade_out_can_pause_or_keep_sounds() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        let spawn = |app: &mut App| {
+            app.world_mut()
+                .spawn(SfxBundle {
+                    playback: PlaybackSettings::LOOP,
+                    ..SfxBundle::new(Handle::default(), TestSfx::UI)
+                })
+                .id()
+        };
+        let paused = spawn(&mut app);
+        let kept = spawn(&mut app);
+        app.update();
+
+        let fade = FadeOut::new(Duration::from_millis(100));
+        app.world_mut()
+            .entity_mut(paused)
+            .insert(fade.clone().with_on_complete(TweenCompletion::Pause));
+        app.world_mut()
+            .entity_mut(kept)
+            .insert(fade.with_on_complete(TweenCompletion::Keep));
+        for entity in [paused, kept] {
+            app.world_mut()
+                .get_mut::<FadeOut>(entity)
+                .unwrap()
+                .tween
+                .timer
+                .set_elapsed(Duration::from_millis(100));
+        }
+        app.update();
+
+        for entity in [paused, kept] {
+            assert!(app.world().get::<FadeOut>(entity).is_none());
+            let sink = app.world().get::<AudioSink>(entity).unwrap();
+            assert!(crate::systems::extract_linear_volume(sink.volume()).abs() < f32::EPSILON);
+        }
+        assert!(app.world().get::<AudioSink>(paused).unwrap().is_paused());
+        assert!(!app.world().get::<AudioSink>(kept).unwrap().is_paused());
+    }
+
+    #[test]
+    fn fade_out_follows_config_volume_changes() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.insert_resource(TestConfig::FULL);
+        app.insert_resource(VolumeRamp::INSTANT);
+        let entity = app
+            .world_mut()
+            .spawn(SfxBundle {
+                playback: PlaybackSettings::LOOP,
+                ..SfxBundle::new(Handle::default(), TestSfx::UI)
+            })
+            .id();
+        app.update();
+        app.update();
+
+        let mut fade = FadeOut::new(Duration::from_secs(10));
+        fade.tween.timer.set_elapsed(Duration::from_secs(5));
+        app.world_mut().entity_mut(entity).insert(fade);
+        app.update();
+        let volume = |app: &App| {
+            crate::systems::extract_linear_volume(
+                app.world().get::<AudioSink>(entity).unwrap().volume(),
+            )
+        };
+        assert!((volume(&app) - 0.5).abs() < 0.01);
+
+        // Halving the slider mid-fade scales the fade instead of snapping back
+        app.world_mut().resource_mut::<TestConfig>().sfx = 0.5;
+        app.update();
+        let full = app
+            .world()
+            .get::<AudioVolumeState>(entity)
+            .unwrap()
+            .category;
+        assert!(full < 1.0);
+        assert!((volume(&app) - full * 0.5).abs() < 0.01);
+    }
 }
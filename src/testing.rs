@@ -0,0 +1,378 @@
+//! Headless audio for tests.
+//!
+//! [`TestAudioPlugin`] stands in for `bevy_audio`'s output: it gives every
+//! [`AudioPlayer`], including those of [`Tone`]s, an [`AudioSink`] that is
+//! never connected to an audio device, so the crate's volume, fade, and
+//! concurrency systems run as usual, and records what happens to each sound
+//! in the [`AudioLog`] resource.
+
+use bevy::{
+    audio::{Decodable, PlaybackMode},
+    prelude::*,
+};
+use rodio::{source::Zero, Sink};
+use std::time::Duration;
+
+use crate::components::StartPosition;
+use crate::systems::extract_linear_volume;
+use crate::tone::Tone;
+
+/// Plugin replacing audio output with a silent, inspectable sink for tests.
+///
+/// Use it with `MinimalPlugins` instead of Bevy's `AudioPlugin`. Sinks are
+/// created in `PostUpdate`, like `bevy_audio` does, so a sound's category
+/// volume is applied on the update after the one that spawned it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{AudioLog, PlaySfx, TestAudioPlugin};
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, game_audio_plugin(), TestAudioPlugin));
+///
+/// app.world_mut().write_message(PlaySfx::new(Handle::default(), GameSfx::UI));
+/// app.update();
+/// app.update();
+///
+/// let log = app.world().resource::<AudioLog>();
+/// let (entity, _) = log.plays().next().unwrap();
+/// assert_eq!(log.volume(entity), Some(expected_ui_volume));
+/// ```
+pub struct TestAudioPlugin;
+
+impl Plugin for TestAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AudioLog>();
+        app.add_message::<FinishSound>();
+        app.add_systems(PreUpdate, finish_test_sounds);
+        app.add_systems(
+            PostUpdate,
+            (attach_test_sinks::<AudioSource>, attach_test_sinks::<Tone>),
+        );
+        app.add_systems(Last, record_test_sinks);
+        app.add_observer(record_removed_test_sinks);
+    }
+}
+
+/// A call made on a sound, as recorded by [`TestAudioPlugin`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AudioCall {
+    /// A sound started playing.
+    Play {
+        /// The sound entity.
+        entity: Entity,
+        /// The played audio source, an [`AudioSource`] or a [`Tone`].
+        handle: UntypedHandle,
+        /// Linear volume from the playback settings.
+        volume: f32,
+        /// Playback speed from the playback settings.
+        speed: f32,
+        /// Whether the sound started paused.
+        paused: bool,
+        /// Position the sound started from, taken from its [`StartPosition`].
+        start: Duration,
+    },
+    /// The volume of a sound changed.
+    SetVolume {
+        /// The sound entity.
+        entity: Entity,
+        /// New linear volume.
+        volume: f32,
+    },
+    /// The playback speed of a sound changed.
+    SetSpeed {
+        /// The sound entity.
+        entity: Entity,
+        /// New playback speed.
+        speed: f32,
+    },
+    /// A sound was paused.
+    Pause {
+        /// The sound entity.
+        entity: Entity,
+    },
+    /// A paused sound was resumed.
+    Resume {
+        /// The sound entity.
+        entity: Entity,
+    },
+    /// A sound reached its end through a [`FinishSound`] message.
+    ///
+    /// Like `bevy_audio`, sounds played with [`PlaybackMode::Despawn`] are
+    /// then despawned, and those with [`PlaybackMode::Remove`] lose their
+    /// player and sink.
+    Finish {
+        /// The sound entity.
+        entity: Entity,
+    },
+    /// A sound was stopped by removing its sink or despawning it.
+    Stop {
+        /// The sound entity.
+        entity: Entity,
+    },
+}
+
+impl AudioCall {
+    /// Returns the sound entity the call was made on.
+    #[must_use]
+    pub fn entity(&self) -> Entity {
+        match *self {
+            Self::Play { entity, .. }
+            | Self::SetVolume { entity, .. }
+            | Self::SetSpeed { entity, .. }
+            | Self::Pause { entity }
+            | Self::Resume { entity }
+            | Self::Finish { entity }
+            | Self::Stop { entity } => entity,
+        }
+    }
+}
+
+/// Resource recording every [`AudioCall`] in order.
+///
+/// Changes are recorded once per frame in `Last`, so a volume set twice in
+/// one frame is recorded once, with its final value.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct AudioLog {
+    calls: Vec<AudioCall>,
+}
+
+impl AudioLog {
+    /// Returns every recorded call, oldest first.
+    #[must_use]
+    pub fn calls(&self) -> &[AudioCall] {
+        &self.calls
+    }
+
+    /// Returns the entity and source of every sound that started playing.
+    pub fn plays(&self) -> impl Iterator<Item = (Entity, &UntypedHandle)> {
+        self.calls.iter().filter_map(|call| match call {
+            AudioCall::Play { entity, handle, .. } => Some((*entity, handle)),
+            _ => None,
+        })
+    }
+
+    /// Returns the calls made on one sound, oldest first.
+    pub fn calls_for(&self, entity: Entity) -> impl Iterator<Item = &AudioCall> {
+        self.calls
+            .iter()
+            .filter(move |call| call.entity() == entity)
+    }
+
+    /// Returns the last recorded volume of a sound.
+    #[must_use]
+    pub fn volume(&self, entity: Entity) -> Option<f32> {
+        self.calls_for(entity)
+            .filter_map(|call| match *call {
+                AudioCall::Play { volume, .. } | AudioCall::SetVolume { volume, .. } => {
+                    Some(volume)
+                }
+                _ => None,
+            })
+            .last()
+    }
+
+    /// Returns `true` if the sound was stopped.
+    #[must_use]
+    pub fn is_stopped(&self, entity: Entity) -> bool {
+        self.calls_for(entity)
+            .any(|call| matches!(call, AudioCall::Stop { .. }))
+    }
+
+    /// Forgets all recorded calls.
+    pub fn clear(&mut self) {
+        self.calls.clear();
+    }
+}
+
+/// Message ending a sound played by [`TestAudioPlugin`], as if it had
+/// reached the end of its source.
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FinishSound {
+    /// The sound entity.
+    pub entity: Entity,
+}
+
+/// Sink state as of the last recorded call.
+#[derive(Component, Debug, Clone, Copy)]
+struct TestSinkState {
+    volume: f32,
+    speed: f32,
+    paused: bool,
+}
+
+impl TestSinkState {
+    fn of(sink: &AudioSink) -> Self {
+        Self {
+            volume: extract_linear_volume(sink.volume()),
+            speed: sink.speed(),
+            paused: sink.is_paused(),
+        }
+    }
+}
+
+/// Creates a sink that is never played, holding a silent source so it only
+/// reports being empty once finished.
+fn test_sink(state: TestSinkState, finished: bool) -> AudioSink {
+    let (sink, _output) = Sink::new_idle();
+    if !finished {
+        sink.append(Zero::<f32>::new(1, 44_100));
+    }
+    sink.set_volume(state.volume);
+    sink.set_speed(state.speed);
+    if state.paused {
+        sink.pause();
+    }
+    AudioSink::new(sink)
+}
+
+fn attach_test_sinks<P: Asset + Decodable>(
+    mut commands: Commands,
+    mut log: ResMut<AudioLog>,
+    query: Query<
+        (
+            Entity,
+            &AudioPlayer<P>,
+            Option<&PlaybackSettings>,
+            Option<&StartPosition>,
+        ),
+        Without<AudioSink>,
+    >,
+) {
+    for (entity, player, playback, start) in &query {
+        let playback = playback.copied().unwrap_or_default();
+        let state = TestSinkState {
+            volume: extract_linear_volume(playback.volume),
+            speed: playback.speed,
+            paused: playback.paused,
+        };
+        // Seeking waits for an output to pull the sink, which never happens here
+        commands
+            .entity(entity)
+            .insert((test_sink(state, false), state))
+            .remove::<StartPosition>();
+        log.calls.push(AudioCall::Play {
+            entity,
+            handle: player.0.clone().untyped(),
+            volume: state.volume,
+            speed: state.speed,
+            paused: state.paused,
+            start: start.map_or(Duration::ZERO, |start| start.0),
+        });
+    }
+}
+
+fn record_test_sinks(
+    mut log: ResMut<AudioLog>,
+    mut query: Query<(Entity, &AudioSink, &mut TestSinkState)>,
+) {
+    for (entity, sink, mut state) in &mut query {
+        let current = TestSinkState::of(sink);
+        if (current.volume - state.volume).abs() > f32::EPSILON {
+            log.calls.push(AudioCall::SetVolume {
+                entity,
+                volume: current.volume,
+            });
+        }
+        if (current.speed - state.speed).abs() > f32::EPSILON {
+            log.calls.push(AudioCall::SetSpeed {
+                entity,
+                speed: current.speed,
+            });
+        }
+        if current.paused != state.paused {
+            log.calls.push(if current.paused {
+                AudioCall::Pause { entity }
+            } else {
+                AudioCall::Resume { entity }
+            });
+        }
+        *state = current;
+    }
+}
+
+fn record_removed_test_sinks(
+    remove: On<Remove, AudioSink>,
+    mut log: ResMut<AudioLog>,
+    query: Query<(), With<TestSinkState>>,
+) {
+    let finished = matches!(
+        log.calls_for(remove.entity).last(),
+        Some(AudioCall::Finish { .. })
+    );
+    if query.contains(remove.entity) && !finished {
+        log.calls.push(AudioCall::Stop {
+            entity: remove.entity,
+        });
+    }
+}
+
+fn finish_test_sounds(
+    mut commands: Commands,
+    mut log: ResMut<AudioLog>,
+    mut messages: MessageReader<FinishSound>,
+    query: Query<(&AudioSink, Option<&PlaybackSettings>), With<TestSinkState>>,
+) {
+    for message in messages.read() {
+        let Ok((sink, playback)) = query.get(message.entity) else {
+            continue;
+        };
+        log.calls.push(AudioCall::Finish {
+            entity: message.entity,
+        });
+        // Mirror `bevy_audio`'s cleanup of finished sounds
+        let mut entity = commands.entity(message.entity);
+        entity.insert(test_sink(TestSinkState::of(sink), true));
+        match playback.map_or(PlaybackMode::Once, |playback| playback.mode) {
+            PlaybackMode::Despawn => entity.despawn(),
+            PlaybackMode::Remove => {
+                entity.remove::<(
+                    AudioPlayer,
+                    AudioPlayer<Tone>,
+                    AudioSink,
+                    PlaybackSettings,
+                    TestSinkState,
+                )>();
+            }
+            PlaybackMode::Once | PlaybackMode::Loop => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestConfig, TestPlugin, TestSfx};
+    use crate::{AudioConfigTrait, PlaySfx};
+
+    #[test]
+    fn test_audio_plugin_records_sfx_volume_and_stop() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.insert_resource(TestConfig {
+            sfx: 0.5,
+            ..TestConfig::FULL
+        });
+
+        app.world_mut()
+            .write_message(PlaySfx::new(Handle::default(), TestSfx::UI));
+        app.update();
+        app.update();
+
+        let config = app.world().resource::<TestConfig>();
+        let expected = config.effective_volume() * config.to_linear_gain(0.5);
+        let log = app.world().resource::<AudioLog>();
+        let (entity, _) = log.plays().next().unwrap();
+        assert!((log.volume(entity).unwrap() - expected).abs() < 1e-5);
+        assert!(!log.is_stopped(entity));
+
+        app.world_mut().write_message(FinishSound { entity });
+        app.update();
+        let log = app.world().resource::<AudioLog>();
+        assert!(matches!(
+            log.calls_for(entity).last(),
+            Some(AudioCall::Finish { .. })
+        ));
+        assert!(app.world().get_entity(entity).is_err());
+    }
+}
@@ -0,0 +1,573 @@
+//! Procedurally generated tones and noise.
+//!
+//! A [`Tone`] is an audio source computed on the fly, such as a sine beep
+//! or a burst of pink noise, so UI feedback prototypes, alarms, and tests
+//! can make sound without asset files. Play one with a [`PlayTone`] message
+//! once the [`ToneAudioPlugin`] is added; it is mixed, limited, and echoed
+//! like any other sound effect of its category.
+
+use bevy::{
+    audio::{AddAudioSource, AudioPlugin, Decodable},
+    prelude::*,
+};
+use std::f64::consts::TAU;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use crate::backend::{AudioBackend, BevyAudioBackend};
+use crate::bundles::DEFAULT_MAX_CONCURRENT;
+use crate::components::{
+    ConcurrencyPolicy, FadeIn, MaxConcurrent, PlaybackLabel, PlaybackRandomizer,
+};
+use crate::delay::{release_delayed_messages, DelayedMessages};
+use crate::effects::{Echo, EchoTap, EffectSend};
+use crate::request::{AudioRequest, ConcurrencyRequest};
+use crate::rng::AudioRng;
+use crate::systems::{
+    echo_of, echo_tap_playback, enforce_global_voice_limit, enforce_sfx_concurrency,
+};
+use crate::traits::{AudioConfigTrait, SfxCategory};
+use crate::MsgAudioSet;
+
+/// Sample rate of generated tones.
+pub const TONE_SAMPLE_RATE: u32 = 44_100;
+
+/// Number of samples faded in and out at the edges of a tone, about 2 ms,
+/// so it starts and stops without a click.
+const DECLICK_SAMPLES: u64 = 88;
+
+/// Shape of the signal generated by a [`Tone`].
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub enum Waveform {
+    /// A pure tone at the frequency in Hz.
+    Sine(f32),
+    /// A hollow, buzzy tone at the frequency in Hz.
+    Square(f32),
+    /// Noise with equal energy at every frequency, like static.
+    WhiteNoise,
+    /// Noise with equal energy per octave, softer than white noise, like
+    /// rain or a waterfall.
+    PinkNoise,
+}
+
+/// Audio source generating a waveform for a fixed duration.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::Tone;
+///
+/// let beep = Tone::sine(880.0, Duration::from_millis(120)).with_amplitude(0.5);
+/// let hiss = Tone::pink_noise(Duration::from_secs(2));
+/// ```
+#[derive(Asset, TypePath, Debug, Clone, Copy, PartialEq)]
+pub struct Tone {
+    /// The generated signal.
+    pub waveform: Waveform,
+    /// How long the tone plays.
+    pub duration: Duration,
+    /// Peak amplitude of the signal, from `0.0` to `1.0`.
+    pub amplitude: f32,
+}
+
+impl Tone {
+    /// Creates a tone of the waveform at full amplitude.
+    #[must_use]
+    pub fn new(waveform: Waveform, duration: Duration) -> Self {
+        Self {
+            waveform,
+            duration,
+            amplitude: 1.0,
+        }
+    }
+
+    /// Creates a sine tone at `frequency` Hz.
+    #[must_use]
+    pub fn sine(frequency: f32, duration: Duration) -> Self {
+        Self::new(Waveform::Sine(frequency), duration)
+    }
+
+    /// Creates a square tone at `frequency` Hz.
+    #[must_use]
+    pub fn square(frequency: f32, duration: Duration) -> Self {
+        Self::new(Waveform::Square(frequency), duration)
+    }
+
+    /// Creates white noise.
+    #[must_use]
+    pub fn white_noise(duration: Duration) -> Self {
+        Self::new(Waveform::WhiteNoise, duration)
+    }
+
+    /// Creates pink noise.
+    #[must_use]
+    pub fn pink_noise(duration: Duration) -> Self {
+        Self::new(Waveform::PinkNoise, duration)
+    }
+
+    /// Sets the peak amplitude, clamped to `0.0..=1.0`.
+    #[must_use]
+    pub fn with_amplitude(mut self, amplitude: f32) -> Self {
+        self.amplitude = amplitude.clamp(0.0, 1.0);
+        self
+    }
+}
+
+impl Decodable for Tone {
+    type DecoderItem = f32;
+    type Decoder = ToneDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        ToneDecoder::new(*self)
+    }
+}
+
+/// Mono sample stream of a [`Tone`].
+#[derive(Debug, Clone)]
+pub struct ToneDecoder {
+    tone: Tone,
+    sample: u64,
+    samples: u64,
+    seed: u32,
+    pink: [f32; 7],
+}
+
+impl ToneDecoder {
+    fn new(tone: Tone) -> Self {
+        Self {
+            tone,
+            sample: 0,
+            samples: (tone.duration.as_secs_f64() * f64::from(TONE_SAMPLE_RATE)).round() as u64,
+            seed: 0x9E37_79B9,
+            pink: [0.0; 7],
+        }
+    }
+
+    /// Returns white noise from `-1.0` to `1.0`, from a xorshift generator.
+    fn white(&mut self) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        self.seed as f32 / u32::MAX as f32 * 2.0 - 1.0
+    }
+
+    /// Filters white noise to pink noise, after Paul Kellet's method.
+    fn pink(&mut self) -> f32 {
+        let white = self.white();
+        let b = &mut self.pink;
+        b[0] = 0.99886 * b[0] + white * 0.055_517_9;
+        b[1] = 0.99332 * b[1] + white * 0.075_075_9;
+        b[2] = 0.969 * b[2] + white * 0.153_852;
+        b[3] = 0.8665 * b[3] + white * 0.310_485_6;
+        b[4] = 0.55 * b[4] + white * 0.532_952_2;
+        b[5] = -0.7616 * b[5] - white * 0.016_898;
+        let pink = b[0] + b[1] + b[2] + b[3] + b[4] + b[5] + b[6] + white * 0.5362;
+        b[6] = white * 0.115_926;
+        (pink * 0.11).clamp(-1.0, 1.0)
+    }
+}
+
+impl Iterator for ToneDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.sample >= self.samples {
+            return None;
+        }
+        let time = self.sample as f64 / f64::from(TONE_SAMPLE_RATE);
+        let value = match self.tone.waveform {
+            Waveform::Sine(frequency) => (TAU * f64::from(frequency) * time).sin() as f32,
+            Waveform::Square(frequency) => {
+                if (f64::from(frequency) * time).fract() < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Waveform::WhiteNoise => self.white(),
+            Waveform::PinkNoise => self.pink(),
+        };
+        let edge = self.sample.min(self.samples - 1 - self.sample);
+        let envelope = (edge as f32 / DECLICK_SAMPLES as f32).min(1.0);
+        self.sample += 1;
+        Some(value * self.tone.amplitude * envelope)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.samples - self.sample) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl rodio::Source for ToneDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        Some((self.samples - self.sample) as usize)
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        TONE_SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        Some(self.tone.duration)
+    }
+}
+
+/// Message to request playing a [`Tone`] as a sound effect of category `S`.
+///
+/// The tone goes through the same volume, concurrency, and echo systems as
+/// sounds played with [`PlaySfx`](crate::PlaySfx), and reports an
+/// [`AudioStarted<Tone>`](crate::AudioStarted) once it plays.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{PlayTone, Tone};
+///
+/// fn beep_on_error(mut tones: MessageWriter<PlayTone<GameSfx>>) {
+///     tones.write(PlayTone::new(
+///         Tone::square(440.0, Duration::from_millis(80)).with_amplitude(0.3),
+///         GameSfx::UI,
+///     ));
+/// }
+/// ```
+#[derive(Message, Clone)]
+pub struct PlayTone<S: SfxCategory> {
+    /// The tone to generate.
+    pub tone: Tone,
+    /// The sound effect category for volume control.
+    pub category: S,
+    /// Custom playback settings (defaults to DESPAWN).
+    pub playback: PlaybackSettings,
+    /// Maximum concurrent instances of this tone.
+    pub max_concurrent: u32,
+    /// Which instances are stopped when `max_concurrent` is exceeded.
+    pub concurrency_policy: ConcurrencyPolicy,
+    /// Optional label identifying the spawned instance.
+    pub label: Option<PlaybackLabel>,
+    /// Randomization rolled from the [`AudioRng`] when the tone is spawned.
+    pub randomizer: Option<PlaybackRandomizer>,
+    /// Optional fade-in from silence to the category volume.
+    pub fade_in: Option<FadeIn>,
+    /// Optional delay before the tone is played.
+    pub delay: Option<Duration>,
+    /// Delayed copy played by an [`Echo`](crate::Echo), see [`EchoTap`].
+    pub echo_tap: bool,
+}
+
+impl<S: SfxCategory> PlayTone<S> {
+    /// Creates a new tone message with default settings.
+    #[must_use]
+    pub fn new(tone: Tone, category: S) -> Self {
+        Self {
+            tone,
+            category,
+            playback: PlaybackSettings::DESPAWN,
+            max_concurrent: DEFAULT_MAX_CONCURRENT,
+            concurrency_policy: ConcurrencyPolicy::default(),
+            label: None,
+            randomizer: None,
+            fade_in: None,
+            delay: None,
+            echo_tap: false,
+        }
+    }
+
+    /// Labels the spawned instance.
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<PlaybackLabel>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+impl<S: SfxCategory> AudioRequest for PlayTone<S> {
+    fn playback_mut(&mut self) -> &mut PlaybackSettings {
+        &mut self.playback
+    }
+
+    fn randomizer_mut(&mut self) -> Option<&mut PlaybackRandomizer> {
+        self.randomizer.as_mut()
+    }
+
+    fn with_randomizer(mut self, randomizer: PlaybackRandomizer) -> Self {
+        self.randomizer = Some(randomizer);
+        self
+    }
+
+    fn fade_in_mut(&mut self) -> &mut Option<FadeIn> {
+        &mut self.fade_in
+    }
+
+    fn delay_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.delay
+    }
+}
+
+impl<S: SfxCategory> ConcurrencyRequest for PlayTone<S> {
+    fn with_max_concurrent(mut self, max: u32) -> Self {
+        self.max_concurrent = max;
+        self
+    }
+
+    fn with_concurrency_policy(mut self, policy: ConcurrencyPolicy) -> Self {
+        self.concurrency_policy = policy;
+        self
+    }
+}
+
+/// Optional plugin playing [`PlayTone`] messages of category type `S`.
+///
+/// Registers [`Tone`] as an audio source played through Bevy's `AudioPlugin`,
+/// or through the [`TestAudioPlugin`](crate::TestAudioPlugin) in tests, so do
+/// not register it yourself. Add it once per sound effect category type that
+/// plays tones, with the same [`AudioBackend`] as the
+/// [`MsgAudioPlugin`](crate::MsgAudioPlugin).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::ToneAudioPlugin;
+///
+/// app.add_plugins(ToneAudioPlugin::<GameSfx>::default());
+/// ```
+pub struct ToneAudioPlugin<S: SfxCategory, B: AudioBackend = BevyAudioBackend> {
+    _phantom: PhantomData<(S, B)>,
+}
+
+impl<S: SfxCategory, B: AudioBackend> Default for ToneAudioPlugin<S, B> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<S, B> Plugin for ToneAudioPlugin<S, B>
+where
+    S: SfxCategory,
+    S::Config: AudioConfigTrait,
+    B: AudioBackend,
+{
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<ToneSourcePlugin>() {
+            app.add_plugins(ToneSourcePlugin);
+        }
+        app.add_message::<PlayTone<S>>();
+        app.init_resource::<DelayedMessages<PlayTone<S>>>();
+        app.add_systems(
+            Update,
+            (
+                release_delayed_messages::<PlayTone<S>>.before(MsgAudioSet::EventHandling),
+                handle_play_tone_events::<S, B>.in_set(MsgAudioSet::EventHandling),
+                enforce_sfx_concurrency::<S, B, Tone>
+                    .in_set(MsgAudioSet::Concurrency)
+                    .before(enforce_global_voice_limit::<B>),
+                play_tone_echoes::<S>.in_set(MsgAudioSet::VolumeApplication),
+            ),
+        );
+    }
+}
+
+/// Registers [`Tone`] as an audio source once, however many
+/// [`ToneAudioPlugin`]s are added.
+struct ToneSourcePlugin;
+
+impl Plugin for ToneSourcePlugin {
+    fn build(&self, _app: &mut App) {}
+
+    fn finish(&self, app: &mut App) {
+        if app.is_plugin_added::<AudioPlugin>() {
+            app.add_audio_source::<Tone>();
+        } else if !app.world().contains_resource::<Assets<Tone>>() {
+            // Without an audio output, e.g. under the `TestAudioPlugin`,
+            // tones only need their assets
+            app.init_asset::<Tone>();
+        }
+    }
+}
+
+/// System that handles [`PlayTone`] messages by spawning tone entities.
+///
+/// Equal tones share one asset, so their [`MaxConcurrent`] limit applies
+/// across messages.
+pub fn handle_play_tone_events<S: SfxCategory, B: AudioBackend>(
+    mut commands: Commands,
+    mut messages: MessageReader<PlayTone<S>>,
+    mut delayed: ResMut<DelayedMessages<PlayTone<S>>>,
+    mut tones: ResMut<Assets<Tone>>,
+    mut rng: ResMut<AudioRng>,
+) {
+    for event in messages.read() {
+        if let Some(delay) = event.delay {
+            delayed.push(
+                delay,
+                PlayTone {
+                    delay: None,
+                    ..event.clone()
+                },
+            );
+            continue;
+        }
+        let mut playback = event.playback;
+        if let Some(randomizer) = &event.randomizer {
+            randomizer.apply_with(&mut playback, &mut *rng);
+        }
+        let existing = tones
+            .iter()
+            .find_map(|(id, tone)| (*tone == event.tone).then_some(id));
+        let handle = existing
+            .and_then(|id| tones.get_strong_handle(id))
+            .unwrap_or_else(|| tones.add(event.tone));
+        let mut entity = commands.spawn((
+            event.category,
+            MaxConcurrent::new(&handle, event.max_concurrent).with_policy(event.concurrency_policy),
+        ));
+        B::play(&mut entity, handle, playback);
+        if let Some(label) = &event.label {
+            entity.insert(label.clone());
+        }
+        if let Some(fade_in) = &event.fade_in {
+            entity.insert(fade_in.clone());
+        }
+        if event.echo_tap {
+            entity.insert(EchoTap);
+        }
+    }
+}
+
+/// Plays the delayed taps of tones that started since the last run, like
+/// [`play_sfx_echoes`](crate::audio_systems::play_sfx_echoes) does for sound effects.
+pub fn play_tone_echoes<S: SfxCategory>(
+    query: Query<
+        (
+            &S,
+            &AudioPlayer<Tone>,
+            &PlaybackSettings,
+            Option<&Echo>,
+            Option<&EffectSend>,
+        ),
+        (Added<AudioPlayer<Tone>>, Without<EchoTap>),
+    >,
+    tones: Res<Assets<Tone>>,
+    mut messages: MessageWriter<PlayTone<S>>,
+) {
+    for (category, player, playback, echo, send) in &query {
+        let (Some(echo), Some(tone)) = (echo_of(playback, echo, send), tones.get(&player.0)) else {
+            continue;
+        };
+        for tap in 1..=echo.taps {
+            let mut request = PlayTone::new(*tone, *category);
+            request.playback = echo_tap_playback(echo, tap, playback);
+            request.echo_tap = true;
+            messages.write(request.after(echo.tap_delay(tap)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestConfig, TestPlugin, TestSfx};
+    use crate::{AudioConfigTrait, AudioDropped, AudioLog, AudioStarted, TestAudioPlugin};
+    use rodio::Source;
+
+    #[test]
+    fn tone_generates_its_duration_of_samples() {
+        let decoder = Tone::sine(440.0, Duration::from_millis(100)).decoder();
+        assert_eq!(decoder.channels(), 1);
+        assert_eq!(decoder.total_duration(), Some(Duration::from_millis(100)));
+        assert_eq!(decoder.count(), 4_410);
+    }
+
+    #[test]
+    fn waveforms_stay_within_amplitude() {
+        for waveform in [
+            Waveform::Sine(440.0),
+            Waveform::Square(440.0),
+            Waveform::WhiteNoise,
+            Waveform::PinkNoise,
+        ] {
+            let samples: Vec<f32> = Tone::new(waveform, Duration::from_millis(50))
+                .with_amplitude(0.5)
+                .decoder()
+                .collect();
+            assert!(samples.iter().all(|sample| sample.abs() <= 0.5));
+            assert!(samples.iter().any(|sample| sample.abs() > 0.1));
+            assert_eq!(samples[0], 0.0);
+        }
+    }
+
+    #[test]
+    fn play_tone_plays_at_category_volume() {
+        let mut app = test_app((
+            AssetPlugin::default(),
+            TestPlugin::default(),
+            ToneAudioPlugin::<TestSfx>::default(),
+            TestAudioPlugin,
+        ));
+        app.finish();
+        app.insert_resource(TestConfig {
+            sfx: 0.5,
+            ..TestConfig::FULL
+        });
+
+        app.world_mut().write_message(
+            PlayTone::new(Tone::sine(440.0, Duration::from_millis(100)), TestSfx::UI)
+                .volume(0.8)
+                .with_label("beep"),
+        );
+        app.update();
+        app.update();
+
+        let config = app.world().resource::<TestConfig>();
+        let expected = config.effective_volume() * config.to_linear_gain(0.5) * 0.8;
+        let log = app.world().resource::<AudioLog>();
+        let (entity, handle) = log.plays().next().unwrap();
+        assert!((log.volume(entity).unwrap() - expected).abs() < 1e-5);
+        let tone = app
+            .world()
+            .resource::<Assets<Tone>>()
+            .get(handle.id().typed::<Tone>())
+            .unwrap();
+        assert_eq!(tone.waveform, Waveform::Sine(440.0));
+
+        let started = app.world().resource::<Messages<AudioStarted<Tone>>>();
+        let started: Vec<_> = started.get_cursor().read(started).cloned().collect();
+        assert_eq!(started.len(), 1);
+        assert_eq!(started[0].entity, entity);
+    }
+
+    #[test]
+    fn equal_tones_share_a_concurrency_limit() {
+        let mut app = test_app((
+            AssetPlugin::default(),
+            TestPlugin::default(),
+            TestAudioPlugin,
+        ));
+        // Assets registered beforehand must not keep tones from playing
+        app.init_asset::<Tone>();
+        app.add_plugins(ToneAudioPlugin::<TestSfx>::default());
+        app.finish();
+
+        let beep = Tone::square(880.0, Duration::from_millis(50));
+        for _ in 0..3 {
+            app.world_mut().write_message(
+                PlayTone::new(beep, TestSfx::UI)
+                    .with_max_concurrent(2)
+                    .with_concurrency_policy(ConcurrencyPolicy::RejectNew),
+            );
+        }
+        app.update();
+        app.update();
+
+        assert_eq!(app.world().resource::<AudioLog>().plays().count(), 2);
+        assert_eq!(app.world().resource::<Assets<Tone>>().len(), 1);
+        let dropped = app.world().resource::<Messages<AudioDropped<Tone>>>();
+        assert_eq!(dropped.get_cursor().read(dropped).count(), 1);
+    }
+}
@@ -0,0 +1,348 @@
+//! Metadata about music tracks.
+//!
+//! The [`TrackRegistry`] associates audio sources with a [`TrackInfo`]: a
+//! title and artist for display, a loudness offset to level the track, its
+//! tempo, and how deep it is ducked. The loudness offset is folded into the
+//! final volume of every sound playing the source, so asset packs mastered at
+//! different levels play at a consistent perceived loudness. Music started
+//! with [`PlayMusic`](crate::PlayMusic) picks up the tempo for the
+//! [`MusicClock`](crate::MusicClock), stingers duck each track by its own
+//! amount, and [`NowPlaying`](crate::NowPlaying) lists the info of each
+//! playing track.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use crate::clock::MusicTempo;
+
+/// Default reference loudness in LUFS for
+/// [`TrackInfo::with_measured_loudness`].
+pub const DEFAULT_REFERENCE_LOUDNESS: f32 = -16.0;
+
+/// Metadata about a music track.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{MusicTempo, TrackInfo};
+///
+/// let info = TrackInfo::new("Into the Depths")
+///     .with_artist("The Composers")
+///     .with_loudness_offset(-3.0)
+///     .with_tempo(MusicTempo::new(96.0, 4));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TrackInfo {
+    /// Display title of the track.
+    pub title: String,
+    /// Display name of the artist.
+    pub artist: Option<String>,
+    /// Loudness correction in decibels, e.g. `-3.0` for a track mastered
+    /// 3 dB louder than the rest of the soundtrack.
+    pub loudness_offset: f32,
+    /// Tempo and meter of the track.
+    pub tempo: Option<MusicTempo>,
+    /// Attenuation in decibels while a [`Stinger`](crate::Stinger) plays,
+    /// replacing the stinger's duck volume, e.g. a deeper duck for a dense
+    /// track.
+    pub duck_db: Option<f32>,
+}
+
+impl TrackInfo {
+    /// Creates track info with a title.
+    #[must_use]
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..default()
+        }
+    }
+
+    /// Sets the artist.
+    #[must_use]
+    pub fn with_artist(mut self, artist: impl Into<String>) -> Self {
+        self.artist = Some(artist.into());
+        self
+    }
+
+    /// Sets the loudness correction in decibels.
+    #[must_use]
+    pub fn with_loudness_offset(mut self, decibels: f32) -> Self {
+        self.loudness_offset = decibels;
+        self
+    }
+
+    /// Sets the loudness correction from the measured integrated loudness of
+    /// the track in LUFS, leveling it to `reference` LUFS, e.g.
+    /// [`DEFAULT_REFERENCE_LOUDNESS`].
+    #[must_use]
+    pub fn with_measured_loudness(self, lufs: f32, reference: f32) -> Self {
+        self.with_loudness_offset(reference - lufs)
+    }
+
+    /// Sets the tempo.
+    #[must_use]
+    pub fn with_tempo(mut self, tempo: MusicTempo) -> Self {
+        self.tempo = Some(tempo);
+        self
+    }
+
+    /// Sets how far the track is ducked while a stinger plays, in decibels.
+    #[must_use]
+    pub fn with_duck_db(mut self, decibels: f32) -> Self {
+        self.duck_db = Some(decibels.max(0.0));
+        self
+    }
+
+    /// Returns the linear gain of the track while ducked for a stinger, if
+    /// it sets its own.
+    #[must_use]
+    pub fn duck_gain(&self) -> Option<f32> {
+        self.duck_db.map(|db| 10_f32.powf(-db / 20.0))
+    }
+
+    /// Returns the loudness correction as a linear gain.
+    #[must_use]
+    pub fn gain(&self) -> f32 {
+        10_f32.powf(self.loudness_offset / 20.0)
+    }
+}
+
+/// Resource associating audio sources with their [`TrackInfo`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{TrackInfo, TrackRegistry};
+///
+/// fn register_tracks(mut registry: ResMut<TrackRegistry>, assets: Res<AssetServer>) {
+///     registry.insert(&assets.load("music/depths.ogg"), TrackInfo::new("Into the Depths"));
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct TrackRegistry {
+    tracks: HashMap<AssetId<AudioSource>, TrackInfo>,
+    handles: Vec<Handle<AudioSource>>,
+}
+
+impl TrackRegistry {
+    /// Stores the info of a track, replacing any previous info.
+    pub fn insert(&mut self, source: impl Into<AssetId<AudioSource>>, info: TrackInfo) {
+        self.tracks.insert(source.into(), info);
+    }
+
+    /// Returns the info of a track.
+    #[must_use]
+    pub fn get(&self, source: impl Into<AssetId<AudioSource>>) -> Option<&TrackInfo> {
+        self.tracks.get(&source.into())
+    }
+
+    /// Sets the loudness correction of a source in decibels, registering it
+    /// without a title if it has no info yet.
+    ///
+    /// Suits sound effects, which need a loudness tag but no display info.
+    pub fn set_loudness_offset(&mut self, source: impl Into<AssetId<AudioSource>>, decibels: f32) {
+        self.tracks
+            .entry(source.into())
+            .or_default()
+            .loudness_offset = decibels;
+    }
+
+    /// Returns the linear loudness correction of a source, `1.0` if it has
+    /// no info.
+    #[must_use]
+    pub fn loudness_gain(&self, source: impl Into<AssetId<AudioSource>>) -> f32 {
+        self.get(source).map_or(1.0, TrackInfo::gain)
+    }
+
+    /// Removes the info of a track.
+    pub fn remove(&mut self, source: impl Into<AssetId<AudioSource>>) -> Option<TrackInfo> {
+        self.tracks.remove(&source.into())
+    }
+
+    /// Returns the number of registered tracks.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tracks.len()
+    }
+
+    /// Returns `true` if no tracks are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tracks.is_empty()
+    }
+
+    /// Loads every track of a manifest and stores its info.
+    ///
+    /// The loaded handles are kept by the registry, so the tracks stay
+    /// resident and keep their asset ids.
+    pub fn load_manifest(&mut self, manifest: &TrackManifest, assets: &AssetServer) {
+        for (path, info) in &manifest.tracks {
+            let handle: Handle<AudioSource> = assets.load(path.clone());
+            self.insert(&handle, info.clone());
+            self.handles.push(handle);
+        }
+    }
+}
+
+/// List of track asset paths and their [`TrackInfo`], loaded into a
+/// [`TrackRegistry`] with [`TrackRegistry::load_manifest`].
+///
+/// With the `serde` feature the manifest can be read from a file, e.g. RON:
+///
+/// ```ron
+/// (tracks: [
+///     ("music/depths.ogg", (title: "Into the Depths", loudness_offset: -3.0)),
+/// ])
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrackManifest {
+    /// Asset paths and the info of their tracks.
+    pub tracks: Vec<(String, TrackInfo)>,
+}
+
+impl TrackManifest {
+    /// Adds a track to the manifest.
+    #[must_use]
+    pub fn with_track(mut self, path: impl Into<String>, info: TrackInfo) -> Self {
+        self.tracks.push((path.into(), info));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestConfig, TestMusic, TestPlugin};
+    use crate::{
+        AudioConfigTrait, AudioLog, AudioVolumeState, FinishSound, NowPlaying, PlayMusic,
+        PlayStinger, TestAudioPlugin,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn loudness_offset_converts_to_gain() {
+        assert!((TrackInfo::new("Quiet").gain() - 1.0).abs() < f32::EPSILON);
+        let info = TrackInfo::new("Loud").with_loudness_offset(-20.0);
+        assert!((info.gain() - 0.1).abs() < 0.0001);
+    }
+
+    #[test]
+    fn loudness_offsets_register_without_info() {
+        let handle = Handle::<AudioSource>::default();
+        let mut registry = TrackRegistry::default();
+        assert!((registry.loudness_gain(&handle) - 1.0).abs() < f32::EPSILON);
+
+        registry.set_loudness_offset(&handle, -20.0);
+        assert!((registry.loudness_gain(&handle) - 0.1).abs() < 0.0001);
+        assert_eq!(registry.get(&handle).unwrap().title, "");
+
+        let info = TrackInfo::new("Loud").with_measured_loudness(-10.0, DEFAULT_REFERENCE_LOUDNESS);
+        assert!((info.loudness_offset + 6.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn duck_db_converts_to_gain() {
+        assert_eq!(TrackInfo::new("Sparse").duck_gain(), None);
+        let info = TrackInfo::new("Dense").with_duck_db(20.0);
+        assert!((info.duck_gain().unwrap() - 0.1).abs() < 0.0001);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn manifest_parses_from_ron() {
+        let manifest: TrackManifest = ron::from_str(
+            r#"(tracks: [("music/depths.ogg", (title: "Into the Depths", loudness_offset: -6.0))])"#,
+        )
+        .unwrap();
+
+        let (path, info) = &manifest.tracks[0];
+        assert_eq!(path, "music/depths.ogg");
+        assert_eq!(info.title, "Into the Depths");
+        assert!((info.gain() - 0.501).abs() < 0.001);
+        assert_eq!(info.tempo, None);
+    }
+
+    #[test]
+    fn track_info_applies_to_music_playback() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.insert_resource(TestConfig::FULL);
+        let handle = Handle::<AudioSource>::default();
+        app.world_mut().resource_mut::<TrackRegistry>().insert(
+            &handle,
+            TrackInfo::new("Main Theme")
+                .with_loudness_offset(-6.0)
+                .with_tempo(MusicTempo::new(120.0, 4)),
+        );
+        app.world_mut()
+            .write_message(PlayMusic::new(handle.clone(), TestMusic::Main));
+        app.update();
+        app.update();
+
+        let world = app.world_mut();
+        let (state, tempo, sink) = world
+            .query::<(&AudioVolumeState, &MusicTempo, &AudioSink)>()
+            .single(world)
+            .unwrap();
+        assert!((state.loudness - 0.501).abs() < 0.001);
+        assert!(
+            (crate::systems::extract_linear_volume(sink.volume()) - state.loudness).abs() < 0.0001
+        );
+        assert_eq!(*tempo, MusicTempo::new(120.0, 4));
+        let now_playing = app.world().resource::<NowPlaying<TestMusic>>();
+        let info = now_playing.get(TestMusic::Main).unwrap().info.as_ref();
+        assert_eq!(info.map(|info| info.title.as_str()), Some("Main Theme"));
+    }
+
+    #[test]
+    fn stingers_duck_music_by_its_track_info() {
+        let mut app = test_app((
+            TestPlugin::default().with_volume_ramp(Duration::ZERO),
+            TestAudioPlugin,
+        ));
+        app.insert_resource(TestConfig::FULL);
+        let theme = Handle::<AudioSource>::default();
+        app.world_mut()
+            .resource_mut::<TrackRegistry>()
+            .insert(&theme, TrackInfo::new("Main Theme").with_duck_db(20.0));
+        app.world_mut()
+            .write_message(PlayMusic::new(theme, TestMusic::Main));
+        app.update();
+        app.update();
+        let (music, _) = app.world().resource::<AudioLog>().plays().next().unwrap();
+
+        let fanfare = bevy::asset::uuid_handle!("1e6b0d2a-3c4f-4a5b-8d7e-9f0a1b2c3d4e");
+        app.world_mut().write_message(
+            PlayStinger::new(fanfare, TestMusic::Main)
+                .with_duck_volume(0.5)
+                .with_fade(Duration::ZERO),
+        );
+        for _ in 0..3 {
+            app.update();
+        }
+        let log = app.world().resource::<AudioLog>();
+        let (stinger, _) = log.plays().nth(1).unwrap();
+        assert!((log.volume(music).unwrap() - 0.1).abs() < 0.0001);
+        assert!((log.volume(stinger).unwrap() - 1.0).abs() < 0.0001);
+
+        // Config changes while ducked keep the duck
+        app.insert_resource(TestConfig {
+            music: 0.5,
+            ..TestConfig::FULL
+        });
+        app.update();
+        let config = app.world().resource::<TestConfig>();
+        let music_gain = config.to_linear_gain(0.5);
+        let log = app.world().resource::<AudioLog>();
+        assert!((log.volume(music).unwrap() - 0.1 * music_gain).abs() < 0.0001);
+
+        app.world_mut()
+            .write_message(FinishSound { entity: stinger });
+        app.update();
+        app.update();
+        let log = app.world().resource::<AudioLog>();
+        assert!((log.volume(music).unwrap() - music_gain).abs() < 0.0001);
+    }
+}
@@ -9,7 +9,9 @@ use bevy::prelude::*;
 ///
 /// This trait is implemented by both music and sound effect category types.
 /// Each category can have its own volume level in the audio configuration.
-pub trait AudioCategory: Component + Clone + Copy + Default + PartialEq + Send + Sync + 'static {
+pub trait AudioCategory:
+    Component + Clone + Copy + Default + PartialEq + Send + Sync + 'static
+{
     /// The configuration type that provides volume settings for this category.
     type Config: Resource;
 
@@ -17,6 +19,24 @@ pub trait AudioCategory: Component + Clone + Copy + Default + PartialEq + Send +
     ///
     /// The returned value should be in the range [0.0, 1.0].
     fn volume_multiplier(&self, config: &Self::Config) -> f32;
+
+    /// Returns whether this category keeps playing normally while the app
+    /// is out of focus.
+    ///
+    /// Only has an effect when focus handling is enabled on the plugin.
+    /// Default implementation returns `false`.
+    fn plays_in_background(&self) -> bool {
+        false
+    }
+
+    /// Returns whether this category is muffled while a
+    /// [`MuffleAudio`](crate::MuffleAudio) exists.
+    ///
+    /// Return `false` for UI categories, so menus stay clear.
+    /// Default implementation returns `true`.
+    fn muffles(&self) -> bool {
+        true
+    }
 }
 
 /// Marker trait for music categories.
@@ -87,9 +107,117 @@ pub trait MusicCategory: AudioCategory {}
 ///     }
 /// }
 ///
-/// impl SfxCategory for GameSfx {}
+/// impl SfxCategory for GameSfx {
+///     // UI sounds keep their pitch during slow motion
+///     fn follows_time_scale(&self) -> bool {
+///         !matches!(self, GameSfx::UI)
+///     }
+/// }
+/// ```
+pub trait SfxCategory: AudioCategory {
+    /// Returns whether this category's playback speed follows the game's time scale.
+    ///
+    /// Only has an effect when time-scaled sound effects are enabled on the plugin.
+    /// Default implementation returns `true`.
+    fn follows_time_scale(&self) -> bool {
+        true
+    }
+
+    /// Returns whether this category is paused while the game is paused.
+    ///
+    /// Only has an effect when game-pause handling is enabled on the plugin.
+    /// Default implementation returns `true`.
+    fn pauses_with_game(&self) -> bool {
+        true
+    }
+}
+
+/// Marker trait for voice and dialogue categories.
+///
+/// Voice lines of a category type play one at a time through the
+/// [`DialogueQueue`](crate::DialogueQueue). Register the type with
+/// [`AddAudioCategory::add_voice_category`](crate::AddAudioCategory::add_voice_category).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use bevy::prelude::*;
+/// use msg_audio::{AudioCategory, VoiceCategory};
+///
+/// #[derive(Component, Clone, Copy, Default, Debug, PartialEq, Eq, Reflect)]
+/// #[reflect(Component)]
+/// pub enum GameVoice {
+///     #[default]
+///     Dialogue,
+///     Narrator,
+/// }
+///
+/// impl AudioCategory for GameVoice {
+///     type Config = MyAudioConfig;
+///
+///     fn volume_multiplier(&self, config: &Self::Config) -> f32 {
+///         config.voice
+///     }
+/// }
+///
+/// impl VoiceCategory for GameVoice {}
 /// ```
-pub trait SfxCategory: AudioCategory {}
+pub trait VoiceCategory: AudioCategory {}
+
+/// Unit in which an [`AudioConfigTrait`] expresses its volume levels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VolumeScale {
+    /// Volumes are slider values, where `1.0` is unchanged. They are shaped
+    /// by [`AudioConfigTrait::volume_curve`] before being applied.
+    #[default]
+    Linear,
+    /// Volumes are gains in decibels, where `0.0` is unchanged and
+    /// `-6.0` is roughly half the amplitude.
+    Decibels,
+}
+
+impl VolumeScale {
+    /// Converts a value in this scale to a linear amplitude multiplier.
+    #[must_use]
+    pub fn to_linear(self, value: f32) -> f32 {
+        match self {
+            Self::Linear => value,
+            Self::Decibels => 10_f32.powf(value / 20.0),
+        }
+    }
+}
+
+/// Curve mapping linear slider values to sink amplitude.
+///
+/// Loudness is perceived roughly logarithmically, so feeding slider values
+/// straight to the sink makes a 50% slider sound barely attenuated.
+/// [`VolumeCurve::Cubic`] approximates a perceptual taper.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum VolumeCurve {
+    /// Slider values are used as amplitude unchanged.
+    Linear,
+    /// Slider values are cubed, so 50% is roughly half the loudness.
+    #[default]
+    Cubic,
+    /// User-provided curve from slider value to amplitude.
+    Custom(fn(f32) -> f32),
+}
+
+impl VolumeCurve {
+    /// Maps a slider value to a linear amplitude multiplier.
+    ///
+    /// Negative values are treated as `0.0`.
+    #[must_use]
+    pub fn apply(self, value: f32) -> f32 {
+        let value = value.max(0.0);
+        match self {
+            Self::Linear => value,
+            Self::Cubic => value * value * value,
+            Self::Custom(f) => f(value),
+        }
+    }
+}
 
 /// Trait for audio configuration resources.
 ///
@@ -126,9 +254,36 @@ pub trait AudioConfigTrait: Resource + Clone + Default + Send + Sync + 'static {
     /// Returns the master volume level.
     ///
     /// This value is multiplied with category volumes to get the final volume.
-    /// Range: [0.0, 1.0]
+    /// Range: [0.0, 1.0] for [`VolumeScale::Linear`], or a gain in dB for
+    /// [`VolumeScale::Decibels`].
     fn master_volume(&self) -> f32;
 
+    /// Returns the unit of [`master_volume()`](Self::master_volume) and of the
+    /// category [`volume_multiplier()`](AudioCategory::volume_multiplier) values.
+    ///
+    /// Default implementation returns [`VolumeScale::Linear`].
+    fn volume_scale(&self) -> VolumeScale {
+        VolumeScale::Linear
+    }
+
+    /// Returns the curve applied to slider values when the config uses
+    /// [`VolumeScale::Linear`].
+    ///
+    /// Default implementation returns [`VolumeCurve::Cubic`]. Return
+    /// [`VolumeCurve::Linear`] to use slider values as amplitude directly.
+    fn volume_curve(&self) -> VolumeCurve {
+        VolumeCurve::Cubic
+    }
+
+    /// Converts a master or category volume value from this config to a
+    /// linear amplitude multiplier.
+    fn to_linear_gain(&self, value: f32) -> f32 {
+        match self.volume_scale() {
+            VolumeScale::Linear => self.volume_curve().apply(value),
+            VolumeScale::Decibels => VolumeScale::Decibels.to_linear(value),
+        }
+    }
+
     /// Returns whether audio is globally muted.
     ///
     /// When muted, all audio should play at zero volume.
@@ -137,14 +292,15 @@ pub trait AudioConfigTrait: Resource + Clone + Default + Send + Sync + 'static {
         false
     }
 
-    /// Returns the effective master volume, accounting for mute state.
+    /// Returns the effective linear master volume, accounting for mute state.
     ///
-    /// Returns 0.0 if muted, otherwise returns [`master_volume()`](Self::master_volume).
+    /// Returns 0.0 if muted, otherwise returns [`master_volume()`](Self::master_volume)
+    /// converted with [`to_linear_gain()`](Self::to_linear_gain).
     fn effective_volume(&self) -> f32 {
         if self.is_muted() {
             0.0
         } else {
-            self.master_volume()
+            self.to_linear_gain(self.master_volume())
         }
     }
 }
@@ -167,6 +323,10 @@ mod tests {
         fn is_muted(&self) -> bool {
             self.muted
         }
+
+        fn volume_curve(&self) -> VolumeCurve {
+            VolumeCurve::Linear
+        }
     }
 
     #[derive(Resource, Clone, Default)]
@@ -179,6 +339,44 @@ mod tests {
             self.master
         }
         // Uses default is_muted() which returns false
+        // and default volume_curve() which is cubic
+    }
+
+    #[derive(Resource, Clone, Default)]
+    struct TestDecibelConfig {
+        master_db: f32,
+    }
+
+    impl AudioConfigTrait for TestDecibelConfig {
+        fn master_volume(&self) -> f32 {
+            self.master_db
+        }
+
+        fn volume_scale(&self) -> VolumeScale {
+            VolumeScale::Decibels
+        }
+    }
+
+    #[test]
+    fn decibel_scale_converts_to_linear() {
+        assert!((VolumeScale::Decibels.to_linear(0.0) - 1.0).abs() < f32::EPSILON);
+        assert!((VolumeScale::Decibels.to_linear(-20.0) - 0.1).abs() < 0.001);
+        assert!((VolumeScale::Linear.to_linear(0.3) - 0.3).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn volume_curves() {
+        assert!((VolumeCurve::Linear.apply(0.5) - 0.5).abs() < f32::EPSILON);
+        assert!((VolumeCurve::Cubic.apply(0.5) - 0.125).abs() < f32::EPSILON);
+        assert!((VolumeCurve::Cubic.apply(1.0) - 1.0).abs() < f32::EPSILON);
+        assert!(VolumeCurve::Cubic.apply(-0.5).abs() < f32::EPSILON);
+        assert!((VolumeCurve::Custom(|v| v * v).apply(0.5) - 0.25).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn effective_volume_converts_decibels() {
+        let config = TestDecibelConfig { master_db: -20.0 };
+        assert!((config.effective_volume() - 0.1).abs() < 0.001);
     }
 
     #[test]
@@ -206,7 +404,7 @@ mod tests {
         let config = TestConfigWithoutMute { master: 0.5 };
 
         assert!(!config.is_muted());
-        assert!((config.effective_volume() - 0.5).abs() < f32::EPSILON);
+        assert!((config.effective_volume() - 0.125).abs() < f32::EPSILON);
     }
 
     #[test]
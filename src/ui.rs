@@ -0,0 +1,202 @@
+//! Sounds for Bevy UI interactions.
+//!
+//! [`UiAudioPlugin`] plays the hover, press, and release sounds of every UI
+//! node carrying [`UiSounds`] as its [`Interaction`] changes, so menus don't
+//! need a click-sound system per screen.
+
+use bevy::prelude::*;
+
+use crate::events::PlaySfx;
+use crate::traits::SfxCategory;
+use crate::MsgAudioSet;
+
+/// Optional plugin that plays [`UiSounds`] through [`PlaySfx`] messages of type `S`.
+///
+/// Requires [`MsgAudioPlugin`](crate::MsgAudioPlugin) (or
+/// [`AddAudioCategory::add_sfx_category`](crate::AddAudioCategory::add_sfx_category))
+/// to handle the messages for `S`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{UiAudioPlugin, UiSounds};
+///
+/// app.add_plugins(UiAudioPlugin::new(GameSfx::UI));
+///
+/// commands.spawn((
+///     Button,
+///     UiSounds::new()
+///         .with_hover(assets.load("sfx/ui/hover.ogg"))
+///         .with_press(assets.load("sfx/ui/click.ogg")),
+/// ));
+/// ```
+pub struct UiAudioPlugin<S: SfxCategory> {
+    category: S,
+}
+
+impl<S: SfxCategory> UiAudioPlugin<S> {
+    /// Creates the plugin, playing UI sounds in the given category.
+    #[must_use]
+    pub fn new(category: S) -> Self {
+        Self { category }
+    }
+}
+
+impl<S: SfxCategory> Default for UiAudioPlugin<S> {
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<S: SfxCategory> Plugin for UiAudioPlugin<S> {
+    fn build(&self, app: &mut App) {
+        app.register_type::<UiSounds>();
+        app.insert_resource(UiSoundCategory(self.category));
+        app.add_systems(
+            Update,
+            play_ui_sounds::<S>.before(MsgAudioSet::EventHandling),
+        );
+    }
+}
+
+/// Resource holding the category [`UiAudioPlugin`] plays UI sounds in.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct UiSoundCategory<S: SfxCategory>(pub S);
+
+/// Sounds played when a UI node's [`Interaction`] changes.
+///
+/// Release sounds play when the pointer is released over the node, not when
+/// a press is cancelled by moving away.
+#[derive(Component, Reflect, Debug, Clone, Default)]
+#[reflect(Component)]
+pub struct UiSounds {
+    /// Played when the pointer starts hovering the node.
+    pub hover: Option<Handle<AudioSource>>,
+    /// Played when the node is pressed.
+    pub press: Option<Handle<AudioSource>>,
+    /// Played when a press is released over the node.
+    pub release: Option<Handle<AudioSource>>,
+    #[reflect(ignore)]
+    last: Option<Interaction>,
+}
+
+impl UiSounds {
+    /// Creates an empty set of UI sounds.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the hover sound.
+    #[must_use]
+    pub fn with_hover(mut self, handle: Handle<AudioSource>) -> Self {
+        self.hover = Some(handle);
+        self
+    }
+
+    /// Sets the press sound.
+    #[must_use]
+    pub fn with_press(mut self, handle: Handle<AudioSource>) -> Self {
+        self.press = Some(handle);
+        self
+    }
+
+    /// Sets the release sound.
+    #[must_use]
+    pub fn with_release(mut self, handle: Handle<AudioSource>) -> Self {
+        self.release = Some(handle);
+        self
+    }
+
+    /// Returns the sound for a change from `previous` to `current`.
+    #[must_use]
+    pub fn sound_for(
+        &self,
+        previous: Interaction,
+        current: Interaction,
+    ) -> Option<&Handle<AudioSource>> {
+        match (previous, current) {
+            (Interaction::Pressed, Interaction::Hovered) => self.release.as_ref(),
+            (Interaction::None, Interaction::Hovered) => self.hover.as_ref(),
+            (previous, Interaction::Pressed) if previous != Interaction::Pressed => {
+                self.press.as_ref()
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Plays [`UiSounds`] when the [`Interaction`] of their node changes.
+pub fn play_ui_sounds<S: SfxCategory>(
+    category: Res<UiSoundCategory<S>>,
+    mut query: Query<(&Interaction, &mut UiSounds), Changed<Interaction>>,
+    mut messages: MessageWriter<PlaySfx<S>>,
+) {
+    for (interaction, mut sounds) in &mut query {
+        let previous = sounds
+            .last
+            .replace(*interaction)
+            .unwrap_or(Interaction::None);
+        if let Some(handle) = sounds.sound_for(previous, *interaction) {
+            messages.write(PlaySfx::new(handle.clone(), category.0));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestPlugin, TestSfx};
+
+    #[test]
+    fn picks_sound_for_transition() {
+        let mut assets = Assets::<AudioSource>::default();
+        let mut source = || {
+            assets.add(AudioSource {
+                bytes: Vec::new().into(),
+            })
+        };
+        let (hover, press, release) = (source(), source(), source());
+        let sounds = UiSounds::new()
+            .with_hover(hover.clone())
+            .with_press(press.clone())
+            .with_release(release.clone());
+
+        assert_eq!(
+            sounds.sound_for(Interaction::None, Interaction::Hovered),
+            Some(&hover)
+        );
+        assert_eq!(
+            sounds.sound_for(Interaction::Hovered, Interaction::Pressed),
+            Some(&press)
+        );
+        assert_eq!(
+            sounds.sound_for(Interaction::Pressed, Interaction::Hovered),
+            Some(&release)
+        );
+        assert_eq!(
+            sounds.sound_for(Interaction::Pressed, Interaction::None),
+            None
+        );
+    }
+
+    #[test]
+    fn ui_audio_plugin_plays_hover_sound() {
+        let mut app = test_app((TestPlugin::default(), UiAudioPlugin::new(TestSfx::UI)));
+
+        let button = app
+            .world_mut()
+            .spawn((
+                Interaction::None,
+                UiSounds::new().with_hover(Handle::default()),
+            ))
+            .id();
+        app.update();
+        let mut sfx = app.world_mut().query::<&TestSfx>();
+        assert_eq!(sfx.iter(app.world()).count(), 0);
+
+        *app.world_mut().get_mut::<Interaction>(button).unwrap() = Interaction::Hovered;
+        app.update();
+        assert_eq!(sfx.iter(app.world()).count(), 1);
+    }
+}
@@ -0,0 +1,235 @@
+//! Voice virtualization.
+//!
+//! The [`VoiceBudget`] resource caps how many sounds play at once. When more
+//! sounds are active, the lowest-priority and least audible ones are
+//! virtualized: paused and marked [`Virtualized`] until they rank among the
+//! audible voices again, or despawned outright.
+//!
+//! The [`GlobalVoiceLimit`] resource is a hard cap on top of that: it bounds
+//! how many sinks exist at all, paused or not.
+
+use bevy::prelude::*;
+
+/// Default bonus given to audible voices when ranking, to avoid flapping.
+pub const DEFAULT_VOICE_HYSTERESIS: f32 = 0.25;
+
+/// How voices over the [`VoiceBudget`] are silenced.
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VirtualizationMode {
+    /// Pause the sink and resume it once the voice ranks high enough again.
+    #[default]
+    Pause,
+    /// Despawn the voice.
+    Despawn,
+}
+
+/// Resource limiting how many sounds play at once.
+///
+/// Voices are ranked by [`AudioPriority`] first, then by audibility: the sink
+/// volume, divided by `1 + distance` to the [`SpatialListener`] for positioned
+/// sounds. Audible voices get a `hysteresis` bonus on their audibility, so a
+/// virtualized voice must be clearly louder than an audible one to replace it.
+///
+/// Sounds paused by other means (including [`PausedByGame`](crate::PausedByGame))
+/// neither count against the budget nor get resumed.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{MsgAudioPlugin, VoiceBudget};
+///
+/// app.add_plugins(
+///     MsgAudioPlugin::<GameMusic, GameSfx, GameAudioConfig>::default()
+///         .with_voice_budget(VoiceBudget::new(32)),
+/// );
+/// ```
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource)]
+pub struct VoiceBudget {
+    /// Maximum number of audible voices.
+    pub max_voices: usize,
+    /// Relative audibility bonus of voices that are currently audible.
+    pub hysteresis: f32,
+    /// How voices over the budget are silenced.
+    pub mode: VirtualizationMode,
+}
+
+impl VoiceBudget {
+    /// A budget that never virtualizes voices.
+    pub const UNLIMITED: Self = Self {
+        max_voices: usize::MAX,
+        hysteresis: DEFAULT_VOICE_HYSTERESIS,
+        mode: VirtualizationMode::Pause,
+    };
+
+    /// Creates a budget of `max_voices` audible voices.
+    #[must_use]
+    pub fn new(max_voices: usize) -> Self {
+        Self {
+            max_voices,
+            ..Self::UNLIMITED
+        }
+    }
+
+    /// Sets the relative audibility bonus of audible voices.
+    #[must_use]
+    pub fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Sets how voices over the budget are silenced.
+    #[must_use]
+    pub fn with_mode(mut self, mode: VirtualizationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns the ranking score of a voice.
+    #[must_use]
+    pub fn score(&self, audibility: f32, virtualized: bool) -> f32 {
+        if virtualized {
+            audibility
+        } else {
+            audibility * (1.0 + self.hysteresis)
+        }
+    }
+}
+
+impl Default for VoiceBudget {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
+
+/// Default [`GlobalVoiceLimit`] of the target platform: 32 sounds on the web,
+/// where browsers struggle with many simultaneous sources, and 128 elsewhere.
+pub const DEFAULT_GLOBAL_VOICE_LIMIT: usize = if cfg!(target_arch = "wasm32") {
+    32
+} else {
+    128
+};
+
+/// Resource capping how many sounds exist at once, across all categories.
+///
+/// Every entity with an [`AudioPlayer`] counts, including generated
+/// [`Tone`](crate::Tone)s and paused and [`Virtualized`] sounds. When new
+/// sounds push the count over the limit, the sounds with the lowest
+/// [`AudioPriority`] are despawned; among equal priorities, the newest go
+/// first, so new sounds are dropped before they interrupt playing ones.
+///
+/// Defaults to [`DEFAULT_GLOBAL_VOICE_LIMIT`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{GlobalVoiceLimit, MsgAudioPlugin};
+///
+/// app.add_plugins(
+///     MsgAudioPlugin::<GameMusic, GameSfx, GameAudioConfig>::default()
+///         .with_global_voice_limit(GlobalVoiceLimit::new(24)),
+/// );
+/// ```
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+pub struct GlobalVoiceLimit {
+    /// Maximum number of sounds.
+    pub max_voices: usize,
+}
+
+impl GlobalVoiceLimit {
+    /// A limit that never drops sounds.
+    pub const UNLIMITED: Self = Self {
+        max_voices: usize::MAX,
+    };
+
+    /// Creates a limit of `max_voices` sounds.
+    #[must_use]
+    pub fn new(max_voices: usize) -> Self {
+        Self { max_voices }
+    }
+}
+
+impl Default for GlobalVoiceLimit {
+    fn default() -> Self {
+        Self::new(DEFAULT_GLOBAL_VOICE_LIMIT)
+    }
+}
+
+/// Priority of a sound when the [`VoiceBudget`] or [`GlobalVoiceLimit`] is
+/// exceeded.
+///
+/// Higher priorities are kept audible first. Sounds without this component
+/// have priority `0`.
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[reflect(Component)]
+pub struct AudioPriority(pub i32);
+
+/// Marks a sound paused by the [`VoiceBudget`].
+#[derive(Component, Reflect, Debug, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct Virtualized;
+
+/// A voice considered by the [`VoiceBudget`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct VoiceRank {
+    pub entity: Entity,
+    pub priority: i32,
+    pub score: f32,
+    pub virtualized: bool,
+}
+
+/// Sorts voices from the most to the least important.
+pub(crate) fn rank_voices(voices: &mut [VoiceRank]) {
+    voices.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then(b.score.total_cmp(&a.score))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rank(budget: &VoiceBudget, voices: &[(u32, i32, f32, bool)]) -> Vec<u32> {
+        let mut voices: Vec<_> = voices
+            .iter()
+            .map(|&(index, priority, audibility, virtualized)| VoiceRank {
+                entity: Entity::from_raw_u32(index).unwrap(),
+                priority,
+                score: budget.score(audibility, virtualized),
+                virtualized,
+            })
+            .collect();
+        rank_voices(&mut voices);
+        voices
+            .iter()
+            .take(budget.max_voices)
+            .map(|voice| voice.entity.index())
+            .collect()
+    }
+
+    #[test]
+    fn keeps_highest_priority_then_loudest() {
+        let budget = VoiceBudget::new(2);
+        let kept = rank(
+            &budget,
+            &[(1, 0, 0.9, false), (2, 1, 0.1, false), (3, 0, 0.5, false)],
+        );
+        assert_eq!(kept, vec![2, 1]);
+    }
+
+    #[test]
+    fn hysteresis_keeps_audible_voice() {
+        let budget = VoiceBudget::new(1).with_hysteresis(0.5);
+        assert_eq!(
+            rank(&budget, &[(1, 0, 0.5, false), (2, 0, 0.6, true)]),
+            vec![1]
+        );
+        assert_eq!(
+            rank(&budget, &[(1, 0, 0.5, false), (2, 0, 0.8, true)]),
+            vec![2]
+        );
+    }
+}
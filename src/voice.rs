@@ -0,0 +1,352 @@
+//! Dialogue and voice-over playback.
+//!
+//! Voice lines of a [`VoiceCategory`] play one at a time. Lines requested with
+//! [`PlayVoiceLine`] while another line is speaking are queued by priority, or
+//! interrupt the current line if their priority is higher. Every line that
+//! started playing emits a [`VoiceLineFinished`] message when it ends, which
+//! subtitle systems can use to clear their text.
+//!
+//! While a voice line plays, music is ducked as configured by the
+//! [`DialogueDucking`] resource.
+
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::components::PlaybackLabel;
+use crate::events::PlayVoiceLine;
+use crate::traits::VoiceCategory;
+
+/// Marks a playing voice line.
+///
+/// Query `Added<VoiceLine>` to show subtitles when a line starts.
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct VoiceLine {
+    /// Priority the line was requested with.
+    pub priority: i32,
+}
+
+/// Default attenuation applied to music while a voice line plays, in decibels.
+pub const DEFAULT_DIALOGUE_DUCK_DB: f32 = 9.0;
+
+/// Default duration over which music is ducked when a voice line starts.
+pub const DEFAULT_DIALOGUE_DUCK_ATTACK: Duration = Duration::from_millis(200);
+
+/// Default duration over which music recovers after the last voice line ends.
+pub const DEFAULT_DIALOGUE_DUCK_RELEASE: Duration = Duration::from_millis(800);
+
+/// Resource controlling how music is ducked while voice lines play.
+///
+/// The duck is applied through each music type's [`CategoryMixerState`](crate::CategoryMixerState),
+/// so it combines with the config volume, mixer snapshots, and volume changes
+/// made while ducked.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::DialogueDucking;
+///
+/// app.insert_resource(DialogueDucking::new(12.0).with_release(Duration::from_secs(1)));
+/// ```
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource)]
+pub struct DialogueDucking {
+    /// Attenuation in decibels. `0.0` disables ducking.
+    pub amount_db: f32,
+    /// Duration of the duck when a voice line starts.
+    pub attack: Duration,
+    /// Duration of the recovery after the last voice line ends.
+    pub release: Duration,
+}
+
+impl DialogueDucking {
+    /// Disables dialogue ducking.
+    pub const DISABLED: Self = Self {
+        amount_db: 0.0,
+        attack: DEFAULT_DIALOGUE_DUCK_ATTACK,
+        release: DEFAULT_DIALOGUE_DUCK_RELEASE,
+    };
+
+    /// Creates a ducking configuration with the default attack and release.
+    #[must_use]
+    pub fn new(amount_db: f32) -> Self {
+        Self {
+            amount_db: amount_db.max(0.0),
+            ..Self::DISABLED
+        }
+    }
+
+    /// Sets the duration of the duck.
+    #[must_use]
+    pub fn with_attack(mut self, attack: Duration) -> Self {
+        self.attack = attack;
+        self
+    }
+
+    /// Sets the duration of the recovery.
+    #[must_use]
+    pub fn with_release(mut self, release: Duration) -> Self {
+        self.release = release;
+        self
+    }
+
+    /// Returns the linear gain applied to music while fully ducked.
+    #[must_use]
+    pub fn ducked_gain(&self) -> f32 {
+        10f32.powf(-self.amount_db.max(0.0) / 20.0)
+    }
+
+    /// Moves a duck gain towards its target over `delta`.
+    ///
+    /// Ducking follows the attack time and recovery the release time, both
+    /// measured for the full range between unducked and fully ducked.
+    #[must_use]
+    pub fn step(&self, current: f32, ducking: bool, delta: Duration) -> f32 {
+        let floor = self.ducked_gain();
+        let (target, duration) = if ducking {
+            (floor, self.attack)
+        } else {
+            (1.0, self.release)
+        };
+        if duration.is_zero() || floor >= 1.0 {
+            return target;
+        }
+        let max_step = (1.0 - floor) * delta.as_secs_f32() / duration.as_secs_f32();
+        if current > target {
+            (current - max_step).max(target)
+        } else {
+            (current + max_step).min(target)
+        }
+    }
+}
+
+impl Default for DialogueDucking {
+    fn default() -> Self {
+        Self::new(DEFAULT_DIALOGUE_DUCK_DB)
+    }
+}
+
+/// The voice line that is currently speaking.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveVoiceLine<V: VoiceCategory> {
+    /// The voice line entity.
+    pub entity: Entity,
+    /// The voice category of the line.
+    pub category: V,
+    /// Priority the line was requested with.
+    pub priority: i32,
+    /// Label the line was requested with.
+    pub label: Option<PlaybackLabel>,
+}
+
+/// Queue of voice lines for category type `V`.
+///
+/// Only one line plays at a time. Pending lines are ordered by priority, and
+/// lines of equal priority play in the order they were requested.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::DialogueQueue;
+///
+/// fn skip_cutscene(mut queue: ResMut<DialogueQueue<GameVoice>>) {
+///     queue.clear();
+/// }
+/// ```
+#[derive(Resource, Debug)]
+pub struct DialogueQueue<V: VoiceCategory> {
+    current: Option<ActiveVoiceLine<V>>,
+    pending: VecDeque<PlayVoiceLine<V>>,
+}
+
+impl<V: VoiceCategory> Default for DialogueQueue<V> {
+    fn default() -> Self {
+        Self {
+            current: None,
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+impl<V: VoiceCategory> DialogueQueue<V> {
+    /// Returns the line that is currently speaking.
+    #[must_use]
+    pub fn current(&self) -> Option<&ActiveVoiceLine<V>> {
+        self.current.as_ref()
+    }
+
+    /// Returns `true` if a line is currently speaking.
+    #[must_use]
+    pub fn is_playing(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// Returns the pending lines in playback order.
+    pub fn pending(&self) -> impl Iterator<Item = &PlayVoiceLine<V>> {
+        self.pending.iter()
+    }
+
+    /// Returns the number of pending lines.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns `true` if no lines are pending.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Drops all pending lines. The current line keeps playing.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Adds a line behind every pending line of equal or higher priority.
+    pub(crate) fn enqueue(&mut self, line: PlayVoiceLine<V>) {
+        let index = self
+            .pending
+            .iter()
+            .position(|pending| pending.priority < line.priority)
+            .unwrap_or(self.pending.len());
+        self.pending.insert(index, line);
+    }
+
+    /// Removes the next pending line.
+    pub(crate) fn pop_next(&mut self) -> Option<PlayVoiceLine<V>> {
+        self.pending.pop_front()
+    }
+
+    /// Sets the line that is currently speaking.
+    pub(crate) fn set_current(&mut self, current: Option<ActiveVoiceLine<V>>) {
+        self.current = current;
+    }
+
+    /// Clears and returns the current line.
+    pub(crate) fn take_current(&mut self) -> Option<ActiveVoiceLine<V>> {
+        self.current.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin, TestVoice};
+
+    use crate::{AddAudioCategory, CategoryMixerState, VoiceLineFinished};
+
+    fn line(label: &'static str, priority: i32) -> PlayVoiceLine<TestVoice> {
+        PlayVoiceLine::new(Handle::default(), TestVoice::Dialogue)
+            .with_priority(priority)
+            .with_label(label)
+    }
+
+    #[test]
+    fn enqueue_orders_by_priority_then_request() {
+        let mut queue = DialogueQueue::default();
+        queue.enqueue(line("a", 0));
+        queue.enqueue(line("b", 5));
+        queue.enqueue(line("c", 0));
+        queue.enqueue(line("d", 5));
+
+        let order: Vec<_> = queue
+            .pending()
+            .map(|line| line.label.as_ref().unwrap().as_str())
+            .collect();
+        assert_eq!(order, ["b", "d", "a", "c"]);
+    }
+
+    #[test]
+    fn ducking_moves_towards_target_at_attack_and_release_rates() {
+        let ducking = DialogueDucking::new(20.0)
+            .with_attack(Duration::from_millis(100))
+            .with_release(Duration::from_millis(900));
+        assert!((ducking.ducked_gain() - 0.1).abs() < 1e-4);
+
+        let half = ducking.step(1.0, true, Duration::from_millis(50));
+        assert!((half - 0.55).abs() < 1e-4);
+        let ducked = ducking.step(half, true, Duration::from_secs(1));
+        assert!((ducked - 0.1).abs() < 1e-4);
+
+        let recovering = ducking.step(ducked, false, Duration::from_millis(300));
+        assert!((recovering - 0.4).abs() < 1e-4);
+    }
+
+    #[test]
+    fn disabled_ducking_keeps_full_gain() {
+        let ducking = DialogueDucking::DISABLED;
+        assert!((ducking.step(1.0, true, Duration::from_millis(16)) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn clear_keeps_current_line() {
+        let mut queue = DialogueQueue::default();
+        queue.enqueue(line("a", 0));
+        queue.set_current(Some(ActiveVoiceLine {
+            entity: Entity::PLACEHOLDER,
+            category: TestVoice::Dialogue,
+            priority: 0,
+            label: None,
+        }));
+
+        queue.clear();
+        assert!(queue.is_empty());
+        assert!(queue.is_playing());
+    }
+
+    #[test]
+    fn voice_lines_play_one_at_a_time_and_interrupt_by_priority() {
+        let mut app = test_app(TestPlugin::default());
+        app.add_voice_category::<TestVoice>();
+
+        let line = |label: &'static str| {
+            PlayVoiceLine::new(Handle::default(), TestVoice::Dialogue).with_label(label)
+        };
+        app.world_mut().write_message(line("first"));
+        app.world_mut().write_message(line("second"));
+        app.update();
+
+        let queue = app.world().resource::<DialogueQueue<TestVoice>>();
+        let first = queue.current().unwrap().entity;
+        assert_eq!(queue.len(), 1);
+
+        app.world_mut()
+            .write_message(line("alarm").with_priority(10));
+        app.update();
+
+        assert!(app.world().get_entity(first).is_err());
+        let queue = app.world().resource::<DialogueQueue<TestVoice>>();
+        let current = queue.current().unwrap();
+        assert_eq!(current.label.as_ref().unwrap().as_str(), "alarm");
+
+        let finished = app
+            .world()
+            .resource::<Messages<VoiceLineFinished<TestVoice>>>();
+        let mut cursor = finished.get_cursor();
+        let interrupted: Vec<_> = cursor.read(finished).collect();
+        assert_eq!(interrupted.len(), 1);
+        assert!(interrupted[0].interrupted);
+        assert_eq!(interrupted[0].entity, first);
+    }
+
+    #[test]
+    fn music_ducks_while_voice_line_plays() {
+        let mut app = test_app(
+            TestPlugin::default()
+                .with_dialogue_ducking(DialogueDucking::new(20.0).with_attack(Duration::ZERO)),
+        );
+        app.add_voice_category::<TestVoice>();
+
+        let line = app.world_mut().spawn(VoiceLine::default()).id();
+        app.update();
+        let mixer = app.world().resource::<CategoryMixerState<TestMusic>>();
+        assert!((mixer.duck_gain() - 0.1).abs() < 1e-4);
+
+        app.world_mut().despawn(line);
+        app.update();
+        let mixer = app.world().resource::<CategoryMixerState<TestMusic>>();
+        assert!(mixer.duck_gain() > 0.1);
+    }
+}
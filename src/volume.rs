@@ -0,0 +1,160 @@
+//! The volume pipeline.
+//!
+//! The volume a sink plays at is the product of a fixed set of stages: the
+//! master volume, the category volume, the sound's own gain, its loudness
+//! normalization, its distance attenuation, dialogue ducking, the mixer
+//! snapshots, and a running tween. [`VolumeStages`] holds one factor per
+//! stage and [`compute_final_volume`] combines them, so every volume system
+//! and new volume feature computes the same product.
+
+use bevy::prelude::*;
+
+use crate::components::{AudioVolumeState, TrackGain};
+use crate::mixer::CategoryMixerState;
+use crate::systems::extract_linear_volume;
+use crate::traits::{AudioCategory, AudioConfigTrait};
+
+/// The linear gain factors making up the volume of one sound.
+///
+/// Every stage defaults to `1.0`, so a stage that does not apply leaves the
+/// volume unchanged.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{compute_final_volume, VolumeStages};
+///
+/// let stages = VolumeStages::for_category(&config, &mixer, GameSfx::UI)
+///     .with_base(&playback, gain)
+///     .with_tween(0.5);
+/// sink.set_volume(Volume::Linear(compute_final_volume(&stages)));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VolumeStages {
+    /// Master volume of the config, `0.0` while muted.
+    pub master: f32,
+    /// Volume of the category in the config.
+    pub category: f32,
+    /// Volume of the sound itself: its playback volume and [`TrackGain`].
+    pub base: f32,
+    /// Loudness normalization of the audio source, from the
+    /// [`TrackRegistry`](crate::TrackRegistry).
+    pub loudness: f32,
+    /// Distance attenuation of a spatial sound with
+    /// [`SpatialSettings`](crate::SpatialSettings).
+    pub attenuation: f32,
+    /// Gain applied by dialogue and stinger ducking.
+    pub duck: f32,
+    /// Mute, solo, snapshot, background, and limiter gain of the mixer.
+    pub snapshot: f32,
+    /// Progress of a running fade or tween.
+    pub tween: f32,
+}
+
+impl Default for VolumeStages {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            category: 1.0,
+            base: 1.0,
+            loudness: 1.0,
+            attenuation: 1.0,
+            duck: 1.0,
+            snapshot: 1.0,
+            tween: 1.0,
+        }
+    }
+}
+
+impl VolumeStages {
+    /// Creates the stages shared by every sound of a category: the master and
+    /// category volumes, ducking, and the mixer snapshots.
+    #[must_use]
+    pub fn for_category<A: AudioCategory>(
+        config: &A::Config,
+        mixer: &CategoryMixerState<A>,
+        category: A,
+    ) -> Self
+    where
+        A::Config: AudioConfigTrait,
+    {
+        Self {
+            master: config.effective_volume(),
+            category: config.to_linear_gain(category.volume_multiplier(config)),
+            duck: mixer.duck_gain() * mixer.stinger_duck_gain(None),
+            snapshot: mixer.snapshot_gain(category),
+            ..default()
+        }
+    }
+
+    /// Sets the duck stage for a track ducked to `volume` while stingers
+    /// play, or to the mixer's stinger duck volume when `None`.
+    #[must_use]
+    pub fn with_stinger_duck<A: AudioCategory>(
+        mut self,
+        mixer: &CategoryMixerState<A>,
+        volume: Option<f32>,
+    ) -> Self {
+        self.duck = mixer.duck_gain() * mixer.stinger_duck_gain(volume);
+        self
+    }
+
+    /// Sets the base stage from a sound's playback volume and optional
+    /// [`TrackGain`].
+    #[must_use]
+    pub fn with_base(mut self, playback: &PlaybackSettings, gain: Option<&TrackGain>) -> Self {
+        self.base = extract_linear_volume(playback.volume) * gain.map_or(1.0, |gain| gain.0);
+        self
+    }
+
+    /// Sets the base stage from a sound's [`AudioVolumeState`] and optional
+    /// [`TrackGain`].
+    #[must_use]
+    pub fn with_state(mut self, state: &AudioVolumeState, gain: Option<&TrackGain>) -> Self {
+        self.base = state.base * gain.map_or(1.0, |gain| gain.0);
+        self.loudness = state.loudness;
+        self.attenuation = state.attenuation;
+        self
+    }
+
+    /// Sets the loudness stage.
+    #[must_use]
+    pub fn with_loudness(mut self, loudness: f32) -> Self {
+        self.loudness = loudness;
+        self
+    }
+
+    /// Sets the tween stage.
+    #[must_use]
+    pub fn with_tween(mut self, tween: f32) -> Self {
+        self.tween = tween;
+        self
+    }
+
+    /// Returns the product of the stages shared by a whole category, leaving
+    /// out the base, loudness, attenuation, and tween stages.
+    #[must_use]
+    pub fn category_gain(&self) -> f32 {
+        self.master * self.category * self.duck * self.snapshot
+    }
+}
+
+/// Computes the final linear volume of a sound from its stages.
+///
+/// Stages are applied in order: master, category, base gain, loudness,
+/// attenuation, duck, snapshot, and tween.
+#[must_use]
+pub fn compute_final_volume(stages: &VolumeStages) -> f32 {
+    [
+        stages.master,
+        stages.category,
+        stages.base,
+        stages.loudness,
+        stages.attenuation,
+        stages.duck,
+        stages.snapshot,
+        stages.tween,
+    ]
+    .into_iter()
+    .product()
+}
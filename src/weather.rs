@@ -0,0 +1,627 @@
+//! Weather sounds.
+//!
+//! A [`WeatherAudio`] resource registers the loops, such as rain and wind,
+//! and the thunder of every state of a user-provided [`WeatherState`]
+//! resource. States are told apart by their kind, so data such as the
+//! strength of the rain only scales the volume. When the weather changes,
+//! the loops crossfade to the new state; loops shared by both states keep
+//! playing. Thunder strikes at random
+//! intervals, and its sound arrives after a delay given by its distance.
+
+use bevy::prelude::*;
+use rand::Rng;
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use crate::ambience::step_towards;
+use crate::backend::{AudioBackend, BevyAudioBackend};
+use crate::components::{SoundPool, TrackGain};
+use crate::events::PlaySfx;
+use crate::mixer::CategoryMixerState;
+use crate::request::AudioRequest;
+use crate::rng::AudioRng;
+use crate::systems::{drive_loop_source, LoopSourceData};
+use crate::traits::{AudioConfigTrait, SfxCategory};
+use crate::volume::VolumeStages;
+use crate::MsgAudioSet;
+
+/// Default time a weather loop takes to fade between silence and full volume.
+pub const DEFAULT_WEATHER_FADE: Duration = Duration::from_secs(2);
+
+/// Default speed of sound in world units per second, for units in meters.
+pub const DEFAULT_SPEED_OF_SOUND: f32 = 343.0;
+
+/// Resource describing the current weather.
+///
+/// Sounds are registered per kind of weather, as told apart by
+/// [`same_kind`](Self::same_kind), so `Weather::Rain { strength: 0.2 }` plays
+/// the sounds registered for `Weather::Rain { strength: 1.0 }`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::WeatherState;
+///
+/// #[derive(Resource, Clone)]
+/// enum Weather {
+///     Clear,
+///     Rain { strength: f32 },
+///     Storm,
+/// }
+///
+/// impl WeatherState for Weather {
+///     fn intensity(&self) -> f32 {
+///         match self {
+///             Weather::Rain { strength } => *strength,
+///             _ => 1.0,
+///         }
+///     }
+/// }
+/// ```
+pub trait WeatherState: Resource + Clone {
+    /// Returns how strong the weather is, scaling the volume of its loops.
+    ///
+    /// Default implementation returns `1.0`.
+    fn intensity(&self) -> f32 {
+        1.0
+    }
+
+    /// Returns `true` if `other` is the same kind of weather, which plays the
+    /// same sounds.
+    ///
+    /// Default implementation compares enum variants, ignoring their fields.
+    /// Weather types that are not enums must override it.
+    fn same_kind(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+/// Randomized thunder of a weather state.
+#[derive(Debug, Clone)]
+pub struct Thunder {
+    /// Thunder sounds to pick from.
+    pub pool: SoundPool,
+    /// Time between two strikes.
+    pub interval: RangeInclusive<Duration>,
+    /// Distance of a strike from the listener.
+    pub distance: RangeInclusive<f32>,
+    /// Linear volume of a strike at the nearest distance.
+    pub volume: f32,
+    /// Speed of sound used to delay the sound of a strike.
+    pub speed_of_sound: f32,
+}
+
+impl Thunder {
+    /// Creates thunder striking every 8 to 30 seconds, 300 to 3000 units away.
+    #[must_use]
+    pub fn new(pool: SoundPool) -> Self {
+        Self {
+            pool,
+            interval: Duration::from_secs(8)..=Duration::from_secs(30),
+            distance: 300.0..=3000.0,
+            volume: 1.0,
+            speed_of_sound: DEFAULT_SPEED_OF_SOUND,
+        }
+    }
+
+    /// Sets the range of times between two strikes.
+    #[must_use]
+    pub fn with_interval(mut self, min: Duration, max: Duration) -> Self {
+        self.interval = min..=max;
+        self
+    }
+
+    /// Sets the range of distances of a strike.
+    #[must_use]
+    pub fn with_distance(mut self, min: f32, max: f32) -> Self {
+        self.distance = min..=max;
+        self
+    }
+
+    /// Sets the volume of a strike at the nearest distance.
+    #[must_use]
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Sets the speed of sound in world units per second.
+    #[must_use]
+    pub fn with_speed_of_sound(mut self, speed_of_sound: f32) -> Self {
+        self.speed_of_sound = speed_of_sound;
+        self
+    }
+
+    /// Returns the time the sound of a strike takes to travel `distance`.
+    #[must_use]
+    pub fn delay(&self, distance: f32) -> Duration {
+        if self.speed_of_sound <= 0.0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f32((distance / self.speed_of_sound).max(0.0))
+    }
+
+    /// Returns the volume of a strike at `distance`, falling off inversely
+    /// beyond the nearest distance.
+    #[must_use]
+    pub fn volume_at(&self, distance: f32) -> f32 {
+        let nearest = self.distance.start().max(f32::EPSILON);
+        self.volume * (nearest / distance.max(nearest))
+    }
+
+    fn roll_interval(&self, rng: &mut impl Rng) -> Duration {
+        let (min, max) = (*self.interval.start(), *self.interval.end());
+        if max <= min {
+            return min;
+        }
+        Duration::from_secs_f32(rng.random_range(min.as_secs_f32()..=max.as_secs_f32()))
+    }
+
+    fn roll_distance(&self, rng: &mut impl Rng) -> f32 {
+        let (min, max) = (*self.distance.start(), *self.distance.end());
+        if max <= min {
+            return min;
+        }
+        rng.random_range(min..=max)
+    }
+}
+
+/// Loops and thunder of one weather state.
+#[derive(Debug, Clone, Default)]
+pub struct WeatherSounds {
+    /// Looping sounds with their linear volume.
+    pub loops: Vec<(Handle<AudioSource>, f32)>,
+    /// Randomized thunder, if any.
+    pub thunder: Option<Thunder>,
+}
+
+impl WeatherSounds {
+    /// Creates a state without sounds.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a loop played at `volume`.
+    #[must_use]
+    pub fn with_loop(mut self, handle: Handle<AudioSource>, volume: f32) -> Self {
+        self.loops.push((handle, volume));
+        self
+    }
+
+    /// Sets the thunder of the state.
+    #[must_use]
+    pub fn with_thunder(mut self, thunder: Thunder) -> Self {
+        self.thunder = Some(thunder);
+        self
+    }
+
+    fn volume_of(&self, handle: &Handle<AudioSource>) -> f32 {
+        self.loops
+            .iter()
+            .filter(|(existing, _)| existing == handle)
+            .map(|(_, volume)| volume)
+            .sum()
+    }
+}
+
+/// A loop playing for the weather.
+#[derive(Debug, Clone)]
+pub(crate) struct WeatherLoop {
+    handle: Handle<AudioSource>,
+    source: Option<Entity>,
+    gain: f32,
+}
+
+/// Resource registering the sounds of every state of weather `W` for
+/// category `S`.
+///
+/// States without an entry are silent.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::{SoundPool, Thunder, WeatherAudio, WeatherSounds};
+///
+/// app.insert_resource(
+///     WeatherAudio::<Weather, GameSfx>::new(GameSfx::Ambience)
+///         .with_state(Weather::Rain, WeatherSounds::new().with_loop(rain, 0.8))
+///         .with_state(
+///             Weather::Storm,
+///             WeatherSounds::new()
+///                 .with_loop(rain, 1.0)
+///                 .with_loop(wind, 0.6)
+///                 .with_thunder(Thunder::new(SoundPool::new([thunder_1, thunder_2]))),
+///         ),
+/// );
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct WeatherAudio<W: WeatherState, S: SfxCategory> {
+    /// The sound effect category for volume control.
+    pub category: S,
+    /// Time a loop takes to move between silence and a volume of `1.0`.
+    pub fade: Duration,
+    /// Sounds keyed by weather state.
+    pub states: Vec<(W, WeatherSounds)>,
+    loops: Vec<WeatherLoop>,
+    next_thunder: Option<Duration>,
+    thundering: Option<W>,
+}
+
+impl<W: WeatherState, S: SfxCategory> WeatherAudio<W, S> {
+    /// Creates a registry without states.
+    #[must_use]
+    pub fn new(category: S) -> Self {
+        Self {
+            category,
+            fade: DEFAULT_WEATHER_FADE,
+            states: Vec::new(),
+            loops: Vec::new(),
+            next_thunder: None,
+            thundering: None,
+        }
+    }
+
+    /// Adds or replaces the sounds of a kind of weather.
+    #[must_use]
+    pub fn with_state(mut self, state: W, sounds: WeatherSounds) -> Self {
+        self.states
+            .retain(|(existing, _)| !existing.same_kind(&state));
+        self.states.push((state, sounds));
+        self
+    }
+
+    /// Sets the time a loop takes to move between silence and a volume of
+    /// `1.0`.
+    #[must_use]
+    pub fn with_fade(mut self, fade: Duration) -> Self {
+        self.fade = fade;
+        self
+    }
+
+    /// Returns the sounds of the kind of weather of `state`.
+    #[must_use]
+    pub fn sounds(&self, state: &W) -> Option<&WeatherSounds> {
+        self.states
+            .iter()
+            .find(|(existing, _)| existing.same_kind(state))
+            .map(|(_, sounds)| sounds)
+    }
+
+    /// Returns the entity and gain of every playing loop.
+    pub fn playing(&self) -> impl Iterator<Item = (Entity, f32)> + '_ {
+        self.loops
+            .iter()
+            .filter_map(|layer| layer.source.map(|source| (source, layer.gain)))
+    }
+
+    /// Adds a loop entry for every registered handle not tracked yet.
+    fn track_loops(&mut self) {
+        for (_, sounds) in &self.states {
+            for (handle, _) in &sounds.loops {
+                if !self.loops.iter().any(|layer| layer.handle == *handle) {
+                    self.loops.push(WeatherLoop {
+                        handle: handle.clone(),
+                        source: None,
+                        gain: 0.0,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Message sent when thunder strikes, before its sound arrives.
+///
+/// Use it to flash lightning in sync with the thunder.
+#[derive(Message, Clone, Copy, Debug, PartialEq)]
+pub struct ThunderStrike {
+    /// Distance of the strike from the listener.
+    pub distance: f32,
+    /// Time until the sound of the strike arrives.
+    pub delay: Duration,
+}
+
+/// Marks the entity playing a weather loop.
+#[derive(Component, Reflect, Debug, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct WeatherSource;
+
+/// Optional plugin playing the sounds of weather `W` with category `S`.
+///
+/// The `W` and [`WeatherAudio<W, S>`] resources must be inserted by the app.
+/// The loops play through the [`AudioBackend`] `B`, [`BevyAudioBackend`] by
+/// default.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::WeatherAudioPlugin;
+///
+/// app.add_plugins(WeatherAudioPlugin::<Weather, GameSfx>::default());
+/// ```
+pub struct WeatherAudioPlugin<W, S, B = BevyAudioBackend> {
+    _phantom: PhantomData<(W, S, B)>,
+}
+
+impl<W, S, B> Default for WeatherAudioPlugin<W, S, B> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<W, S, B> Plugin for WeatherAudioPlugin<W, S, B>
+where
+    W: WeatherState,
+    S: SfxCategory,
+    S::Config: AudioConfigTrait,
+    B: AudioBackend,
+{
+    fn build(&self, app: &mut App) {
+        app.register_type::<WeatherSource>();
+        app.add_message::<ThunderStrike>();
+        app.add_systems(
+            Update,
+            (
+                play_thunder::<W, S>.before(MsgAudioSet::EventHandling),
+                update_weather_loops::<W, S, S::Config, B>.in_set(MsgAudioSet::Fades),
+            )
+                .run_if(resource_exists::<W>.and(resource_exists::<WeatherAudio<W, S>>)),
+        );
+    }
+}
+
+/// Crossfades the weather loops towards the current state, scaled by its
+/// [`WeatherState::intensity`].
+pub fn update_weather_loops<W, S, C, B>(
+    mut commands: Commands,
+    time: Res<Time>,
+    weather: Res<W>,
+    config: Res<C>,
+    mixer: Res<CategoryMixerState<S>>,
+    mut audio: ResMut<WeatherAudio<W, S>>,
+    mut sources: Query<LoopSourceData<B>, With<WeatherSource>>,
+) where
+    W: WeatherState,
+    S: SfxCategory<Config = C>,
+    C: AudioConfigTrait,
+    B: AudioBackend,
+{
+    audio.track_loops();
+    let audio = &mut *audio;
+    let current = audio.sounds(&weather).cloned().unwrap_or_default();
+    let intensity = weather.intensity().max(0.0);
+    let category = audio.category;
+
+    for layer in &mut audio.loops {
+        let volume = current.volume_of(&layer.handle);
+        layer.gain = step_towards(
+            layer.gain,
+            volume * intensity,
+            1.0,
+            audio.fade,
+            time.delta(),
+        );
+        let gain = layer.gain;
+        let handle = &layer.handle;
+        drive_loop_source::<B, _>(
+            &mut commands,
+            &mut layer.source,
+            gain,
+            &mut sources,
+            VolumeStages::for_category(&*config, &mixer, category),
+            |commands| {
+                let mut source = commands.spawn((category, TrackGain::new(gain), WeatherSource));
+                B::play(&mut source, handle.clone(), PlaybackSettings::LOOP);
+                source.id()
+            },
+        );
+    }
+}
+
+/// Strikes thunder at random intervals while the current state has
+/// [`Thunder`], playing its sound after the delay for its distance.
+///
+/// The time to the next strike is only rolled again when the kind of
+/// weather changes, not when its strength does.
+pub fn play_thunder<W: WeatherState, S: SfxCategory>(
+    time: Res<Time>,
+    weather: Res<W>,
+    mut audio: ResMut<WeatherAudio<W, S>>,
+    mut rng: ResMut<AudioRng>,
+    mut strikes: MessageWriter<ThunderStrike>,
+    mut messages: MessageWriter<PlaySfx<S>>,
+) {
+    let audio = &mut *audio;
+    if !audio
+        .thundering
+        .as_ref()
+        .is_some_and(|thundering| thundering.same_kind(&weather))
+    {
+        audio.thundering = Some(weather.clone());
+        audio.next_thunder = None;
+    }
+    let Some(thunder) = audio
+        .states
+        .iter()
+        .find(|(state, _)| state.same_kind(&weather))
+        .and_then(|(_, sounds)| sounds.thunder.as_ref())
+    else {
+        return;
+    };
+
+    let remaining = audio
+        .next_thunder
+        .unwrap_or_else(|| thunder.roll_interval(&mut *rng))
+        .saturating_sub(time.delta());
+    if !remaining.is_zero() {
+        audio.next_thunder = Some(remaining);
+        return;
+    }
+    audio.next_thunder = Some(thunder.roll_interval(&mut *rng));
+
+    let Some(handle) = thunder.pool.pick_with(&mut *rng) else {
+        return;
+    };
+    let distance = thunder.roll_distance(&mut *rng);
+    let delay = thunder.delay(distance);
+    strikes.write(ThunderStrike { distance, delay });
+    messages.write(
+        PlaySfx::new(handle.clone(), audio.category)
+            .volume(thunder.volume_at(distance))
+            .after(delay),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestPlugin, TestSfx};
+    use crate::DelayedMessages;
+
+    #[test]
+    fn thunder_is_delayed_and_quieter_with_distance() {
+        let thunder = Thunder::new(SoundPool::default()).with_distance(340.0, 3400.0);
+        assert_eq!(thunder.delay(340.0), Duration::from_secs_f32(340.0 / 343.0));
+        assert!((thunder.volume_at(100.0) - 1.0).abs() < f32::EPSILON);
+        assert!((thunder.volume_at(680.0) - 0.5).abs() < f32::EPSILON);
+        assert_eq!(
+            thunder.with_speed_of_sound(0.0).delay(1000.0),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn weather_audio_crossfades_loops_and_strikes_thunder() {
+        #[derive(Resource, Clone, PartialEq)]
+        enum Weather {
+            Rain,
+            Storm,
+        }
+
+        impl WeatherState for Weather {}
+
+        let rain = bevy::asset::uuid_handle!("7d0f3a52-94a1-4b8e-9c1e-2f4b6a8d0c11");
+        let wind = bevy::asset::uuid_handle!("7d0f3a52-94a1-4b8e-9c1e-2f4b6a8d0c12");
+        let mut app = test_app((
+            TestPlugin::default(),
+            WeatherAudioPlugin::<Weather, TestSfx>::default(),
+        ));
+        app.insert_resource(Weather::Storm);
+        app.insert_resource(
+            WeatherAudio::<Weather, TestSfx>::new(TestSfx::UI)
+                .with_fade(Duration::ZERO)
+                .with_state(
+                    Weather::Rain,
+                    WeatherSounds::new().with_loop(rain.clone(), 0.5),
+                )
+                .with_state(
+                    Weather::Storm,
+                    WeatherSounds::new()
+                        .with_loop(rain.clone(), 1.0)
+                        .with_loop(wind.clone(), 0.5)
+                        .with_thunder(
+                            Thunder::new(SoundPool::new([Handle::default()]))
+                                .with_interval(Duration::ZERO, Duration::ZERO)
+                                .with_distance(686.0, 686.0),
+                        ),
+                ),
+        );
+        app.update();
+
+        let playing = |app: &App| {
+            let mut playing: Vec<_> = app
+                .world()
+                .resource::<WeatherAudio<Weather, TestSfx>>()
+                .playing()
+                .collect();
+            playing.sort_by(|a, b| a.1.total_cmp(&b.1));
+            playing
+        };
+        let storm = playing(&app);
+        assert_eq!(storm.len(), 2);
+        assert_eq!(app.world().get::<AudioPlayer>(storm[0].0).unwrap().0, wind);
+        let strikes = app.world().resource::<Messages<ThunderStrike>>();
+        let strike = strikes.iter_current_update_messages().next().unwrap();
+        assert_eq!(strike.delay, Duration::from_secs(2));
+        assert_eq!(
+            app.world()
+                .resource::<DelayedMessages<PlaySfx<TestSfx>>>()
+                .len(),
+            1
+        );
+
+        app.insert_resource(Weather::Rain);
+        app.update();
+        app.update();
+        let rain_only = playing(&app);
+        assert_eq!(rain_only.len(), 1);
+        assert_eq!(rain_only[0].0, storm[1].0);
+        assert!((rain_only[0].1 - 0.5).abs() < f32::EPSILON);
+        assert!(app.world().get_entity(storm[0].0).is_err());
+    }
+
+    #[test]
+    fn weather_sounds_are_keyed_by_kind_of_weather() {
+        #[derive(Resource, Clone)]
+        enum Weather {
+            Storm { strength: f32 },
+        }
+
+        impl WeatherState for Weather {
+            fn intensity(&self) -> f32 {
+                match self {
+                    Weather::Storm { strength } => *strength,
+                }
+            }
+        }
+
+        let rain = bevy::asset::uuid_handle!("7d0f3a52-94a1-4b8e-9c1e-2f4b6a8d0c13");
+        let mut app = test_app((
+            TestPlugin::default(),
+            WeatherAudioPlugin::<Weather, TestSfx>::default(),
+        ));
+        app.insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+            Duration::from_millis(400),
+        ));
+        app.insert_resource(Weather::Storm { strength: 1.0 });
+        app.insert_resource(
+            WeatherAudio::<Weather, TestSfx>::new(TestSfx::UI)
+                .with_fade(Duration::ZERO)
+                .with_state(
+                    Weather::Storm { strength: 1.0 },
+                    WeatherSounds::new()
+                        .with_loop(rain.clone(), 0.8)
+                        .with_thunder(
+                            Thunder::new(SoundPool::new([Handle::default()]))
+                                .with_interval(Duration::from_secs(1), Duration::from_secs(1)),
+                        ),
+                ),
+        );
+
+        let mut strikes = 0;
+        for frame in 0..10 {
+            app.insert_resource(Weather::Storm {
+                strength: if frame % 2 == 0 { 0.5 } else { 0.25 },
+            });
+            app.update();
+            strikes += app
+                .world()
+                .resource::<Messages<ThunderStrike>>()
+                .iter_current_update_messages()
+                .count();
+        }
+        assert!(strikes >= 2);
+
+        let playing: Vec<_> = app
+            .world()
+            .resource::<WeatherAudio<Weather, TestSfx>>()
+            .playing()
+            .collect();
+        assert_eq!(playing.len(), 1);
+        assert!((playing[0].1 - 0.2).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,133 @@
+//! World extension for sending audio messages.
+//!
+//! Exclusive systems, world commands, and test setups have a [`World`] but
+//! no [`MessageWriter`]. [`WorldAudioExt`] sends the common audio messages
+//! straight into the world.
+
+use bevy::prelude::*;
+use std::time::Duration;
+
+use crate::events::{
+    FadeOutAllMusic, FadeOutMusic, FadeOutSfx, PlayMusic, PlaySfx, PlayVoiceLine, StopAllMusic,
+    StopMusic,
+};
+use crate::traits::{MusicCategory, SfxCategory, VoiceCategory};
+
+/// Extension trait sending audio messages from a [`World`].
+///
+/// The messages are handled on the next update, like messages sent through
+/// a [`MessageWriter`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use msg_audio::WorldAudioExt;
+///
+/// fn game_over(world: &mut World) {
+///     let jingle = world.resource::<Sounds>().game_over.clone();
+///     world
+///         .stop_all_music::<GameMusic>()
+///         .play_sfx(PlaySfx::new(jingle, GameSfx::UI));
+/// }
+/// ```
+pub trait WorldAudioExt {
+    /// Sends a [`PlayMusic`] message.
+    fn play_music<M: MusicCategory>(&mut self, message: PlayMusic<M>) -> &mut Self;
+
+    /// Sends a [`PlaySfx`] message.
+    fn play_sfx<S: SfxCategory>(&mut self, message: PlaySfx<S>) -> &mut Self;
+
+    /// Sends a [`PlayVoiceLine`] message.
+    fn play_voice_line<V: VoiceCategory>(&mut self, message: PlayVoiceLine<V>) -> &mut Self;
+
+    /// Stops the music of a category.
+    fn stop_music<M: MusicCategory>(&mut self, category: M) -> &mut Self;
+
+    /// Stops all music of type `M`.
+    fn stop_all_music<M: MusicCategory>(&mut self) -> &mut Self;
+
+    /// Fades out the music of a category over `duration`.
+    fn fade_out_music<M: MusicCategory>(&mut self, category: M, duration: Duration) -> &mut Self;
+
+    /// Fades out all music of type `M` over `duration`.
+    fn fade_out_all_music<M: MusicCategory>(&mut self, duration: Duration) -> &mut Self;
+
+    /// Fades out the sound effects of a category over `duration`.
+    fn fade_out_sfx<S: SfxCategory>(&mut self, category: S, duration: Duration) -> &mut Self;
+}
+
+impl WorldAudioExt for World {
+    fn play_music<M: MusicCategory>(&mut self, message: PlayMusic<M>) -> &mut Self {
+        self.write_message(message);
+        self
+    }
+
+    fn play_sfx<S: SfxCategory>(&mut self, message: PlaySfx<S>) -> &mut Self {
+        self.write_message(message);
+        self
+    }
+
+    fn play_voice_line<V: VoiceCategory>(&mut self, message: PlayVoiceLine<V>) -> &mut Self {
+        self.write_message(message);
+        self
+    }
+
+    fn stop_music<M: MusicCategory>(&mut self, category: M) -> &mut Self {
+        self.write_message(StopMusic::new(category));
+        self
+    }
+
+    fn stop_all_music<M: MusicCategory>(&mut self) -> &mut Self {
+        self.write_message(StopAllMusic::<M>::default());
+        self
+    }
+
+    fn fade_out_music<M: MusicCategory>(&mut self, category: M, duration: Duration) -> &mut Self {
+        self.write_message(FadeOutMusic::new(category, duration));
+        self
+    }
+
+    fn fade_out_all_music<M: MusicCategory>(&mut self, duration: Duration) -> &mut Self {
+        self.write_message(FadeOutAllMusic::<M>::new(duration));
+        self
+    }
+
+    fn fade_out_sfx<S: SfxCategory>(&mut self, category: S, duration: Duration) -> &mut Self {
+        self.write_message(FadeOutSfx::new(category, duration));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_app, TestMusic, TestPlugin, TestSfx};
+    use crate::{FadeOut, TestAudioPlugin};
+
+    #[test]
+    fn world_audio_ext_sends_messages() {
+        let mut app = test_app((TestPlugin::default(), TestAudioPlugin));
+        app.world_mut()
+            .play_music(PlayMusic::new(Handle::default(), TestMusic::Main))
+            .play_sfx(PlaySfx::new(Handle::default(), TestSfx::UI));
+        app.update();
+        app.update();
+        let world = app.world_mut();
+        assert_eq!(world.query::<&TestMusic>().iter(world).count(), 1);
+        assert_eq!(world.query::<&TestSfx>().iter(world).count(), 1);
+
+        app.world_mut()
+            .fade_out_sfx(TestSfx::UI, Duration::from_secs(1))
+            .stop_all_music::<TestMusic>();
+        app.update();
+        let world = app.world_mut();
+        assert_eq!(world.query::<&TestMusic>().iter(world).count(), 0);
+        assert_eq!(
+            world
+                .query_filtered::<(), (With<TestSfx>, With<FadeOut>)>()
+                .iter(world)
+                .count(),
+            1
+        );
+    }
+}